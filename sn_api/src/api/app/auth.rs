@@ -73,6 +73,10 @@ impl Safe {
 
     // Connect to the SAFE Network using the provided app id and auth credentials
     pub async fn connect(&mut self, app_id: &str, auth_credentials: Option<&str>) -> Result<()> {
+        // Private content cached was decrypted with the keypair of the previous connection
+        if let Some(cache) = &self.resolution_cache {
+            cache.clear();
+        }
         self.safe_client.connect(app_id, auth_credentials).await
     }
 }
@@ -0,0 +1,1053 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use super::{
+    super::{
+        fetch::Range,
+        helpers::{gen_timestamp_secs, xorname_to_hex},
+    },
+    StorageBackend, TransferRecord,
+};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use fs2::FileExt;
+use lazy_static::lazy_static;
+use log::debug;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sn_data_types::{
+    Blob, Keypair, Map, MapAction, MapPermissionSet, MapSeqValue, MapValue, Money, PrivateBlob,
+    PublicBlob, PublicKey as SafeNdPublicKey, SeqMap,
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use xor_name::XorName;
+
+lazy_static! {
+    // All in-memory backends of the process share the same storage, the same way
+    // all clients connected to the same network see the same data
+    static ref IN_MEMORY_STORE: Arc<Mutex<LocalStore>> = Arc::new(Mutex::new(LocalStore::default()));
+}
+
+// All the data held by a local stand-in of the network
+#[derive(Default, Serialize, Deserialize)]
+struct LocalStore {
+    // Keypair used by the on-disk backend, so all connections to it share the same identity
+    keypair: Option<Keypair>,
+    blobs: BTreeMap<XorName, Vec<u8>>,
+    private_blobs: BTreeMap<XorName, Vec<u8>>,
+    maps: BTreeMap<(XorName, u64), BTreeMap<Vec<u8>, MapSeqValue>>,
+    // Sequences are keyed by xorname, type tag, and whether they are private or not
+    sequences: BTreeMap<(XorName, u64, bool), Vec<Vec<u8>>>,
+    sequence_owners: BTreeMap<(XorName, u64, bool), SafeNdPublicKey>,
+    balances: BTreeMap<XorName, Money>,
+    transfers_count: u64,
+    // All transfers made, including payouts, in the order they were made
+    transfers: Vec<TransferRecord>,
+}
+
+// Storage backend which keeps all data locally, either in memory or persisted on disk.
+// It doesn't enforce any permissions, all data is readable and writable by any keypair.
+#[derive(Clone)]
+pub(crate) struct LocalBackend {
+    keypair: Arc<Keypair>,
+    store: Arc<Mutex<LocalStore>>,
+    path: Option<PathBuf>,
+}
+
+impl LocalBackend {
+    // Backend which keeps the data in memory for as long as the process runs
+    pub fn in_memory() -> Self {
+        Self {
+            keypair: Arc::new(Keypair::new_ed25519(&mut OsRng)),
+            store: IN_MEMORY_STORE.clone(),
+            path: None,
+        }
+    }
+
+    // Backend which persists the data onto the file at the given path,
+    // it's loaded and saved on each operation so it can be shared among processes
+    pub fn on_disk(path: &Path) -> Result<Self> {
+        debug!("Using local storage at: {}", path.display());
+        let _lock = lock_store(path)?;
+        let mut store = load_store(path)?;
+        let keypair = match &store.keypair {
+            Some(keypair) => keypair.clone(),
+            None => {
+                let keypair = Keypair::new_ed25519(&mut OsRng);
+                store.keypair = Some(keypair.clone());
+                save_store(path, &store)?;
+                keypair
+            }
+        };
+
+        Ok(Self {
+            keypair: Arc::new(keypair),
+            store: Arc::new(Mutex::new(store)),
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    // Private helper to run an operation on the store, reloading it from disk
+    // before, and saving it after if the operation mutates it. The file is locked
+    // throughout so other processes cannot interleave their changes with ours.
+    fn with_store<T, F>(&self, mutates: bool, op: F) -> Result<T>
+    where
+        F: FnOnce(&mut LocalStore) -> Result<T>,
+    {
+        let mut store = self
+            .store
+            .lock()
+            .map_err(|_| Error::Unexpected("Local storage lock is poisoned".to_string()))?;
+
+        let _lock = match &self.path {
+            Some(path) => {
+                let lock = lock_store(path)?;
+                *store = load_store(path)?;
+                Some(lock)
+            }
+            None => None,
+        };
+
+        let result = op(&mut store)?;
+
+        if mutates {
+            if let Some(path) = &self.path {
+                save_store(path, &store)?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn transfer(&self, from_id: Option<Keypair>, to: XorName, amount: Money) -> Result<u64> {
+        let from = match from_id {
+            Some(id) => XorName::from(id.public_key()),
+            None => XorName::from(self.keypair.public_key()),
+        };
+
+        self.with_store(true, |store| {
+            if amount == Money::from_nano(0) {
+                return Err(Error::InvalidAmount(amount.to_string()));
+            }
+            // Both balances are computed from the ones before the transfer,
+            // the credit would overwrite the debit if they were the same
+            if from == to {
+                return Err(Error::InvalidInput(format!(
+                    "Cannot transfer coins from and to the same SafeKey: {}",
+                    xorname_to_hex(&from)
+                )));
+            }
+
+            let from_balance = balance_of(store, &from);
+            let from_new_balance = from_balance
+                .checked_sub(amount)
+                .ok_or_else(|| Error::NotEnoughBalance(from_balance.to_string()))?;
+            let to_new_balance = balance_of(store, &to)
+                .checked_add(amount)
+                .ok_or_else(|| Error::InvalidAmount(amount.to_string()))?;
+
+            let _ = store.balances.insert(from, from_new_balance);
+            let _ = store.balances.insert(to, to_new_balance);
+
+            Ok(record_transfer(store, Some(from), to, amount))
+        })
+    }
+}
+
+#[async_trait]
+impl StorageBackend for LocalBackend {
+    async fn keypair(&self) -> Result<Arc<Keypair>> {
+        Ok(self.keypair.clone())
+    }
+
+    // Only the on-disk store keeps the keypair, each in-memory backend gets a new one
+    fn has_persistent_keypair(&self) -> bool {
+        self.path.is_some()
+    }
+
+    // === Money operations ===
+    async fn read_balance_from_keypair(&self, id: Keypair) -> Result<Money> {
+        let xorname = XorName::from(id.public_key());
+        self.with_store(false, |store| Ok(balance_of(store, &xorname)))
+    }
+
+    #[cfg(feature = "simulated-payouts")]
+    async fn trigger_simulated_farming_payout(
+        &self,
+        amount: Money,
+        id: Option<Keypair>,
+    ) -> Result<()> {
+        let xorname = match id {
+            Some(id) => XorName::from(id.public_key()),
+            None => XorName::from(self.keypair.public_key()),
+        };
+
+        self.with_store(true, |store| {
+            let new_balance = balance_of(store, &xorname)
+                .checked_add(amount)
+                .ok_or_else(|| Error::InvalidAmount(amount.to_string()))?;
+            let _ = store.balances.insert(xorname, new_balance);
+            let _ = record_transfer(store, None, xorname, amount);
+            Ok(())
+        })
+    }
+
+    async fn safecoin_transfer_to_xorname(
+        &self,
+        from_id: Option<Keypair>,
+        to_xorname: XorName,
+        amount: Money,
+    ) -> Result<u64> {
+        self.transfer(from_id, to_xorname, amount)
+    }
+
+    async fn safecoin_transfer_to_pk(
+        &self,
+        from_id: Option<Keypair>,
+        to_pk: SafeNdPublicKey,
+        amount: Money,
+    ) -> Result<(u64, SafeNdPublicKey)> {
+        let transfer_id = self.transfer(from_id, XorName::from(to_pk), amount)?;
+        Ok((transfer_id, to_pk))
+    }
+
+    async fn transfer_history(&self, id: Keypair) -> Result<Vec<TransferRecord>> {
+        let xorname = XorName::from(id.public_key());
+        self.with_store(false, |store| {
+            let history = store
+                .transfers
+                .iter()
+                .filter(|transfer| transfer.to == xorname || transfer.from == Some(xorname))
+                .cloned()
+                .collect();
+            Ok(history)
+        })
+    }
+
+    // === Blob operations ===
+    async fn store_public_blob(&self, data: &[u8], dry_run: bool) -> Result<XorName> {
+        let blob_for_storage = Blob::Public(PublicBlob::new(data.to_vec()));
+        let xorname = *blob_for_storage.address().name();
+
+        if !dry_run {
+            self.with_store(true, |store| {
+                let _ = store.blobs.insert(xorname, data.to_vec());
+                Ok(())
+            })?;
+        }
+
+        Ok(xorname)
+    }
+
+    async fn get_public_blob(&self, xorname: XorName, range: Range) -> Result<Vec<u8>> {
+        self.with_store(false, |store| {
+            let data = store.blobs.get(&xorname).ok_or_else(|| {
+                Error::NetDataError(format!(
+                    "Failed to GET Public Blob: no Blob found at Xor name {}",
+                    xorname_to_hex(&xorname)
+                ))
+            })?;
+
+            let (start, end) = match range {
+                Some((start, end)) => (
+                    start.unwrap_or_else(|| 0) as usize,
+                    end.map_or_else(|| data.len(), |end| end as usize),
+                ),
+                None => (0, data.len()),
+            };
+
+            let end = std::cmp::min(end, data.len());
+            if start > end {
+                return Err(Error::NetDataError(format!(
+                    "Failed to GET Public Blob: invalid range ({}..{}) for Blob of {} bytes",
+                    start,
+                    end,
+                    data.len()
+                )));
+            }
+
+            Ok(data[start..end].to_vec())
+        })
+    }
+
+    async fn store_private_blob(&self, data: &[u8], dry_run: bool) -> Result<XorName> {
+        let owner = self.keypair.public_key();
+        let blob_for_storage = Blob::Private(PrivateBlob::new(data.to_vec(), owner));
+        let xorname = *blob_for_storage.address().name();
+
+        if !dry_run {
+            self.with_store(true, |store| {
+                let _ = store.private_blobs.insert(xorname, data.to_vec());
+                Ok(())
+            })?;
+        }
+
+        Ok(xorname)
+    }
+
+    async fn get_private_blob(&self, xorname: XorName) -> Result<Vec<u8>> {
+        self.with_store(false, |store| {
+            store.private_blobs.get(&xorname).cloned().ok_or_else(|| {
+                Error::NetDataError(format!(
+                    "Failed to GET Private Blob: no Blob found at Xor name {}",
+                    xorname_to_hex(&xorname)
+                ))
+            })
+        })
+    }
+
+    // === Map operations ===
+    async fn store_map(
+        &self,
+        name: Option<XorName>,
+        tag: u64,
+        _permissions: Option<String>,
+    ) -> Result<XorName> {
+        let xorname = name.unwrap_or_else(rand::random);
+        self.with_store(true, |store| {
+            if store.maps.contains_key(&(xorname, tag)) {
+                return Err(Error::NetDataError(format!(
+                    "Failed to put mutable data: data already exists at Xor name {}",
+                    xorname_to_hex(&xorname)
+                )));
+            }
+            let _ = store.maps.insert((xorname, tag), BTreeMap::new());
+            Ok(xorname)
+        })
+    }
+
+    async fn get_map(&self, name: XorName, tag: u64) -> Result<Map> {
+        let owner = self.keypair.public_key();
+        let entries = self.list_map_entries(name, tag).await?;
+
+        let permission_set = MapPermissionSet::new()
+            .allow(MapAction::Read)
+            .allow(MapAction::Insert)
+            .allow(MapAction::Update)
+            .allow(MapAction::Delete)
+            .allow(MapAction::ManagePermissions);
+        let mut permission_map = BTreeMap::new();
+        let _ = permission_map.insert(owner, permission_set);
+
+        Ok(Map::Seq(SeqMap::new_with_data(
+            name,
+            tag,
+            entries,
+            permission_map,
+            owner,
+        )))
+    }
+
+    async fn map_insert(&self, name: XorName, tag: u64, key: &[u8], value: &[u8]) -> Result<()> {
+        self.with_store(true, |store| {
+            let entries = get_map_mut(store, name, tag)?;
+            if entries.contains_key(key) {
+                return Err(Error::EntryExists(format!(
+                    "Failed to insert to SeqMD: entry already exists at Xor name {}",
+                    xorname_to_hex(&name)
+                )));
+            }
+
+            let value = MapSeqValue {
+                data: value.to_vec(),
+                version: 0,
+            };
+            let _ = entries.insert(key.to_vec(), value);
+            Ok(())
+        })
+    }
+
+    async fn map_get_value(&self, name: XorName, tag: u64, key: &[u8]) -> Result<MapValue> {
+        self.with_store(false, |store| {
+            let entries = store.maps.get(&(name, tag)).ok_or_else(|| {
+                Error::ContentNotFound(format!(
+                    "Sequenced Map not found at Xor name: {}",
+                    xorname_to_hex(&name)
+                ))
+            })?;
+
+            entries
+                .get(key)
+                .map(|value| MapValue::Seq(value.clone()))
+                .ok_or_else(|| {
+                    Error::EntryNotFound(format!(
+                        "Entry not found in Sequenced Map found at Xor name: {}",
+                        xorname_to_hex(&name)
+                    ))
+                })
+        })
+    }
+
+    async fn list_map_entries(
+        &self,
+        name: XorName,
+        tag: u64,
+    ) -> Result<BTreeMap<Vec<u8>, MapSeqValue>> {
+        self.with_store(false, |store| {
+            store.maps.get(&(name, tag)).cloned().ok_or_else(|| {
+                Error::ContentNotFound(format!(
+                    "Sequenced Map not found at Xor name: {} (type tag: {})",
+                    xorname_to_hex(&name),
+                    tag
+                ))
+            })
+        })
+    }
+
+    async fn update_map(
+        &self,
+        name: XorName,
+        tag: u64,
+        key: &[u8],
+        value: &[u8],
+        version: u64,
+    ) -> Result<()> {
+        self.with_store(true, |store| {
+            let entries = get_map_mut(store, name, tag)?;
+            let current = entries.get_mut(key).ok_or_else(|| {
+                Error::EntryNotFound(format!(
+                    "Entry not found in Sequenced Map found at Xor name: {}",
+                    xorname_to_hex(&name)
+                ))
+            })?;
+
+            if version != current.version + 1 {
+                return Err(Error::NetDataError(format!(
+                    "Failed to update SeqMD: invalid version {}, the current version is {}",
+                    version, current.version
+                )));
+            }
+
+            current.data = value.to_vec();
+            current.version = version;
+            Ok(())
+        })
+    }
+
+    async fn map_delete(&self, name: XorName, tag: u64, key: &[u8], version: u64) -> Result<()> {
+        self.with_store(true, |store| {
+            let entries = get_map_mut(store, name, tag)?;
+            let current_version =
+                entries
+                    .get(key)
+                    .map(|current| current.version)
+                    .ok_or_else(|| {
+                        Error::EntryNotFound(format!(
+                            "Entry not found in Sequenced Map found at Xor name: {}",
+                            xorname_to_hex(&name)
+                        ))
+                    })?;
+
+            if version != current_version + 1 {
+                return Err(Error::NetDataError(format!(
+                    "Failed to delete from SeqMD: invalid version {}, the current version is {}",
+                    version, current_version
+                )));
+            }
+
+            let _ = entries.remove(key);
+            Ok(())
+        })
+    }
+
+    // === Sequence data operations ===
+    async fn store_sequence(
+        &self,
+        data: &[u8],
+        name: Option<XorName>,
+        tag: u64,
+        _permissions: Option<String>,
+        private: bool,
+    ) -> Result<XorName> {
+        let xorname = name.unwrap_or_else(rand::random);
+        self.with_store(true, |store| {
+            if store.sequences.contains_key(&(xorname, tag, private)) {
+                return Err(Error::NetDataError(format!(
+                    "Failed to store {} Sequence data: data already exists at Xor name {}",
+                    if private { "Private" } else { "Public" },
+                    xorname_to_hex(&xorname)
+                )));
+            }
+
+            let _ = store
+                .sequences
+                .insert((xorname, tag, private), vec![data.to_vec()]);
+            let _ = store
+                .sequence_owners
+                .insert((xorname, tag, private), self.keypair.public_key());
+            Ok(xorname)
+        })
+    }
+
+    async fn sequence_get_last_entry(
+        &self,
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<(u64, Vec<u8>)> {
+        self.with_store(false, |store| {
+            let entries = get_sequence(store, name, tag, private)?;
+            match entries.last() {
+                Some(entry) => Ok(((entries.len() - 1) as u64, entry.clone())),
+                None => Err(Error::EmptyContent(format!(
+                    "Empty Sequence found at XoR name {}",
+                    name
+                ))),
+            }
+        })
+    }
+
+    async fn sequence_get_last_index(&self, name: XorName, tag: u64, private: bool) -> Result<u64> {
+        self.with_store(false, |store| {
+            let entries = get_sequence(store, name, tag, private)?;
+            if entries.is_empty() {
+                Err(Error::EmptyContent(format!(
+                    "Empty Sequence found at XoR name {}",
+                    name
+                )))
+            } else {
+                Ok((entries.len() - 1) as u64)
+            }
+        })
+    }
+
+    async fn sequence_get_entry(
+        &self,
+        name: XorName,
+        tag: u64,
+        index: u64,
+        private: bool,
+    ) -> Result<Vec<u8>> {
+        self.with_store(false, |store| {
+            let entries = get_sequence(store, name, tag, private)?;
+            entries.get(index as usize).cloned().ok_or_else(|| {
+                Error::VersionNotFound(format!(
+                    "Invalid version ({}) for Sequence found at XoR name {}",
+                    index, name
+                ))
+            })
+        })
+    }
+
+    async fn append_to_sequence(
+        &self,
+        data: &[u8],
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<()> {
+        self.with_store(true, |store| {
+            let entries = store
+                .sequences
+                .get_mut(&(name, tag, private))
+                .ok_or_else(|| {
+                    Error::NetDataError(format!(
+                        "Failed to append to Sequence: no Sequence found at Xor name {}",
+                        xorname_to_hex(&name)
+                    ))
+                })?;
+            entries.push(data.to_vec());
+            Ok(())
+        })
+    }
+
+    async fn sequence_get_owner(
+        &self,
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<SafeNdPublicKey> {
+        self.with_store(false, |store| {
+            let _ = get_sequence(store, name, tag, private)?;
+            // The owner is recorded when the Sequence is stored, thus it's never
+            // assumed to be the keypair in use if it's not found
+            store
+                .sequence_owners
+                .get(&(name, tag, private))
+                .copied()
+                .ok_or_else(|| {
+                    Error::NetDataError(format!(
+                        "Failed to get owner of {} Sequence: no owner recorded at Xor name {}",
+                        if private { "Private" } else { "Public" },
+                        xorname_to_hex(&name)
+                    ))
+                })
+        })
+    }
+
+    async fn sequence_set_owner(
+        &self,
+        name: XorName,
+        tag: u64,
+        owner: SafeNdPublicKey,
+        private: bool,
+    ) -> Result<()> {
+        self.with_store(true, |store| {
+            let _ = get_sequence(store, name, tag, private)?;
+            let _ = store.sequence_owners.insert((name, tag, private), owner);
+            Ok(())
+        })
+    }
+}
+
+// Helpers
+
+fn balance_of(store: &LocalStore, xorname: &XorName) -> Money {
+    store
+        .balances
+        .get(xorname)
+        .copied()
+        .unwrap_or_else(|| Money::from_nano(0))
+}
+
+fn get_map_mut(
+    store: &mut LocalStore,
+    name: XorName,
+    tag: u64,
+) -> Result<&mut BTreeMap<Vec<u8>, MapSeqValue>> {
+    store.maps.get_mut(&(name, tag)).ok_or_else(|| {
+        Error::ContentNotFound(format!(
+            "Sequenced Map not found at Xor name: {}",
+            xorname_to_hex(&name)
+        ))
+    })
+}
+
+fn get_sequence(
+    store: &LocalStore,
+    name: XorName,
+    tag: u64,
+    private: bool,
+) -> Result<&Vec<Vec<u8>>> {
+    store.sequences.get(&(name, tag, private)).ok_or_else(|| {
+        Error::ContentNotFound(format!(
+            "Sequence not found at Xor name: {} (type tag: {})",
+            xorname_to_hex(&name),
+            tag
+        ))
+    })
+}
+
+// Take an exclusive lock on the local storage at the given path, which is held until the
+// returned file is dropped. A separate lock file is used as the storage file may not exist yet.
+fn lock_store(path: &Path) -> Result<fs::File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            Error::FileSystemError(format!(
+                "Failed to create folder for local storage at '{}': {}",
+                parent.display(),
+                err
+            ))
+        })?;
+    }
+
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    let lock_file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|err| {
+            Error::FileSystemError(format!(
+                "Failed to open lock file for local storage at '{}': {}",
+                path.display(),
+                err
+            ))
+        })?;
+
+    lock_file.lock_exclusive().map_err(|err| {
+        Error::FileSystemError(format!(
+            "Failed to lock local storage at '{}': {}",
+            path.display(),
+            err
+        ))
+    })?;
+
+    Ok(lock_file)
+}
+
+fn load_store(path: &Path) -> Result<LocalStore> {
+    if !path.exists() {
+        return Ok(LocalStore::default());
+    }
+
+    let serialised = fs::read(path).map_err(|err| {
+        Error::FileSystemError(format!(
+            "Failed to read local storage from '{}': {}",
+            path.display(),
+            err
+        ))
+    })?;
+
+    bincode::deserialize(&serialised).map_err(|err| {
+        Error::FileSystemError(format!(
+            "Failed to parse local storage from '{}': {}",
+            path.display(),
+            err
+        ))
+    })
+}
+
+// Keep a record of a transfer already applied to the balances, returning its id
+fn record_transfer(
+    store: &mut LocalStore,
+    from: Option<XorName>,
+    to: XorName,
+    amount: Money,
+) -> u64 {
+    store.transfers_count += 1;
+    store.transfers.push(TransferRecord {
+        id: store.transfers_count,
+        from,
+        to,
+        amount,
+        timestamp: gen_timestamp_secs(),
+    });
+    store.transfers_count
+}
+
+fn save_store(path: &Path, store: &LocalStore) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| {
+            Error::FileSystemError(format!(
+                "Failed to create folder for local storage at '{}': {}",
+                parent.display(),
+                err
+            ))
+        })?;
+    }
+
+    let serialised = bincode::serialize(store)
+        .map_err(|err| Error::Unexpected(format!("Failed to serialise local storage: {}", err)))?;
+
+    fs::write(path, serialised).map_err(|err| {
+        Error::FileSystemError(format!(
+            "Failed to write local storage to '{}': {}",
+            path.display(),
+            err
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_backend_blob_range() -> Result<()> {
+        let backend = LocalBackend::in_memory();
+        let data = b"Something super good";
+        let xorname = backend.store_public_blob(data, false).await?;
+
+        let retrieved = backend.get_public_blob(xorname, None).await?;
+        assert_eq!(retrieved, data.to_vec());
+
+        let retrieved = backend
+            .get_public_blob(xorname, Some((Some(10), Some(15))))
+            .await?;
+        assert_eq!(retrieved, b"super".to_vec());
+
+        let retrieved = backend
+            .get_public_blob(xorname, Some((Some(16), None)))
+            .await?;
+        assert_eq!(retrieved, b"good".to_vec());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_blob_dry_run() -> Result<()> {
+        let backend = LocalBackend::in_memory();
+        let data = b"Something never stored";
+        let xorname = backend.store_public_blob(data, true).await?;
+        let real_xorname = backend.store_public_blob(data, false).await?;
+        assert_eq!(xorname, real_xorname);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_private_blob() -> Result<()> {
+        let backend = LocalBackend::in_memory();
+        let data = b"Something super private";
+        let xorname = backend.store_private_blob(data, false).await?;
+        assert_ne!(xorname, backend.store_public_blob(data, true).await?);
+
+        let retrieved = backend.get_private_blob(xorname).await?;
+        assert_eq!(retrieved, data.to_vec());
+
+        match backend.get_public_blob(xorname, None).await {
+            Err(Error::NetDataError(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_map_insert_and_update() -> Result<()> {
+        let backend = LocalBackend::in_memory();
+        let xorname = backend.store_map(None, 20_000, None).await?;
+        backend
+            .map_insert(xorname, 20_000, b"key", b"value")
+            .await?;
+
+        match backend.map_insert(xorname, 20_000, b"key", b"value").await {
+            Err(Error::EntryExists(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        match backend.update_map(xorname, 20_000, b"key", b"new", 2).await {
+            Err(Error::NetDataError(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        backend
+            .update_map(xorname, 20_000, b"key", b"new value", 1)
+            .await?;
+        let entries = backend.list_map_entries(xorname, 20_000).await?;
+        let value = entries.get(b"key".as_ref()).unwrap();
+        assert_eq!(value.data, b"new value".to_vec());
+        assert_eq!(value.version, 1);
+
+        match backend.map_delete(xorname, 20_000, b"key", 1).await {
+            Err(Error::NetDataError(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        backend.map_delete(xorname, 20_000, b"key", 2).await?;
+        match backend.map_get_value(xorname, 20_000, b"key").await {
+            Err(Error::EntryNotFound(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        match backend.map_get_value(xorname, 20_000, b"missing").await {
+            Err(Error::EntryNotFound(_)) => Ok(()),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_sequence() -> Result<()> {
+        let backend = LocalBackend::in_memory();
+        let xorname = backend
+            .store_sequence(b"first", None, 20_000, None, false)
+            .await?;
+        backend
+            .append_to_sequence(b"second", xorname, 20_000, false)
+            .await?;
+
+        let (version, data) = backend
+            .sequence_get_last_entry(xorname, 20_000, false)
+            .await?;
+        assert_eq!(version, 1);
+        assert_eq!(data, b"second".to_vec());
+        assert_eq!(
+            backend
+                .sequence_get_entry(xorname, 20_000, 0, false)
+                .await?,
+            b"first".to_vec()
+        );
+
+        match backend.sequence_get_entry(xorname, 20_000, 2, false).await {
+            Err(Error::VersionNotFound(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        // a private Sequence at the same address is a different Sequence
+        match backend.sequence_get_last_entry(xorname, 20_000, true).await {
+            Err(Error::ContentNotFound(_)) => Ok(()),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_sequence_owner() -> Result<()> {
+        let backend = LocalBackend::in_memory();
+        let xorname = backend
+            .store_sequence(b"first", None, 20_000, None, false)
+            .await?;
+
+        // the owner is the creator, not whoever is reading it
+        let other_backend = LocalBackend::in_memory();
+        assert_eq!(
+            other_backend
+                .sequence_get_owner(xorname, 20_000, false)
+                .await?,
+            backend.keypair.public_key()
+        );
+
+        // it's never assumed to be the keypair in use if it's not recorded
+        backend.with_store(true, |store| {
+            let _ = store.sequence_owners.remove(&(xorname, 20_000, false));
+            Ok(())
+        })?;
+        match backend.sequence_get_owner(xorname, 20_000, false).await {
+            Err(Error::NetDataError(_)) => Ok(()),
+            other => panic!("Unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_transfer() -> Result<()> {
+        // an on-disk store is used so no other test makes transfers with it
+        let path = std::env::temp_dir().join(format!(
+            "sn_api_local_storage_{}.db",
+            xorname_to_hex(&rand::random())
+        ));
+        let backend = LocalBackend::on_disk(&path)?;
+        let from = Keypair::new_ed25519(&mut OsRng);
+        let to = Keypair::new_ed25519(&mut OsRng);
+        let to_xorname = XorName::from(to.public_key());
+
+        match backend
+            .safecoin_transfer_to_xorname(Some(from.clone()), to_xorname, Money::from_nano(1))
+            .await
+        {
+            Err(Error::NotEnoughBalance(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+
+        let from_xorname = XorName::from(from.public_key());
+        backend.with_store(true, |store| {
+            let _ = store.balances.insert(from_xorname, Money::from_nano(10));
+            Ok(())
+        })?;
+
+        let transfer_id = backend
+            .safecoin_transfer_to_xorname(Some(from.clone()), to_xorname, Money::from_nano(4))
+            .await?;
+        assert_eq!(transfer_id, 1);
+        assert_eq!(
+            backend.read_balance_from_keypair(from.clone()).await?,
+            Money::from_nano(6)
+        );
+        assert_eq!(
+            backend.read_balance_from_keypair(to.clone()).await?,
+            Money::from_nano(4)
+        );
+
+        // the failed transfer is not part of the history
+        let from_history = backend.transfer_history(from).await?;
+        assert_eq!(from_history.len(), 1);
+        assert_eq!(from_history[0].id, transfer_id);
+        assert_eq!(from_history[0].from, Some(from_xorname));
+        assert_eq!(from_history[0].to, to_xorname);
+        assert_eq!(from_history[0].amount, Money::from_nano(4));
+        assert_eq!(backend.transfer_history(to).await?, from_history);
+
+        let _ = fs::remove_file(&path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_transfer_to_self() -> Result<()> {
+        // an on-disk store is used so no other test makes transfers with it
+        let path = std::env::temp_dir().join(format!(
+            "sn_api_local_storage_{}.db",
+            xorname_to_hex(&rand::random())
+        ));
+        let backend = LocalBackend::on_disk(&path)?;
+        let keypair = Keypair::new_ed25519(&mut OsRng);
+        let xorname = XorName::from(keypair.public_key());
+        backend.with_store(true, |store| {
+            let _ = store.balances.insert(xorname, Money::from_nano(10));
+            Ok(())
+        })?;
+
+        match backend
+            .safecoin_transfer_to_xorname(Some(keypair.clone()), xorname, Money::from_nano(4))
+            .await
+        {
+            Err(Error::InvalidInput(_)) => {}
+            other => panic!("Unexpected result: {:?}", other),
+        }
+        assert_eq!(
+            backend.read_balance_from_keypair(keypair.clone()).await?,
+            Money::from_nano(10)
+        );
+        assert!(backend.transfer_history(keypair).await?.is_empty());
+
+        let _ = fs::remove_file(&path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_on_disk() -> Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "sn_api_local_storage_{}.db",
+            xorname_to_hex(&rand::random())
+        ));
+
+        let xorname = {
+            let backend = LocalBackend::on_disk(&path)?;
+            backend
+                .store_sequence(b"persisted", None, 20_000, None, false)
+                .await?
+        };
+
+        let backend = LocalBackend::on_disk(&path)?;
+        let (_, data) = backend
+            .sequence_get_last_entry(xorname, 20_000, false)
+            .await?;
+        assert_eq!(data, b"persisted".to_vec());
+
+        let _ = fs::remove_file(&path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_backend_on_disk_concurrent_writes() -> Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "sn_api_local_storage_{}.db",
+            xorname_to_hex(&rand::random())
+        ));
+        let backend = LocalBackend::on_disk(&path)?;
+        let xorname = backend
+            .store_sequence(b"initial", None, 20_000, None, false)
+            .await?;
+
+        // Each backend keeps its own copy of the store in memory, as separate processes would,
+        // so only the lock on the file prevents them from overwriting each other's entries
+        let writers: Vec<_> = (0..4)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || -> Result<()> {
+                    let backend = LocalBackend::on_disk(&path)?;
+                    for j in 0..5 {
+                        let data = format!("entry {}-{}", i, j);
+                        futures::executor::block_on(backend.append_to_sequence(
+                            data.as_bytes(),
+                            xorname,
+                            20_000,
+                            false,
+                        ))?;
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer
+                .join()
+                .map_err(|_| Error::Unexpected("Writer thread panicked".to_string()))??;
+        }
+
+        let (index, _) = backend
+            .sequence_get_last_entry(xorname, 20_000, false)
+            .await?;
+        assert_eq!(index, 20);
+
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(path.with_extension("db.lock"));
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+mod local;
+mod network;
+
+pub(crate) use local::LocalBackend;
+pub(crate) use network::NetworkBackend;
+
+use super::fetch::Range;
+use crate::{Error, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sn_data_types::{Keypair, Map, MapSeqValue, MapValue, Money, PublicKey as SafeNdPublicKey};
+use std::{collections::BTreeMap, fmt, path::PathBuf, str::FromStr, sync::Arc};
+use xor_name::XorName;
+
+const LOCAL_STORAGE_FILENAME: &str = "local_storage.db";
+
+/// Where a `Safe` instance stores and retrieves its data from
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackendConfig {
+    /// The SAFE Network, accessed through `sn_client`
+    Network,
+    /// A stand-in for the network kept in memory, shared by all `Safe` instances of the process
+    InMemory,
+    /// A stand-in for the network persisted at the given file path
+    LocalDisk(PathBuf),
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self::Network
+    }
+}
+
+impl fmt::Display for BackendConfig {
+    // Formatted the same way it's parsed from
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Network => write!(f, "network"),
+            Self::InMemory => write!(f, "memory"),
+            Self::LocalDisk(path) => write!(f, "local:{}", path.display()),
+        }
+    }
+}
+
+impl FromStr for BackendConfig {
+    type Err = Error;
+
+    // Supported values are "network", "memory", "local" and "local:<file path>"
+    fn from_str(str: &str) -> Result<Self> {
+        match str {
+            "network" => Ok(Self::Network),
+            "memory" => Ok(Self::InMemory),
+            "local" => {
+                let mut path = dirs_next::home_dir().ok_or_else(|| {
+                    Error::InvalidInput(
+                        "Failed to obtain local home directory for the local storage".to_string(),
+                    )
+                })?;
+                path.push(".safe");
+                path.push(LOCAL_STORAGE_FILENAME);
+                Ok(Self::LocalDisk(path))
+            }
+            other if other.starts_with("local:") && other.len() > "local:".len() => {
+                Ok(Self::LocalDisk(PathBuf::from(&other["local:".len()..])))
+            }
+            other => Err(Error::InvalidInput(format!(
+                "Invalid storage backend '{}'. Supported values are 'network', 'memory', 'local' and 'local:<path>'",
+                other
+            ))),
+        }
+    }
+}
+
+// A transfer of safecoins as recorded by the storage. Coins credited by the network itself,
+// e.g. simulated farming payouts, have no source SafeKey.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct TransferRecord {
+    pub id: u64,
+    pub from: Option<XorName>,
+    pub to: XorName,
+    pub amount: Money,
+    pub timestamp: String,
+}
+
+// Operations the API needs from the storage, either the SAFE Network or a local stand-in.
+// Implementations are expected to return the same Error variants for the same failure cases.
+#[async_trait]
+pub(crate) trait StorageBackend: Send + Sync {
+    async fn keypair(&self) -> Result<Arc<Keypair>>;
+
+    // Whether the same keypair is used again when connecting to this backend in another session,
+    // rather than a new random one. Private data can only be decrypted with the same keypair.
+    fn has_persistent_keypair(&self) -> bool;
+
+    // === Money operations ===
+    async fn read_balance_from_keypair(&self, id: Keypair) -> Result<Money>;
+
+    #[cfg(feature = "simulated-payouts")]
+    async fn trigger_simulated_farming_payout(
+        &self,
+        amount: Money,
+        id: Option<Keypair>,
+    ) -> Result<()>;
+
+    async fn safecoin_transfer_to_xorname(
+        &self,
+        from_id: Option<Keypair>,
+        to_xorname: XorName,
+        amount: Money,
+    ) -> Result<u64>;
+
+    async fn safecoin_transfer_to_pk(
+        &self,
+        from_id: Option<Keypair>,
+        to_pk: SafeNdPublicKey,
+        amount: Money,
+    ) -> Result<(u64, SafeNdPublicKey)>;
+
+    // Transfers to and from the SafeKey of the given keypair, oldest first
+    async fn transfer_history(&self, id: Keypair) -> Result<Vec<TransferRecord>>;
+
+    // === Blob operations ===
+    async fn store_public_blob(&self, data: &[u8], dry_run: bool) -> Result<XorName>;
+
+    async fn get_public_blob(&self, xorname: XorName, range: Range) -> Result<Vec<u8>>;
+
+    async fn store_private_blob(&self, data: &[u8], dry_run: bool) -> Result<XorName>;
+
+    async fn get_private_blob(&self, xorname: XorName) -> Result<Vec<u8>>;
+
+    // === Map operations ===
+    async fn store_map(
+        &self,
+        name: Option<XorName>,
+        tag: u64,
+        permissions: Option<String>,
+    ) -> Result<XorName>;
+
+    async fn get_map(&self, name: XorName, tag: u64) -> Result<Map>;
+
+    async fn map_insert(&self, name: XorName, tag: u64, key: &[u8], value: &[u8]) -> Result<()>;
+
+    async fn map_get_value(&self, name: XorName, tag: u64, key: &[u8]) -> Result<MapValue>;
+
+    async fn list_map_entries(
+        &self,
+        name: XorName,
+        tag: u64,
+    ) -> Result<BTreeMap<Vec<u8>, MapSeqValue>>;
+
+    async fn update_map(
+        &self,
+        name: XorName,
+        tag: u64,
+        key: &[u8],
+        value: &[u8],
+        version: u64,
+    ) -> Result<()>;
+
+    async fn map_delete(&self, name: XorName, tag: u64, key: &[u8], version: u64) -> Result<()>;
+
+    // === Sequence data operations ===
+    async fn store_sequence(
+        &self,
+        data: &[u8],
+        name: Option<XorName>,
+        tag: u64,
+        permissions: Option<String>,
+        private: bool,
+    ) -> Result<XorName>;
+
+    async fn sequence_get_last_entry(
+        &self,
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<(u64, Vec<u8>)>;
+
+    async fn sequence_get_last_index(&self, name: XorName, tag: u64, private: bool) -> Result<u64>;
+
+    async fn sequence_get_entry(
+        &self,
+        name: XorName,
+        tag: u64,
+        index: u64,
+        private: bool,
+    ) -> Result<Vec<u8>>;
+
+    async fn append_to_sequence(
+        &self,
+        data: &[u8],
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<()>;
+
+    async fn sequence_get_owner(
+        &self,
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<SafeNdPublicKey>;
+
+    async fn sequence_set_owner(
+        &self,
+        name: XorName,
+        tag: u64,
+        owner: SafeNdPublicKey,
+        private: bool,
+    ) -> Result<()>;
+}
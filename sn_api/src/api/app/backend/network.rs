@@ -0,0 +1,647 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use super::{
+    super::{fetch::Range, helpers::xorname_to_hex},
+    StorageBackend, TransferRecord,
+};
+use crate::{Error, Result};
+use async_trait::async_trait;
+use log::{debug, info, warn};
+use sn_client::{Client, ClientError as SafeClientError};
+use sn_data_types::{
+    Blob, BlobAddress, Error as SafeNdError, Keypair, Map, MapAction, MapAddress, MapEntryActions,
+    MapPermissionSet, MapSeqEntryActions, MapSeqValue, MapValue, Money, PrivateBlob, PublicBlob,
+    PublicKey as SafeNdPublicKey, SeqMap, SequenceAddress, SequenceIndex,
+    SequencePrivatePermissions, SequencePublicPermissions, SequenceUser,
+};
+use std::{collections::BTreeMap, sync::Arc};
+use threshold_crypto::PublicKey;
+use xor_name::XorName;
+
+// Storage backend which sends all requests to the SAFE Network
+#[derive(Clone)]
+pub(crate) struct NetworkBackend {
+    client: Client,
+}
+
+impl NetworkBackend {
+    // Connect to the SAFE Network
+    pub async fn connect() -> Result<Self> {
+        debug!("Connecting to SAFE Network...");
+
+        let _disconnect_cb = || {
+            warn!("Connection with the SAFE Network was lost");
+        };
+
+        let client = Client::new(None).await?;
+
+        // let app = match auth_credentials {
+        //     Some(auth_credentials) => {
+        //         let auth_granted = decode_ipc_msg(auth_credentials)?;
+        //         match auth_granted {
+        //             AuthResponseType::Registered(authgranted) => {
+        //                 // TODO: This needs an existing SK now.
+        //                 Client::new(None).await
+        //                 // Client::new(app_id.to_string(), authgranted, disconnect_cb).await
+        //             }
+        //             // unregistered type used for returning bootstrap config for client
+        //             // TODO: rename?
+        //             AuthResponseType::Unregistered(config) => {
+        //                 // TODO: what to do with config...
+        //                 Client::new(None).await
+        //             }
+        //         }
+        //     }
+        //     None => Client::new(None).await,
+        // }
+        // .map_err(|err| {
+        //     Error::ConnectionError(format!("Failed to connect to the SAFE Network: {:?}", err))
+        // })?;
+
+        debug!("Successfully connected to the Network!!!");
+        Ok(Self { client })
+    }
+
+    async fn edit_map_entries(
+        &self,
+        name: XorName,
+        tag: u64,
+        entry_actions: MapSeqEntryActions,
+        error_msg: &str,
+    ) -> Result<()> {
+        let mut client = self.client.clone();
+        let message = error_msg.to_string();
+        let address = MapAddress::Seq { name, tag };
+        client
+            .edit_map_entries(address, MapEntryActions::Seq(entry_actions))
+            .await
+            .map_err(|err| {
+                if let SafeClientError::DataError(SafeNdError::InvalidEntryActions(_)) = err {
+                    Error::EntryExists(format!("{}: {}", message, err))
+                } else {
+                    Error::NetDataError(format!("{}: {}", message, err))
+                }
+            })
+    }
+}
+
+#[async_trait]
+impl StorageBackend for NetworkBackend {
+    async fn keypair(&self) -> Result<Arc<Keypair>> {
+        let kp = self.client.keypair().await;
+        Ok(kp)
+    }
+
+    // The client is created with a new random keypair on each connection,
+    // until the auth credentials are used to connect with the app's keypair
+    fn has_persistent_keypair(&self) -> bool {
+        false
+    }
+
+    // === Money operations ===
+    async fn read_balance_from_keypair(&self, id: Keypair) -> Result<Money> {
+        let mut temp_client = Client::new(Some(id)).await?;
+        let coins = temp_client
+            .get_balance()
+            .await
+            .map_err(|e| Error::NetDataError(format!("Failed to retrieve balance: {:?}", e)))?;
+
+        Ok(coins)
+    }
+
+    #[cfg(feature = "simulated-payouts")]
+    async fn trigger_simulated_farming_payout(
+        &self,
+        amount: Money,
+        id: Option<Keypair>,
+    ) -> Result<()> {
+        let mut client = match id {
+            Some(id) => Client::new(Some(id)).await?,
+            None => self.client.clone(),
+        };
+
+        client.trigger_simulated_farming_payout(amount).await?;
+
+        Ok(())
+    }
+
+    async fn safecoin_transfer_to_xorname(
+        &self,
+        _from_id: Option<Keypair>,
+        to_xorname: XorName,
+        _amount: Money,
+    ) -> Result<u64> {
+        // TODO: attempt to get wallet pk from xorname
+
+        // let to_pk = self.fetch(format!("safe://{:?}", to_xorname));
+        // let to_url  = xorurl::SafeUrl::from::<XorName>(to_xorname);
+
+        // let from_fullid = from_sk.map(Keypair::from);
+        // let transfer_id = client
+        //     .send_money( to_xorname, amount)
+        //     .await
+        //     .map_err(|err| match err {
+        //         SafeClientError::DataError(SafeNdError::ExcessiveValue)
+        //         | SafeClientError::DataError(SafeNdError::InsufficientBalance) => {
+        //             Error::NotEnoughBalance(amount.to_string())
+        //         }
+        //         SafeClientError::DataError(SafeNdError::InvalidOperation) => {
+        //             Error::InvalidAmount(amount.to_string())
+        //         }
+        //         other => Error::NetDataError(format!("Failed to transfer coins: {:?}", other)),
+        //     })?;
+
+        // Ok(transfer_id)
+
+        Err(Error::NotImplementedError(format!(
+            "Failed to transfer coins to {}: transfers to a XorName are not supported yet by the SAFE Network client",
+            xorname_to_hex(&to_xorname)
+        )))
+    }
+
+    async fn safecoin_transfer_to_pk(
+        &self,
+        from_id: Option<Keypair>,
+        to_pk: SafeNdPublicKey,
+        amount: Money,
+    ) -> Result<(u64, SafeNdPublicKey)> {
+        let mut client = match from_id {
+            Some(id) => Client::new(Some(id)).await?,
+            None => self.client.clone(),
+        };
+
+        let transfer_id = client.send_money(to_pk, amount).await?;
+
+        Ok(transfer_id)
+    }
+
+    async fn transfer_history(&self, _id: Keypair) -> Result<Vec<TransferRecord>> {
+        // TODO: read the history from the Replicas once the client exposes it
+        Err(Error::NetDataError(
+            "Failed to retrieve transfer history: not supported yet by the SAFE Network client"
+                .to_string(),
+        ))
+    }
+
+    // === Blob operations ===
+    async fn store_public_blob(&self, data: &[u8], dry_run: bool) -> Result<XorName> {
+        let mut client = self.client.clone();
+
+        let data_vec = data.to_vec();
+        let blob_for_storage = Blob::Public(PublicBlob::new(data_vec));
+        let xorname = *blob_for_storage.address().name();
+
+        let _data_map = client
+            .generate_data_map(&blob_for_storage)
+            .await
+            .map_err(|e| {
+                Error::NetDataError(format!(
+                    "Failed to create data map for Public Blob: {:?}",
+                    e
+                ))
+            })?;
+
+        if !dry_run {
+            client
+                .store_blob(blob_for_storage)
+                .await
+                .map_err(|e| Error::NetDataError(format!("Failed to PUT Public Blob: {:?}", e)))?;
+        }
+
+        Ok(xorname)
+    }
+
+    async fn get_public_blob(&self, xorname: XorName, range: Range) -> Result<Vec<u8>> {
+        let mut client = self.client.clone();
+        let blob_address = BlobAddress::Public(xorname);
+        let data = if let Some((start, end)) = range {
+            let len = if let Some(end_index) = end {
+                Some(end_index - start.unwrap_or_else(|| 0))
+            } else {
+                None
+            };
+            client.get_blob(blob_address, start, len).await
+        } else {
+            client.get_blob(blob_address, None, None).await
+        }
+        .map_err(|e| Error::NetDataError(format!("Failed to GET Public Blob: {:?}", e)))?;
+
+        Ok(data.value().clone())
+    }
+
+    async fn store_private_blob(&self, data: &[u8], dry_run: bool) -> Result<XorName> {
+        let mut client = self.client.clone();
+        let owner = client.public_key().await;
+
+        let blob_for_storage = Blob::Private(PrivateBlob::new(data.to_vec(), owner));
+        let xorname = *blob_for_storage.address().name();
+
+        if !dry_run {
+            client
+                .store_blob(blob_for_storage)
+                .await
+                .map_err(|e| Error::NetDataError(format!("Failed to PUT Private Blob: {:?}", e)))?;
+        }
+
+        Ok(xorname)
+    }
+
+    async fn get_private_blob(&self, xorname: XorName) -> Result<Vec<u8>> {
+        let mut client = self.client.clone();
+        let data = client
+            .get_blob(BlobAddress::Private(xorname), None, None)
+            .await
+            .map_err(|e| Error::NetDataError(format!("Failed to GET Private Blob: {:?}", e)))?;
+
+        Ok(data.value().clone())
+    }
+
+    // === Map operations ===
+    async fn store_map(
+        &self,
+        name: Option<XorName>,
+        tag: u64,
+        // _data: Option<String>,
+        _permissions: Option<String>,
+    ) -> Result<XorName> {
+        let mut safe_client = self.client.clone();
+        let client = &safe_client;
+        let owner_key_option = client.public_key().await;
+        let owners = if let SafeNdPublicKey::Bls(owners) = owner_key_option {
+            owners
+        } else {
+            return Err(Error::Unexpected(
+                "Failed to retrieve public key.".to_string(),
+            ));
+        };
+
+        let xorname = name.unwrap_or_else(rand::random);
+
+        let permission_set = MapPermissionSet::new()
+            .allow(MapAction::Read)
+            .allow(MapAction::Insert)
+            .allow(MapAction::Update)
+            .allow(MapAction::Delete)
+            .allow(MapAction::ManagePermissions);
+
+        let mut permission_map = BTreeMap::new();
+        let sign_pk = get_public_bls_key(&safe_client).await?;
+        let app_pk = SafeNdPublicKey::Bls(sign_pk);
+        permission_map.insert(app_pk, permission_set);
+
+        let map = Map::Seq(SeqMap::new_with_data(
+            xorname,
+            tag,
+            BTreeMap::new(),
+            permission_map,
+            SafeNdPublicKey::Bls(owners),
+        ));
+
+        safe_client
+            .store_map(map)
+            .await
+            .map_err(|err| Error::NetDataError(format!("Failed to put mutable data: {}", err)))?;
+
+        Ok(xorname)
+    }
+
+    async fn get_map(&self, name: XorName, tag: u64) -> Result<Map> {
+        let mut client = self.client.clone();
+        let address = MapAddress::Seq { name, tag };
+
+        client
+            .get_map(address)
+            .await
+            .map_err(|e| Error::NetDataError(format!("Failed to get MD: {:?}", e)))
+    }
+
+    async fn map_insert(&self, name: XorName, tag: u64, key: &[u8], value: &[u8]) -> Result<()> {
+        let entry_actions = MapSeqEntryActions::new();
+        let entry_actions = entry_actions.ins(key.to_vec(), value.to_vec(), 0);
+        self.edit_map_entries(name, tag, entry_actions, "Failed to insert to SeqMD")
+            .await
+    }
+
+    async fn map_get_value(&self, name: XorName, tag: u64, key: &[u8]) -> Result<MapValue> {
+        let mut client = self.client.clone();
+        let key_vec = key.to_vec();
+        let address = MapAddress::Seq { name, tag };
+
+        client
+            .get_map_value(address, key_vec)
+            .await
+            .map_err(|err| match err {
+                SafeClientError::DataError(SafeNdError::AccessDenied) => {
+                    Error::AccessDenied(format!("Failed to retrieve a key: {:?}", key))
+                }
+                SafeClientError::DataError(SafeNdError::NoSuchData) => {
+                    Error::ContentNotFound(format!(
+                        "Sequenced Map not found at Xor name: {}",
+                        xorname_to_hex(&name)
+                    ))
+                }
+                SafeClientError::DataError(SafeNdError::NoSuchEntry) => {
+                    Error::EntryNotFound(format!(
+                        "Entry not found in Sequenced Map found at Xor name: {}",
+                        xorname_to_hex(&name)
+                    ))
+                }
+                err => Error::NetDataError(format!("Failed to retrieve a key. {:?}", err)),
+            })
+    }
+
+    async fn list_map_entries(
+        &self,
+        name: XorName,
+        tag: u64,
+    ) -> Result<BTreeMap<Vec<u8>, MapSeqValue>> {
+        let mut client = self.client.clone();
+        client
+            .list_seq_map_entries(name, tag)
+            .await
+            .map_err(|err| match err {
+                SafeClientError::DataError(SafeNdError::AccessDenied) => {
+                    Error::AccessDenied(format!(
+                        "Failed to get Sequenced Map at: {:?} (type tag: {})",
+                        name, tag
+                    ))
+                }
+                SafeClientError::DataError(SafeNdError::NoSuchData) => {
+                    Error::ContentNotFound(format!(
+                        "Sequenced Map not found at Xor name: {} (type tag: {})",
+                        xorname_to_hex(&name),
+                        tag
+                    ))
+                }
+                SafeClientError::DataError(SafeNdError::NoSuchEntry) => {
+                    Error::EntryNotFound(format!(
+                        "Entry not found in Sequenced Map found at Xor name: {} (type tag: {})",
+                        xorname_to_hex(&name),
+                        tag
+                    ))
+                }
+                err => Error::NetDataError(format!("Failed to get Sequenced Map. {:?}", err)),
+            })
+    }
+
+    async fn update_map(
+        &self,
+        name: XorName,
+        tag: u64,
+        key: &[u8],
+        value: &[u8],
+        version: u64,
+    ) -> Result<()> {
+        let entry_actions = MapSeqEntryActions::new();
+        let entry_actions = entry_actions.update(key.to_vec(), value.to_vec(), version);
+        self.edit_map_entries(name, tag, entry_actions, "Failed to update SeqMD")
+            .await
+    }
+
+    async fn map_delete(&self, name: XorName, tag: u64, key: &[u8], version: u64) -> Result<()> {
+        let entry_actions = MapSeqEntryActions::new();
+        let entry_actions = entry_actions.del(key.to_vec(), version);
+        self.edit_map_entries(name, tag, entry_actions, "Failed to delete from SeqMD")
+            .await
+    }
+
+    // === Sequence data operations ===
+    async fn store_sequence(
+        &self,
+        data: &[u8],
+        name: Option<XorName>,
+        tag: u64,
+        _permissions: Option<String>,
+        private: bool,
+    ) -> Result<XorName> {
+        let mut safe_client = self.client.clone();
+        let xorname = name.unwrap_or_else(rand::random);
+        info!("Xorname for storage: {:?}", &xorname);
+
+        let app_public_key = get_public_bls_key(&safe_client).await?;
+
+        // The Sequence's owner will be the user
+        let user_acc_owner = safe_client.public_key().await;
+
+        // Store the Sequence on the network
+        let _address = if private {
+            // Set permissions for append, delete, and manage perms to this application
+            let mut perms = BTreeMap::default();
+            let _ = perms.insert(
+                SafeNdPublicKey::Bls(app_public_key),
+                SequencePrivatePermissions::new(true, true, true),
+            );
+
+            safe_client
+                .store_private_sequence(
+                    Some(vec![data.to_vec()]),
+                    xorname,
+                    tag,
+                    user_acc_owner,
+                    perms,
+                )
+                .await
+                .map_err(|e| {
+                    Error::NetDataError(format!("Failed to store Private Sequence data: {:?}", e))
+                })?
+        } else {
+            // Set permissions for append and manage perms to this application
+            let user_app = SequenceUser::Key(SafeNdPublicKey::Bls(app_public_key));
+            let mut perms = BTreeMap::default();
+            let _ = perms.insert(user_app, SequencePublicPermissions::new(true, true));
+
+            safe_client
+                .store_public_sequence(
+                    Some(vec![data.to_vec()]),
+                    xorname,
+                    tag,
+                    user_acc_owner,
+                    perms,
+                )
+                .await
+                .map_err(|e| {
+                    Error::NetDataError(format!("Failed to store Public Sequence data: {:?}", e))
+                })?
+        };
+
+        Ok(xorname)
+    }
+
+    async fn sequence_get_last_entry(
+        &self,
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<(u64, Vec<u8>)> {
+        let mut safe_client = self.client.clone();
+
+        let sequence_address = if private {
+            SequenceAddress::Private { name, tag }
+        } else {
+            SequenceAddress::Public { name, tag }
+        };
+        safe_client
+            .get_sequence_last_entry(sequence_address)
+            .await
+            .map_err(|err| {
+                if let SafeClientError::DataError(SafeNdError::NoSuchEntry) = err {
+                    Error::EmptyContent(format!("Empty Sequence found at XoR name {}", name))
+                } else {
+                    Error::NetDataError(format!(
+                        "Failed to retrieve last entry from Sequence data: {:?}",
+                        err
+                    ))
+                }
+            })
+    }
+
+    // The network doesn't serve the index of the last entry on its own,
+    // it's still cheaper for callers than decoding the entry though
+    async fn sequence_get_last_index(&self, name: XorName, tag: u64, private: bool) -> Result<u64> {
+        let (index, _) = self.sequence_get_last_entry(name, tag, private).await?;
+        Ok(index)
+    }
+
+    async fn sequence_get_entry(
+        &self,
+        name: XorName,
+        tag: u64,
+        index: u64,
+        private: bool,
+    ) -> Result<Vec<u8>> {
+        let mut safe_client = self.client.clone();
+
+        let sequence_address = if private {
+            SequenceAddress::Private { name, tag }
+        } else {
+            SequenceAddress::Public { name, tag }
+        };
+        let start = SequenceIndex::FromStart(index);
+        let end = SequenceIndex::FromStart(index + 1);
+        let res = safe_client
+            .get_sequence_range(sequence_address, (start, end))
+            .await
+            .map_err(|err| {
+                if let SafeClientError::DataError(SafeNdError::NoSuchEntry) = err {
+                    Error::VersionNotFound(format!(
+                        "Invalid version ({}) for Sequence found at XoR name {}",
+                        index, name
+                    ))
+                } else {
+                    Error::NetDataError(format!(
+                        "Failed to retrieve entry at index {} from Sequence data: {:?}",
+                        index, err
+                    ))
+                }
+            })?;
+
+        let entry = res.get(0).ok_or_else(|| {
+            Error::EmptyContent(format!(
+                "Empty Sequence found at Xor name {}",
+                xorname_to_hex(&name)
+            ))
+        })?;
+
+        Ok(entry.to_vec())
+    }
+
+    async fn append_to_sequence(
+        &self,
+        data: &[u8],
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<()> {
+        let mut safe_client = self.client.clone();
+
+        let sequence_address = if private {
+            SequenceAddress::Private { name, tag }
+        } else {
+            SequenceAddress::Public { name, tag }
+        };
+        safe_client
+            .append_to_sequence(sequence_address, data.to_vec())
+            .await
+            .map_err(|e| Error::NetDataError(format!("Failed to append to Sequence: {:?}", e)))
+    }
+
+    async fn sequence_get_owner(
+        &self,
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<SafeNdPublicKey> {
+        let mut safe_client = self.client.clone();
+
+        let sequence_address = if private {
+            SequenceAddress::Private { name, tag }
+        } else {
+            SequenceAddress::Public { name, tag }
+        };
+        let owner = safe_client
+            .get_sequence_owner(sequence_address)
+            .await
+            .map_err(|err| match err {
+                SafeClientError::DataError(SafeNdError::NoSuchData) => {
+                    Error::ContentNotFound(format!(
+                        "Sequence not found at Xor name: {} (type tag: {})",
+                        xorname_to_hex(&name),
+                        tag
+                    ))
+                }
+                err => Error::NetDataError(format!(
+                    "Failed to retrieve the owner of Sequence data: {:?}",
+                    err
+                )),
+            })?;
+
+        Ok(owner.public_key)
+    }
+
+    async fn sequence_set_owner(
+        &self,
+        name: XorName,
+        tag: u64,
+        owner: SafeNdPublicKey,
+        private: bool,
+    ) -> Result<()> {
+        let mut safe_client = self.client.clone();
+
+        let sequence_address = if private {
+            SequenceAddress::Private { name, tag }
+        } else {
+            SequenceAddress::Public { name, tag }
+        };
+        safe_client
+            .set_sequence_owner(sequence_address, owner)
+            .await
+            .map_err(|err| match err {
+                SafeClientError::DataError(SafeNdError::AccessDenied) => Error::AccessDenied(
+                    format!("Not allowed to change the owner of Sequence at: {}", name),
+                ),
+                err => Error::NetDataError(format!(
+                    "Failed to change the owner of Sequence data: {:?}",
+                    err
+                )),
+            })
+    }
+}
+
+// Helpers
+
+async fn get_public_bls_key(safe_client: &Client) -> Result<PublicKey> {
+    let pk = safe_client
+        .public_key()
+        .await
+        .bls()
+        .ok_or_else(|| Error::Unexpected("Client's key is not a BLS Public Key".to_string()))?;
+
+    Ok(pk)
+}
@@ -0,0 +1,163 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use super::fetch::Range;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::{max, min};
+use xor_name::XorName;
+
+// A chunk of data stored as a Blob, and its size in bytes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BlobChunk {
+    pub xorname: XorName,
+    pub size: u64,
+}
+
+// List of the Blobs, in order, which together make up a piece of data
+// too large to be stored as a single Blob. A Blob holding a manifest is targeted
+// by XOR-URLs with the BlobManifest content type, thus the media type of the
+// content, if any, is kept in the manifest instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BlobManifest {
+    pub size: u64,
+    pub chunks: Vec<BlobChunk>,
+    #[serde(default)]
+    pub media_type: Option<String>,
+}
+
+impl BlobManifest {
+    pub fn new(chunks: Vec<BlobChunk>, media_type: Option<&str>) -> Self {
+        let size = chunks.iter().map(|chunk| chunk.size).sum();
+        Self {
+            size,
+            chunks,
+            media_type: media_type.map(|media_type| media_type.to_string()),
+        }
+    }
+
+    pub fn serialise(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|err| {
+            Error::Unexpected(format!("Couldn't serialise the Blob manifest: {:?}", err))
+        })
+    }
+
+    pub fn deserialise(data: &[u8]) -> Result<Self> {
+        serde_json::from_slice(data).map_err(|err| {
+            Error::ContentError(format!("Couldn't deserialise the Blob manifest: {:?}", err))
+        })
+    }
+
+    // Returns the chunks which hold the data within the range, along with the range to read
+    // from each of them, which is None when the whole chunk is needed
+    pub fn chunks_for_range(&self, range: Range) -> Vec<(XorName, Range)> {
+        let (start, end) = match range {
+            Some((start, end)) => (start.unwrap_or(0), end.unwrap_or(self.size)),
+            None => (0, self.size),
+        };
+        let end = min(end, self.size);
+
+        let mut chunks = vec![];
+        let mut offset = 0;
+        for chunk in self.chunks.iter() {
+            let chunk_end = offset + chunk.size;
+            if chunk_end > start && offset < end {
+                let from = max(start, offset) - offset;
+                let to = min(end, chunk_end) - offset;
+                let chunk_range = if from == 0 && to == chunk.size {
+                    None
+                } else {
+                    Some((Some(from), Some(to)))
+                };
+                chunks.push((chunk.xorname, chunk_range));
+            }
+            offset = chunk_end;
+        }
+
+        chunks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gen_manifest() -> BlobManifest {
+        BlobManifest::new(
+            vec![
+                BlobChunk {
+                    xorname: XorName([1; 32]),
+                    size: 10,
+                },
+                BlobChunk {
+                    xorname: XorName([2; 32]),
+                    size: 10,
+                },
+                BlobChunk {
+                    xorname: XorName([3; 32]),
+                    size: 5,
+                },
+            ],
+            Some("text/plain"),
+        )
+    }
+
+    #[test]
+    fn test_blob_manifest_serialisation() -> Result<()> {
+        let manifest = gen_manifest();
+        assert_eq!(manifest.size, 25);
+
+        let serialised = manifest.serialise()?;
+        assert_eq!(BlobManifest::deserialise(&serialised)?, manifest);
+        match BlobManifest::deserialise(b"some other content") {
+            Err(Error::ContentError(_)) => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Unexpected result when deserialising a non-manifest: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[test]
+    fn test_blob_manifest_chunks_for_range() {
+        let manifest = gen_manifest();
+
+        assert_eq!(
+            manifest.chunks_for_range(None),
+            vec![
+                (XorName([1; 32]), None),
+                (XorName([2; 32]), None),
+                (XorName([3; 32]), None)
+            ]
+        );
+
+        assert_eq!(
+            manifest.chunks_for_range(Some((Some(12), Some(15)))),
+            vec![(XorName([2; 32]), Some((Some(2), Some(5))))]
+        );
+
+        assert_eq!(
+            manifest.chunks_for_range(Some((Some(5), Some(22)))),
+            vec![
+                (XorName([1; 32]), Some((Some(5), Some(10)))),
+                (XorName([2; 32]), None),
+                (XorName([3; 32]), Some((Some(0), Some(2))))
+            ]
+        );
+
+        assert_eq!(
+            manifest.chunks_for_range(Some((Some(20), None))),
+            vec![(XorName([3; 32]), None)]
+        );
+
+        assert!(manifest
+            .chunks_for_range(Some((Some(25), Some(30))))
+            .is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use futures::io::{AsyncRead, AsyncReadExt};
+use lazy_static::lazy_static;
+use std::{cmp::min, io};
+
+// Chunks are never smaller than this, other than the last chunk of the data
+pub(crate) const CHUNK_MIN_SIZE: usize = 256 * 1024;
+
+// Chunks are cut at this size if no boundary was found before reaching it
+pub(crate) const CHUNK_MAX_SIZE: usize = 4 * 1024 * 1024;
+
+// A boundary is found when the top 20 bits of the rolling hash are all zero,
+// which makes chunks be around 1MB larger than the min size on average
+const CHUNK_BOUNDARY_MASK: u64 = ((1 << 20) - 1) << 44;
+
+lazy_static! {
+    // Random values for each byte value, used by the rolling hash. These are generated
+    // from a fixed seed since changing them would change where all chunks boundaries are
+    static ref GEAR: [u64; 256] = {
+        let mut gear = [0; 256];
+        let mut seed: u64 = 0x5afe_c0de_5afe_c0de;
+        for value in gear.iter_mut() {
+            // splitmix64
+            seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            *value = z ^ (z >> 31);
+        }
+        gear
+    };
+}
+
+// Splits the data read from a stream into chunks whose boundaries are defined by their content,
+// using a rolling hash, so inserting or removing bytes only changes the chunks around the edit.
+// No more than CHUNK_MAX_SIZE bytes are kept in memory at any time.
+pub(crate) struct ContentChunker {
+    buffer: Vec<u8>,
+    eof: bool,
+}
+
+impl ContentChunker {
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::with_capacity(CHUNK_MAX_SIZE),
+            eof: false,
+        }
+    }
+
+    // Returns the next chunk of data, or None if all data was already read
+    pub async fn next_chunk<R>(&mut self, reader: &mut R) -> io::Result<Option<Vec<u8>>>
+    where
+        R: AsyncRead + Unpin + ?Sized,
+    {
+        if !self.eof && self.buffer.len() < CHUNK_MAX_SIZE {
+            let missing = CHUNK_MAX_SIZE - self.buffer.len();
+            let read = (&mut *reader)
+                .take(missing as u64)
+                .read_to_end(&mut self.buffer)
+                .await?;
+            if read < missing {
+                self.eof = true;
+            }
+        }
+
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+
+        let boundary = find_boundary(&self.buffer);
+        let rest = self.buffer.split_off(boundary);
+        Ok(Some(std::mem::replace(&mut self.buffer, rest)))
+    }
+}
+
+// Find where the first chunk of the data ends
+fn find_boundary(data: &[u8]) -> usize {
+    if data.len() <= CHUNK_MIN_SIZE {
+        return data.len();
+    }
+
+    let end = min(data.len(), CHUNK_MAX_SIZE);
+    let mut hash: u64 = 0;
+    for (i, byte) in data[..end].iter().enumerate().skip(CHUNK_MIN_SIZE) {
+        hash = (hash << 1).wrapping_add(GEAR[*byte as usize]);
+        if hash & CHUNK_BOUNDARY_MASK == 0 {
+            return i + 1;
+        }
+    }
+
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+    use rand::{rngs::StdRng, RngCore, SeedableRng};
+    use std::collections::BTreeSet;
+
+    fn gen_random_data(size: usize) -> Vec<u8> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut data = vec![0; size];
+        rng.fill_bytes(&mut data);
+        data
+    }
+
+    async fn chunk_data(data: &[u8]) -> Result<Vec<Vec<u8>>> {
+        let mut chunker = ContentChunker::new();
+        let mut reader = data;
+        let mut chunks = vec![];
+        while let Some(chunk) = chunker
+            .next_chunk(&mut reader)
+            .await
+            .map_err(|err| crate::Error::Unexpected(err.to_string()))?
+        {
+            chunks.push(chunk);
+        }
+        Ok(chunks)
+    }
+
+    #[tokio::test]
+    async fn test_chunker_sizes() -> Result<()> {
+        let data = gen_random_data(10 * 1024 * 1024);
+        let chunks = chunk_data(&data).await?;
+
+        assert!(chunks.len() > 1);
+        let last = chunks.len() - 1;
+        for (i, chunk) in chunks.iter().enumerate() {
+            assert!(chunk.len() <= CHUNK_MAX_SIZE);
+            if i != last {
+                assert!(chunk.len() > CHUNK_MIN_SIZE);
+            }
+        }
+        assert_eq!(chunks.concat(), data);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunker_small_data() -> Result<()> {
+        let chunks = chunk_data(b"Something super good").await?;
+        assert_eq!(chunks, vec![b"Something super good".to_vec()]);
+
+        let chunks = chunk_data(b"").await?;
+        assert!(chunks.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_chunker_insertion_keeps_most_chunks() -> Result<()> {
+        let data = gen_random_data(10 * 1024 * 1024);
+        let chunks: BTreeSet<Vec<u8>> = chunk_data(&data).await?.into_iter().collect();
+
+        // let's insert a few bytes in the middle of the data
+        let mut modified_data = data[..5 * 1024 * 1024].to_vec();
+        modified_data.extend(b"some new bytes");
+        modified_data.extend(&data[5 * 1024 * 1024..]);
+        let modified_chunks = chunk_data(&modified_data).await?;
+
+        let new_chunks = modified_chunks
+            .iter()
+            .filter(|chunk| !chunks.contains(*chunk))
+            .count();
+        // only the chunk where the bytes were inserted, and perhaps its neighbour, shall change
+        assert!(new_chunks <= 2);
+        assert!(modified_chunks.len() > new_chunks);
+        Ok(())
+    }
+}
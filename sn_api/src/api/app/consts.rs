@@ -12,9 +12,15 @@ use super::xorurl::XorUrlBase;
 // Default base encoding used for XOR URLs
 pub const DEFAULT_XORURL_BASE: XorUrlBase = XorUrlBase::Base32z;
 
+// Default maximum number of files uploaded concurrently
+pub const DEFAULT_UPLOAD_CONCURRENCY: usize = 4;
+
 pub const CONTENT_ADDED_SIGN: &str = "+";
 pub const CONTENT_UPDATED_SIGN: &str = "*";
 pub const CONTENT_DELETED_SIGN: &str = "-";
+pub const CONTENT_RENAMED_SIGN: &str = ">";
+pub const CONTENT_RESUMED_SIGN: &str = "R";
+pub const CONTENT_SKIPPED_SIGN: &str = "S";
 pub const CONTENT_ERROR_SIGN: &str = "E";
 
 pub const FAKE_RDF_PREDICATE_LINK: &str = "link";
@@ -26,6 +32,9 @@ pub const FAKE_RDF_PREDICATE_ORIGINAL_MODIFIED: &str = "o_modified";
 pub const FAKE_RDF_PREDICATE_ORIGINAL_CREATED: &str = "o_created";
 pub const FAKE_RDF_PREDICATE_READONLY: &str = "readonly";
 pub const FAKE_RDF_PREDICATE_MODE_BITS: &str = "mode_bits";
+pub const FAKE_RDF_PREDICATE_CAPTURE: &str = "capture";
+pub const FAKE_RDF_PREDICATE_ALIAS: &str = "alias";
+pub const FAKE_RDF_PREDICATE_LINK_POLICY: &str = "link_policy";
 
 // see: https://stackoverflow.com/questions/18869772/mime-type-for-a-directory
 // We will use the FreeDesktop standard for directories and symlinks.
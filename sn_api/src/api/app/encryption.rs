@@ -0,0 +1,100 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Error, Result};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use sn_data_types::Keypair;
+use threshold_crypto::{Ciphertext, SecretKey};
+use tiny_keccak::sha3_256;
+
+// Domain separator so the key for private data is not shared with any other use of the keypair
+const PRIVATE_DATA_KEY_DOMAIN: &[u8] = b"safe-private-data/1";
+
+// Encrypts and decrypts the content of private data with a key derived from the app's keypair,
+// thus only the same keypair can decrypt the content it encrypted
+pub(crate) struct DataEncryptor {
+    secret_key: SecretKey,
+    seed: [u8; 32],
+}
+
+impl DataEncryptor {
+    pub fn from_keypair(keypair: &Keypair) -> Result<Self> {
+        let mut seeder = PRIVATE_DATA_KEY_DOMAIN.to_vec();
+        seeder.extend(keypair.secret_key()?.to_string().as_bytes());
+        let seed = sha3_256(&seeder);
+        let secret_key: SecretKey = StdRng::from_seed(seed).gen();
+        Ok(Self { secret_key, seed })
+    }
+
+    // The randomness used for the encryption is derived from the content itself, so the same
+    // content is always encrypted into the same ciphertext, and stored at the same location.
+    // This allows private content to be deduplicated the same way public content is.
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut seeder = self.seed.to_vec();
+        seeder.extend(data);
+        let mut rng = StdRng::from_seed(sha3_256(&seeder));
+        let ciphertext = self
+            .secret_key
+            .public_key()
+            .encrypt_with_rng(&mut rng, data);
+
+        bincode::serialize(&ciphertext)
+            .map_err(|err| Error::Unexpected(format!("Failed to serialise ciphertext: {}", err)))
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let ciphertext: Ciphertext = bincode::deserialize(data).map_err(|err| {
+            Error::ContentError(format!("Failed to deserialise encrypted content: {}", err))
+        })?;
+
+        self.secret_key.decrypt(&ciphertext).ok_or_else(|| {
+            Error::ContentError(
+                "Failed to decrypt content, it was not encrypted with the keypair in use"
+                    .to_string(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    #[test]
+    fn test_data_encryptor_roundtrip() -> Result<()> {
+        let keypair = Keypair::new_ed25519(&mut OsRng);
+        let encryptor = DataEncryptor::from_keypair(&keypair)?;
+
+        let data = b"Something super secret";
+        let encrypted = encryptor.encrypt(data)?;
+        assert!(!encrypted.windows(data.len()).any(|window| window == data));
+        assert_eq!(encryptor.decrypt(&encrypted)?, data);
+
+        // the same content is encrypted the same way, even with another instance
+        let other_encryptor = DataEncryptor::from_keypair(&keypair)?;
+        assert_eq!(other_encryptor.encrypt(data)?, encrypted);
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_encryptor_other_keypair() -> Result<()> {
+        let encryptor = DataEncryptor::from_keypair(&Keypair::new_ed25519(&mut OsRng))?;
+        let other_encryptor = DataEncryptor::from_keypair(&Keypair::new_ed25519(&mut OsRng))?;
+
+        let encrypted = encryptor.encrypt(b"Something super secret")?;
+        match other_encryptor.decrypt(&encrypted) {
+            Err(Error::ContentError(_)) => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Unexpected result when decrypting with another keypair: {:?}",
+                other
+            ))),
+        }
+    }
+}
@@ -9,7 +9,7 @@
 
 use super::{
     files::{FileItem, FileMeta, FilesMap},
-    nrs_map::NrsMap,
+    nrs_map::{NrsDefaultUsed, NrsLinkPolicy, NrsMap},
     realpath::RealPath,
     Safe, XorName,
 };
@@ -20,7 +20,10 @@ pub use super::{
 use crate::{Error, Result};
 use log::{debug, info};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
 
 pub type Range = Option<(Option<u64>, Option<u64>)>;
 
@@ -59,6 +62,14 @@ pub enum SafeData {
         metadata: Option<FileItem>,
         resolved_from: String,
     },
+    PrivateBlob {
+        xorurl: String,
+        xorname: XorName,
+        data: Vec<u8>,
+        media_type: Option<String>,
+        metadata: Option<FileItem>,
+        resolved_from: String,
+    },
     NrsMapContainer {
         public_name: Option<String>,
         xorurl: String,
@@ -67,6 +78,7 @@ pub enum SafeData {
         version: u64,
         nrs_map: NrsMap,
         data_type: SafeDataType,
+        link_policy: NrsLinkPolicy,
         resolved_from: String,
     },
     PublicSequence {
@@ -87,6 +99,25 @@ pub enum SafeData {
     },
 }
 
+/// How a single hop of the resolution of a safe:// URL was taken
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone, Default)]
+pub struct ResolutionHop {
+    /// The URL resolved in this hop
+    pub url: String,
+    /// Sub name of the NRS entry matched, e.g. `*.docs` for `pr-1.docs.mysite`
+    pub matched_sub_name: Option<String>,
+    /// Default of the NRS Map used, if the URL had no sub names
+    pub default: Option<NrsDefaultUsed>,
+    /// Version of the content selected, if it's versionable
+    pub version: Option<u64>,
+    /// Policy of the NRS entry which selected the version for the next hop
+    pub link_policy: Option<NrsLinkPolicy>,
+    /// Path within the FilesContainer found through `realpath`, e.g. following symlinks
+    pub realpath: Option<String>,
+    /// Time taken to resolve the hop
+    pub elapsed: Duration,
+}
+
 impl SafeData {
     pub fn xorurl(&self) -> String {
         use SafeData::*;
@@ -95,12 +126,25 @@ impl SafeData {
             | Wallet { xorurl, .. }
             | FilesContainer { xorurl, .. }
             | PublicBlob { xorurl, .. }
+            | PrivateBlob { xorurl, .. }
             | NrsMapContainer { xorurl, .. }
             | PublicSequence { xorurl, .. }
             | PrivateSequence { xorurl, .. } => xorurl.clone(),
         }
     }
 
+    // Version of the content, if it's versionable
+    pub fn version(&self) -> Option<u64> {
+        use SafeData::*;
+        match self {
+            FilesContainer { version, .. }
+            | NrsMapContainer { version, .. }
+            | PublicSequence { version, .. }
+            | PrivateSequence { version, .. } => Some(*version),
+            SafeKey { .. } | Wallet { .. } | PublicBlob { .. } | PrivateBlob { .. } => None,
+        }
+    }
+
     pub fn resolved_from(&self) -> String {
         use SafeData::*;
         match self {
@@ -108,6 +152,7 @@ impl SafeData {
             | Wallet { resolved_from, .. }
             | FilesContainer { resolved_from, .. }
             | PublicBlob { resolved_from, .. }
+            | PrivateBlob { resolved_from, .. }
             | NrsMapContainer { resolved_from, .. }
             | PrivateSequence { resolved_from, .. }
             | PublicSequence { resolved_from, .. } => resolved_from.clone(),
@@ -122,12 +167,12 @@ impl Safe {
     ///
     /// ### Fetch FilesContainer relative path file
     /// ```rust
-    /// # use sn_api::{Safe, fetch::SafeData};
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe, fetch::SafeData};
     /// # use std::collections::BTreeMap;
     /// # let mut safe = Safe::default();
     /// # async_std::task::block_on(async {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
-    ///     let (xorurl, _, _) = safe.files_container_create(Some("../testdata/"), None, true, false, false).await.unwrap();
+    ///     let (xorurl, _, _) = safe.files_container_create(Some("../testdata/"), None, true, false, FilesContainerCreateOptions::default()).await.unwrap();
     ///
     ///     let safe_data = safe.fetch( &format!( "{}/test.md", &xorurl.replace("?v=0", "") ), None ).await.unwrap();
     ///     let data_string = match safe_data {
@@ -147,7 +192,7 @@ impl Safe {
     /// # });
     /// ```
     pub async fn fetch(&mut self, url: &str, range: Range) -> Result<SafeData> {
-        let mut resolution_chain = self.retrieve_from_url(url, true, range, true).await?;
+        let (mut resolution_chain, _) = self.retrieve_from_url(url, true, range, true).await?;
         // Construct return data using the last and first items from the resolution chain
         match resolution_chain.pop() {
             Some(other_safe_data) => Ok(other_safe_data),
@@ -168,12 +213,12 @@ impl Safe {
     ///
     /// ### Inspect FilesContainer relative path file
     /// ```rust
-    /// # use sn_api::{Safe, fetch::SafeData};
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe, fetch::SafeData};
     /// # use std::collections::BTreeMap;
     /// # let mut safe = Safe::default();
     /// # async_std::task::block_on(async {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
-    ///     let (container_xorurl, _, _) = safe.files_container_create(Some("../testdata/"), None, true, false, false).await.unwrap();
+    ///     let (container_xorurl, _, _) = safe.files_container_create(Some("../testdata/"), None, true, false, FilesContainerCreateOptions::default()).await.unwrap();
     ///
     ///     let inspected_content = safe.inspect( &format!( "{}/test.md", &container_xorurl.replace("?v=0", "") ) ).await.unwrap();
     ///     match &inspected_content[0] {
@@ -199,6 +244,37 @@ impl Safe {
     /// # });
     /// ```
     pub async fn inspect(&mut self, url: &str) -> Result<Vec<SafeData>> {
+        let (resolution_chain, _) = self.retrieve_from_url(url, false, None, true).await?;
+        Ok(resolution_chain)
+    }
+
+    /// # Inspect a safe:// URL as the 'inspect' function does, also returning a trace of
+    /// # how each of the hops was resolved, e.g. the NRS entry matched or the time it took.
+    ///
+    /// ## Examples
+    ///
+    /// ### Trace the resolution of an NRS-URL
+    /// ```rust
+    /// # use sn_api::{files::FilesContainerCreateOptions, nrs_map::NrsLinkOptions, Safe};
+    /// # use rand::distributions::Alphanumeric;
+    /// # use rand::{thread_rng, Rng};
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let rand_string: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
+    ///     let (container_xorurl, _, _) = safe.files_container_create(Some("../testdata/"), None, true, false, FilesContainerCreateOptions::default()).await.unwrap();
+    ///     let _ = safe.nrs_map_container_create(&format!("docs.{}", rand_string), &container_xorurl, true, false, NrsLinkOptions::default(), false).await.unwrap();
+    ///
+    ///     let (inspected_content, trace) = safe.inspect_with_trace(&format!("safe://docs.{}/test.md", rand_string)).await.unwrap();
+    ///     assert_eq!(inspected_content.len(), trace.len());
+    ///     assert_eq!(trace[0].matched_sub_name, Some("docs".to_string()));
+    ///     assert_eq!(trace[1].version, Some(0));
+    /// # });
+    /// ```
+    pub async fn inspect_with_trace(
+        &mut self,
+        url: &str,
+    ) -> Result<(Vec<SafeData>, Vec<ResolutionHop>)> {
         self.retrieve_from_url(url, false, None, true).await
     }
 
@@ -211,7 +287,7 @@ impl Safe {
         retrieve_data: bool,
         range: Range,
         resolve_path: bool,
-    ) -> Result<Vec<SafeData>> {
+    ) -> Result<(Vec<SafeData>, Vec<ResolutionHop>)> {
         let current_xorurl_encoder = Safe::parse_url(url)?;
         info!(
             "URL parsed successfully, fetching: {}",
@@ -225,13 +301,37 @@ impl Safe {
         // Let's create a list keeping track each of the resolution hops we go through
         // TODO: pass option to get raw content AKA: Do not resolve beyond first thing.
         let mut resolution_chain = Vec::<SafeData>::default();
+        let mut resolution_trace = Vec::<ResolutionHop>::default();
         let mut next_to_resolve = Some((current_xorurl_encoder, None));
         let mut indirections_count = 0;
+        // NRS names visited, resolving the same one again means aliases make up a loop
+        let mut visited_nrs_names = Vec::<String>::default();
         while let Some((next_xorurl_encoder, metadata)) = next_to_resolve {
             if indirections_count == INDIRECTION_LIMIT {
                 return Err(Error::ContentError(format!("The maximum number of indirections ({}) was reached when trying to resolve the URL provided", INDIRECTION_LIMIT)));
             }
 
+            if next_xorurl_encoder.content_type() == SafeContentType::NrsMapContainer {
+                // The path doesn't change how a name is resolved, only where it ends up
+                let mut nrs_name_encoder = next_xorurl_encoder.clone();
+                nrs_name_encoder.set_path("");
+                let nrs_name = nrs_name_encoder.to_string();
+                if let Some(index) = visited_nrs_names.iter().position(|name| *name == nrs_name) {
+                    let mut nrs_loop = visited_nrs_names.split_off(index);
+                    nrs_loop.push(nrs_name);
+                    return Err(Error::ContentError(format!(
+                        "NRS resolution loop detected when trying to resolve the URL provided: {}",
+                        nrs_loop.join(" -> ")
+                    )));
+                }
+                visited_nrs_names.push(nrs_name);
+            }
+
+            let mut hop = ResolutionHop {
+                url: next_xorurl_encoder.to_string(),
+                ..ResolutionHop::default()
+            };
+            let started = Instant::now();
             let (step, next) = self
                 .resolve_one_indirection(
                     next_xorurl_encoder,
@@ -239,17 +339,23 @@ impl Safe {
                     retrieve_data,
                     range,
                     resolve_path,
+                    &mut hop,
                 )
                 .await?;
+            hop.elapsed = started.elapsed();
+            hop.version = step.version();
+            debug!("URL resolution hop: {:?}", hop);
 
             resolution_chain.push(step);
+            resolution_trace.push(hop);
             next_to_resolve = next;
             indirections_count += 1;
         }
 
-        Ok(resolution_chain)
+        Ok((resolution_chain, resolution_trace))
     }
 
+    // Resolves a single hop, recording in the trace how it was resolved
     async fn resolve_one_indirection(
         &mut self,
         mut the_xor: XorUrlEncoder,
@@ -257,6 +363,7 @@ impl Safe {
         retrieve_data: bool,
         range: Range,
         resolve_path: bool,
+        hop: &mut ResolutionHop,
     ) -> Result<(SafeData, Option<NextStepInfo>)> {
         let url = the_xor.to_string();
         let xorurl = the_xor.to_xorurl_string();
@@ -284,6 +391,7 @@ impl Safe {
                 let (files_map, next) = if resolve_path && path != "/" && !path.is_empty() {
                     // TODO: Move this logic (path resolver) to the FilesMap struct
                     let realpath = files_map.realpath(&path)?;
+                    hop.realpath = Some(realpath.clone());
                     match &files_map.get(&realpath) {
                         Some(file_item) => match file_item.get("type") {
                             Some(file_type) => {
@@ -354,10 +462,14 @@ impl Safe {
                     nrs_map
                 );
 
-                let target_url = nrs_map.resolve_for_subnames(the_xor.sub_names_vec())?;
-                debug!("Resolved target: {}", target_url);
+                let resolution = nrs_map.resolve_entry(the_xor.sub_names_vec())?;
+                let link_policy = resolution.link_policy;
+                debug!("Resolved target: {} ({})", resolution.link, link_policy);
+                hop.matched_sub_name = resolution.matched_sub_name;
+                hop.default = resolution.default;
+                hop.link_policy = Some(link_policy);
 
-                let mut target_xorurl_encoder = Safe::parse_url(&target_url)?;
+                let mut target_xorurl_encoder = Safe::parse_url(&resolution.link)?;
                 // Let's concatenate the path corresponding to the URL we are processing
                 // to the URL we resolved from NRS Map
                 let url_path = the_xor.path_decoded()?;
@@ -391,6 +503,7 @@ impl Safe {
                     version,
                     nrs_map,
                     data_type: the_xor.data_type(),
+                    link_policy,
                     resolved_from: url,
                 };
 
@@ -415,7 +528,7 @@ impl Safe {
                         };
                         Ok((safe_data, None))
                     }
-                    SafeDataType::PublicBlob => {
+                    SafeDataType::PublicBlob | SafeDataType::PrivateBlob => {
                         self.retrieve_blob(&the_xor, retrieve_data, None, &metadata, range)
                             .await
                     }
@@ -468,7 +581,7 @@ impl Safe {
                 }
 
                 match the_xor.data_type() {
-                    SafeDataType::PublicBlob => {
+                    SafeDataType::PublicBlob | SafeDataType::PrivateBlob => {
                         self.retrieve_blob(
                             &the_xor,
                             retrieve_data,
@@ -484,6 +597,29 @@ impl Safe {
                     ))),
                 }
             }
+            SafeContentType::BlobManifest => {
+                if !the_xor.sub_names_vec().is_empty() {
+                    let msg = format!(
+                        "Cannot resolve URL targetting raw content as it contains subnames: {}",
+                        xorurl
+                    );
+                    debug!("{}", msg);
+                    return Err(Error::InvalidXorUrl(msg));
+                }
+
+                match the_xor.data_type() {
+                    SafeDataType::PublicBlob | SafeDataType::PrivateBlob => {
+                        // The media type of content split into chunks is kept in its manifest
+                        let media_type = self.fetch_blob_manifest_media_type(&the_xor).await?;
+                        self.retrieve_blob(&the_xor, retrieve_data, media_type, &metadata, range)
+                            .await
+                    }
+                    other => Err(Error::ContentError(format!(
+                        "Data type '{:?}' not supported yet",
+                        other
+                    ))),
+                }
+            }
             SafeContentType::Wallet => {
                 if !the_xor.sub_names_vec().is_empty() {
                     let msg = format!(
@@ -531,21 +667,31 @@ impl Safe {
             )));
         };
 
+        // The content of a Private Blob is decrypted when fetched
         let data = if retrieve_data {
-            self.safe_client
-                .get_public_blob(the_xor.xorname(), range)
-                .await?
+            self.fetch_blob(the_xor, range).await?
         } else {
             vec![]
         };
 
-        let safe_data = SafeData::PublicBlob {
-            xorurl: the_xor.to_xorurl_string(),
-            xorname: the_xor.xorname(),
-            data,
-            media_type,
-            metadata: metadata.clone(),
-            resolved_from: the_xor.to_string(),
+        let safe_data = if the_xor.data_type() == SafeDataType::PrivateBlob {
+            SafeData::PrivateBlob {
+                xorurl: the_xor.to_xorurl_string(),
+                xorname: the_xor.xorname(),
+                data,
+                media_type,
+                metadata: metadata.clone(),
+                resolved_from: the_xor.to_string(),
+            }
+        } else {
+            SafeData::PublicBlob {
+                xorurl: the_xor.to_xorurl_string(),
+                xorname: the_xor.xorname(),
+                data,
+                media_type,
+                metadata: metadata.clone(),
+                resolved_from: the_xor.to_string(),
+            }
         };
 
         Ok((safe_data, None))
@@ -583,7 +729,11 @@ type NextStepInfo = (XorUrlEncoder, Option<FileItem>);
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::app::test_helpers::new_safe_instance;
+    use crate::api::app::files::FilesContainerCreateOptions;
+    use crate::api::app::nrs_map::NrsLinkOptions;
+    use crate::api::app::test_helpers::{
+        new_safe_instance, new_safe_instance_on_disk, random_local_storage_path,
+    };
     use crate::api::xorurl::XorUrlEncoder;
     use rand::distributions::Alphanumeric;
     use rand::{thread_rng, Rng};
@@ -643,7 +793,13 @@ mod tests {
     async fn test_fetch_files_container() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, false, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
@@ -690,13 +846,26 @@ mod tests {
         let mut safe = new_safe_instance().await?;
 
         let (xorurl, _, the_files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, false, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
         xorurl_encoder.set_content_version(Some(0));
         let (_nrs_map_xorurl, _, _nrs_map) = safe
-            .nrs_map_container_create(&site_name, &xorurl_encoder.to_string(), true, true, false)
+            .nrs_map_container_create(
+                &site_name,
+                &xorurl_encoder.to_string(),
+                true,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
 
         let nrs_url = format!("safe://{}", site_name);
@@ -735,20 +904,260 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_fetch_nrs_follow_latest() -> Result<()> {
+        let site_name: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
+
+        let mut safe = new_safe_instance().await?;
+        let (xorurl, _, _) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+
+        // a versioned link cannot follow the latest version
+        if safe
+            .nrs_map_container_create(
+                &site_name,
+                &xorurl,
+                true,
+                false,
+                NrsLinkOptions {
+                    link_policy: NrsLinkPolicy::FollowLatest,
+                    ..Default::default()
+                },
+                false,
+            )
+            .await
+            .is_ok()
+        {
+            return Err(Error::Unexpected(
+                "NRS map created with a versioned link to follow latest".to_string(),
+            ));
+        }
+
+        let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
+        xorurl_encoder.set_content_version(None);
+        let unversioned_link = xorurl_encoder.to_string();
+        let _ = safe
+            .nrs_map_container_create(
+                &site_name,
+                &unversioned_link,
+                true,
+                false,
+                NrsLinkOptions {
+                    link_policy: NrsLinkPolicy::FollowLatest,
+                    ..Default::default()
+                },
+                false,
+            )
+            .await?;
+
+        let (version, _, _) = safe
+            .files_container_add_from_raw(
+                b"0123456789",
+                &format!("{}/new.md", unversioned_link),
+                false,
+                false,
+                false,
+            )
+            .await?;
+        assert_eq!(version, 1);
+
+        let inspected_content = safe.inspect(&format!("safe://{}", site_name)).await?;
+        assert_eq!(inspected_content.len(), 2);
+        match (&inspected_content[0], &inspected_content[1]) {
+            (
+                SafeData::NrsMapContainer { link_policy, .. },
+                SafeData::FilesContainer { version, .. },
+            ) => {
+                assert_eq!(*link_policy, NrsLinkPolicy::FollowLatest);
+                assert_eq!(*version, 1);
+                Ok(())
+            }
+            other => Err(Error::Unexpected(format!(
+                "Unexpected content resolved following latest version: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_nrs_alias() -> Result<()> {
+        let site_name: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
+        let other_name: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
+
+        let mut safe = new_safe_instance().await?;
+        let (xorurl, _, _) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+        let _ = safe
+            .nrs_map_container_create(
+                &site_name,
+                &xorurl,
+                true,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+        let _ = safe
+            .nrs_map_container_create(
+                &other_name,
+                &xorurl,
+                true,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+
+        let _ = safe
+            .nrs_map_container_add_alias(
+                &format!("blog.{}", other_name),
+                &format!("safe://{}/subfolder", site_name),
+                false,
+                false,
+            )
+            .await?;
+
+        let content = safe
+            .fetch(&format!("safe://blog.{}/subexists.md", other_name), None)
+            .await?;
+        match &content {
+            SafeData::PublicBlob { data, .. } => {
+                assert!(data.starts_with(b"hello from a subfolder!"));
+            }
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected content resolved from NRS alias: {:?}",
+                    other
+                )))
+            }
+        }
+
+        // an alias of the alias' own name makes up a loop
+        let _ = safe
+            .nrs_map_container_add_alias(
+                &site_name,
+                &format!("safe://blog.{}", other_name),
+                true,
+                false,
+            )
+            .await?;
+        match safe.fetch(&format!("safe://{}", site_name), None).await {
+            Err(Error::ContentError(msg)) => {
+                assert!(msg.contains("loop"));
+                assert!(msg.contains(&format!("safe://{} -> ", site_name)));
+                Ok(())
+            }
+            other => Err(Error::Unexpected(format!(
+                "NRS alias loop was not detected: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inspect_with_trace() -> Result<()> {
+        let site_name: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
+
+        let mut safe = new_safe_instance().await?;
+        let (xorurl, _, _) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+        let _ = safe
+            .nrs_map_container_create(
+                &format!("docs.{}", site_name),
+                &xorurl,
+                true,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+        let _ = safe
+            .nrs_map_container_add(
+                &format!("*.docs.{}", site_name),
+                &xorurl,
+                false,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+
+        let (content, trace) = safe
+            .inspect_with_trace(&format!("safe://{}/test.md", site_name))
+            .await?;
+        assert_eq!(content.len(), 3);
+        assert_eq!(trace.len(), 3);
+        assert_eq!(trace[0].url, format!("safe://{}/test.md", site_name));
+        assert_eq!(trace[0].matched_sub_name, Some("docs".to_string()));
+        assert_eq!(
+            trace[0].default,
+            Some(NrsDefaultUsed::ExistingRdf("docs".to_string()))
+        );
+        assert_eq!(trace[0].version, Some(1));
+        assert_eq!(trace[0].link_policy, Some(NrsLinkPolicy::Pinned));
+        assert_eq!(trace[1].version, Some(0));
+        assert_eq!(trace[1].realpath, Some("/test.md".to_string()));
+        assert_eq!(trace[2].version, None);
+        assert_eq!(trace[2].realpath, None);
+
+        let (_, trace) = safe
+            .inspect_with_trace(&format!("safe://pr-1.docs.{}", site_name))
+            .await?;
+        assert_eq!(trace.len(), 2);
+        assert_eq!(trace[0].matched_sub_name, Some("*.docs".to_string()));
+        assert_eq!(trace[0].default, None);
+        assert_eq!(trace[1].realpath, None);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_fetch_resolvable_map_data() -> Result<()> {
         let site_name: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
 
         let mut safe = new_safe_instance().await?;
         let (xorurl, _, _the_files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, false, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
         xorurl_encoder.set_content_version(Some(0));
         let files_container_url = xorurl_encoder.to_string();
         let _ = safe
-            .nrs_map_container_create(&site_name, &files_container_url, true, true, false)
+            .nrs_map_container_create(
+                &site_name,
+                &files_container_url,
+                true,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
 
         let nrs_url = format!("safe://{}", site_name);
@@ -851,6 +1260,95 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_fetch_private_blob() -> Result<()> {
+        let path = random_local_storage_path();
+        let mut safe = new_safe_instance_on_disk(&path).await?;
+        let data = b"Something super private";
+        let xorurl = safe
+            .files_store_private_blob(data, Some("text/plain"), false)
+            .await?;
+
+        let xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
+        assert_eq!(xorurl_encoder.data_type(), SafeDataType::PrivateBlob);
+
+        let content = safe.fetch(&xorurl, None).await?;
+        assert!(
+            content
+                == SafeData::PrivateBlob {
+                    xorurl: xorurl.clone(),
+                    xorname: xorurl_encoder.xorname(),
+                    data: data.to_vec(),
+                    resolved_from: xorurl.clone(),
+                    media_type: Some("text/plain".to_string()),
+                    metadata: None,
+                }
+        );
+
+        let content = safe.fetch(&xorurl, Some((Some(16), None))).await?;
+        let _ = std::fs::remove_file(&path);
+        if let SafeData::PrivateBlob { data, .. } = &content {
+            assert_eq!(data.clone(), b"private".to_vec());
+            Ok(())
+        } else {
+            Err(Error::Unexpected(format!(
+                "Content fetched is not a PrivateBlob: {:?}",
+                content
+            )))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_private_files_container() -> Result<()> {
+        let path = random_local_storage_path();
+        let mut safe = new_safe_instance_on_disk(&path).await?;
+        let (xorurl, _, files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions {
+                    private: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let content = safe.fetch(&xorurl, None).await?;
+        if let SafeData::FilesContainer {
+            files_map: fetched_files_map,
+            data_type,
+            ..
+        } = &content
+        {
+            assert_eq!(*data_type, SafeDataType::PrivateSequence);
+            assert_eq!(*fetched_files_map, files_map);
+        } else {
+            return Err(Error::Unexpected(format!(
+                "Content fetched is not a FilesContainer: {:?}",
+                content
+            )));
+        }
+
+        let mut file_data = Vec::new();
+        std::fs::File::open("../testdata/test.md")
+            .and_then(|mut file| file.read_to_end(&mut file_data))
+            .map_err(|err| Error::Unexpected(format!("Failed to read local file: {}", err)))?;
+
+        let content = safe.fetch(&format!("{}/test.md", xorurl), None).await?;
+        let _ = std::fs::remove_file(&path);
+        if let SafeData::PrivateBlob { data, .. } = &content {
+            assert_eq!(data.clone(), file_data);
+            Ok(())
+        } else {
+            Err(Error::Unexpected(format!(
+                "Content fetched is not a PrivateBlob: {:?}",
+                content
+            )))
+        }
+    }
+
     #[tokio::test]
     async fn test_fetch_range_from_files_container() -> Result<()> {
         use std::fs::File;
@@ -858,13 +1356,26 @@ mod tests {
         let site_name: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
 
         let (xorurl, _, _files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, false, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
         xorurl_encoder.set_content_version(Some(0));
         let (_nrs_map_xorurl, _, _nrs_map) = safe
-            .nrs_map_container_create(&site_name, &xorurl_encoder.to_string(), true, true, false)
+            .nrs_map_container_create(
+                &site_name,
+                &xorurl_encoder.to_string(),
+                true,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
 
         let nrs_url = format!("safe://{}/test.md", site_name);
@@ -960,7 +1471,7 @@ mod tests {
             xorname,
             None,
             type_tag,
-            SafeDataType::PrivateBlob,
+            SafeDataType::UnseqMap,
             SafeContentType::Raw,
             None,
             None,
@@ -979,7 +1490,7 @@ mod tests {
             }
             Err(msg) => assert_eq!(
                 msg,
-                Error::ContentError("Data type 'PrivateBlob' not supported yet".to_string())
+                Error::ContentError("Data type 'UnseqMap' not supported yet".to_string())
             ),
         };
 
@@ -992,7 +1503,7 @@ mod tests {
             }
             Err(msg) => assert_eq!(
                 msg,
-                Error::ContentError("Data type 'PrivateBlob' not supported yet".to_string())
+                Error::ContentError("Data type 'UnseqMap' not supported yet".to_string())
             ),
         };
         Ok(())
@@ -1007,7 +1518,7 @@ mod tests {
             xorname,
             None,
             type_tag,
-            SafeDataType::PrivateBlob,
+            SafeDataType::UnseqMap,
             SafeContentType::MediaType("text/html".to_string()),
             None,
             None,
@@ -1026,7 +1537,7 @@ mod tests {
             }
             Err(msg) => assert_eq!(
                 msg,
-                Error::ContentError("Data type 'PrivateBlob' not supported yet".to_string())
+                Error::ContentError("Data type 'UnseqMap' not supported yet".to_string())
             ),
         };
 
@@ -1039,7 +1550,7 @@ mod tests {
             }
             Err(msg) => assert_eq!(
                 msg,
-                Error::ContentError("Data type 'PrivateBlob' not supported yet".to_string())
+                Error::ContentError("Data type 'UnseqMap' not supported yet".to_string())
             ),
         };
         Ok(())
@@ -8,9 +8,18 @@
 // Software.
 
 use super::{
+    blob_manifest::{BlobChunk, BlobManifest},
+    chunker::ContentChunker,
+    common::pk_bytes_to_hex,
     consts::*,
+    encryption::DataEncryptor,
     fetch::Range,
     helpers::{gen_timestamp_secs, systemtime_to_rfc3339},
+    ignore_rules::IgnoreRules,
+    nrs_map::{NrsLinkOptions, NrsLinkPolicy},
+    rdf::{decode_files_map, encode_files_map},
+    resolution_cache::{CacheKey, CachedContent},
+    upload_journal::journal_file_key,
     xorurl::{SafeContentType, SafeDataType},
     Safe,
 };
@@ -18,13 +27,29 @@ use crate::{
     xorurl::{XorUrl, XorUrlEncoder},
     Error, Result,
 };
+use futures::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt},
+    stream::{self, StreamExt},
+};
 use log::{debug, info, warn};
 use relative_path::RelativePath;
-use std::{collections::BTreeMap, fs, path::Path};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::min,
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
 use walkdir::{DirEntry, WalkDir};
+use xor_name::XorName;
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
+#[cfg(test)]
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 // Each FileItem contains file metadata and the link to the file's Blob XOR-URL
 pub type FileItem = BTreeMap<String, String>;
@@ -48,8 +73,57 @@ impl GetAttr for FileItem {
 // To use for mapping files names (with path in a flattened hierarchy) to FileItems
 pub type FilesMap = BTreeMap<String, FileItem>;
 
+// Details of a file processed when uploading, or removing, files to/from a FilesContainer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessedFile {
+    // Whether the file was added, updated or deleted from FilesContainer, if its upload
+    // was resumed from the upload journal or skipped since it's ignored, or if it failed
+    pub change: String,
+    // The file's link, or the error if it failed, empty for directories and symlinks
+    pub link: String,
+    // Number of bytes of the file's content which were already stored and got deduplicated,
+    // only known for files which content was uploaded
+    pub deduplicated_size: Option<u64>,
+}
+
+impl ProcessedFile {
+    pub fn new(change: &str, link: String, deduplicated_size: Option<u64>) -> Self {
+        Self {
+            change: change.to_string(),
+            link,
+            deduplicated_size,
+        }
+    }
+}
+
 // List of files uploaded with details if they were added, updated or deleted from FilesContainer
-pub type ProcessedFiles = BTreeMap<String, (String, String)>;
+pub type ProcessedFiles = BTreeMap<String, ProcessedFile>;
+
+// Change made to a file between two versions of a FilesContainer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileDiff {
+    // Whether the file was added, removed, updated or renamed, using the same signs as ProcessedFiles
+    pub change: String,
+    // The path the file had in the first version, if it was renamed
+    pub renamed_from: Option<String>,
+    // Link to the file's content, the one in the second version unless the file was removed
+    pub link: String,
+    // Metadata which changed, with the values it had in the first and second version
+    pub metadata_changes: BTreeMap<String, (Option<String>, Option<String>)>,
+}
+
+// Changes made to each file between two versions of a FilesContainer
+pub type FilesMapDiff = BTreeMap<String, FileDiff>;
+
+// Options to create a FilesContainer with
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FilesContainerCreateOptions {
+    // Whether the files content and the FilesContainer are to be encrypted and stored as private data,
+    // which requires a storage backend with a keypair persisted across sessions
+    pub private: bool,
+    // Whether to only report what would be uploaded without actually storing anything
+    pub dry_run: bool,
+}
 
 // Represents file metadata.  Simplifies passing it around.
 // note: all values are String or Option<String>
@@ -255,15 +329,20 @@ const MAX_RECURSIVE_DEPTH: usize = 10_000;
 
 impl Safe {
     /// # Create a FilesContainer.
+    /// If private, the content of the files and the FilesContainer itself are encrypted with
+    /// a key derived from the keypair in use, and stored as Private Blobs and a Private Sequence.
+    /// Private data can only be decrypted with the same keypair, thus it needs a storage backend
+    /// which connects with the same keypair in every session, e.g. a local disk one. An
+    /// `AccessDenied` error is returned otherwise, as nothing stored could be read back later.
     ///
     /// ## Example
     ///
     /// ```rust
-    /// # use sn_api::Safe;
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe};
     /// # let mut safe = Safe::default();
     /// # async_std::task::block_on(async {
     ///     safe.connect("", Some("fake-credentials")).await.unwrap();
-    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, true, false).await.unwrap();
+    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, true, FilesContainerCreateOptions::default()).await.unwrap();
     ///     assert!(xorurl.contains("safe://"))
     /// # });
     /// ```
@@ -273,20 +352,21 @@ impl Safe {
         dest: Option<&str>,
         recursive: bool,
         follow_links: bool,
-        dry_run: bool,
+        options: FilesContainerCreateOptions,
     ) -> Result<(XorUrl, ProcessedFiles, FilesMap)> {
         // TODO: Enable source for funds / ownership
         // Warn about ownership?
+        let FilesContainerCreateOptions { private, dry_run } = options;
 
         // Let's upload the files and generate the list of local files paths
         let (processed_files, files_map) = match location {
             Some(path) => {
                 let mut processed_files =
-                    file_system_dir_walk(self, path, recursive, follow_links, dry_run).await?;
+                    file_system_dir_walk(self, path, recursive, follow_links, private, dry_run)
+                        .await?;
 
                 // The FilesContainer is created as a Sequence with a single entry containing the
                 // timestamp as the entry's key, and the serialised FilesMap as the entry's value
-                // TODO: use RDF format
                 let files_map = files_map_create(
                     self,
                     &mut processed_files,
@@ -304,22 +384,17 @@ impl Safe {
         let xorurl = if dry_run {
             "".to_string()
         } else {
-            let serialised_files_map = serde_json::to_string(&files_map).map_err(|err| {
-                Error::Unexpected(format!(
-                    "Couldn't serialise the FilesMap generated: {:?}",
-                    err
-                ))
-            })?;
+            let serialised_files_map = self.serialise_files_map(&files_map, private).await?;
 
-            // Store the FilesContainer in a Public Sequence
+            // Store the FilesContainer in a Public or Private Sequence
             let xorname = self
                 .safe_client
                 .store_sequence(
-                    serialised_files_map.as_bytes(),
+                    &serialised_files_map,
                     None,
                     FILES_CONTAINER_TYPE_TAG,
                     None,
-                    false,
+                    private,
                 )
                 .await?;
 
@@ -328,7 +403,7 @@ impl Safe {
                 FILES_CONTAINER_TYPE_TAG,
                 SafeContentType::FilesContainer,
                 self.xorurl_base,
-                false,
+                private,
             )?
         };
 
@@ -340,11 +415,11 @@ impl Safe {
     /// ## Example
     ///
     /// ```rust
-    /// # use sn_api::Safe;
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe};
     /// # let mut safe = Safe::default();
     /// # async_std::task::block_on(async {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
-    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, true, false).await.unwrap();
+    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, true, FilesContainerCreateOptions::default()).await.unwrap();
     ///     let (version, files_map) = safe.files_container_get(&xorurl).await.unwrap();
     ///     println!("FilesContainer fetched is at version: {}", version);
     ///     println!("FilesMap of fetched version is: {:?}", files_map);
@@ -362,20 +437,41 @@ impl Safe {
         &mut self,
         xorurl_encoder: &XorUrlEncoder,
     ) -> Result<(u64, FilesMap)> {
+        let cache_key = |version| CacheKey::FilesMap {
+            xorname: xorurl_encoder.xorname(),
+            type_tag: xorurl_encoder.type_tag(),
+            private: xorurl_encoder.data_type() == SafeDataType::PrivateSequence,
+            version,
+        };
+
+        // A specific version of a FilesContainer never changes, thus it can be served from the cache
+        if let (Some(cache), Some(version)) =
+            (&self.resolution_cache, xorurl_encoder.content_version())
+        {
+            if let Some(CachedContent::FilesMap(files_map)) = cache.get(&cache_key(version)) {
+                return Ok((version, files_map));
+            }
+        }
+
         // Check if the URL specifies a specific version of the content or simply the latest available
         match self.fetch_sequence(xorurl_encoder).await {
             Ok((version, serialised_files_map)) => {
                 debug!("Files map retrieved.... v{:?}", &version);
-                // TODO: use RDF format and deserialise it
-                let files_map = serde_json::from_str(&String::from_utf8_lossy(
-                    &serialised_files_map.as_slice(),
-                ))
-                .map_err(|err| {
-                    Error::ContentError(format!(
-                        "Couldn't deserialise the FilesMap stored in the FilesContainer: {:?}",
-                        err
-                    ))
-                })?;
+                let serialised_files_map =
+                    if xorurl_encoder.data_type() == SafeDataType::PrivateSequence {
+                        self.data_encryptor()
+                            .await?
+                            .decrypt(&serialised_files_map)?
+                    } else {
+                        serialised_files_map
+                    };
+
+                let files_map = decode_files_map(&serialised_files_map)?;
+                if let Some(cache) = &self.resolution_cache {
+                    let content = CachedContent::FilesMap(files_map.clone());
+                    cache.insert(cache_key(version), content, serialised_files_map.len());
+                }
+
                 Ok((version, files_map))
             }
             Err(Error::EmptyContent(_)) => {
@@ -402,11 +498,11 @@ impl Safe {
     /// ## Example
     ///
     /// ```rust
-    /// # use sn_api::Safe;
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe};
     /// # let mut safe = Safe::default();
     /// # async_std::task::block_on(async {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
-    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, false, false).await.unwrap();
+    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, false, FilesContainerCreateOptions::default()).await.unwrap();
     ///     let (version, new_processed_files, new_files_map) = safe.files_container_sync("../testdata", &xorurl, true, true, false, false, false).await.unwrap();
     ///     println!("FilesContainer synced up is at version: {}", version);
     ///     println!("The local files that were synced up are: {:?}", new_processed_files);
@@ -454,9 +550,18 @@ impl Safe {
         let (current_version, current_files_map): (u64, FilesMap) =
             self.fetch_files_container(&xorurl_encoder).await?;
 
+        // Files are uploaded as private content if the FilesContainer is private
+        let private = xorurl_encoder.data_type() == SafeDataType::PrivateSequence;
+
         // Let's generate the list of local files paths, without uploading any new file yet
-        let processed_files =
-            file_system_dir_walk(self, location, recursive, follow_links, true).await?;
+        let (mut processed_files, local_files) =
+            file_system_dir_list(self, location, recursive, follow_links)?;
+        for (normalised_path, _) in local_files {
+            processed_files.insert(
+                normalised_path,
+                ProcessedFile::new(CONTENT_ADDED_SIGN, String::default(), None),
+            );
+        }
 
         let dest_path = Some(xorurl_encoder.path());
 
@@ -472,6 +577,7 @@ impl Safe {
                 false,
                 true,
                 follow_links,
+                private,
             )
             .await?;
 
@@ -495,11 +601,11 @@ impl Safe {
     /// ## Example
     ///
     /// ```rust
-    /// # use sn_api::Safe;
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe};
     /// # let mut safe = Safe::default();
     /// # async_std::task::block_on(async {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
-    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, true, false).await.unwrap();
+    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, true, FilesContainerCreateOptions::default()).await.unwrap();
     ///     let new_file_name = format!("{}/new_name_test.md", xorurl);
     ///     let (version, new_processed_files, new_files_map) = safe.files_container_add("../testdata/test.md", &new_file_name, false, false, true, false).await.unwrap();
     ///     println!("FilesContainer is now at version: {}", version);
@@ -520,6 +626,7 @@ impl Safe {
             validate_files_add_params(self, source_file, url, update_nrs).await?;
 
         let dest_path = xorurl_encoder.path();
+        let private = xorurl_encoder.data_type() == SafeDataType::PrivateSequence;
 
         // Let's act according to if it's a local file path or a safe:// location
         let (processed_files, new_files_map, success_count) = if source_file.starts_with("safe://")
@@ -527,7 +634,7 @@ impl Safe {
             files_map_add_link(self, current_files_map, source_file, dest_path, force).await?
         } else {
             // Let's generate the list of local files paths, without uploading any new file yet
-            let processed_files = file_system_single_file(self, source_file, true).await?;
+            let processed_files = file_system_single_file(source_file)?;
 
             files_map_sync(
                 self,
@@ -540,6 +647,7 @@ impl Safe {
                 force,
                 false,
                 follow_links,
+                private,
             )
             .await?
         };
@@ -564,11 +672,11 @@ impl Safe {
     /// ## Example
     ///
     /// ```rust
-    /// # use sn_api::Safe;
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe};
     /// # let mut safe = Safe::default();
     /// # async_std::task::block_on(async {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
-    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, true, false).await.unwrap();
+    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata"), None, true, true, FilesContainerCreateOptions::default()).await.unwrap();
     ///     let new_file_name = format!("{}/new_name_test.md", xorurl);
     ///     let (version, new_processed_files, new_files_map) = safe.files_container_add_from_raw(b"0123456789", &new_file_name, false, false, false).await.unwrap();
     ///     println!("FilesContainer is now at version: {}", version);
@@ -588,7 +696,11 @@ impl Safe {
             validate_files_add_params(self, "", url, update_nrs).await?;
 
         let dest_path = xorurl_encoder.path();
-        let new_file_xorurl = self.files_store_public_blob(data, None, false).await?;
+        let new_file_xorurl = if xorurl_encoder.data_type() == SafeDataType::PrivateSequence {
+            self.files_store_private_blob(data, None, false).await?
+        } else {
+            self.files_store_public_blob(data, None, false).await?
+        };
 
         // Let's act according to if it's a local file path or a safe:// location
         let (processed_files, new_files_map, success_count) =
@@ -613,11 +725,11 @@ impl Safe {
     /// ## Example
     ///
     /// ```rust
-    /// # use sn_api::Safe;
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe};
     /// # let mut safe = Safe::default();
     /// # async_std::task::block_on(async {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
-    ///     let (xorurl, processed_files, files_map) = safe.files_container_create(Some("../testdata/"), None, true, true, false).await.unwrap();
+    ///     let (xorurl, processed_files, files_map) = safe.files_container_create(Some("../testdata/"), None, true, true, FilesContainerCreateOptions::default()).await.unwrap();
     ///     let remote_file_path = format!("{}/test.md", xorurl);
     ///     let (version, new_processed_files, new_files_map) = safe.files_container_remove_path(&remote_file_path, false, false, false).await.unwrap();
     ///     println!("FilesContainer is now at version: {}", version);
@@ -681,6 +793,122 @@ impl Safe {
         Ok((version, processed_files, new_files_map))
     }
 
+    /// # Restore an existing FilesContainer to a previous version.
+    /// A new version of the FilesContainer is created, with the same files it had at the
+    /// version specified. The list of files added, updated or removed to restore it is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe};
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let (xorurl, _processed_files, files_map) = safe.files_container_create(Some("../testdata/"), None, true, true, FilesContainerCreateOptions::default()).await.unwrap();
+    ///     let remote_file_path = format!("{}/test.md", xorurl);
+    ///     let _ = safe.files_container_remove_path(&remote_file_path, false, false, false).await.unwrap();
+    ///     let (version, processed_files, restored_files_map) = safe.files_container_restore(&xorurl, 0, false, false).await.unwrap();
+    ///     println!("FilesContainer is now at version: {}", version);
+    ///     println!("The files restored are: {:?}", processed_files);
+    ///     assert_eq!(restored_files_map, files_map);
+    /// # });
+    /// ```
+    pub async fn files_container_restore(
+        &mut self,
+        url: &str,
+        version: u64,
+        update_nrs: bool,
+        dry_run: bool,
+    ) -> Result<(u64, ProcessedFiles, FilesMap)> {
+        let xorurl_encoder = Safe::parse_url(url)?;
+        if xorurl_encoder.content_version().is_some() {
+            return Err(Error::InvalidInput(format!(
+                "The target URL cannot cannot contain a version: {}",
+                url
+            )));
+        };
+
+        // If NRS name shall be updated then the URL has to be an NRS-URL
+        if update_nrs && xorurl_encoder.content_type() != SafeContentType::NrsMapContainer {
+            return Err(Error::InvalidInput(
+                "'update-nrs' is not allowed since the URL provided is not an NRS URL".to_string(),
+            ));
+        }
+
+        let (mut xorurl_encoder, _) = self.parse_and_resolve_url(url).await?;
+
+        // If the FilesContainer URL was resolved from an NRS name we need to remove
+        // the version from it so we can fetch latest version of it
+        xorurl_encoder.set_content_version(None);
+        let (current_version, current_files_map) =
+            self.fetch_files_container(&xorurl_encoder).await?;
+
+        if version > current_version {
+            return Err(Error::VersionNotFound(format!(
+                "Version '{}' is invalid for FilesContainer found at \"{}\", its latest version is '{}'",
+                version, url, current_version
+            )));
+        }
+
+        xorurl_encoder.set_content_version(Some(version));
+        let (_, restored_files_map) = self.fetch_files_container(&xorurl_encoder).await?;
+        xorurl_encoder.set_content_version(None);
+
+        let processed_files =
+            processed_files_from_diff(&files_map_diff(&current_files_map, &restored_files_map));
+
+        // All the content restored is already stored on the network, no need to upload it
+        let success_count = processed_files.len() as u64;
+        let version = self
+            .append_version_to_files_container(
+                success_count,
+                current_version,
+                &restored_files_map,
+                url,
+                xorurl_encoder,
+                dry_run,
+                update_nrs,
+            )
+            .await?;
+
+        Ok((version, processed_files, restored_files_map))
+    }
+
+    /// # Compare two versions of FilesContainers.
+    /// Returns the versions of the FilesContainers compared, along with the files which were
+    /// added, removed, updated or renamed from the first to the second one. A file is considered
+    /// renamed when a removed file and an added one have the same content.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use sn_api::{files::FilesContainerCreateOptions, Safe};
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let (xorurl, _processed_files, _files_map) = safe.files_container_create(Some("../testdata/"), None, true, true, FilesContainerCreateOptions::default()).await.unwrap();
+    ///     let remote_file_path = format!("{}/test.md", xorurl);
+    ///     let (version, _, _) = safe.files_container_remove_path(&remote_file_path, false, false, false).await.unwrap();
+    ///     let (_, _, diff) = safe.files_container_diff(&format!("{}?v=0", xorurl), &xorurl).await.unwrap();
+    ///     println!("Changes made up to version {}: {:?}", version, diff);
+    ///     assert_eq!(diff["/test.md"].change, "-");
+    /// # });
+    /// ```
+    pub async fn files_container_diff(
+        &mut self,
+        url_a: &str,
+        url_b: &str,
+    ) -> Result<(u64, u64, FilesMapDiff)> {
+        let (version_a, files_map_a) = self.files_container_get(url_a).await?;
+        let (version_b, files_map_b) = self.files_container_get(url_b).await?;
+
+        Ok((
+            version_a,
+            version_b,
+            files_map_diff(&files_map_a, &files_map_b),
+        ))
+    }
+
     // Private helper function to append new version of the FilesMap to the Files Container
     // It flagged with `update_nrs`, it will also update the link in the corresponding NRS Map Container
     #[allow(clippy::too_many_arguments)]
@@ -701,29 +929,38 @@ impl Safe {
         } else {
             // The FilesContainer is updated by adding an entry containing the timestamp as the
             // entry's key, and the serialised new version of the FilesMap as the entry's value
-            let serialised_files_map = serde_json::to_string(new_files_map).map_err(|err| {
-                Error::Unexpected(format!(
-                    "Couldn't serialise the FilesMap generated: {:?}",
-                    err
-                ))
-            })?;
+            let private = xorurl_encoder.data_type() == SafeDataType::PrivateSequence;
+            let serialised_files_map = self.serialise_files_map(new_files_map, private).await?;
 
             let xorname = xorurl_encoder.xorname();
             let type_tag = xorurl_encoder.type_tag();
             self.safe_client
-                .append_to_sequence(serialised_files_map.as_bytes(), xorname, type_tag, false)
+                .append_to_sequence(&serialised_files_map, xorname, type_tag, private)
                 .await?;
 
             let new_version = current_version + 1;
 
             if update_nrs {
-                // We need to update the link in the NRS container as well,
-                // to link it to the new new_version of the FilesContainer we just generated
-                xorurl_encoder.set_content_version(Some(new_version));
-                let new_link_for_nrs = xorurl_encoder.to_string();
-                let _ = self
-                    .nrs_map_container_add(url, &new_link_for_nrs, false, true, false)
-                    .await?;
+                // We need to update the link in the NRS container as well, to link it to
+                // the new new_version of the FilesContainer we just generated, unless the
+                // entry already follows the latest version, in which case it's kept as is
+                let (_, nrs_map) = self.nrs_map_container_get(url).await?;
+                let nrs_url_encoder = Safe::parse_url(url)?;
+                let resolution = nrs_map.resolve_entry(nrs_url_encoder.sub_names_vec())?;
+                if resolution.link_policy == NrsLinkPolicy::Pinned {
+                    xorurl_encoder.set_content_version(Some(new_version));
+                    let new_link_for_nrs = xorurl_encoder.to_string();
+                    let _ = self
+                        .nrs_map_container_add(
+                            url,
+                            &new_link_for_nrs,
+                            false,
+                            true,
+                            NrsLinkOptions::default(),
+                            false,
+                        )
+                        .await?;
+                }
             }
 
             new_version
@@ -732,6 +969,18 @@ impl Safe {
         Ok(version)
     }
 
+    // Private helper to serialise a FilesMap to be stored in a FilesContainer,
+    // encrypting it if the FilesContainer is private
+    async fn serialise_files_map(&self, files_map: &FilesMap, private: bool) -> Result<Vec<u8>> {
+        let serialised_files_map = encode_files_map(files_map, self.legacy_maps_format)?;
+
+        if private {
+            self.data_encryptor().await?.encrypt(&serialised_files_map)
+        } else {
+            Ok(serialised_files_map)
+        }
+    }
+
     /// # Put a Public Blob
     /// Put data blobs onto the network.
     ///
@@ -753,28 +1002,56 @@ impl Safe {
         media_type: Option<&str>,
         dry_run: bool,
     ) -> Result<XorUrl> {
-        let content_type = media_type.map_or_else(
-            || Ok(SafeContentType::Raw),
-            |media_type_str| {
-                if XorUrlEncoder::is_media_type_supported(media_type_str) {
-                    Ok(SafeContentType::MediaType(media_type_str.to_string()))
-                } else {
-                    Err(Error::InvalidMediaType(format!(
-                        "Media-type '{}' not supported. You can pass 'None' as the 'media_type' for this content to be treated as raw",
-                        media_type_str
-                    )))
-                }
-            },
-        )?;
+        let content_type = blob_content_type(media_type)?;
 
         // TODO: do we want ownership from other PKs yet?
         let xorname = self.safe_client.store_public_blob(&data, dry_run).await?;
 
-        XorUrlEncoder::encode_blob(xorname, content_type, self.xorurl_base)
+        XorUrlEncoder::encode_blob(xorname, content_type, self.xorurl_base, false)
     }
 
-    /// # Get a Public Blob
-    /// Put data blobs onto the network.
+    /// # Put a Private Blob
+    /// Put data blobs onto the network, encrypted with a key derived from the keypair
+    /// in use, so only the same keypair can retrieve and decrypt their content.
+    /// As with private FilesContainers, the storage backend needs to connect with the
+    /// same keypair in every session, e.g. a local disk one.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::{BackendConfig, Safe};
+    /// let path = std::env::temp_dir().join("sn_api_private_data.db");
+    /// let mut safe = Safe::with_backend(None, BackendConfig::LocalDisk(path));
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let data = b"Something super private";
+    ///     let xorurl = safe.files_store_private_blob(data, Some("text/plain"), false).await.unwrap();
+    ///     let received_data = safe.files_get_private_blob(&xorurl, None).await.unwrap();
+    ///     assert_eq!(received_data, data);
+    /// # });
+    /// ```
+    pub async fn files_store_private_blob(
+        &mut self,
+        data: &[u8],
+        media_type: Option<&str>,
+        dry_run: bool,
+    ) -> Result<XorUrl> {
+        let content_type = blob_content_type(media_type)?;
+
+        let encrypted_data = self.data_encryptor().await?.encrypt(data)?;
+        let xorname = self
+            .safe_client
+            .store_private_blob(&encrypted_data, dry_run)
+            .await?;
+
+        XorUrlEncoder::encode_blob(xorname, content_type, self.xorurl_base, true)
+    }
+
+    /// # Put a Public Blob from a stream
+    /// Put the data read from a stream onto the network without loading it all in memory.
+    /// The data is split into content-defined chunks of around 1MB, each stored as a Blob,
+    /// along with a manifest Blob listing them, which is what the returned XOR-URL targets,
+    /// with the `BlobManifest` content type and the media type kept in the manifest.
+    /// Data which fits in a single chunk is stored just like `files_store_public_blob` does.
     ///
     /// ## Example
     /// ```
@@ -783,110 +1060,591 @@ impl Safe {
     /// # async_std::task::block_on(async {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
     ///     let data = b"Something super good";
-    ///     let xorurl = safe.files_store_public_blob(data, None, false).await.unwrap();
-    ///     let received_data = safe.files_get_public_blob(&xorurl, None).await.unwrap();
+    ///     let xorurl = safe.files_store_public_blob_from_reader(&mut &data[..], None, false).await.unwrap();
+    ///     let mut received_data = vec![];
+    ///     safe.files_get_public_blob_to_writer(&xorurl, None, &mut received_data).await.unwrap();
     ///     assert_eq!(received_data, data);
     /// # });
     /// ```
-    pub async fn files_get_public_blob(&mut self, url: &str, range: Range) -> Result<Vec<u8>> {
-        // TODO: do we want ownership from other PKs yet?
-        let (xorurl_encoder, _) = self.parse_and_resolve_url(url).await?;
-        self.fetch_public_blob(&xorurl_encoder, range).await
+    pub async fn files_store_public_blob_from_reader<R>(
+        &mut self,
+        reader: &mut R,
+        media_type: Option<&str>,
+        dry_run: bool,
+    ) -> Result<XorUrl>
+    where
+        R: AsyncRead + Unpin + ?Sized,
+    {
+        let (xorurl, _) = self
+            .store_blob_chunks(reader, media_type, &BTreeSet::new(), false, dry_run)
+            .await?;
+        Ok(xorurl)
     }
 
-    /// Fetch an Blob from a XorUrlEncoder without performing any type of URL resolution
-    pub(crate) async fn fetch_public_blob(
+    // Store the data read from a stream as content-defined chunks, skipping those chunks which
+    // are known to be already stored. Returns the XOR-URL of the data, and the number of
+    // bytes which were deduplicated, i.e. that didn't need to be stored.
+    // If private, the chunks and the manifest are encrypted and stored as Private Blobs.
+    async fn store_blob_chunks<R>(
         &mut self,
-        xorurl_encoder: &XorUrlEncoder,
-        range: Range,
-    ) -> Result<Vec<u8>> {
-        self.safe_client
-            .get_public_blob(xorurl_encoder.xorname(), range)
-            .await
-    }
-}
+        reader: &mut R,
+        media_type: Option<&str>,
+        known_chunks: &BTreeSet<XorName>,
+        private: bool,
+        dry_run: bool,
+    ) -> Result<(XorUrl, u64)>
+    where
+        R: AsyncRead + Unpin + ?Sized,
+    {
+        let content_type = blob_content_type(media_type)?;
+        let encryptor = if private {
+            Some(self.data_encryptor().await?)
+        } else {
+            None
+        };
 
-// Helper functions
+        let mut chunker = ContentChunker::new();
+        let mut chunks = vec![];
+        let mut deduplicated = 0;
+        loop {
+            let next_chunk = chunker.next_chunk(reader).await.map_err(|err| {
+                Error::InvalidInput(format!("Failed to read data to be stored: {}", err))
+            })?;
+            let chunk = match next_chunk {
+                Some(chunk) => chunk,
+                // An empty Blob is only stored when there is no data at all
+                None if chunks.is_empty() => vec![],
+                None => break,
+            };
 
-// Make sure the input params are valid for a files_container_add operation
-async fn validate_files_add_params(
-    safe: &mut Safe,
-    source_file: &str,
-    url: &str,
-    update_nrs: bool,
-) -> Result<(XorUrlEncoder, u64, FilesMap)> {
-    let xorurl_encoder = Safe::parse_url(url)?;
-    if xorurl_encoder.content_version().is_some() {
-        return Err(Error::InvalidInput(format!(
-            "The target URL cannot cannot contain a version: {}",
-            url
-        )));
-    };
+            let size = chunk.len() as u64;
+            let data = match &encryptor {
+                Some(encryptor) => encryptor.encrypt(&chunk)?,
+                None => chunk,
+            };
 
-    // If NRS name shall be updated then the URL has to be an NRS-URL
-    if update_nrs && xorurl_encoder.content_type() != SafeContentType::NrsMapContainer {
-        return Err(Error::InvalidInput(
-            "'update-nrs' is not allowed since the URL provided is not an NRS URL".to_string(),
-        ));
-    }
+            let (xorname, is_known) = self
+                .store_blob_data_unless_known(&data, known_chunks, private, dry_run)
+                .await?;
+            if is_known {
+                deduplicated += size;
+            }
 
-    let (mut xorurl_encoder, _) = safe.parse_and_resolve_url(url).await?;
+            chunks.push(BlobChunk { xorname, size });
 
-    // If the FilesContainer URL was resolved from an NRS name we need to remove
-    // the version from it so we can fetch latest version of it for sync-ing
-    xorurl_encoder.set_content_version(None);
+            if size == 0 {
+                break;
+            }
+        }
 
-    let (current_version, current_files_map): (u64, FilesMap) =
-        safe.fetch_files_container(&xorurl_encoder).await?;
+        // If it all fits in a single chunk, that chunk's Blob already has the whole content
+        let (xorname, content_type) = if chunks.len() == 1 {
+            (chunks[0].xorname, content_type)
+        } else {
+            let manifest = BlobManifest::new(chunks, media_type);
+            debug!(
+                "Storing manifest for {} bytes of data split into {} Blobs",
+                manifest.size,
+                manifest.chunks.len()
+            );
+            let data = match &encryptor {
+                Some(encryptor) => encryptor.encrypt(&manifest.serialise()?)?,
+                None => manifest.serialise()?,
+            };
+            let (xorname, _) = self
+                .store_blob_data_unless_known(&data, known_chunks, private, dry_run)
+                .await?;
+            (xorname, SafeContentType::BlobManifest)
+        };
 
-    let dest_path = xorurl_encoder.path().to_string();
+        let xorurl = XorUrlEncoder::encode_blob(xorname, content_type, self.xorurl_base, private)?;
+        Ok((xorurl, deduplicated))
+    }
 
-    // Let's act according to if it's a local file path or a safe:// location
-    if source_file.starts_with("safe://") {
-        let source_xorurl_encoder = Safe::parse_url(source_file)?;
-        if source_xorurl_encoder.data_type() != SafeDataType::PublicBlob {
-            return Err(Error::InvalidInput(format!(
-                "The source URL should target a file ('{}'), but the URL provided targets a '{}'",
-                SafeDataType::PublicBlob,
-                source_xorurl_encoder.content_type()
-            )));
+    // Store the data as a Blob unless it's one of the known chunks, which are already stored.
+    // If there are known chunks, let's first find out the xorname without storing the data,
+    // it's returned along with whether it's one of them.
+    async fn store_blob_data_unless_known(
+        &self,
+        data: &[u8],
+        known_chunks: &BTreeSet<XorName>,
+        private: bool,
+        dry_run: bool,
+    ) -> Result<(XorName, bool)> {
+        if known_chunks.is_empty() {
+            let xorname = self.store_blob_data(data, private, dry_run).await?;
+            return Ok((xorname, false));
         }
 
-        if dest_path.is_empty() {
-            return Err(Error::InvalidInput(
-                "The destination URL should include a target file path since we are adding a link"
-                    .to_string(),
-            ));
+        let xorname = self.store_blob_data(data, private, true).await?;
+        if known_chunks.contains(&xorname) {
+            debug!("Blob already stored at {:?}, skipping it", xorname);
+            return Ok((xorname, true));
         }
+        if !dry_run {
+            let _ = self.store_blob_data(data, private, false).await?;
+        }
+        Ok((xorname, false))
     }
-    Ok((xorurl_encoder, current_version, current_files_map))
-}
-
-// Simply change Windows style path separator into `/`
-fn normalise_path_separator(from: &str) -> String {
-    str::replace(&from, "\\", "/")
-}
-
-// From the location path and the destination path chosen by the user, calculate
-// the destination path considering ending '/' in both the  location and dest path
-fn get_base_paths(location: &str, dest_path: Option<&str>) -> Result<(String, String)> {
-    // Let's normalise the path to use '/' (instead of '\' as on Windows)
-    let location_base_path = if location == "." {
-        "./".to_string()
-    } else {
-        normalise_path_separator(location)
-    };
 
-    let new_dest_path = match dest_path {
-        Some(path) => {
-            if path.is_empty() {
-                "/".to_string()
-            } else {
-                path.to_string()
-            }
+    // Store the data as a Public or Private Blob, the latter is expected to be already encrypted
+    async fn store_blob_data(&self, data: &[u8], private: bool, dry_run: bool) -> Result<XorName> {
+        if private {
+            self.safe_client.store_private_blob(data, dry_run).await
+        } else {
+            self.safe_client.store_public_blob(data, dry_run).await
+        }
+    }
+
+    /// # Get a Public Blob
+    /// Put data blobs onto the network.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::Safe;
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let data = b"Something super good";
+    ///     let xorurl = safe.files_store_public_blob(data, None, false).await.unwrap();
+    ///     let received_data = safe.files_get_public_blob(&xorurl, None).await.unwrap();
+    ///     assert_eq!(received_data, data);
+    /// # });
+    /// ```
+    pub async fn files_get_public_blob(&mut self, url: &str, range: Range) -> Result<Vec<u8>> {
+        // TODO: do we want ownership from other PKs yet?
+        let (xorurl_encoder, _) = self.parse_and_resolve_url(url).await?;
+        if xorurl_encoder.data_type() != SafeDataType::PublicBlob {
+            return Err(Error::InvalidInput(format!(
+                "The URL doesn't target a Public Blob but a '{}': {}",
+                xorurl_encoder.data_type(),
+                url
+            )));
+        }
+
+        self.fetch_blob(&xorurl_encoder, range).await
+    }
+
+    /// # Get a Private Blob
+    /// Get and decrypt the content of a Private Blob stored with the same keypair.
+    /// Since the content needs to be decrypted, it's always fully fetched even
+    /// if only a range of it is requested.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::{BackendConfig, Safe};
+    /// let path = std::env::temp_dir().join("sn_api_private_data.db");
+    /// let mut safe = Safe::with_backend(None, BackendConfig::LocalDisk(path));
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let data = b"Something super private";
+    ///     let xorurl = safe.files_store_private_blob(data, None, false).await.unwrap();
+    ///     let received_data = safe.files_get_private_blob(&xorurl, Some((Some(16), None))).await.unwrap();
+    ///     assert_eq!(received_data, b"private");
+    /// # });
+    /// ```
+    pub async fn files_get_private_blob(&mut self, url: &str, range: Range) -> Result<Vec<u8>> {
+        let (xorurl_encoder, _) = self.parse_and_resolve_url(url).await?;
+        if xorurl_encoder.data_type() != SafeDataType::PrivateBlob {
+            return Err(Error::InvalidInput(format!(
+                "The URL doesn't target a Private Blob but a '{}': {}",
+                xorurl_encoder.data_type(),
+                url
+            )));
+        }
+
+        self.fetch_blob(&xorurl_encoder, range).await
+    }
+
+    /// # Get a Public or Private Blob
+    /// Get the content of a Blob whichever its type is, the content of a Private Blob
+    /// is decrypted as with `files_get_private_blob`.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::{BackendConfig, Safe};
+    /// let path = std::env::temp_dir().join("sn_api_private_data.db");
+    /// let mut safe = Safe::with_backend(None, BackendConfig::LocalDisk(path));
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let data = b"Something super private";
+    ///     let xorurl = safe.files_store_private_blob(data, None, false).await.unwrap();
+    ///     let received_data = safe.files_get_blob(&xorurl, None).await.unwrap();
+    ///     assert_eq!(received_data, data);
+    /// # });
+    /// ```
+    pub async fn files_get_blob(&mut self, url: &str, range: Range) -> Result<Vec<u8>> {
+        let (xorurl_encoder, _) = self.parse_and_resolve_url(url).await?;
+        match xorurl_encoder.data_type() {
+            SafeDataType::PublicBlob | SafeDataType::PrivateBlob => {
+                self.fetch_blob(&xorurl_encoder, range).await
+            }
+            other => Err(Error::InvalidInput(format!(
+                "The URL doesn't target a Blob but a '{}': {}",
+                other, url
+            ))),
+        }
+    }
+
+    /// # Get the size of a Blob
+    /// Get the size of the content of a Public or Private Blob. The size of content split into
+    /// several Blobs is found in its manifest, otherwise the single Blob is fetched to know it.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::Safe;
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let data = b"Something super good";
+    ///     let xorurl = safe.files_store_public_blob(data, None, false).await.unwrap();
+    ///     let size = safe.files_get_blob_size(&xorurl).await.unwrap();
+    ///     assert_eq!(size, data.len() as u64);
+    /// # });
+    /// ```
+    pub async fn files_get_blob_size(&mut self, url: &str) -> Result<u64> {
+        let (xorurl_encoder, _) = self.parse_and_resolve_url(url).await?;
+        let encryptor = match xorurl_encoder.data_type() {
+            SafeDataType::PublicBlob => None,
+            SafeDataType::PrivateBlob => Some(self.data_encryptor().await?),
+            other => {
+                return Err(Error::InvalidInput(format!(
+                    "The URL doesn't target a Blob but a '{}': {}",
+                    other, url
+                )))
+            }
+        };
+
+        if xorurl_encoder.content_type() == SafeContentType::BlobManifest {
+            let manifest = self
+                .fetch_blob_manifest(xorurl_encoder.xorname(), encryptor.as_ref())
+                .await?;
+            Ok(manifest.size)
+        } else {
+            let data = self.fetch_blob(&xorurl_encoder, None).await?;
+            Ok(data.len() as u64)
+        }
+    }
+
+    /// # Get a Public Blob into a stream
+    /// Write the content of a Public Blob, or of the range requested, onto the provided writer.
+    /// If the content was split into several Blobs, they are fetched one at a time, and only
+    /// those which hold data within the range. Returns the number of bytes written.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::Safe;
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let data = b"Something super good";
+    ///     let xorurl = safe.files_store_public_blob(data, None, false).await.unwrap();
+    ///     let mut received_data = vec![];
+    ///     let range = Some((Some(10), Some(15)));
+    ///     safe.files_get_public_blob_to_writer(&xorurl, range, &mut received_data).await.unwrap();
+    ///     assert_eq!(received_data, b"super");
+    /// # });
+    /// ```
+    pub async fn files_get_public_blob_to_writer<W>(
+        &mut self,
+        url: &str,
+        range: Range,
+        writer: &mut W,
+    ) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let (xorurl_encoder, _) = self.parse_and_resolve_url(url).await?;
+        let xorname = xorurl_encoder.xorname();
+
+        let mut bytes_written = 0;
+        if xorurl_encoder.content_type() == SafeContentType::BlobManifest {
+            let manifest = self.fetch_blob_manifest(xorname, None).await?;
+            for (chunk_xorname, chunk_range) in manifest.chunks_for_range(range) {
+                let data = self
+                    .safe_client
+                    .get_public_blob(chunk_xorname, chunk_range)
+                    .await?;
+                write_blob_data(writer, &data).await?;
+                bytes_written += data.len() as u64;
+            }
+        } else {
+            let data = self.safe_client.get_public_blob(xorname, range).await?;
+            write_blob_data(writer, &data).await?;
+            bytes_written += data.len() as u64;
+        }
+
+        writer
+            .flush()
+            .await
+            .map_err(|err| Error::FileSystemError(format!("Failed to flush data: {}", err)))?;
+
+        Ok(bytes_written)
+    }
+
+    /// Fetch a Public or Private Blob from a XorUrlEncoder without performing any type of URL
+    /// resolution. The content of a Private Blob is decrypted.
+    pub(crate) async fn fetch_blob(
+        &mut self,
+        xorurl_encoder: &XorUrlEncoder,
+        range: Range,
+    ) -> Result<Vec<u8>> {
+        let cache = match &self.resolution_cache {
+            Some(cache) => cache.clone(),
+            None => return self.fetch_blob_content(xorurl_encoder, range).await,
+        };
+
+        // Blobs are immutable, thus their content can always be served from the cache
+        let key = CacheKey::Blob {
+            xorname: xorurl_encoder.xorname(),
+            private: xorurl_encoder.data_type() == SafeDataType::PrivateBlob,
+            range,
+        };
+        if let Some(CachedContent::Blob(data)) = cache.get(&key) {
+            return Ok(data);
+        }
+
+        let data = self.fetch_blob_content(xorurl_encoder, range).await?;
+        cache.insert(key, CachedContent::Blob(data.clone()), data.len());
+        Ok(data)
+    }
+
+    async fn fetch_blob_content(
+        &mut self,
+        xorurl_encoder: &XorUrlEncoder,
+        range: Range,
+    ) -> Result<Vec<u8>> {
+        let xorname = xorurl_encoder.xorname();
+        let encryptor = if xorurl_encoder.data_type() == SafeDataType::PrivateBlob {
+            Some(self.data_encryptor().await?)
+        } else {
+            None
+        };
+
+        if xorurl_encoder.content_type() != SafeContentType::BlobManifest {
+            return self
+                .fetch_blob_data(xorname, range, encryptor.as_ref())
+                .await;
+        }
+
+        let manifest = self
+            .fetch_blob_manifest(xorname, encryptor.as_ref())
+            .await?;
+
+        let mut data = vec![];
+        for (chunk_xorname, chunk_range) in manifest.chunks_for_range(range) {
+            let chunk = self
+                .fetch_blob_data(chunk_xorname, chunk_range, encryptor.as_ref())
+                .await?;
+            data.extend(chunk);
         }
-        None => "/".to_string(),
-    };
+
+        Ok(data)
+    }
+
+    // Fetch the data of a single Blob, a Private Blob's data can only be fully fetched
+    // to be decrypted, thus the range is applied after decrypting it
+    async fn fetch_blob_data(
+        &self,
+        xorname: XorName,
+        range: Range,
+        encryptor: Option<&DataEncryptor>,
+    ) -> Result<Vec<u8>> {
+        match encryptor {
+            Some(encryptor) => {
+                let encrypted_data = self.safe_client.get_private_blob(xorname).await?;
+                let data = encryptor.decrypt(&encrypted_data)?;
+                Ok(data_in_range(data, range))
+            }
+            None => self.safe_client.get_public_blob(xorname, range).await,
+        }
+    }
+
+    // Fetch the manifest stored at the given location
+    async fn fetch_blob_manifest(
+        &self,
+        xorname: XorName,
+        encryptor: Option<&DataEncryptor>,
+    ) -> Result<BlobManifest> {
+        let data = self.fetch_blob_data(xorname, None, encryptor).await?;
+        BlobManifest::deserialise(&data)
+    }
+
+    // Get the media type of the content targeted by a BlobManifest XOR-URL,
+    // which is kept in the manifest
+    pub(crate) async fn fetch_blob_manifest_media_type(
+        &self,
+        xorurl_encoder: &XorUrlEncoder,
+    ) -> Result<Option<String>> {
+        let encryptor = if xorurl_encoder.data_type() == SafeDataType::PrivateBlob {
+            Some(self.data_encryptor().await?)
+        } else {
+            None
+        };
+
+        let manifest = self
+            .fetch_blob_manifest(xorurl_encoder.xorname(), encryptor.as_ref())
+            .await?;
+        Ok(manifest.media_type)
+    }
+
+    // Get the xornames of the Blobs where the content targeted by the link is stored,
+    // which is none if the link is not a Blob XOR-URL
+    async fn fetch_blob_chunks(&mut self, link: &str) -> BTreeSet<XorName> {
+        let (xorurl_encoder, encryptor) = match XorUrlEncoder::from_url(link) {
+            Ok(xorurl_encoder) if xorurl_encoder.data_type() == SafeDataType::PublicBlob => {
+                (xorurl_encoder, None)
+            }
+            Ok(xorurl_encoder) if xorurl_encoder.data_type() == SafeDataType::PrivateBlob => {
+                match self.data_encryptor().await {
+                    Ok(encryptor) => (xorurl_encoder, Some(encryptor)),
+                    Err(_) => return BTreeSet::new(),
+                }
+            }
+            _ => return BTreeSet::new(),
+        };
+
+        let xorname = xorurl_encoder.xorname();
+        if xorurl_encoder.content_type() != SafeContentType::BlobManifest {
+            return vec![xorname].into_iter().collect();
+        }
+
+        match self.fetch_blob_manifest(xorname, encryptor.as_ref()).await {
+            Ok(manifest) => manifest.chunks.iter().map(|chunk| chunk.xorname).collect(),
+            Err(err) => {
+                debug!("Failed to fetch Blob manifest from {}: {}", link, err);
+                BTreeSet::new()
+            }
+        }
+    }
+}
+
+// Helper functions
+
+// Get the content type for a Blob with the given media-type
+fn blob_content_type(media_type: Option<&str>) -> Result<SafeContentType> {
+    media_type.map_or_else(
+        || Ok(SafeContentType::Raw),
+        |media_type_str| {
+            if XorUrlEncoder::is_media_type_supported(media_type_str) {
+                Ok(SafeContentType::MediaType(media_type_str.to_string()))
+            } else {
+                Err(Error::InvalidMediaType(format!(
+                    "Media-type '{}' not supported. You can pass 'None' as the 'media_type' for this content to be treated as raw",
+                    media_type_str
+                )))
+            }
+        },
+    )
+}
+
+// Get the part of the data within the range, or all of it if no range was provided
+fn data_in_range(mut data: Vec<u8>, range: Range) -> Vec<u8> {
+    let (start, end) = match range {
+        Some((start, end)) => (
+            start.map_or(0, |start| start as usize),
+            end.map_or(data.len(), |end| end as usize),
+        ),
+        None => return data,
+    };
+
+    let end = min(end, data.len());
+    let start = min(start, end);
+    data.truncate(end);
+    data.split_off(start)
+}
+
+// Write data fetched from a Blob onto a writer
+async fn write_blob_data<W: AsyncWrite + Unpin + ?Sized>(
+    writer: &mut W,
+    data: &[u8],
+) -> Result<()> {
+    writer
+        .write_all(data)
+        .await
+        .map_err(|err| Error::FileSystemError(format!("Failed to write data: {}", err)))
+}
+
+// Make sure the input params are valid for a files_container_add operation
+async fn validate_files_add_params(
+    safe: &mut Safe,
+    source_file: &str,
+    url: &str,
+    update_nrs: bool,
+) -> Result<(XorUrlEncoder, u64, FilesMap)> {
+    let xorurl_encoder = Safe::parse_url(url)?;
+    if xorurl_encoder.content_version().is_some() {
+        return Err(Error::InvalidInput(format!(
+            "The target URL cannot cannot contain a version: {}",
+            url
+        )));
+    };
+
+    // If NRS name shall be updated then the URL has to be an NRS-URL
+    if update_nrs && xorurl_encoder.content_type() != SafeContentType::NrsMapContainer {
+        return Err(Error::InvalidInput(
+            "'update-nrs' is not allowed since the URL provided is not an NRS URL".to_string(),
+        ));
+    }
+
+    let (mut xorurl_encoder, _) = safe.parse_and_resolve_url(url).await?;
+
+    // If the FilesContainer URL was resolved from an NRS name we need to remove
+    // the version from it so we can fetch latest version of it for sync-ing
+    xorurl_encoder.set_content_version(None);
+
+    let (current_version, current_files_map): (u64, FilesMap) =
+        safe.fetch_files_container(&xorurl_encoder).await?;
+
+    let dest_path = xorurl_encoder.path().to_string();
+
+    // Let's act according to if it's a local file path or a safe:// location
+    if source_file.starts_with("safe://") {
+        let source_xorurl_encoder = Safe::parse_url(source_file)?;
+        let data_type = source_xorurl_encoder.data_type();
+        if data_type != SafeDataType::PublicBlob && data_type != SafeDataType::PrivateBlob {
+            return Err(Error::InvalidInput(format!(
+                "The source URL should target a file ('{}'), but the URL provided targets a '{}'",
+                SafeDataType::PublicBlob,
+                source_xorurl_encoder.content_type()
+            )));
+        }
+
+        if dest_path.is_empty() {
+            return Err(Error::InvalidInput(
+                "The destination URL should include a target file path since we are adding a link"
+                    .to_string(),
+            ));
+        }
+    }
+    Ok((xorurl_encoder, current_version, current_files_map))
+}
+
+// Simply change Windows style path separator into `/`
+fn normalise_path_separator(from: &str) -> String {
+    str::replace(&from, "\\", "/")
+}
+
+// From the location path and the destination path chosen by the user, calculate
+// the destination path considering ending '/' in both the  location and dest path
+fn get_base_paths(location: &str, dest_path: Option<&str>) -> Result<(String, String)> {
+    // Let's normalise the path to use '/' (instead of '\' as on Windows)
+    let location_base_path = if location == "." {
+        "./".to_string()
+    } else {
+        normalise_path_separator(location)
+    };
+
+    let new_dest_path = match dest_path {
+        Some(path) => {
+            if path.is_empty() {
+                "/".to_string()
+            } else {
+                path.to_string()
+            }
+        }
+        None => "/".to_string(),
+    };
 
     // Let's first check if it ends with '/'
     let dest_base_path = if new_dest_path.ends_with('/') {
@@ -906,19 +1664,31 @@ fn get_base_paths(location: &str, dest_path: Option<&str>) -> Result<(String, St
     Ok((location_base_path, dest_base_path))
 }
 
-// Generate a FileItem for a file which can then be added to a FilesMap
+// Generate a FileItem for a file which can then be added to a FilesMap, returning it along
+// with the number of bytes deduplicated if the file had to be uploaded, and if its upload was
+// resumed from the upload journal
 // This is now a pseudo-RDF but will eventually be converted to be an RDF graph
 async fn gen_new_file_item(
     safe: &mut Safe,
     file_path: &Path,
     file_meta: &FileMeta,
     link: Option<&str>, // must be symlink target or None if FileMeta::is_symlink() is true.
+    known_chunks: &BTreeSet<XorName>,
+    private: bool,
     dry_run: bool,
-) -> Result<FileItem> {
+) -> Result<(FileItem, Option<u64>, bool)> {
     let mut file_item = file_meta.to_file_item();
+    let mut deduplicated = None;
+    let mut resumed = false;
     if file_meta.is_file() {
         let xorurl = match link {
-            None => upload_file_to_net(safe, file_path, dry_run).await?,
+            None => {
+                let (xorurl, bytes, from_journal) =
+                    upload_file_to_net(safe, file_path, known_chunks, private, dry_run).await?;
+                deduplicated = Some(bytes);
+                resumed = from_journal;
+                xorurl
+            }
             Some(link) => link.to_string(),
         };
         file_item.insert(FAKE_RDF_PREDICATE_LINK.to_string(), xorurl);
@@ -958,7 +1728,7 @@ async fn gen_new_file_item(
         );
     }
 
-    Ok(file_item)
+    Ok((file_item, deduplicated, resumed))
 }
 
 // Helper function to add or update a FileItem in a FilesMap
@@ -970,18 +1740,55 @@ async fn add_or_update_file_item(
     file_path: &Path,
     file_meta: &FileMeta,
     file_link: Option<&str>,
+    known_chunks: &BTreeSet<XorName>,
+    private: bool,
     name_exists: bool,
     dry_run: bool,
     files_map: &mut FilesMap,
     processed_files: &mut ProcessedFiles,
 ) -> bool {
     // We need to add a new FileItem, let's generate the FileItem first
-    match gen_new_file_item(safe, file_path, file_meta, file_link, dry_run).await {
-        Ok(new_file_item) => {
-            let content_added_sign = if name_exists {
-                CONTENT_UPDATED_SIGN.to_string()
+    let result = gen_new_file_item(
+        safe,
+        file_path,
+        file_meta,
+        file_link,
+        known_chunks,
+        private,
+        dry_run,
+    )
+    .await;
+
+    insert_file_item(
+        file_name,
+        file_name_for_map,
+        file_link,
+        result,
+        name_exists,
+        files_map,
+        processed_files,
+    )
+}
+
+// Helper function to add or update in a FilesMap a FileItem which was generated, or report
+// the error if it couldn't be, returning whether it was successfully added or updated
+fn insert_file_item(
+    file_name: &str,
+    file_name_for_map: &str,
+    file_link: Option<&str>,
+    result: Result<(FileItem, Option<u64>, bool)>,
+    name_exists: bool,
+    files_map: &mut FilesMap,
+    processed_files: &mut ProcessedFiles,
+) -> bool {
+    match result {
+        Ok((new_file_item, deduplicated, resumed)) => {
+            let content_added_sign = if resumed {
+                CONTENT_RESUMED_SIGN
+            } else if name_exists {
+                CONTENT_UPDATED_SIGN
             } else {
-                CONTENT_ADDED_SIGN.to_string()
+                CONTENT_ADDED_SIGN
             };
 
             debug!("New FileItem item: {:?}", new_file_item);
@@ -990,7 +1797,7 @@ async fn add_or_update_file_item(
 
             processed_files.insert(
                 file_name.to_string(),
-                (
+                ProcessedFile::new(
                     content_added_sign,
                     // note: files have link property,
                     //       dirs and symlinks do not
@@ -998,6 +1805,7 @@ async fn add_or_update_file_item(
                         .get(FAKE_RDF_PREDICATE_LINK)
                         .unwrap_or(&String::default())
                         .to_string(),
+                    deduplicated,
                 ),
             );
 
@@ -1006,7 +1814,7 @@ async fn add_or_update_file_item(
         Err(err) => {
             processed_files.insert(
                 file_name.to_string(),
-                (CONTENT_ERROR_SIGN.to_string(), format!("<{}>", err)),
+                ProcessedFile::new(CONTENT_ERROR_SIGN, format!("<{}>", err), None),
             );
             info!(
                 "Skipping file \"{}\": {:?}",
@@ -1021,7 +1829,8 @@ async fn add_or_update_file_item(
 
 // From the provided list of local files paths, find the local changes made in comparison with the
 // target FilesContainer, uploading new files as necessary, and creating a new FilesMap with file's
-// metadata and their corresponding links, as well as generating the report of processed files
+// metadata and their corresponding links, as well as generating the report of processed files.
+// Files are compared and uploaded concurrently, up to the upload concurrency set in the Safe instance.
 #[allow(clippy::too_many_arguments)]
 async fn files_map_sync(
     safe: &mut Safe,
@@ -1034,17 +1843,26 @@ async fn files_map_sync(
     force: bool,
     compare_file_content: bool,
     follow_links: bool,
+    private: bool,
 ) -> Result<(ProcessedFiles, FilesMap, u64)> {
     let (location_base_path, dest_base_path) = get_base_paths(location, dest_path)?;
     let mut updated_files_map = FilesMap::new();
     let mut processed_files = ProcessedFiles::new();
     let mut success_count = 0;
 
-    for (local_file_name, _) in new_content
+    // Let's first find out which FileItem, if any, each local file corresponds to
+    let mut files_to_sync = Vec::new();
+    for (local_file_name, processed_file) in new_content
         .iter()
-        .filter(|(_, (change, _))| change != CONTENT_ERROR_SIGN)
+        .filter(|(_, processed_file)| processed_file.change != CONTENT_ERROR_SIGN)
     {
-        let file_path = Path::new(&local_file_name);
+        if processed_file.change == CONTENT_SKIPPED_SIGN {
+            processed_files.insert(
+                local_file_name.to_string(),
+                ProcessedFile::new(CONTENT_SKIPPED_SIGN, String::default(), None),
+            );
+            continue;
+        }
 
         let file_name = RelativePath::new(
             &local_file_name
@@ -1062,84 +1880,118 @@ async fn files_map_sync(
             normalised_file_name = "/".to_string();
         }
 
+        let file_meta = FileMeta::from_path(&local_file_name, follow_links)?;
+        let current_file_item = current_files_map.get(&normalised_file_name).cloned();
+        files_to_sync.push((
+            local_file_name.to_string(),
+            normalised_file_name,
+            file_meta,
+            current_file_item,
+        ));
+    }
+
+    // Each file is compared, and uploaded if needed, with its own clone of the Safe instance.
+    // The outcomes are collected in the same order the files were listed, regardless of the
+    // order the uploads complete in.
+    let syncs = files_to_sync.into_iter().map(
+        |(local_file_name, normalised_file_name, file_meta, current_file_item)| {
+            let mut safe = safe.clone();
+            async move {
+                let outcome = sync_file_item(
+                    &mut safe,
+                    &local_file_name,
+                    &file_meta,
+                    current_file_item.as_ref(),
+                    force,
+                    compare_file_content,
+                    private,
+                    dry_run,
+                )
+                .await;
+                (
+                    local_file_name,
+                    normalised_file_name,
+                    current_file_item,
+                    outcome,
+                )
+            }
+        },
+    );
+    let outcomes: Vec<_> = stream::iter(syncs)
+        .buffered(safe.upload_concurrency)
+        .collect()
+        .await;
+
+    for (local_file_name, normalised_file_name, current_file_item, outcome) in outcomes {
         // Let's update FileItem if there is a change or it doesn't exist in current_files_map
-        match current_files_map.get(&normalised_file_name) {
-            None => {
+        match (current_file_item, outcome) {
+            (None, FileSync::Uploaded(result)) => {
                 // We need to add a new FileItem
-                if add_or_update_file_item(
-                    safe,
+                if insert_file_item(
                     &local_file_name,
                     &normalised_file_name,
-                    &file_path,
-                    &FileMeta::from_path(&local_file_name, follow_links)?,
                     None, // no xorurl link
+                    result,
                     false,
-                    dry_run,
                     &mut updated_files_map,
                     &mut processed_files,
-                )
-                .await
-                {
+                ) {
                     success_count += 1;
 
-                    // We remove self and any parent directories
-                    // from the current list so we know it has been processed
-                    let mut trail = Vec::<&str>::new();
-                    for part in normalised_file_name.split('/') {
-                        trail.push(part);
-                        let ancestor = if trail.len() > 1 {
-                            trail.join("/")
-                        } else {
-                            "/".to_string()
-                        };
-                        if ancestor != normalised_file_name {
-                            if let Some(fi) = current_files_map.get(&ancestor) {
-                                updated_files_map.insert(ancestor.clone(), fi.clone());
-                                current_files_map.remove(&ancestor);
-                            }
-                        }
-                    }
-                }
-            }
-            Some(file_item) => {
-                let is_modified =
-                    is_file_item_modified(safe, &Path::new(local_file_name), file_item).await;
-                if force || (compare_file_content && is_modified) {
-                    // We need to update the current FileItem
-                    if add_or_update_file_item(
-                        safe,
-                        &local_file_name,
+                    // We remove any parent directories from the
+                    // current list so we know it has been processed
+                    keep_ancestors(
                         &normalised_file_name,
-                        &file_path,
-                        &FileMeta::from_path(&local_file_name, follow_links)?,
-                        None, // no xorurl link
-                        true,
-                        dry_run,
+                        &mut current_files_map,
                         &mut updated_files_map,
-                        &mut processed_files,
-                    )
-                    .await
-                    {
-                        success_count += 1;
-                    }
-                } else {
-                    // No need to update FileItem just copy the existing one
-                    updated_files_map.insert(normalised_file_name.to_string(), file_item.clone());
+                    );
+                }
+            }
+            (Some(_), FileSync::Uploaded(result)) => {
+                // We need to update the current FileItem
+                if insert_file_item(
+                    &local_file_name,
+                    &normalised_file_name,
+                    None, // no xorurl link
+                    result,
+                    true,
+                    &mut updated_files_map,
+                    &mut processed_files,
+                ) {
+                    success_count += 1;
+                }
 
-                    if !force && !compare_file_content {
-                        let comp_str = if is_modified { "different" } else { "same" };
-                        processed_files.insert(
-                            local_file_name.to_string(),
-                            (
-                                CONTENT_ERROR_SIGN.to_string(),
-                                format!(
-                                    "File named \"{}\" with {} content already exists on target. Use the 'force' flag to replace it",
-                                    normalised_file_name, comp_str
-                                ),
+                // let's now remove it from the current list so we now it has been processed
+                current_files_map.remove(&normalised_file_name);
+
+                // We also remove any parent directories
+                // from the current list, so they will not be deleted.
+                keep_ancestors(
+                    &normalised_file_name,
+                    &mut current_files_map,
+                    &mut updated_files_map,
+                );
+            }
+            (current_file_item, FileSync::Unchanged(is_modified)) => {
+                // No need to update FileItem just copy the existing one
+                if let Some(file_item) = current_file_item {
+                    updated_files_map.insert(normalised_file_name.to_string(), file_item);
+                }
+
+                if !force && !compare_file_content {
+                    let comp_str = if is_modified { "different" } else { "same" };
+                    processed_files.insert(
+                        local_file_name.to_string(),
+                        ProcessedFile::new(
+                            CONTENT_ERROR_SIGN,
+                            format!(
+                                "File named \"{}\" with {} content already exists on target. Use the 'force' flag to replace it",
+                                normalised_file_name, comp_str
                             ),
-                        );
-                        info!("Skipping file \"{}\" since a file named \"{}\" with {} content already exists on target. You can use the 'force' flag to replace the existing file with the new one", local_file_name, normalised_file_name, comp_str);
-                    }
+                            None,
+                        ),
+                    );
+                    info!("Skipping file \"{}\" since a file named \"{}\" with {} content already exists on target. You can use the 'force' flag to replace the existing file with the new one", local_file_name, normalised_file_name, comp_str);
                 }
 
                 // let's now remove it from the current list so we now it has been processed
@@ -1147,21 +1999,11 @@ async fn files_map_sync(
 
                 // We also remove any parent directories
                 // from the current list, so they will not be deleted.
-                let mut trail = Vec::<&str>::new();
-                for part in normalised_file_name.split('/') {
-                    trail.push(part);
-                    let ancestor = if trail.len() > 1 {
-                        trail.join("/")
-                    } else {
-                        "/".to_string()
-                    };
-                    if ancestor != normalised_file_name {
-                        if let Some(fi) = current_files_map.get(&ancestor) {
-                            updated_files_map.insert(ancestor.clone(), fi.clone());
-                            current_files_map.remove(&ancestor);
-                        }
-                    }
-                }
+                keep_ancestors(
+                    &normalised_file_name,
+                    &mut current_files_map,
+                    &mut updated_files_map,
+                );
             }
         }
     }
@@ -1174,14 +2016,15 @@ async fn files_map_sync(
         } else {
             processed_files.insert(
                 file_name.to_string(),
-                (
-                    CONTENT_DELETED_SIGN.to_string(),
+                ProcessedFile::new(
+                    CONTENT_DELETED_SIGN,
                     // note: files have link property,
                     //       dirs and symlinks do not
                     file_item
                         .get(FAKE_RDF_PREDICATE_LINK)
                         .unwrap_or(&String::default())
                         .to_string(),
+                    None,
                 ),
             );
             success_count += 1;
@@ -1191,14 +2034,129 @@ async fn files_map_sync(
     Ok((processed_files, updated_files_map, success_count))
 }
 
+// Outcome of syncing a local file with the FileItem currently in the FilesContainer for it, if any
+enum FileSync {
+    // A new FileItem was generated, uploading the file if needed, or the error if it couldn't be
+    Uploaded(Result<(FileItem, Option<u64>, bool)>),
+    // The current FileItem is to be kept, along with whether the local file's content is different
+    Unchanged(bool),
+}
+
+// Generate a new FileItem for a local file which is not in the FilesContainer yet,
+// or for which the FileItem needs to be replaced since its content was modified
+#[allow(clippy::too_many_arguments)]
+async fn sync_file_item(
+    safe: &mut Safe,
+    local_file_name: &str,
+    file_meta: &FileMeta,
+    current_file_item: Option<&FileItem>,
+    force: bool,
+    compare_file_content: bool,
+    private: bool,
+    dry_run: bool,
+) -> FileSync {
+    let file_path = Path::new(local_file_name);
+    let file_item = match current_file_item {
+        None => {
+            return FileSync::Uploaded(
+                gen_new_file_item(
+                    safe,
+                    file_path,
+                    file_meta,
+                    None, // no xorurl link
+                    &BTreeSet::new(),
+                    private,
+                    dry_run,
+                )
+                .await,
+            );
+        }
+        Some(file_item) => file_item,
+    };
+
+    // If it's not to be replaced, the file is only compared with the current version
+    if !force && !compare_file_content {
+        let is_modified = is_file_item_modified(safe, file_path, file_item, private).await;
+        return FileSync::Unchanged(is_modified);
+    }
+
+    // The chunks of the current version of the file don't need to be uploaded again, only
+    // those with the changes made to it. The file is then known to be modified if it's not
+    // uploaded to the current link, thus its content is read and hashed only once.
+    let current_link = match file_item.get(FAKE_RDF_PREDICATE_LINK) {
+        Some(link) if FileMeta::filetype_is_file(&file_item[FAKE_RDF_PREDICATE_TYPE]) => Some(link),
+        // for now, symlinks and directories are never considered modified
+        _ if !force => return FileSync::Unchanged(false),
+        _ => None,
+    };
+    let mut known_chunks = BTreeSet::new();
+    if let Some(link) = current_link {
+        known_chunks = safe.fetch_blob_chunks(link).await;
+        if let Ok(xorurl_encoder) = XorUrlEncoder::from_url(link) {
+            // the manifest of the current version is already stored too
+            let _ = known_chunks.insert(xorurl_encoder.xorname());
+        }
+    }
+
+    let result = gen_new_file_item(
+        safe,
+        file_path,
+        file_meta,
+        None, // no xorurl link
+        &known_chunks,
+        private,
+        dry_run,
+    )
+    .await;
+    let is_unchanged = matches!(&result, Ok((new_file_item, _, _))
+        if new_file_item.get(FAKE_RDF_PREDICATE_LINK) == current_link);
+    if !force && is_unchanged {
+        FileSync::Unchanged(false)
+    } else {
+        FileSync::Uploaded(result)
+    }
+}
+
+// Move the parent directories of a file from the current FilesMap onto the updated one,
+// so they are known to be processed and are not deleted
+fn keep_ancestors(
+    file_name: &str,
+    current_files_map: &mut FilesMap,
+    updated_files_map: &mut FilesMap,
+) {
+    let mut trail = Vec::<&str>::new();
+    for part in file_name.split('/') {
+        trail.push(part);
+        let ancestor = if trail.len() > 1 {
+            trail.join("/")
+        } else {
+            "/".to_string()
+        };
+        if ancestor != file_name {
+            if let Some(fi) = current_files_map.remove(&ancestor) {
+                updated_files_map.insert(ancestor, fi);
+            }
+        }
+    }
+}
+
 async fn is_file_item_modified(
     safe: &mut Safe,
     local_filename: &Path,
     file_item: &FileItem,
+    private: bool,
 ) -> bool {
     if FileMeta::filetype_is_file(&file_item[FAKE_RDF_PREDICATE_TYPE]) {
-        match upload_file_to_net(safe, local_filename, true /* dry-run */).await {
-            Ok(local_xorurl) => file_item[FAKE_RDF_PREDICATE_LINK] != local_xorurl,
+        match upload_file_to_net(
+            safe,
+            local_filename,
+            &BTreeSet::new(),
+            private,
+            true, /* dry-run */
+        )
+        .await
+        {
+            Ok((local_xorurl, _, _)) => file_item[FAKE_RDF_PREDICATE_LINK] != local_xorurl,
             Err(_err) => false,
         }
     } else {
@@ -1225,7 +2183,7 @@ async fn files_map_add_link(
         Err(err) => {
             processed_files.insert(
                 file_link.to_string(),
-                (CONTENT_ERROR_SIGN.to_string(), format!("<{}>", err)),
+                ProcessedFile::new(CONTENT_ERROR_SIGN, format!("<{}>", err), None),
             );
             info!("Skipping file \"{}\". {}", file_link, err);
             Ok((processed_files, files_map, success_count))
@@ -1262,6 +2220,8 @@ async fn files_map_add_link(
                                 &file_path,
                                 &file_meta,
                                 Some(file_link),
+                                &BTreeSet::new(),
+                                false,
                                 true,
                                 true,
                                 &mut files_map,
@@ -1272,18 +2232,19 @@ async fn files_map_add_link(
                                 success_count += 1;
                             }
                         } else {
-                            processed_files.insert(file_name.to_string(), (CONTENT_ERROR_SIGN.to_string(), format!("File named \"{}\" already exists on target. Use the 'force' flag to replace it", file_name)));
+                            processed_files.insert(file_name.to_string(), ProcessedFile::new(CONTENT_ERROR_SIGN, format!("File named \"{}\" already exists on target. Use the 'force' flag to replace it", file_name), None));
                             info!("Skipping file \"{}\" since a file with name \"{}\" already exists on target. You can use the 'force' flag to replace the existing file with the new one", file_link, file_name);
                         }
                     } else {
                         processed_files.insert(
                             file_link.to_string(),
-                            (
-                                CONTENT_ERROR_SIGN.to_string(),
+                            ProcessedFile::new(
+                                CONTENT_ERROR_SIGN,
                                 format!(
                                     "File named \"{}\" already exists on target with same link",
                                     file_name
                                 ),
+                                None,
                             ),
                         );
                         info!("Skipping file \"{}\" since a file with name \"{}\" already exists on target with the same link", file_link, file_name);
@@ -1297,6 +2258,8 @@ async fn files_map_add_link(
                         &file_path,
                         &FileMeta::from_type_and_size(&file_type, &file_size),
                         Some(file_link),
+                        &BTreeSet::new(),
+                        false,
                         false,
                         true,
                         &mut files_map,
@@ -1335,14 +2298,15 @@ fn files_map_remove_path(
             if file_path.starts_with(&folder_path) {
                 processed_files.insert(
                     file_path.to_string(),
-                    (
-                        CONTENT_DELETED_SIGN.to_string(),
+                    ProcessedFile::new(
+                        CONTENT_DELETED_SIGN,
                         // note: files have link property,
                         //       dirs and symlinks do not
                         file_item
                             .get(FAKE_RDF_PREDICATE_LINK)
                             .unwrap_or(&String::default())
                             .to_string(),
+                        None,
                     ),
                 );
                 success_count += 1;
@@ -1360,14 +2324,15 @@ fn files_map_remove_path(
             )))?;
         processed_files.insert(
             dest_path.to_string(),
-            (
-                CONTENT_DELETED_SIGN.to_string(),
+            ProcessedFile::new(
+                CONTENT_DELETED_SIGN,
                 // note: files have link property,
                 //       dirs and symlinks do not
                 file_item
                     .get(FAKE_RDF_PREDICATE_LINK)
                     .unwrap_or(&String::default())
                     .to_string(),
+                None,
             ),
         );
         (1, files_map)
@@ -1376,27 +2341,234 @@ fn files_map_remove_path(
     Ok((processed_files, new_files_map, success_count))
 }
 
-// Upload a files to the Network as a Public Blob
-async fn upload_file_to_net(safe: &mut Safe, path: &Path, dry_run: bool) -> Result<XorUrl> {
-    let data = fs::read(path).map_err(|err| {
+// Find the changes made to the files from one FilesMap to another
+fn files_map_diff(files_map_a: &FilesMap, files_map_b: &FilesMap) -> FilesMapDiff {
+    let mut diff = FilesMapDiff::new();
+    let mut removed: BTreeMap<&String, &FileItem> = files_map_a
+        .iter()
+        .filter(|(path, _)| !files_map_b.contains_key(*path))
+        .collect();
+
+    for (path, file_item_b) in files_map_b.iter() {
+        let link = file_item_b
+            .get(FAKE_RDF_PREDICATE_LINK)
+            .cloned()
+            .unwrap_or_default();
+
+        match files_map_a.get(path) {
+            Some(file_item_a) => {
+                let metadata_changes = file_item_metadata_changes(file_item_a, file_item_b);
+                if file_item_a.get(FAKE_RDF_PREDICATE_LINK)
+                    != file_item_b.get(FAKE_RDF_PREDICATE_LINK)
+                    || !metadata_changes.is_empty()
+                {
+                    diff.insert(
+                        path.to_string(),
+                        FileDiff {
+                            change: CONTENT_UPDATED_SIGN.to_string(),
+                            renamed_from: None,
+                            link,
+                            metadata_changes,
+                        },
+                    );
+                }
+            }
+            None => {
+                // If a removed file had the same content, it was renamed rather than added.
+                // Directories and symlinks have no link thus they are never considered renamed.
+                let renamed_from = if link.is_empty() {
+                    None
+                } else {
+                    removed
+                        .iter()
+                        .find(|(_, file_item_a)| {
+                            file_item_a.get(FAKE_RDF_PREDICATE_LINK) == Some(&link)
+                        })
+                        .map(|(removed_path, _)| removed_path.to_string())
+                };
+
+                let file_diff = match renamed_from {
+                    Some(renamed_from) => {
+                        let metadata_changes = match removed.remove(&renamed_from) {
+                            Some(file_item_a) => {
+                                file_item_metadata_changes(file_item_a, file_item_b)
+                            }
+                            None => BTreeMap::new(),
+                        };
+                        FileDiff {
+                            change: CONTENT_RENAMED_SIGN.to_string(),
+                            renamed_from: Some(renamed_from),
+                            link,
+                            metadata_changes,
+                        }
+                    }
+                    None => FileDiff {
+                        change: CONTENT_ADDED_SIGN.to_string(),
+                        renamed_from: None,
+                        link,
+                        metadata_changes: BTreeMap::new(),
+                    },
+                };
+                diff.insert(path.to_string(), file_diff);
+            }
+        }
+    }
+
+    for (path, file_item_a) in removed {
+        diff.insert(
+            path.to_string(),
+            FileDiff {
+                change: CONTENT_DELETED_SIGN.to_string(),
+                renamed_from: None,
+                link: file_item_a
+                    .get(FAKE_RDF_PREDICATE_LINK)
+                    .cloned()
+                    .unwrap_or_default(),
+                metadata_changes: BTreeMap::new(),
+            },
+        );
+    }
+
+    diff
+}
+
+// List the changes made to the files as ProcessedFiles, a renamed file is listed
+// as removed from its previous path and added to the new one
+fn processed_files_from_diff(diff: &FilesMapDiff) -> ProcessedFiles {
+    let mut processed_files = ProcessedFiles::new();
+    for (path, file_diff) in diff.iter() {
+        if let Some(renamed_from) = &file_diff.renamed_from {
+            processed_files.insert(
+                renamed_from.to_string(),
+                ProcessedFile::new(CONTENT_DELETED_SIGN, file_diff.link.clone(), None),
+            );
+            processed_files.insert(
+                path.to_string(),
+                ProcessedFile::new(CONTENT_ADDED_SIGN, file_diff.link.clone(), None),
+            );
+        } else {
+            processed_files.insert(
+                path.to_string(),
+                ProcessedFile::new(&file_diff.change, file_diff.link.clone(), None),
+            );
+        }
+    }
+    processed_files
+}
+
+// Metadata, other than the link, with different values in each of the FileItems
+fn file_item_metadata_changes(
+    file_item_a: &FileItem,
+    file_item_b: &FileItem,
+) -> BTreeMap<String, (Option<String>, Option<String>)> {
+    file_item_a
+        .keys()
+        .chain(file_item_b.keys())
+        .filter(|key| *key != FAKE_RDF_PREDICATE_LINK)
+        .filter_map(|key| {
+            let value_a = file_item_a.get(key);
+            let value_b = file_item_b.get(key);
+            if value_a == value_b {
+                None
+            } else {
+                Some((key.to_string(), (value_a.cloned(), value_b.cloned())))
+            }
+        })
+        .collect()
+}
+
+// Upload a files to the Network as a Public or Private Blob, streaming its content from the file.
+// Chunks of the file which are in the list of known chunks are not uploaded again, the number of
+// bytes deduplicated this way is returned along with the XOR-URL. If the upload journal is in use
+// and the file is found in it, it's not uploaded at all and it's reported as resumed.
+async fn upload_file_to_net(
+    safe: &mut Safe,
+    path: &Path,
+    known_chunks: &BTreeSet<XorName>,
+    private: bool,
+    dry_run: bool,
+) -> Result<(XorUrl, u64, bool)> {
+    let journal_key = match &safe.upload_journal {
+        Some(journal) => {
+            let scope = upload_journal_scope(safe).await?;
+            let (journal_path, hash) = journal_file_key(path)?;
+            if let Some(xorurl) = journal.get(&scope, &journal_path, &hash, private) {
+                debug!("File '{}' found in upload journal", path.display());
+                return Ok((xorurl, 0, true));
+            }
+            Some((scope, journal_path, hash))
+        }
+        None => None,
+    };
+
+    #[cfg(test)]
+    let _upload = safe.uploads_in_flight.start();
+
+    let mut file = async_std::fs::File::open(path).await.map_err(|err| {
         Error::InvalidInput(format!("Failed to read file from local location: {}", err))
     })?;
 
+    // If the media-type is not supported, let's upload it as simply raw content
     let mime_type = mime_guess::from_path(&path);
-    match safe
-        .files_store_public_blob(&data, mime_type.first_raw(), dry_run)
-        .await
-    {
-        Ok(xorurl) => Ok(xorurl),
-        Err(err) => {
-            // Let's then upload it and set media-type to be simply raw content
-            if let Error::InvalidMediaType(_) = err {
-                safe.files_store_public_blob(&data, None, dry_run).await
-            } else {
-                Err(err)
-            }
+    let media_type = mime_type
+        .first_raw()
+        .filter(|media_type| XorUrlEncoder::is_media_type_supported(media_type));
+
+    let (xorurl, deduplicated) = safe
+        .store_blob_chunks(&mut file, media_type, known_chunks, private, dry_run)
+        .await?;
+
+    if let (Some((scope, journal_path, hash)), false) = (journal_key, dry_run) {
+        if let Some(journal) = &safe.upload_journal {
+            journal.record(&scope, &journal_path, &hash, private, &xorurl)?;
         }
     }
+
+    Ok((xorurl, deduplicated, false))
+}
+
+// Number of files being uploaded at the moment by a Safe instance and its clones, and the maximum
+// reached, so tests can check how many files are uploaded concurrently
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UploadsInFlight {
+    current: Arc<AtomicUsize>,
+    max: Arc<AtomicUsize>,
+}
+
+#[cfg(test)]
+impl UploadsInFlight {
+    // The upload is accounted for until the returned guard is dropped
+    fn start(&self) -> UploadInFlight {
+        let current = self.current.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max.fetch_max(current, Ordering::SeqCst);
+        UploadInFlight(self.current.clone())
+    }
+
+    fn max(&self) -> usize {
+        self.max.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+struct UploadInFlight(Arc<AtomicUsize>);
+
+#[cfg(test)]
+impl Drop for UploadInFlight {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Files are recorded in the upload journal per storage backend and keypair, since content
+// stored with any other of them is not necessarily available to the one in use
+async fn upload_journal_scope(safe: &Safe) -> Result<String> {
+    let keypair = safe.safe_client.keypair().await?;
+    Ok(format!(
+        "{}|{}",
+        safe.safe_client.backend_config(),
+        pk_bytes_to_hex(&keypair.public_key())
+    ))
 }
 
 // Get file metadata from local filesystem
@@ -1435,14 +2607,70 @@ fn get_media_type(path: &Path, meta: &fs::Metadata) -> String {
 
 // Walk the local filesystem starting from `location`, creating a list of files paths,
 // and if not requested as a `dry_run` upload the files to the network filling up
-// the list of files with their corresponding XOR-URLs
+// the list of files with their corresponding XOR-URLs. Files are uploaded concurrently,
+// up to the upload concurrency set in the Safe instance.
 async fn file_system_dir_walk(
     safe: &mut Safe,
     location: &str,
     recursive: bool,
     follow_links: bool,
+    private: bool,
     dry_run: bool,
 ) -> Result<ProcessedFiles> {
+    let (mut processed_files, files_to_upload) =
+        file_system_dir_list(safe, location, recursive, follow_links)?;
+
+    // Each upload uses its own clone of the Safe instance, they all share the same
+    // connection and upload journal. The results are collected in the same order
+    // the files were found, regardless of the order the uploads complete in.
+    let uploads = files_to_upload.into_iter().map(|(normalised_path, path)| {
+        let mut safe = safe.clone();
+        async move {
+            let result =
+                upload_file_to_net(&mut safe, &path, &BTreeSet::new(), private, dry_run).await;
+            (normalised_path, result)
+        }
+    });
+    let results: Vec<_> = stream::iter(uploads)
+        .buffered(safe.upload_concurrency)
+        .collect()
+        .await;
+
+    for (normalised_path, result) in results {
+        match result {
+            Ok((xorurl, deduplicated, resumed)) => {
+                let change = if resumed {
+                    CONTENT_RESUMED_SIGN
+                } else {
+                    CONTENT_ADDED_SIGN
+                };
+                processed_files.insert(
+                    normalised_path,
+                    ProcessedFile::new(change, xorurl, Some(deduplicated)),
+                );
+            }
+            Err(err) => {
+                processed_files.insert(
+                    normalised_path.clone(),
+                    ProcessedFile::new(CONTENT_ERROR_SIGN, format!("<{}>", err), None),
+                );
+                info!("Skipping file \"{}\". {}", normalised_path, err);
+            }
+        }
+    }
+
+    Ok(processed_files)
+}
+
+// Walk the local filesystem starting from `location`, creating a list of the directories and
+// symlinks found, and of the paths ignored or which couldn't be read, without uploading anything.
+// The list of files found, which content is yet to be uploaded, is returned separately.
+fn file_system_dir_list(
+    safe: &Safe,
+    location: &str,
+    recursive: bool,
+    follow_links: bool,
+) -> Result<(ProcessedFiles, Vec<(String, PathBuf)>)> {
     let file_path = Path::new(location);
     info!("Reading files from {}", file_path.display());
     let (metadata, _) = get_metadata(&file_path, follow_links)?;
@@ -1451,10 +2679,30 @@ async fn file_system_dir_walk(
         // We now compare both FilesMaps to upload the missing files
         let max_depth = if recursive { MAX_RECURSIVE_DEPTH } else { 1 };
         let mut processed_files = BTreeMap::new();
+        let mut files_to_upload = Vec::new();
+
+        // Paths ignored are not walked through, they are only reported as skipped
+        let ignore_root = if metadata.is_dir() {
+            file_path
+        } else {
+            file_path.parent().unwrap_or_else(|| Path::new(""))
+        };
+        let mut ignore_rules =
+            IgnoreRules::new(ignore_root, &safe.exclude_patterns, &safe.include_patterns)?;
+        let mut skipped_paths = Vec::new();
         let children_to_process = WalkDir::new(file_path)
             .follow_links(follow_links)
             .into_iter()
-            .filter_entry(|e| valid_depth(e, max_depth))
+            .filter_entry(|e| {
+                if !valid_depth(e, max_depth) {
+                    return false;
+                }
+                if ignore_rules.is_ignored(e.path(), e.file_type().is_dir()) {
+                    skipped_paths.push(e.path().to_path_buf());
+                    return false;
+                }
+                true
+            })
             .filter_map(|v| v.ok());
 
         for (idx, child) in children_to_process.enumerate() {
@@ -1486,37 +2734,23 @@ async fn file_system_dir_walk(
                         // Callers can inspect the file's metadata.
                         processed_files.insert(
                             normalised_path.clone(),
-                            (CONTENT_ADDED_SIGN.to_string(), String::default()),
+                            ProcessedFile::new(CONTENT_ADDED_SIGN, String::default(), None),
                         );
                     }
                     if metadata.file_type().is_symlink() {
                         processed_files.insert(
                             normalised_path.clone(),
-                            (CONTENT_ADDED_SIGN.to_string(), String::default()),
+                            ProcessedFile::new(CONTENT_ADDED_SIGN, String::default(), None),
                         );
                     }
                     if metadata.file_type().is_file() {
-                        match upload_file_to_net(safe, &current_file_path, dry_run).await {
-                            Ok(xorurl) => {
-                                processed_files.insert(
-                                    normalised_path,
-                                    (CONTENT_ADDED_SIGN.to_string(), xorurl),
-                                );
-                            }
-                            Err(err) => {
-                                processed_files.insert(
-                                    normalised_path.clone(),
-                                    (CONTENT_ERROR_SIGN.to_string(), format!("<{}>", err)),
-                                );
-                                info!("Skipping file \"{}\". {}", normalised_path, err);
-                            }
-                        }
+                        files_to_upload.push((normalised_path, current_file_path.to_path_buf()));
                     }
                 }
                 Err(err) => {
                     processed_files.insert(
                         normalised_path.clone(),
-                        (CONTENT_ERROR_SIGN.to_string(), format!("<{}>", err)),
+                        ProcessedFile::new(CONTENT_ERROR_SIGN, format!("<{}>", err), None),
                     );
                     info!(
                         "Skipping file \"{}\" since no metadata could be read from local location: {:?}",
@@ -1525,7 +2759,17 @@ async fn file_system_dir_walk(
             }
         }
 
-        Ok(processed_files)
+        for skipped_path in skipped_paths {
+            let normalised_path =
+                normalise_path_separator(skipped_path.to_str().unwrap_or_else(|| ""));
+            info!("Skipping \"{}\" since it's ignored", normalised_path);
+            processed_files.insert(
+                normalised_path,
+                ProcessedFile::new(CONTENT_SKIPPED_SIGN, String::default(), None),
+            );
+        }
+
+        Ok((processed_files, files_to_upload))
     } else {
         // Recursive only works on a dir path. Let's error as the user may be making a mistake
         // so it's better for the user to double check and either provide the correct path
@@ -1547,18 +2791,12 @@ fn valid_depth(entry: &DirEntry, max_depth: usize) -> bool {
 }
 
 // Read the local filesystem at `location`, creating a list of one single file's path,
-// and if not as a `dry_run` upload the file to the network and putting
-// the obtained XOR-URL in the single file list returned
-async fn file_system_single_file(
-    safe: &mut Safe,
-    location: &str,
-    dry_run: bool,
-) -> Result<ProcessedFiles> {
+// the file is not uploaded, this is left to be done when syncing it with the FilesContainer
+fn file_system_single_file(location: &str) -> Result<ProcessedFiles> {
     let file_path = Path::new(location);
     info!("Reading file {}", file_path.display());
     let (metadata, _) = get_metadata(&file_path, true)?; // follows symlinks.
 
-    // We now compare both FilesMaps to upload the missing files
     let mut processed_files = BTreeMap::new();
     let normalised_path = normalise_path_separator(file_path.to_str().unwrap_or_else(|| ""));
     if metadata.is_dir() {
@@ -1567,18 +2805,10 @@ async fn file_system_single_file(
             location
         )))
     } else {
-        match upload_file_to_net(safe, &file_path, dry_run).await {
-            Ok(xorurl) => {
-                processed_files.insert(normalised_path, (CONTENT_ADDED_SIGN.to_string(), xorurl));
-            }
-            Err(err) => {
-                processed_files.insert(
-                    normalised_path.clone(),
-                    (CONTENT_ERROR_SIGN.to_string(), format!("<{}>", err)),
-                );
-                info!("Skipping file \"{}\". {}", normalised_path, err);
-            }
-        };
+        processed_files.insert(
+            normalised_path,
+            ProcessedFile::new(CONTENT_ADDED_SIGN, String::default(), None),
+        );
         Ok(processed_files)
     }
 }
@@ -1604,9 +2834,11 @@ async fn files_map_create(
     // either, but is much less data.  Is there a more efficient way?
     let keys = content.keys().cloned().collect::<Vec<_>>();
     for file_name in keys {
-        let (change, link) = &content[&file_name].clone();
+        let processed_file = content[&file_name].clone();
+        let change = processed_file.change.as_str();
+        let link = processed_file.link.as_str();
 
-        if change == CONTENT_ERROR_SIGN {
+        if change == CONTENT_ERROR_SIGN || change == CONTENT_SKIPPED_SIGN {
             continue;
         }
 
@@ -1631,13 +2863,26 @@ async fn files_map_create(
             &final_name,
             &Path::new(&file_name),
             &FileMeta::from_path(&file_name, follow_links)?,
-            if link.is_empty() { None } else { Some(&link) },
+            if link.is_empty() { None } else { Some(link) },
+            &BTreeSet::new(),
+            false,
             false,
             dry_run,
             &mut files_map,
             &mut content,
         )
         .await;
+
+        // Keep reporting the files which upload was resumed from the upload journal as such,
+        // along with the number of bytes which got deduplicated when uploading them
+        if let Some(new_processed_file) = content.get_mut(&file_name) {
+            if new_processed_file.change != CONTENT_ERROR_SIGN {
+                if change == CONTENT_RESUMED_SIGN {
+                    new_processed_file.change = CONTENT_RESUMED_SIGN.to_string();
+                }
+                new_processed_file.deduplicated_size = processed_file.deduplicated_size;
+            }
+        }
     }
     Ok(files_map)
 }
@@ -1645,7 +2890,14 @@ async fn files_map_create(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::app::test_helpers::{new_safe_instance, random_nrs_name};
+    use crate::api::app::{
+        chunker::CHUNK_MAX_SIZE,
+        test_helpers::{
+            new_safe_instance, new_safe_instance_on_disk, random_local_storage_path,
+            random_nrs_name,
+        },
+    };
+    use crate::BackendConfig;
 
     // make some constants for these, in case entries in the
     // testdata folder change.
@@ -1663,11 +2915,11 @@ mod tests {
 
         processed_files.insert(
             "../testdata/test.md".to_string(),
-            (CONTENT_ADDED_SIGN.to_string(), first_xorurl.clone()),
+            ProcessedFile::new(CONTENT_ADDED_SIGN, first_xorurl.clone(), None),
         );
         processed_files.insert(
             "../testdata/subfolder/subexists.md".to_string(),
-            (CONTENT_ADDED_SIGN.to_string(), second_xorurl.clone()),
+            ProcessedFile::new(CONTENT_ADDED_SIGN, second_xorurl.clone(), None),
         );
         let files_map = files_map_create(
             &mut safe,
@@ -1695,7 +2947,13 @@ mod tests {
     async fn test_files_container_create_empty() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(None, None, false, false, false)
+            .files_container_create(
+                None,
+                None,
+                false,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert!(xorurl.starts_with("safe://"));
@@ -1712,29 +2970,65 @@ mod tests {
         assert_eq!(new_files_map.len(), 1);
 
         let filename = "../testdata/test.md";
-        assert_eq!(new_processed_files[filename].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[filename].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[filename].1,
+            new_processed_files[filename].link,
             new_files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_files_container_create_legacy_maps_format() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        safe.set_legacy_maps_format(true);
+        let (xorurl, _, files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+
+        // the FilesMap is stored as plain JSON, which is still read
+        let xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
+        let serialised_files_map = safe
+            .safe_client
+            .sequence_get_last_entry(xorurl_encoder.xorname(), FILES_CONTAINER_TYPE_TAG, false)
+            .await?
+            .1;
+        assert_eq!(
+            serde_json::from_slice::<FilesMap>(&serialised_files_map).ok(),
+            Some(files_map.clone())
+        );
+        let (_, fetched_files_map) = safe.files_container_get(&xorurl).await?;
+        assert_eq!(fetched_files_map, files_map);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_files_container_create_file() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let filename = "../testdata/test.md";
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some(filename), None, false, false, false)
+            .files_container_create(
+                Some(filename),
+                None,
+                false,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert!(xorurl.starts_with("safe://"));
         assert_eq!(processed_files.len(), 1);
         assert_eq!(files_map.len(), 1);
         let file_path = "/test.md";
-        assert_eq!(processed_files[filename].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename].1,
+            processed_files[filename].link,
             files_map[file_path][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -1745,7 +3039,16 @@ mod tests {
         let mut safe = new_safe_instance().await?;
         let filename = "../testdata/";
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some(filename), None, true, false, true)
+            .files_container_create(
+                Some(filename),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions {
+                    dry_run: true,
+                    ..Default::default()
+                },
+            )
             .await?;
 
         assert!(xorurl.is_empty());
@@ -1753,44 +3056,236 @@ mod tests {
         assert_eq!(files_map.len(), TESTDATA_PUT_FILEITEM_COUNT);
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
-        assert!(!processed_files[filename1].1.is_empty());
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
+        assert!(!processed_files[filename1].link.is_empty());
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/another.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
-        assert!(!processed_files[filename2].1.is_empty());
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
+        assert!(!processed_files[filename2].link.is_empty());
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             files_map["/another.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename3].0, CONTENT_ADDED_SIGN);
-        assert!(!processed_files[filename3].1.is_empty());
+        assert_eq!(processed_files[filename3].change, CONTENT_ADDED_SIGN);
+        assert!(!processed_files[filename3].link.is_empty());
         assert_eq!(
-            processed_files[filename3].1,
+            processed_files[filename3].link,
             files_map["/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename4 = "../testdata/noextension";
-        assert_eq!(processed_files[filename4].0, CONTENT_ADDED_SIGN);
-        assert!(!processed_files[filename4].1.is_empty());
+        assert_eq!(processed_files[filename4].change, CONTENT_ADDED_SIGN);
+        assert!(!processed_files[filename4].link.is_empty());
         assert_eq!(
-            processed_files[filename4].1,
+            processed_files[filename4].link,
             files_map["/noextension"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_files_container_create_ignored_paths() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let folder = std::env::temp_dir().join(random_nrs_name());
+        for (path, content) in &[
+            (".safeignore", "node_modules/\n*.log\n"),
+            ("index.html", "<html></html>"),
+            ("debug.log", "some logs"),
+            ("node_modules/module/index.js", "function() {}"),
+            ("dist/bundle.js", "function() {}"),
+        ] {
+            let path = folder.join(path);
+            fs::create_dir_all(path.parent().unwrap_or(&folder))
+                .and_then(|_| fs::write(path, content))
+                .map_err(|err| Error::FileSystemError(err.to_string()))?;
+        }
+        let location = format!("{}/", folder.display());
+
+        safe.set_upload_patterns(&["dist".to_string()], &["debug.log".to_string()])?;
+        let (xorurl, processed_files, files_map) = safe
+            .files_container_create(
+                Some(&location),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+
+        let node_modules = format!("{}node_modules", location);
+        let dist = format!("{}dist", location);
+        assert_eq!(processed_files[&node_modules].change, CONTENT_SKIPPED_SIGN);
+        assert_eq!(processed_files[&dist].change, CONTENT_SKIPPED_SIGN);
+        assert_eq!(
+            processed_files[&format!("{}index.html", location)].change,
+            CONTENT_ADDED_SIGN
+        );
+        assert_eq!(
+            processed_files[&format!("{}debug.log", location)].change,
+            CONTENT_ADDED_SIGN
+        );
+        assert!(!processed_files.contains_key(&format!("{}node_modules/module", location)));
+        assert_eq!(
+            files_map.keys().collect::<Vec<_>>(),
+            vec!["/.safeignore", "/debug.log", "/index.html"]
+        );
+
+        // the ignored paths are also skipped when syncing
+        safe.set_upload_patterns(&[], &[])?;
+        let (version, processed_files, files_map) = safe
+            .files_container_sync(&location, &xorurl, true, false, false, false, false)
+            .await?;
+        assert_eq!(version, 1);
+        assert_eq!(processed_files[&node_modules].change, CONTENT_SKIPPED_SIGN);
+        assert_eq!(
+            processed_files[&format!("{}debug.log", location)].change,
+            CONTENT_SKIPPED_SIGN
+        );
+        assert_eq!(processed_files[&dist].change, CONTENT_ADDED_SIGN);
+        assert!(files_map.contains_key("/dist/bundle.js"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_container_create_concurrency() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        safe.set_upload_concurrency(1);
+        let (_, processed_files, files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+
+        // the outcome is the same regardless of the number of concurrent uploads
+        safe.set_upload_concurrency(8);
+        let (_, concurrent_processed_files, concurrent_files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+        assert_eq!(
+            concurrent_processed_files.keys().collect::<Vec<_>>(),
+            processed_files.keys().collect::<Vec<_>>()
+        );
+        for (file_name, processed_file) in processed_files.iter() {
+            assert_eq!(
+                concurrent_processed_files[file_name].change,
+                processed_file.change
+            );
+            assert_eq!(
+                concurrent_processed_files[file_name].link,
+                processed_file.link
+            );
+        }
+        assert_eq!(concurrent_files_map.len(), files_map.len());
+        for (path, file_item) in files_map.iter() {
+            assert_eq!(
+                concurrent_files_map[path].get(FAKE_RDF_PREDICATE_LINK),
+                file_item.get(FAKE_RDF_PREDICATE_LINK)
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_container_create_resumed() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let journal_path = std::env::temp_dir()
+            .join(random_nrs_name())
+            .join("upload_journal");
+        safe.set_upload_journal(&journal_path)?;
+
+        // a dry run doesn't record anything in the journal
+        let (_, processed_files, _) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions {
+                    dry_run: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        assert!(processed_files
+            .values()
+            .all(|processed_file| processed_file.change == CONTENT_ADDED_SIGN));
+
+        let (_, processed_files, files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+        assert!(processed_files
+            .values()
+            .all(|processed_file| processed_file.change == CONTENT_ADDED_SIGN));
+
+        // the files are now found in the journal thus they are not uploaded again,
+        // while directories are not uploaded in any case
+        let (_, resumed_processed_files, resumed_files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+        assert_eq!(resumed_processed_files.len(), TESTDATA_PUT_FILEITEM_COUNT);
+        assert_eq!(
+            resumed_processed_files["../testdata/test.md"].change,
+            CONTENT_RESUMED_SIGN
+        );
+        assert_eq!(
+            resumed_processed_files["../testdata/test.md"].link,
+            processed_files["../testdata/test.md"].link
+        );
+        assert_eq!(
+            resumed_processed_files["../testdata/subfolder/subexists.md"].change,
+            CONTENT_RESUMED_SIGN
+        );
+        assert_eq!(
+            resumed_processed_files["../testdata/subfolder"].change,
+            CONTENT_ADDED_SIGN
+        );
+        assert_eq!(
+            resumed_files_map["/test.md"][FAKE_RDF_PREDICATE_LINK],
+            files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_files_container_create_folder_without_trailing_slash() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert!(xorurl.starts_with("safe://"));
@@ -1798,40 +3293,190 @@ mod tests {
         assert_eq!(files_map.len(), TESTDATA_NO_SLASH_PUT_FILEITEM_COUNT);
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             files_map["/testdata/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/another.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             files_map["/testdata/another.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename3].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename3].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename3].1,
+            processed_files[filename3].link,
             files_map["/testdata/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename4 = "../testdata/noextension";
-        assert_eq!(processed_files[filename4].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename4].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename4].1,
+            processed_files[filename4].link,
             files_map["/testdata/noextension"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_files_container_create_private() -> Result<()> {
+        let path = random_local_storage_path();
+        let mut safe = new_safe_instance_on_disk(&path).await?;
+        let (xorurl, processed_files, files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions {
+                    private: true,
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
+        assert_eq!(xorurl_encoder.data_type(), SafeDataType::PrivateSequence);
+        assert_eq!(processed_files.len(), TESTDATA_PUT_FILEITEM_COUNT);
+
+        let file_link = &files_map["/test.md"][FAKE_RDF_PREDICATE_LINK];
+        assert_eq!(
+            XorUrlEncoder::from_url(file_link)?.data_type(),
+            SafeDataType::PrivateBlob
+        );
+        let file_data = fs::read("../testdata/test.md")
+            .map_err(|err| Error::Unexpected(format!("Failed to read local file: {}", err)))?;
+        assert_eq!(
+            safe.files_get_private_blob(file_link, None).await?,
+            file_data
+        );
+        assert_eq!(safe.files_get_blob(file_link, None).await?, file_data);
+        // the public API doesn't fetch, nor decrypt, Private Blobs
+        match safe.files_get_public_blob(file_link, None).await {
+            Err(Error::InvalidInput(msg)) => assert!(msg.contains("doesn't target a Public Blob")),
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected result when getting a Private Blob as public: {:?}",
+                    other
+                )))
+            }
+        }
+
+        // the FilesContainer is decrypted when fetched
+        let (version, fetched_files_map) = safe.files_container_get(&xorurl).await?;
+        assert_eq!(version, 0);
+        assert_eq!(fetched_files_map, files_map);
+
+        // files added to a private FilesContainer are stored as private content as well
+        let (version, _, new_files_map) = safe
+            .files_container_add_from_raw(
+                b"0123456789",
+                &format!("{}/new_file.md", xorurl),
+                false,
+                false,
+                false,
+            )
+            .await?;
+        assert_eq!(version, 1);
+        let new_link = &new_files_map["/new_file.md"][FAKE_RDF_PREDICATE_LINK];
+        assert_eq!(
+            XorUrlEncoder::from_url(new_link)?.data_type(),
+            SafeDataType::PrivateBlob
+        );
+        assert_eq!(
+            safe.files_get_private_blob(new_link, None).await?,
+            b"0123456789"
+        );
+
+        let (version, fetched_files_map) = safe.files_container_get(&xorurl).await?;
+        assert_eq!(version, 1);
+        assert_eq!(fetched_files_map, new_files_map);
+
+        let _ = fs::remove_file(&path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_container_private_new_session() -> Result<()> {
+        let path = random_local_storage_path();
+        let (xorurl, files_map) = {
+            let mut safe = new_safe_instance_on_disk(&path).await?;
+            let (xorurl, _, files_map) = safe
+                .files_container_create(
+                    Some("../testdata/"),
+                    None,
+                    true,
+                    true,
+                    FilesContainerCreateOptions {
+                        private: true,
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            (xorurl, files_map)
+        };
+
+        // a new instance connected to the same storage can decrypt what the previous one stored
+        let mut safe = new_safe_instance_on_disk(&path).await?;
+        let (version, fetched_files_map) = safe.files_container_get(&xorurl).await?;
+        assert_eq!(version, 0);
+        assert_eq!(fetched_files_map, files_map);
+        let file_data = fs::read("../testdata/test.md")
+            .map_err(|err| Error::Unexpected(format!("Failed to read local file: {}", err)))?;
+        assert_eq!(
+            safe.files_get_private_blob(&files_map["/test.md"][FAKE_RDF_PREDICATE_LINK], None)
+                .await?,
+            file_data
+        );
+
+        let _ = fs::remove_file(&path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_container_private_ephemeral_keypair() -> Result<()> {
+        // each in-memory instance gets a new keypair, thus it cannot store private data
+        let mut safe = Safe::with_backend(None, BackendConfig::InMemory);
+        safe.connect("", Some("fake-credentials")).await?;
+        match safe
+            .files_container_create(
+                Some("../testdata/test.md"),
+                None,
+                false,
+                false,
+                FilesContainerCreateOptions {
+                    private: true,
+                    ..Default::default()
+                },
+            )
+            .await
+        {
+            Err(Error::AccessDenied(msg)) => {
+                assert!(msg.contains("persists across sessions"));
+                Ok(())
+            }
+            other => Err(Error::Unexpected(format!(
+                "Private FilesContainer created with an ephemeral keypair: {:?}",
+                other
+            ))),
+        }
+    }
+
     #[tokio::test]
     async fn test_files_container_create_folder_with_trailing_slash() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert!(xorurl.starts_with("safe://"));
@@ -1839,30 +3484,30 @@ mod tests {
         assert_eq!(files_map.len(), TESTDATA_PUT_FILEITEM_COUNT);
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/another.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             files_map["/another.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename3].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename3].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename3].1,
+            processed_files[filename3].link,
             files_map["/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename4 = "../testdata/noextension";
-        assert_eq!(processed_files[filename4].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename4].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename4].1,
+            processed_files[filename4].link,
             files_map["/noextension"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -1872,7 +3517,13 @@ mod tests {
     async fn test_files_container_create_dest_path_without_trailing_slash() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata"), Some("/myroot"), true, true, false)
+            .files_container_create(
+                Some("../testdata"),
+                Some("/myroot"),
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert!(xorurl.starts_with("safe://"));
@@ -1880,30 +3531,30 @@ mod tests {
         assert_eq!(files_map.len(), TESTDATA_NO_SLASH_PUT_FILEITEM_COUNT);
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             files_map["/myroot/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/another.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             files_map["/myroot/another.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename3].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename3].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename3].1,
+            processed_files[filename3].link,
             files_map["/myroot/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename4 = "../testdata/noextension";
-        assert_eq!(processed_files[filename4].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename4].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename4].1,
+            processed_files[filename4].link,
             files_map["/myroot/noextension"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -1913,7 +3564,13 @@ mod tests {
     async fn test_files_container_create_dest_path_with_trailing_slash() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata"), Some("/myroot/"), true, true, false)
+            .files_container_create(
+                Some("../testdata"),
+                Some("/myroot/"),
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert!(xorurl.starts_with("safe://"));
@@ -1921,40 +3578,81 @@ mod tests {
         assert_eq!(files_map.len(), TESTDATA_NO_SLASH_PUT_FILEITEM_COUNT);
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             files_map["/myroot/testdata/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/another.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             files_map["/myroot/testdata/another.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename3].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename3].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename3].1,
+            processed_files[filename3].link,
             files_map["/myroot/testdata/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename4 = "../testdata/noextension";
-        assert_eq!(processed_files[filename4].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename4].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename4].1,
+            processed_files[filename4].link,
             files_map["/myroot/testdata/noextension"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_files_container_sync_concurrency() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        safe.set_upload_concurrency(1);
+        let (xorurl, _, _) = safe
+            .files_container_create(None, None, true, false, Default::default())
+            .await?;
+        let (_, processed_files, files_map) = safe
+            .files_container_sync("../testdata/", &xorurl, true, false, false, false, false)
+            .await?;
+        assert_eq!(safe.uploads_in_flight.max(), 1);
+
+        // the files are uploaded in parallel, with the same outcome
+        let mut concurrent_safe = new_safe_instance().await?;
+        concurrent_safe.set_upload_concurrency(4);
+        let (xorurl, _, _) = concurrent_safe
+            .files_container_create(None, None, true, false, Default::default())
+            .await?;
+        let (_, concurrent_processed_files, concurrent_files_map) = concurrent_safe
+            .files_container_sync("../testdata/", &xorurl, true, false, false, false, false)
+            .await?;
+        assert!(concurrent_safe.uploads_in_flight.max() > 1);
+        assert!(concurrent_safe.uploads_in_flight.max() <= 4);
+
+        assert_eq!(concurrent_processed_files, processed_files);
+        assert_eq!(concurrent_files_map.len(), files_map.len());
+        for (path, file_item) in files_map.iter() {
+            assert_eq!(
+                concurrent_files_map[path].get(FAKE_RDF_PREDICATE_LINK),
+                file_item.get(FAKE_RDF_PREDICATE_LINK)
+            );
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_files_container_sync() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert_eq!(processed_files.len(), TESTDATA_PUT_FILEITEM_COUNT);
@@ -1980,44 +3678,44 @@ mod tests {
         );
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             new_files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/another.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             new_files_map["/another.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename3].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename3].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename3].1,
+            processed_files[filename3].link,
             new_files_map["/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename4 = "../testdata/noextension";
-        assert_eq!(processed_files[filename4].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename4].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename4].1,
+            processed_files[filename4].link,
             new_files_map["/noextension"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename5 = "../testdata/subfolder/subexists.md";
-        assert_eq!(new_processed_files[filename5].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[filename5].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[filename5].1,
+            new_processed_files[filename5].link,
             new_files_map["/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename6 = "../testdata/subfolder/sub2.md";
-        assert_eq!(new_processed_files[filename6].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[filename6].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[filename6].1,
+            new_processed_files[filename6].link,
             new_files_map["/sub2.md"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -2027,7 +3725,13 @@ mod tests {
     async fn test_files_container_sync_dry_run() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert_eq!(processed_files.len(), TESTDATA_PUT_FILEITEM_COUNT);
@@ -2053,46 +3757,46 @@ mod tests {
         );
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             new_files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/another.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             new_files_map["/another.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename3].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename3].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename3].1,
+            processed_files[filename3].link,
             new_files_map["/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename4 = "../testdata/noextension";
-        assert_eq!(processed_files[filename4].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename4].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename4].1,
+            processed_files[filename4].link,
             new_files_map["/noextension"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename5 = "../testdata/subfolder/subexists.md";
-        assert_eq!(new_processed_files[filename5].0, CONTENT_ADDED_SIGN);
-        assert!(!new_processed_files[filename5].1.is_empty());
+        assert_eq!(new_processed_files[filename5].change, CONTENT_ADDED_SIGN);
+        assert!(!new_processed_files[filename5].link.is_empty());
         assert_eq!(
-            new_processed_files[filename5].1,
+            new_processed_files[filename5].link,
             new_files_map["/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename6 = "../testdata/subfolder/sub2.md";
-        assert_eq!(new_processed_files[filename6].0, CONTENT_ADDED_SIGN);
-        assert!(!new_processed_files[filename6].1.is_empty());
+        assert_eq!(new_processed_files[filename6].change, CONTENT_ADDED_SIGN);
+        assert!(!new_processed_files[filename6].link.is_empty());
         assert_eq!(
-            new_processed_files[filename6].1,
+            new_processed_files[filename6].link,
             new_files_map["/sub2.md"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -2102,7 +3806,13 @@ mod tests {
     async fn test_files_container_sync_same_size() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/test.md"), None, false, false, false)
+            .files_container_create(
+                Some("../testdata/test.md"),
+                None,
+                false,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert_eq!(processed_files.len(), 1);
@@ -2125,15 +3835,15 @@ mod tests {
         assert_eq!(new_files_map.len(), 1);
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
         let filename2 = "../testdata/.subhidden/test.md";
-        assert_eq!(new_processed_files[filename2].0, CONTENT_UPDATED_SIGN);
+        assert_eq!(new_processed_files[filename2].change, CONTENT_UPDATED_SIGN);
         assert_eq!(
-            new_processed_files[filename2].1,
+            new_processed_files[filename2].link,
             new_files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
@@ -2149,11 +3859,62 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_files_container_sync_deduplicated_size() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let folder = std::env::temp_dir().join(random_nrs_name());
+        let file_path = folder.join("large.bin");
+        let data: Vec<u8> = (0..(CHUNK_MAX_SIZE * 3))
+            .map(|i| ((i * 7 + i / 1013) % 256) as u8)
+            .collect();
+        fs::create_dir_all(&folder)
+            .and_then(|_| fs::write(&file_path, &data))
+            .map_err(|err| Error::FileSystemError(err.to_string()))?;
+        let location = format!("{}/", folder.display());
+        let file_name = format!("{}large.bin", location);
+
+        let (xorurl, processed_files, _) = safe
+            .files_container_create(
+                Some(&location),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+        assert_eq!(processed_files[&file_name].change, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[&file_name].deduplicated_size, Some(0));
+
+        // only the chunks with the bytes appended are uploaded when syncing
+        let mut modified_data = data.clone();
+        modified_data.extend(b"some new bytes");
+        fs::write(&file_path, &modified_data)
+            .map_err(|err| Error::FileSystemError(err.to_string()))?;
+        let (version, processed_files, _) = safe
+            .files_container_sync(&location, &xorurl, true, false, false, false, false)
+            .await?;
+        assert_eq!(version, 1);
+        assert_eq!(processed_files[&file_name].change, CONTENT_UPDATED_SIGN);
+        match processed_files[&file_name].deduplicated_size {
+            Some(size) if size > 0 && size < modified_data.len() as u64 => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Unexpected number of bytes deduplicated: {:?}",
+                other
+            ))),
+        }
+    }
+
     #[tokio::test]
     async fn test_files_container_sync_with_versioned_target() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _, _) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let versioned_xorurl = format!("{}?v=5", xorurl);
@@ -2189,7 +3950,13 @@ mod tests {
     async fn test_files_container_sync_with_delete() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert_eq!(processed_files.len(), TESTDATA_PUT_FILEITEM_COUNT);
@@ -2216,38 +3983,38 @@ mod tests {
 
         // first check all previous files were removed
         let file_path1 = "/test.md";
-        assert_eq!(new_processed_files[file_path1].0, CONTENT_DELETED_SIGN);
+        assert_eq!(new_processed_files[file_path1].change, CONTENT_DELETED_SIGN);
         assert_eq!(
-            new_processed_files[file_path1].1,
+            new_processed_files[file_path1].link,
             files_map[file_path1][FAKE_RDF_PREDICATE_LINK]
         );
 
         let file_path2 = "/another.md";
-        assert_eq!(new_processed_files[file_path2].0, CONTENT_DELETED_SIGN);
+        assert_eq!(new_processed_files[file_path2].change, CONTENT_DELETED_SIGN);
         assert_eq!(
-            new_processed_files[file_path2].1,
+            new_processed_files[file_path2].link,
             files_map[file_path2][FAKE_RDF_PREDICATE_LINK]
         );
 
         let file_path3 = "/subfolder/subexists.md";
-        assert_eq!(new_processed_files[file_path3].0, CONTENT_DELETED_SIGN);
+        assert_eq!(new_processed_files[file_path3].change, CONTENT_DELETED_SIGN);
         assert_eq!(
-            new_processed_files[file_path3].1,
+            new_processed_files[file_path3].link,
             files_map[file_path3][FAKE_RDF_PREDICATE_LINK]
         );
 
         let file_path4 = "/noextension";
-        assert_eq!(new_processed_files[file_path4].0, CONTENT_DELETED_SIGN);
+        assert_eq!(new_processed_files[file_path4].change, CONTENT_DELETED_SIGN);
         assert_eq!(
-            new_processed_files[file_path4].1,
+            new_processed_files[file_path4].link,
             files_map[file_path4][FAKE_RDF_PREDICATE_LINK]
         );
 
         // and finally check the synced file was added
         let filename5 = "../testdata/subfolder/subexists.md";
-        assert_eq!(new_processed_files[filename5].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[filename5].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[filename5].1,
+            new_processed_files[filename5].link,
             new_files_map["/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -2287,7 +4054,13 @@ mod tests {
     async fn test_files_container_sync_update_nrs_unversioned_link() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _, _) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let nrsurl = random_nrs_name();
@@ -2295,7 +4068,14 @@ mod tests {
         xorurl_encoder.set_content_version(None);
         let unversioned_link = xorurl_encoder.to_string();
         match safe
-            .nrs_map_container_create(&nrsurl, &unversioned_link, false, true, false)
+            .nrs_map_container_create(
+                &nrsurl,
+                &unversioned_link,
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await
         {
             Ok(_) => Err(Error::Unexpected(
@@ -2318,7 +4098,13 @@ mod tests {
     async fn test_files_container_sync_update_nrs_with_xorurl() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _, _) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         match safe
@@ -2350,21 +4136,85 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_files_container_sync_update_nrs_versioned_link() -> Result<()> {
+    async fn test_files_container_sync_update_nrs_versioned_link() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let (xorurl, _, _) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+
+        let nrsurl = random_nrs_name();
+
+        let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
+        xorurl_encoder.set_content_version(Some(0));
+        let _ = safe
+            .nrs_map_container_create(
+                &nrsurl,
+                &xorurl_encoder.to_string(),
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+
+        let _ = safe
+            .files_container_sync(
+                "../testdata/subfolder/",
+                &nrsurl,
+                false,
+                false,
+                false,
+                true, // this flag requests the update-nrs
+                false,
+            )
+            .await?;
+
+        let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
+        xorurl_encoder.set_content_version(Some(1));
+        let (new_link, _) = safe.parse_and_resolve_url(&nrsurl).await?;
+        assert_eq!(new_link.to_string(), xorurl_encoder.to_string());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_container_sync_update_nrs_follow_latest() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _, _) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let nrsurl = random_nrs_name();
 
         let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
-        xorurl_encoder.set_content_version(Some(0));
-        let _ = safe
-            .nrs_map_container_create(&nrsurl, &xorurl_encoder.to_string(), false, true, false)
+        xorurl_encoder.set_content_version(None);
+        let unversioned_link = xorurl_encoder.to_string();
+        let (nrs_xorurl, _, _) = safe
+            .nrs_map_container_create(
+                &nrsurl,
+                &unversioned_link,
+                false,
+                true,
+                NrsLinkOptions {
+                    link_policy: NrsLinkPolicy::FollowLatest,
+                    ..Default::default()
+                },
+                false,
+            )
             .await?;
 
-        let _ = safe
+        let (version, _, _) = safe
             .files_container_sync(
                 "../testdata/subfolder/",
                 &nrsurl,
@@ -2375,11 +4225,14 @@ mod tests {
                 false,
             )
             .await?;
+        assert_eq!(version, 1);
 
-        let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
-        xorurl_encoder.set_content_version(Some(1));
-        let (new_link, _) = safe.parse_and_resolve_url(&nrsurl).await?;
-        assert_eq!(new_link.to_string(), xorurl_encoder.to_string());
+        // the entry keeps following the latest version rather than being pinned to it
+        let (nrs_version, nrs_map) = safe.nrs_map_container_get(&nrs_xorurl).await?;
+        assert_eq!(nrs_version, 0);
+        let resolution = nrs_map.resolve_entry(&[])?;
+        assert_eq!(resolution.link, unversioned_link);
+        assert_eq!(resolution.link_policy, NrsLinkPolicy::FollowLatest);
         Ok(())
     }
 
@@ -2387,7 +4240,13 @@ mod tests {
     async fn test_files_container_sync_target_path_without_trailing_slash() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert_eq!(processed_files.len(), TESTDATA_PUT_FILEITEM_COUNT);
@@ -2417,38 +4276,38 @@ mod tests {
         );
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             new_files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/another.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             new_files_map["/another.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename3].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename3].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename3].1,
+            processed_files[filename3].link,
             new_files_map["/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename4 = "../testdata/noextension";
-        assert_eq!(processed_files[filename4].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename4].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename4].1,
+            processed_files[filename4].link,
             new_files_map["/noextension"][FAKE_RDF_PREDICATE_LINK]
         );
 
         // and finally check the synced file is there
         let filename5 = "../testdata/subfolder/subexists.md";
-        assert_eq!(new_processed_files[filename5].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[filename5].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[filename5].1,
+            new_processed_files[filename5].link,
             new_files_map["/path/when/sync/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -2458,7 +4317,13 @@ mod tests {
     async fn test_files_container_sync_target_path_with_trailing_slash() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         assert_eq!(processed_files.len(), TESTDATA_PUT_FILEITEM_COUNT);
@@ -2488,38 +4353,38 @@ mod tests {
         );
 
         let filename1 = "../testdata/test.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             new_files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/another.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             new_files_map["/another.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename3].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename3].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename3].1,
+            processed_files[filename3].link,
             new_files_map["/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename4 = "../testdata/noextension";
-        assert_eq!(processed_files[filename4].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename4].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename4].1,
+            processed_files[filename4].link,
             new_files_map["/noextension"][FAKE_RDF_PREDICATE_LINK]
         );
 
         // and finally check the synced file is there
         let filename5 = "../testdata/subfolder/subexists.md";
-        assert_eq!(new_processed_files[filename5].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[filename5].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[filename5].1,
+            new_processed_files[filename5].link,
             new_files_map["/path/when/sync/subfolder/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -2529,7 +4394,13 @@ mod tests {
     async fn test_files_container_get() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let (version, fetched_files_map) = safe.files_container_get(&xorurl).await?;
@@ -2551,7 +4422,13 @@ mod tests {
     async fn test_files_container_version() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _, _) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let (version, _) = safe.files_container_get(&xorurl).await?;
@@ -2583,7 +4460,13 @@ mod tests {
     async fn test_files_container_get_with_version() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         // let's create a new version of the files container
@@ -2659,7 +4542,13 @@ mod tests {
     async fn test_files_container_create_get_empty_folder() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let (_version, files_map_get) = safe.files_container_get(&xorurl.to_string()).await?;
@@ -2678,7 +4567,13 @@ mod tests {
     async fn test_files_container_sync_with_nrs_url() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, _, _) = safe
-            .files_container_create(Some("../testdata/test.md"), None, false, true, false)
+            .files_container_create(
+                Some("../testdata/test.md"),
+                None,
+                false,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
 
         let nrsurl = random_nrs_name();
@@ -2686,7 +4581,14 @@ mod tests {
         let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
         xorurl_encoder.set_content_version(Some(0));
         let _ = safe
-            .nrs_map_container_create(&nrsurl, &xorurl_encoder.to_string(), false, true, false)
+            .nrs_map_container_create(
+                &nrsurl,
+                &xorurl_encoder.to_string(),
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
 
         let _ = safe
@@ -2733,7 +4635,13 @@ mod tests {
     async fn test_files_container_add() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/subfolder/"), None, false, true, false)
+            .files_container_create(
+                Some("../testdata/subfolder/"),
+                None,
+                false,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
         assert_eq!(processed_files.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
         assert_eq!(files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
@@ -2754,23 +4662,23 @@ mod tests {
         assert_eq!(new_files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT + 1);
 
         let filename1 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             new_files_map["/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/subfolder/sub2.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             new_files_map["/sub2.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename3 = "../testdata/test.md";
-        assert_eq!(new_processed_files[filename3].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[filename3].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[filename3].1,
+            new_processed_files[filename3].link,
             new_files_map["/new_filename_test.md"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -2780,7 +4688,13 @@ mod tests {
     async fn test_files_container_add_dry_run() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/subfolder/"), None, false, true, false)
+            .files_container_create(
+                Some("../testdata/subfolder/"),
+                None,
+                false,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
         assert_eq!(processed_files.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
         assert_eq!(files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
@@ -2817,14 +4731,14 @@ mod tests {
         assert_eq!(new_files_map.len(), new_files_map2.len());
 
         let filename = "../testdata/test.md";
-        assert_eq!(new_processed_files[filename].0, CONTENT_ADDED_SIGN);
-        assert_eq!(new_processed_files2[filename].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[filename].change, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files2[filename].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[filename].1,
+            new_processed_files[filename].link,
             new_files_map["/new_filename_test.md"][FAKE_RDF_PREDICATE_LINK]
         );
         assert_eq!(
-            new_processed_files2[filename].1,
+            new_processed_files2[filename].link,
             new_files_map2["/new_filename_test.md"][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -2834,7 +4748,13 @@ mod tests {
     async fn test_files_container_add_dir() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/subfolder/"), None, false, true, false)
+            .files_container_create(
+                Some("../testdata/subfolder/"),
+                None,
+                false,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
         assert_eq!(processed_files.len(), SUBFOLDER_PUT_FILEITEM_COUNT); // root "/" + 2 files
         assert_eq!(files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
@@ -2864,7 +4784,13 @@ mod tests {
     async fn test_files_container_add_existing_name() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/subfolder/"), None, false, true, false)
+            .files_container_create(
+                Some("../testdata/subfolder/"),
+                None,
+                false,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
         assert_eq!(processed_files.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
         assert_eq!(files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
@@ -2885,7 +4811,7 @@ mod tests {
         assert_eq!(new_processed_files.len(), 1);
         assert_eq!(new_files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
         assert_eq!(
-            new_processed_files["../testdata/subfolder/sub2.md"].1,
+            new_processed_files["../testdata/subfolder/sub2.md"].link,
             "File named \"/sub2.md\" with same content already exists on target. Use the \'force\' flag to replace it"
         );
         assert_eq!(files_map, new_files_map);
@@ -2906,7 +4832,7 @@ mod tests {
         assert_eq!(new_processed_files.len(), 1);
         assert_eq!(new_files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
         assert_eq!(
-            new_processed_files["../testdata/test.md"].1,
+            new_processed_files["../testdata/test.md"].link,
             "File named \"/sub2.md\" with different content already exists on target. Use the \'force\' flag to replace it"
         );
         assert_eq!(files_map, new_files_map);
@@ -2927,11 +4853,11 @@ mod tests {
         assert_eq!(new_processed_files.len(), 1);
         assert_eq!(new_files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
         assert_eq!(
-            new_processed_files["../testdata/test.md"].0,
+            new_processed_files["../testdata/test.md"].change,
             CONTENT_UPDATED_SIGN
         );
         assert_eq!(
-            new_processed_files["../testdata/test.md"].1,
+            new_processed_files["../testdata/test.md"].link,
             new_files_map["/sub2.md"]["link"]
         );
         Ok(())
@@ -2941,7 +4867,13 @@ mod tests {
     async fn test_files_container_fail_add_or_sync_invalid_path() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/test.md"), None, false, true, false)
+            .files_container_create(
+                Some("../testdata/test.md"),
+                None,
+                false,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
         assert_eq!(processed_files.len(), 1);
         assert_eq!(files_map.len(), 1);
@@ -3005,7 +4937,13 @@ mod tests {
     async fn test_files_container_add_a_url() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/subfolder/"), None, false, true, false)
+            .files_container_create(
+                Some("../testdata/subfolder/"),
+                None,
+                false,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
         assert_eq!(processed_files.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
         assert_eq!(files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
@@ -3029,22 +4967,22 @@ mod tests {
         assert_eq!(new_files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT + 1);
 
         let filename1 = "../testdata/subfolder/subexists.md";
-        assert_eq!(processed_files[filename1].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename1].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename1].1,
+            processed_files[filename1].link,
             new_files_map["/subexists.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "../testdata/subfolder/sub2.md";
-        assert_eq!(processed_files[filename2].0, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files[filename2].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            processed_files[filename2].1,
+            processed_files[filename2].link,
             new_files_map["/sub2.md"][FAKE_RDF_PREDICATE_LINK]
         );
 
-        assert_eq!(new_processed_files[new_filename].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[new_filename].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[new_filename].1,
+            new_processed_files[new_filename].link,
             new_files_map[new_filename][FAKE_RDF_PREDICATE_LINK]
         );
         assert_eq!(
@@ -3069,9 +5007,12 @@ mod tests {
         assert_eq!(version, 2);
         assert_eq!(new_processed_files.len(), 1);
         assert_eq!(new_files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT + 1);
-        assert_eq!(new_processed_files[new_filename].0, CONTENT_UPDATED_SIGN);
         assert_eq!(
-            new_processed_files[new_filename].1,
+            new_processed_files[new_filename].change,
+            CONTENT_UPDATED_SIGN
+        );
+        assert_eq!(
+            new_processed_files[new_filename].link,
             new_files_map[new_filename][FAKE_RDF_PREDICATE_LINK]
         );
         assert_eq!(
@@ -3085,7 +5026,13 @@ mod tests {
     async fn test_files_container_add_from_raw() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/subfolder/"), None, false, true, false)
+            .files_container_create(
+                Some("../testdata/subfolder/"),
+                None,
+                false,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
         assert_eq!(processed_files.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
         assert_eq!(files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT);
@@ -3107,9 +5054,9 @@ mod tests {
         assert_eq!(new_processed_files.len(), 1);
         assert_eq!(new_files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT + 1);
 
-        assert_eq!(new_processed_files[new_filename].0, CONTENT_ADDED_SIGN);
+        assert_eq!(new_processed_files[new_filename].change, CONTENT_ADDED_SIGN);
         assert_eq!(
-            new_processed_files[new_filename].1,
+            new_processed_files[new_filename].link,
             new_files_map[new_filename][FAKE_RDF_PREDICATE_LINK]
         );
 
@@ -3128,9 +5075,12 @@ mod tests {
         assert_eq!(version, 2);
         assert_eq!(new_processed_files.len(), 1);
         assert_eq!(new_files_map.len(), SUBFOLDER_PUT_FILEITEM_COUNT + 1);
-        assert_eq!(new_processed_files[new_filename].0, CONTENT_UPDATED_SIGN);
         assert_eq!(
-            new_processed_files[new_filename].1,
+            new_processed_files[new_filename].change,
+            CONTENT_UPDATED_SIGN
+        );
+        assert_eq!(
+            new_processed_files[new_filename].link,
             new_files_map[new_filename][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
@@ -3140,7 +5090,13 @@ mod tests {
     async fn test_files_container_remove_path() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let (xorurl, processed_files, files_map) = safe
-            .files_container_create(Some("../testdata/"), None, true, true, false)
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
             .await?;
         assert_eq!(processed_files.len(), TESTDATA_PUT_FILEITEM_COUNT);
         assert_eq!(files_map.len(), TESTDATA_PUT_FILEITEM_COUNT);
@@ -3155,9 +5111,9 @@ mod tests {
         assert_eq!(new_files_map.len(), TESTDATA_PUT_FILEITEM_COUNT - 1);
 
         let filepath = "/test.md";
-        assert_eq!(new_processed_files[filepath].0, CONTENT_DELETED_SIGN);
+        assert_eq!(new_processed_files[filepath].change, CONTENT_DELETED_SIGN);
         assert_eq!(
-            new_processed_files[filepath].1,
+            new_processed_files[filepath].link,
             files_map[filepath][FAKE_RDF_PREDICATE_LINK]
         );
 
@@ -3174,18 +5130,368 @@ mod tests {
         );
 
         let filename1 = "/subfolder/subexists.md";
-        assert_eq!(new_processed_files[filename1].0, CONTENT_DELETED_SIGN);
+        assert_eq!(new_processed_files[filename1].change, CONTENT_DELETED_SIGN);
         assert_eq!(
-            new_processed_files[filename1].1,
+            new_processed_files[filename1].link,
             files_map[filename1][FAKE_RDF_PREDICATE_LINK]
         );
 
         let filename2 = "/subfolder/sub2.md";
-        assert_eq!(new_processed_files[filename2].0, CONTENT_DELETED_SIGN);
+        assert_eq!(new_processed_files[filename2].change, CONTENT_DELETED_SIGN);
         assert_eq!(
-            new_processed_files[filename2].1,
+            new_processed_files[filename2].link,
             files_map[filename2][FAKE_RDF_PREDICATE_LINK]
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_files_container_restore() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let (xorurl, _, files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+
+        let _ = safe
+            .files_container_remove_path(&format!("{}/test.md", xorurl), false, false, false)
+            .await?;
+        let (version, _, new_files_map) = safe
+            .files_container_add_from_raw(
+                b"0123456789",
+                &format!("{}/new.md", xorurl),
+                false,
+                false,
+                false,
+            )
+            .await?;
+        assert_eq!(version, 2);
+
+        // a dry run only reports the changes it would make
+        let (version, processed_files, restored_files_map) = safe
+            .files_container_restore(&xorurl, 0, false, true)
+            .await?;
+        assert_eq!(version, 3);
+        assert_eq!(processed_files.len(), 2);
+        assert_eq!(processed_files["/test.md"].change, CONTENT_ADDED_SIGN);
+        assert_eq!(
+            processed_files["/test.md"].link,
+            files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
+        );
+        assert_eq!(processed_files["/new.md"].change, CONTENT_DELETED_SIGN);
+        assert_eq!(
+            processed_files["/new.md"].link,
+            new_files_map["/new.md"][FAKE_RDF_PREDICATE_LINK]
+        );
+        assert_eq!(restored_files_map, files_map);
+        let (version, current_files_map) = safe.files_container_get(&xorurl).await?;
+        assert_eq!(version, 2);
+        assert_eq!(current_files_map, new_files_map);
+
+        let (version, _, _) = safe
+            .files_container_restore(&xorurl, 0, false, false)
+            .await?;
+        assert_eq!(version, 3);
+        let (version, current_files_map) = safe.files_container_get(&xorurl).await?;
+        assert_eq!(version, 3);
+        assert_eq!(current_files_map, files_map);
+
+        // restoring the current version doesn't create a new one
+        let (version, processed_files, _) = safe
+            .files_container_restore(&xorurl, 3, false, false)
+            .await?;
+        assert_eq!(version, 3);
+        assert!(processed_files.is_empty());
+
+        match safe.files_container_restore(&xorurl, 4, false, false).await {
+            Err(Error::VersionNotFound(_)) => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Unexpected result when restoring an invalid version: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_files_container_restore_changes() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let (xorurl, _, files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+
+        let _ = safe
+            .files_container_add_from_raw(
+                b"0123456789",
+                &format!("{}/new.md", xorurl),
+                false,
+                false,
+                false,
+            )
+            .await?;
+        let _ = safe
+            .files_container_remove_path(&format!("{}/test.md", xorurl), false, false, false)
+            .await?;
+        let (version, _, _) = safe
+            .files_container_add_from_raw(
+                b"9876543210",
+                &format!("{}/another.md", xorurl),
+                true,
+                false,
+                false,
+            )
+            .await?;
+        assert_eq!(version, 3);
+
+        // the changes are the ones made to the current version to restore the first one
+        let (version, processed_files, restored_files_map) = safe
+            .files_container_restore(&xorurl, 0, false, false)
+            .await?;
+        assert_eq!(version, 4);
+        assert_eq!(processed_files.len(), 3);
+        assert_eq!(processed_files["/test.md"].change, CONTENT_ADDED_SIGN);
+        assert_eq!(processed_files["/new.md"].change, CONTENT_DELETED_SIGN);
+        assert_eq!(processed_files["/another.md"].change, CONTENT_UPDATED_SIGN);
+        assert_eq!(
+            processed_files["/another.md"].link,
+            files_map["/another.md"][FAKE_RDF_PREDICATE_LINK]
+        );
+        assert!(processed_files
+            .values()
+            .all(|processed_file| processed_file.deduplicated_size.is_none()));
+        assert_eq!(restored_files_map, files_map);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_container_diff() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let (xorurl, _, files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                true,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+
+        let _ = safe
+            .files_container_add_from_raw(
+                b"0123456789",
+                &format!("{}/new.md", xorurl),
+                false,
+                false,
+                false,
+            )
+            .await?;
+        let _ = safe
+            .files_container_remove_path(&format!("{}/test.md", xorurl), false, false, false)
+            .await?;
+        let (version, _, new_files_map) = safe
+            .files_container_add_from_raw(
+                b"9876543210",
+                &format!("{}/another.md", xorurl),
+                true,
+                false,
+                false,
+            )
+            .await?;
+        assert_eq!(version, 3);
+
+        let (version_a, version_b, diff) = safe
+            .files_container_diff(&format!("{}?v=0", xorurl), &xorurl)
+            .await?;
+        assert_eq!(version_a, 0);
+        assert_eq!(version_b, 3);
+        assert_eq!(diff.len(), 3);
+
+        assert_eq!(diff["/new.md"].change, CONTENT_ADDED_SIGN);
+        assert_eq!(
+            diff["/new.md"].link,
+            new_files_map["/new.md"][FAKE_RDF_PREDICATE_LINK]
+        );
+        assert_eq!(diff["/test.md"].change, CONTENT_DELETED_SIGN);
+        assert_eq!(
+            diff["/test.md"].link,
+            files_map["/test.md"][FAKE_RDF_PREDICATE_LINK]
+        );
+        assert_eq!(diff["/another.md"].change, CONTENT_UPDATED_SIGN);
+        assert_eq!(
+            diff["/another.md"].link,
+            new_files_map["/another.md"][FAKE_RDF_PREDICATE_LINK]
+        );
+        // the size of content added from raw bytes is unknown
+        assert_eq!(
+            diff["/another.md"].metadata_changes[FAKE_RDF_PREDICATE_SIZE],
+            (
+                files_map["/another.md"]
+                    .get(FAKE_RDF_PREDICATE_SIZE)
+                    .cloned(),
+                Some("".to_string())
+            )
+        );
+
+        // nothing changed when comparing a version with itself
+        let (_, _, diff) = safe
+            .files_container_diff(&format!("{}?v=1", xorurl), &format!("{}?v=1", xorurl))
+            .await?;
+        assert!(diff.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_files_map_diff_renamed() {
+        let mut file_item = FileItem::new();
+        file_item.insert(
+            FAKE_RDF_PREDICATE_LINK.to_string(),
+            "safe://linktocontent".to_string(),
+        );
+        file_item.insert(FAKE_RDF_PREDICATE_SIZE.to_string(), "10".to_string());
+
+        let mut files_map_a = FilesMap::new();
+        files_map_a.insert("/old.md".to_string(), file_item.clone());
+        let mut files_map_b = FilesMap::new();
+        files_map_b.insert("/new.md".to_string(), file_item);
+
+        let diff = files_map_diff(&files_map_a, &files_map_b);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff["/new.md"].change, CONTENT_RENAMED_SIGN);
+        assert_eq!(diff["/new.md"].renamed_from, Some("/old.md".to_string()));
+        assert_eq!(diff["/new.md"].link, "safe://linktocontent");
+        assert!(diff["/new.md"].metadata_changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_files_store_public_blob_from_reader() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        // let's have enough data for it to be split into several chunks
+        let data: Vec<u8> = (0..(CHUNK_MAX_SIZE * 2 + 100))
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        let xorurl = safe
+            .files_store_public_blob_from_reader(&mut &data[..], Some("text/plain"), false)
+            .await?;
+        let xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
+        assert_eq!(xorurl_encoder.content_type(), SafeContentType::BlobManifest);
+        assert_eq!(
+            safe.fetch_blob_manifest_media_type(&xorurl_encoder).await?,
+            Some("text/plain".to_string())
+        );
+
+        let mut received_data = vec![];
+        let bytes_written = safe
+            .files_get_public_blob_to_writer(&xorurl, None, &mut received_data)
+            .await?;
+        assert_eq!(bytes_written, data.len() as u64);
+        assert_eq!(received_data, data);
+
+        // a range spanning over the boundary of the first two chunks
+        let start = CHUNK_MAX_SIZE - 10;
+        let end = CHUNK_MAX_SIZE + 10;
+        let range = Some((Some(start as u64), Some(end as u64)));
+        let mut received_data = vec![];
+        let _ = safe
+            .files_get_public_blob_to_writer(&xorurl, range, &mut received_data)
+            .await?;
+        assert_eq!(received_data, data[start..end].to_vec());
+
+        let received_data = safe.files_get_public_blob(&xorurl, range).await?;
+        assert_eq!(received_data, data[start..end].to_vec());
+
+        let received_data = safe.files_get_public_blob(&xorurl, None).await?;
+        assert_eq!(received_data, data);
+
+        // the size is found in the manifest
+        assert_eq!(safe.files_get_blob_size(&xorurl).await?, data.len() as u64);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_store_public_blob_from_reader_single_chunk() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let data = b"Something super good";
+        let xorurl = safe
+            .files_store_public_blob_from_reader(&mut &data[..], None, false)
+            .await?;
+
+        // it shall be stored just like any other Blob
+        let blob_xorurl = safe.files_store_public_blob(data, None, true).await?;
+        assert_eq!(xorurl, blob_xorurl);
+
+        let received_data = safe.files_get_public_blob(&xorurl, None).await?;
+        assert_eq!(received_data, data);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_get_public_blob_manifest_content_type() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let manifest = BlobManifest::new(vec![], None).serialise()?;
+
+        // content which happens to be a manifest is not treated as such unless the URL says so
+        let xorurl = safe.files_store_public_blob(&manifest, None, false).await?;
+        let received_data = safe.files_get_public_blob(&xorurl, None).await?;
+        assert_eq!(received_data, manifest);
+
+        // and content which isn't a manifest fails to be fetched as one
+        let xorurl = safe
+            .files_store_public_blob(b"not a manifest", None, false)
+            .await?;
+        let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
+        xorurl_encoder.set_content_type(SafeContentType::BlobManifest)?;
+        match safe
+            .files_get_public_blob(&xorurl_encoder.to_string(), None)
+            .await
+        {
+            Err(Error::ContentError(msg)) => {
+                assert!(msg.contains("Couldn't deserialise the Blob manifest"));
+                Ok(())
+            }
+            other => Err(Error::Unexpected(format!(
+                "Unexpected result when fetching a broken manifest: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_files_store_blob_chunks_deduplication() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let data: Vec<u8> = (0..(CHUNK_MAX_SIZE * 3))
+            .map(|i| ((i * 7 + i / 1013) % 256) as u8)
+            .collect();
+
+        let (xorurl, deduplicated) = safe
+            .store_blob_chunks(&mut &data[..], None, &BTreeSet::new(), false, false)
+            .await?;
+        assert_eq!(deduplicated, 0);
+        let known_chunks = safe.fetch_blob_chunks(&xorurl).await;
+        assert!(known_chunks.len() > 1);
+
+        // let's append some bytes, all the chunks but the last one shall be reused
+        let mut modified_data = data.clone();
+        modified_data.extend(b"some new bytes");
+        let (modified_xorurl, deduplicated) = safe
+            .store_blob_chunks(&mut &modified_data[..], None, &known_chunks, false, false)
+            .await?;
+        assert_ne!(modified_xorurl, xorurl);
+        assert!(deduplicated > 0);
+        assert!(deduplicated < modified_data.len() as u64);
+
+        let received_data = safe.files_get_public_blob(&modified_xorurl, None).await?;
+        assert_eq!(received_data, modified_data);
+        Ok(())
+    }
 }
@@ -0,0 +1,424 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use super::{
+    consts::*,
+    fetch::SafeData,
+    files::{FileItem, FileMeta, FilesMap},
+    Safe,
+};
+use crate::{Error, Result};
+use chrono::DateTime;
+use std::{
+    cmp::min,
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::SystemTime,
+};
+
+/// Inode number of the root directory of a FilesContainerView
+pub const ROOT_INODE: u64 = 1;
+
+// Permissions used when the FileItem doesn't have the mode bits, e.g. if
+// uploaded from a platform which doesn't support them
+const DEFAULT_FILE_PERM: u16 = 0o444;
+const DEFAULT_DIR_PERM: u16 = 0o555;
+
+// All write permissions, which are removed since the view is read-only
+const WRITE_PERM_BITS: u16 = 0o222;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FsNodeKind {
+    Directory,
+    File,
+    Symlink,
+}
+
+/// A file, directory or symlink of a FilesContainerView
+#[derive(Debug, Clone)]
+pub struct FsNode {
+    pub ino: u64,
+    pub parent: u64,
+    pub name: String,
+    pub kind: FsNodeKind,
+    /// Size of the content, unknown for files added from a link until it's fetched,
+    /// see `FilesContainerView::file_size`
+    pub size: Option<u64>,
+    pub perm: u16,
+    pub created: Option<SystemTime>,
+    pub modified: Option<SystemTime>,
+    /// Link to the content of a file
+    pub link: Option<String>,
+    /// Target path of a symlink
+    pub symlink_target: Option<String>,
+    children: BTreeMap<String, u64>,
+}
+
+/// A read-only, POSIX-like view of the files of a version of a FilesContainer, where each
+/// file, directory and symlink is identified by an inode number, e.g. to expose it as
+/// a filesystem. Directories are derived from the files' paths even when the FilesMap
+/// doesn't have them, and the content of files is fetched only when it's read.
+#[derive(Debug, Clone)]
+pub struct FilesContainerView {
+    nodes: Vec<FsNode>,
+    // Sizes fetched for the files which size is not in their FileItem, by inode number
+    fetched_sizes: Arc<Mutex<BTreeMap<u64, u64>>>,
+}
+
+impl FilesContainerView {
+    pub fn from_files_map(files_map: &FilesMap) -> Self {
+        let root = FsNode {
+            ino: ROOT_INODE,
+            parent: ROOT_INODE,
+            name: String::default(),
+            kind: FsNodeKind::Directory,
+            size: Some(0),
+            perm: DEFAULT_DIR_PERM,
+            created: None,
+            modified: None,
+            link: None,
+            symlink_target: None,
+            children: BTreeMap::new(),
+        };
+        let mut view = Self {
+            nodes: vec![root],
+            fetched_sizes: Arc::new(Mutex::new(BTreeMap::new())),
+        };
+
+        for (path, file_item) in files_map.iter() {
+            let mut parent = ROOT_INODE;
+            let mut components = path.split('/').filter(|c| !c.is_empty()).peekable();
+            while let Some(name) = components.next() {
+                let is_last = components.peek().is_none();
+                let existing = view.nodes[(parent - 1) as usize]
+                    .children
+                    .get(name)
+                    .copied();
+                parent = match existing {
+                    Some(ino) => {
+                        // An implicit directory created before its FileItem was found
+                        if is_last {
+                            let node = &mut view.nodes[(ino - 1) as usize];
+                            set_node_attrs(node, file_item);
+                        }
+                        ino
+                    }
+                    None => {
+                        let ino = view.nodes.len() as u64 + 1;
+                        let mut node = FsNode {
+                            ino,
+                            parent,
+                            name: name.to_string(),
+                            kind: FsNodeKind::Directory,
+                            size: Some(0),
+                            perm: DEFAULT_DIR_PERM,
+                            created: None,
+                            modified: None,
+                            link: None,
+                            symlink_target: None,
+                            children: BTreeMap::new(),
+                        };
+                        if is_last {
+                            set_node_attrs(&mut node, file_item);
+                        }
+                        view.nodes[(parent - 1) as usize]
+                            .children
+                            .insert(name.to_string(), ino);
+                        view.nodes.push(node);
+                        ino
+                    }
+                };
+            }
+        }
+
+        view
+    }
+
+    pub fn getattr(&self, ino: u64) -> Option<&FsNode> {
+        if ino == 0 {
+            None
+        } else {
+            self.nodes.get((ino - 1) as usize)
+        }
+    }
+
+    pub fn lookup(&self, parent: u64, name: &str) -> Option<&FsNode> {
+        let ino = self.getattr(parent)?.children.get(name)?;
+        self.getattr(*ino)
+    }
+
+    /// List the entries of a directory, in the order of their names
+    pub fn readdir(&self, ino: u64) -> Option<Vec<&FsNode>> {
+        let node = self.getattr(ino)?;
+        if node.kind != FsNodeKind::Directory {
+            return None;
+        }
+
+        Some(
+            node.children
+                .values()
+                .filter_map(|child| self.getattr(*child))
+                .collect(),
+        )
+    }
+
+    pub fn readlink(&self, ino: u64) -> Option<&str> {
+        self.getattr(ino)?.symlink_target.as_deref()
+    }
+
+    /// Size of a node's content if it's already known, i.e. unless it's
+    /// a file whose size is only known once its content is fetched
+    pub fn known_size(&self, ino: u64) -> Option<u64> {
+        let node = self.getattr(ino)?;
+        node.size
+            .or_else(|| self.lock_fetched_sizes().get(&node.ino).copied())
+    }
+
+    /// Size of a node's content, fetching it the first time it's needed if the
+    /// FileItem doesn't have it, e.g. for files added from a link
+    pub async fn file_size(&self, safe: &mut Safe, ino: u64) -> Result<u64> {
+        if let Some(size) = self.known_size(ino) {
+            return Ok(size);
+        }
+
+        let link = self.file_link(ino)?;
+        let size = safe.files_get_blob_size(link).await?;
+        let _ = self.lock_fetched_sizes().insert(ino, size);
+        Ok(size)
+    }
+
+    /// Read up to `size` bytes of a file's content starting at `offset`,
+    /// fetching only that range of the content
+    pub async fn read(&self, safe: &mut Safe, ino: u64, offset: u64, size: u64) -> Result<Vec<u8>> {
+        let link = self.file_link(ino)?;
+        let file_size = self.file_size(safe, ino).await?;
+        if offset >= file_size {
+            return Ok(vec![]);
+        }
+        let end = min(offset + size, file_size);
+
+        match safe.fetch(link, Some((Some(offset), Some(end)))).await? {
+            SafeData::PublicBlob { data, .. } | SafeData::PrivateBlob { data, .. } => Ok(data),
+            other => Err(Error::ContentError(format!(
+                "Content of file with inode {} is not a Blob: {:?}",
+                ino, other
+            ))),
+        }
+    }
+
+    // Link to the content of a file
+    fn file_link(&self, ino: u64) -> Result<&str> {
+        let node = self
+            .getattr(ino)
+            .ok_or_else(|| Error::ContentNotFound(format!("No file found with inode {}", ino)))?;
+        match (&node.kind, &node.link) {
+            (FsNodeKind::File, Some(link)) => Ok(link),
+            _ => Err(Error::InvalidInput(format!(
+                "'{}' is not a file with content",
+                node.name
+            ))),
+        }
+    }
+
+    // A panic while holding the lock cannot leave the sizes inconsistent,
+    // thus they can still be used if the lock was poisoned
+    fn lock_fetched_sizes(&self) -> MutexGuard<BTreeMap<u64, u64>> {
+        self.fetched_sizes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// Set the attributes of a node from its FileItem
+fn set_node_attrs(node: &mut FsNode, file_item: &FileItem) {
+    let file_type = file_item
+        .get(FAKE_RDF_PREDICATE_TYPE)
+        .map_or("", |file_type| file_type.as_str());
+    let (kind, default_perm) = if FileMeta::filetype_is_dir(file_type) {
+        (FsNodeKind::Directory, DEFAULT_DIR_PERM)
+    } else if FileMeta::filetype_is_symlink(file_type) {
+        (FsNodeKind::Symlink, DEFAULT_FILE_PERM)
+    } else {
+        (FsNodeKind::File, DEFAULT_FILE_PERM)
+    };
+
+    node.kind = kind;
+    node.size = match kind {
+        // Files added from a link have no size in their FileItem
+        FsNodeKind::File => file_item
+            .get(FAKE_RDF_PREDICATE_SIZE)
+            .and_then(|size| size.parse().ok()),
+        _ => Some(0),
+    };
+    node.perm = file_item
+        .get(FAKE_RDF_PREDICATE_MODE_BITS)
+        .and_then(|mode| mode.parse::<u32>().ok())
+        .map_or(default_perm, |mode| (mode & 0o7777) as u16)
+        & !WRITE_PERM_BITS;
+    node.created = file_item_time(
+        file_item,
+        FAKE_RDF_PREDICATE_ORIGINAL_CREATED,
+        FAKE_RDF_PREDICATE_CREATED,
+    );
+    node.modified = file_item_time(
+        file_item,
+        FAKE_RDF_PREDICATE_ORIGINAL_MODIFIED,
+        FAKE_RDF_PREDICATE_MODIFIED,
+    );
+    node.link = file_item.get(FAKE_RDF_PREDICATE_LINK).cloned();
+    node.symlink_target = file_item.get("symlink_target").cloned();
+}
+
+// Time from the original file if available, otherwise from when it was uploaded
+fn file_item_time(file_item: &FileItem, original_key: &str, key: &str) -> Option<SystemTime> {
+    file_item
+        .get(original_key)
+        .or_else(|| file_item.get(key))
+        .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+        .map(SystemTime::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::app::files::FilesContainerCreateOptions;
+    use crate::api::app::test_helpers::new_safe_instance;
+
+    #[tokio::test]
+    async fn test_files_container_view() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let (_, _, files_map) = safe
+            .files_container_create(
+                Some("../testdata/"),
+                None,
+                true,
+                false,
+                FilesContainerCreateOptions::default(),
+            )
+            .await?;
+
+        let view = FilesContainerView::from_files_map(&files_map);
+        let root_entries: Vec<&str> = view
+            .readdir(ROOT_INODE)
+            .unwrap_or_default()
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect();
+        assert_eq!(
+            root_entries,
+            vec![
+                ".hidden.txt",
+                ".subhidden",
+                "another.md",
+                "emptyfolder",
+                "noextension",
+                "subfolder",
+                "test.md"
+            ]
+        );
+
+        let subfolder = view
+            .lookup(ROOT_INODE, "subfolder")
+            .ok_or_else(|| Error::Unexpected("subfolder not found".to_string()))?;
+        assert_eq!(subfolder.kind, FsNodeKind::Directory);
+        assert_eq!(subfolder.perm & WRITE_PERM_BITS, 0);
+        let file = view
+            .lookup(subfolder.ino, "subexists.md")
+            .ok_or_else(|| Error::Unexpected("subexists.md not found".to_string()))?;
+        assert_eq!(file.kind, FsNodeKind::File);
+        assert_eq!(file.parent, subfolder.ino);
+        assert_eq!(file.perm & WRITE_PERM_BITS, 0);
+        assert!(view.readdir(file.ino).is_none());
+        assert!(view.lookup(subfolder.ino, "missing.md").is_none());
+
+        let file = view
+            .lookup(ROOT_INODE, "test.md")
+            .ok_or_else(|| Error::Unexpected("test.md not found".to_string()))?;
+        assert_eq!(file.size, Some(12));
+        assert!(file.modified.is_some());
+        assert_eq!(
+            view.read(&mut safe, file.ino, 0, 4096).await?,
+            b"hello tests!"
+        );
+        assert_eq!(view.read(&mut safe, file.ino, 6, 5).await?, b"tests");
+        assert!(view.read(&mut safe, file.ino, 12, 10).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_files_container_view_unknown_size() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let data = b"Something super good";
+        let xorurl = safe.files_store_public_blob(data, None, false).await?;
+
+        // a file added from a link has no size in its FileItem
+        let mut file_item = FileItem::new();
+        file_item.insert(
+            FAKE_RDF_PREDICATE_TYPE.to_string(),
+            "text/plain".to_string(),
+        );
+        file_item.insert(FAKE_RDF_PREDICATE_LINK.to_string(), xorurl);
+        let mut files_map = FilesMap::new();
+        files_map.insert("/file.txt".to_string(), file_item);
+
+        let view = FilesContainerView::from_files_map(&files_map);
+        let ino = view
+            .lookup(ROOT_INODE, "file.txt")
+            .map(|node| node.ino)
+            .ok_or_else(|| Error::Unexpected("file.txt not found".to_string()))?;
+        assert_eq!(view.known_size(ino), None);
+
+        // its real size is fetched when it's first needed
+        assert_eq!(view.read(&mut safe, ino, 10, 4096).await?, b"super good");
+        assert_eq!(view.known_size(ino), Some(data.len() as u64));
+        assert_eq!(view.file_size(&mut safe, ino).await?, data.len() as u64);
+        assert!(view.read(&mut safe, ino, 20, 10).await?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_files_container_view_implicit_dirs() {
+        let mut file_item = FileItem::new();
+        file_item.insert(
+            FAKE_RDF_PREDICATE_TYPE.to_string(),
+            "text/plain".to_string(),
+        );
+        file_item.insert(FAKE_RDF_PREDICATE_SIZE.to_string(), "5".to_string());
+        file_item.insert(
+            FAKE_RDF_PREDICATE_MODE_BITS.to_string(),
+            "33188".to_string(), // regular file with 0o644 permissions
+        );
+        let mut symlink_item = FileItem::new();
+        symlink_item.insert(
+            FAKE_RDF_PREDICATE_TYPE.to_string(),
+            MIMETYPE_FILESYSTEM_SYMLINK.to_string(),
+        );
+        symlink_item.insert("symlink_target".to_string(), "../file.txt".to_string());
+
+        let mut files_map = FilesMap::new();
+        files_map.insert("/a/b/file.txt".to_string(), file_item);
+        files_map.insert("/a/link".to_string(), symlink_item);
+
+        let view = FilesContainerView::from_files_map(&files_map);
+        let dir_a = view.lookup(ROOT_INODE, "a").cloned();
+        assert!(dir_a.is_some());
+        if let Some(dir_a) = dir_a {
+            assert_eq!(dir_a.kind, FsNodeKind::Directory);
+            assert_eq!(dir_a.perm, DEFAULT_DIR_PERM);
+
+            let dir_b = view.lookup(dir_a.ino, "b").map(|node| node.ino);
+            let file = dir_b.and_then(|ino| view.lookup(ino, "file.txt"));
+            assert_eq!(file.and_then(|node| node.size), Some(5));
+            assert_eq!(file.map(|node| node.perm), Some(0o444));
+
+            let link = view.lookup(dir_a.ino, "link").map(|node| node.ino);
+            assert_eq!(link.and_then(|ino| view.readlink(ino)), Some("../file.txt"));
+        }
+    }
+}
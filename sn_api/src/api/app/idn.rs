@@ -0,0 +1,243 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Error, Result};
+use log::debug;
+
+// Prefix of the labels of a public name which are punycode encoded
+const PUNYCODE_PREFIX: &str = "xn--";
+
+// Lowercase letters of the Greek and Cyrillic scripts which look the same as a Latin letter.
+// Only these scripts are checked, letters of other scripts which look like Latin ones,
+// e.g. the Armenian 'օ', are not detected as confusable.
+const LATIN_LOOKALIKES: &[char] = &[
+    'α', 'ι', 'κ', 'ν', 'ο', 'ρ', 'υ', 'ϲ', 'ϳ', 'а', 'е', 'і', 'ј', 'о', 'р', 'с', 'у', 'х', 'ѕ',
+    'һ', 'ӏ', 'ԁ', 'ԛ', 'ԝ',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+}
+
+// Encodes a public name, i.e. sub names and top name, the way it's used in URLs and hashed
+// into an XorName. Each label is encoded on its own and keeps its case, so a label is encoded
+// the same way whatever the other labels of the name are. Labels with ASCII characters only
+// are left as they are. Other labels are normalised as per UTS #46, i.e. NFC normalisation,
+// and punycode encoded, e.g. "café" is encoded as "xn--caf-dma". As the normalisation also
+// folds the case, such labels with uppercase letters are rejected rather than changed. Labels
+// already punycode encoded are lowercased instead, as their case doesn't change their decoding.
+// Labels mixing Latin, Greek and Cyrillic letters, or made of Greek or Cyrillic letters which
+// look like Latin ones, are rejected as well. Characters of other scripts are not checked.
+pub(crate) fn encode_public_name(name: &str) -> Result<String> {
+    if name.is_ascii() && !has_punycode_labels(name) {
+        return Ok(name.to_string());
+    }
+
+    let encoded = name
+        .split('.')
+        .map(encode_label)
+        .collect::<Result<Vec<String>>>()?
+        .join(".");
+
+    debug!("Public name \"{}\" encoded as \"{}\"", name, encoded);
+    Ok(encoded)
+}
+
+// Unicode form of an encoded public name for it to be displayed, e.g. "café" for "xn--caf-dma".
+// Names which cannot be decoded are displayed as they are.
+pub(crate) fn decode_public_name(name: &str) -> String {
+    if !has_punycode_labels(name) {
+        return name.to_string();
+    }
+
+    match idna::domain_to_unicode(name) {
+        (display, Ok(())) => display,
+        (_, Err(_)) => name.to_string(),
+    }
+}
+
+fn encode_label(label: &str) -> Result<String> {
+    if label.is_ascii() && !is_punycode_label(label) {
+        return Ok(label.to_string());
+    }
+
+    // Labels which are already punycode encoded are decoded the same way whatever their case
+    // is, thus they are lowercased rather than rejected, e.g. "XN--CAF-DMA" is "xn--caf-dma"
+    let label = if label.is_ascii() {
+        label.to_ascii_lowercase()
+    } else if label.to_lowercase() != label {
+        return Err(Error::InvalidXorUrl(format!(
+            "The name label \"{}\" has non-ASCII characters, thus it must be in lowercase",
+            label
+        )));
+    } else {
+        label.to_string()
+    };
+
+    let encoded = idna::domain_to_ascii(&label).map_err(|err| {
+        Error::InvalidXorUrl(format!(
+            "Invalid internationalised name label \"{}\": {:?}",
+            label, err
+        ))
+    })?;
+
+    let (display, result) = idna::domain_to_unicode(&encoded);
+    result.map_err(|err| {
+        Error::InvalidXorUrl(format!(
+            "Invalid internationalised name label \"{}\": {:?}",
+            label, err
+        ))
+    })?;
+    check_confusable_label(&display)?;
+
+    Ok(encoded)
+}
+
+fn has_punycode_labels(name: &str) -> bool {
+    name.split('.').any(is_punycode_label)
+}
+
+fn is_punycode_label(label: &str) -> bool {
+    label
+        .get(..PUNYCODE_PREFIX.len())
+        .map_or(false, |prefix| prefix.eq_ignore_ascii_case(PUNYCODE_PREFIX))
+}
+
+// A label is confusable if it mixes Latin, Greek and Cyrillic letters, e.g. "pаypal" with
+// a Cyrillic 'а', or if it's made of Greek or Cyrillic letters only which look like Latin ones
+fn check_confusable_label(label: &str) -> Result<()> {
+    let mut label_script = None;
+    let mut only_lookalikes = true;
+    for c in label.chars() {
+        let script = match script_of(c) {
+            Some(script) => script,
+            None => continue,
+        };
+        match label_script {
+            Some(label_script) if label_script != script => {
+                return Err(Error::InvalidXorUrl(format!(
+                    "The name label \"{}\" mixes characters of the {:?} and {:?} scripts which can be confused with each other",
+                    label, label_script, script
+                )))
+            }
+            _ => label_script = Some(script),
+        }
+        only_lookalikes = only_lookalikes && LATIN_LOOKALIKES.contains(&c);
+    }
+
+    match label_script {
+        Some(Script::Greek) | Some(Script::Cyrillic) if only_lookalikes => {
+            Err(Error::InvalidXorUrl(format!(
+                "The name label \"{}\" is made of characters which can be confused with Latin ones",
+                label
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Script of the letters which can be confused with letters of the other scripts. Only the
+// Latin, Greek and Cyrillic scripts are told apart, characters of any other script are
+// skipped, thus labels mixing them with these scripts are not detected as confusable.
+fn script_of(c: char) -> Option<Script> {
+    match c {
+        'a'..='z' | 'A'..='Z' | '\u{00c0}'..='\u{024f}' | '\u{1e00}'..='\u{1eff}' => {
+            Some(Script::Latin)
+        }
+        '\u{0370}'..='\u{03ff}' | '\u{1f00}'..='\u{1fff}' => Some(Script::Greek),
+        '\u{0400}'..='\u{052f}' => Some(Script::Cyrillic),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_public_name() -> Result<()> {
+        // ASCII names are left as they are, including their case
+        assert_eq!(encode_public_name("a.b.MySite")?, "a.b.MySite");
+        assert_eq!(encode_public_name("*.docs.mysite")?, "*.docs.mysite");
+
+        // names are normalised, e.g. "e" followed by a combining acute accent is "é"
+        assert_eq!(encode_public_name("café")?, "xn--caf-dma");
+        assert_eq!(encode_public_name("cafe\u{301}")?, "xn--caf-dma");
+        assert_eq!(encode_public_name("blog.café")?, "blog.xn--caf-dma");
+        assert_eq!(encode_public_name("xn--caf-dma")?, "xn--caf-dma");
+        assert_eq!(encode_public_name("ελληνικά")?, "xn--hxargifdar");
+
+        assert_eq!(decode_public_name("blog.xn--caf-dma"), "blog.café");
+        assert_eq!(decode_public_name("a.b.MySite"), "a.b.MySite");
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_public_name_mixed_labels() -> Result<()> {
+        // ASCII labels keep their case when mixed with non-ASCII ones,
+        // so the top name is the same as when it's on its own
+        assert_eq!(
+            encode_public_name("café.Blog.MySite")?,
+            "xn--caf-dma.Blog.MySite"
+        );
+        assert_eq!(encode_public_name("*.café.MySite")?, "*.xn--caf-dma.MySite");
+        assert_eq!(encode_public_name("Blog.xn--caf-dma")?, "Blog.xn--caf-dma");
+
+        // punycode encoded labels are lowercased, as their case doesn't change what they decode to
+        assert_eq!(encode_public_name("XN--CAF-DMA")?, "xn--caf-dma");
+        assert_eq!(encode_public_name("Blog.Xn--Caf-Dma")?, "Blog.xn--caf-dma");
+
+        // non-ASCII labels are not case folded but rejected
+        match encode_public_name("Café.MySite") {
+            Err(Error::InvalidXorUrl(msg)) => assert!(msg.contains("must be in lowercase")),
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Non-ASCII label with uppercase letters was not rejected: {:?}",
+                    other
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_public_name_confusables() -> Result<()> {
+        // Latin letters mixed with a Cyrillic 'а'
+        match encode_public_name("p\u{430}ypal") {
+            Err(Error::InvalidXorUrl(msg)) => assert!(msg.contains("Latin and Cyrillic")),
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Mixed scripts name was not rejected: {:?}",
+                    other
+                )))
+            }
+        }
+
+        // Cyrillic letters which all look like Latin ones, i.e. "popa"
+        match encode_public_name("\u{440}\u{43e}\u{440}\u{430}") {
+            Err(Error::InvalidXorUrl(msg)) => assert!(msg.contains("confused with Latin")),
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Confusable name was not rejected: {:?}",
+                    other
+                )))
+            }
+        }
+
+        // each label is checked on its own
+        assert_eq!(
+            encode_public_name("docs.\u{43c}\u{438}\u{440}")?,
+            "docs.xn--h1ahn"
+        );
+        Ok(())
+    }
+}
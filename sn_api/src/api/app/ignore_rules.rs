@@ -0,0 +1,215 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{Error, Result};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use log::{debug, warn};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+// Names of the files with gitignore-syntax patterns of paths to be ignored, in order of precedence
+const IGNORE_FILENAMES: &[&str] = &[".gitignore", ".safeignore"];
+
+// Paths ignored even if not listed in any ignore file, unless explicitly included
+const DEFAULT_EXCLUDE_PATTERNS: &[&str] = &[".git/"];
+
+// Decides which paths found in a local folder are not to be uploaded, based on the ignore files
+// found in the folder and its subfolders, as well as on explicit exclude and include patterns.
+// Patterns found in ignore files of subfolders take precedence over those of their parents.
+pub(crate) struct IgnoreRules {
+    root: PathBuf,
+    excludes: Gitignore,
+    includes: Gitignore,
+    // Patterns from the ignore files of each folder, None if there are no ignore files in it
+    ignore_files: HashMap<PathBuf, Option<Gitignore>>,
+}
+
+impl IgnoreRules {
+    pub fn new(
+        root: &Path,
+        exclude_patterns: &[String],
+        include_patterns: &[String],
+    ) -> Result<Self> {
+        let default_excludes = DEFAULT_EXCLUDE_PATTERNS.iter().map(|p| p.to_string());
+        let excludes = build_matcher(
+            root,
+            default_excludes.chain(exclude_patterns.iter().cloned()),
+        )?;
+        let includes = build_matcher(root, include_patterns.iter().cloned())?;
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            excludes,
+            includes,
+            ignore_files: HashMap::new(),
+        })
+    }
+
+    pub fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        if path == self.root {
+            return false;
+        }
+
+        if self.includes.matched(path, is_dir).is_ignore() {
+            return false;
+        }
+
+        if self.excludes.matched(path, is_dir).is_ignore() {
+            debug!("Path '{}' excluded", path.display());
+            return true;
+        }
+
+        // Check the patterns from the top folder down to the one containing the path
+        let mut folders: Vec<PathBuf> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|folder| folder.starts_with(&self.root))
+            .map(|folder| folder.to_path_buf())
+            .collect();
+        folders.reverse();
+
+        let mut ignored = false;
+        for folder in folders {
+            if let Some(matcher) = self.folder_matcher(&folder) {
+                let matched = matcher.matched(path, is_dir);
+                if matched.is_ignore() {
+                    ignored = true;
+                } else if matched.is_whitelist() {
+                    ignored = false;
+                }
+            }
+        }
+
+        if ignored {
+            debug!("Path '{}' ignored", path.display());
+        }
+        ignored
+    }
+
+    // Patterns from the ignore files found in the folder, they are read only once
+    fn folder_matcher(&mut self, folder: &Path) -> Option<&Gitignore> {
+        self.ignore_files
+            .entry(folder.to_path_buf())
+            .or_insert_with(|| {
+                let mut builder = GitignoreBuilder::new(folder);
+                let mut found = false;
+                for filename in IGNORE_FILENAMES {
+                    let ignore_file = folder.join(filename);
+                    if ignore_file.is_file() {
+                        found = true;
+                        if let Some(err) = builder.add(&ignore_file) {
+                            warn!(
+                                "Failed to read some patterns from '{}': {}",
+                                ignore_file.display(),
+                                err
+                            );
+                        }
+                    }
+                }
+
+                if !found {
+                    return None;
+                }
+
+                builder
+                    .build()
+                    .map_err(|err| {
+                        warn!("Ignore files in '{}' not used: {}", folder.display(), err)
+                    })
+                    .ok()
+            })
+            .as_ref()
+    }
+}
+
+// Check that the patterns are valid gitignore-syntax patterns
+pub(crate) fn validate_patterns(patterns: &[String]) -> Result<()> {
+    let _ = build_matcher(Path::new(""), patterns.iter().cloned())?;
+    Ok(())
+}
+
+fn build_matcher(root: &Path, patterns: impl Iterator<Item = String>) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        let _ = builder.add_line(None, &pattern).map_err(|err| {
+            Error::InvalidInput(format!("Invalid pattern '{}': {}", pattern, err))
+        })?;
+    }
+
+    builder
+        .build()
+        .map_err(|err| Error::InvalidInput(format!("Invalid patterns: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::app::test_helpers::random_nrs_name;
+    use std::fs;
+
+    #[test]
+    fn test_ignore_rules_patterns() -> Result<()> {
+        let root = Path::new("../testdata");
+        let mut rules = IgnoreRules::new(
+            root,
+            &["*.md".to_string(), "emptyfolder".to_string()],
+            &["subfolder/sub2.md".to_string()],
+        )?;
+
+        assert!(!rules.is_ignored(root, true));
+        assert!(rules.is_ignored(&root.join("test.md"), false));
+        assert!(rules.is_ignored(&root.join("subfolder/subexists.md"), false));
+        assert!(rules.is_ignored(&root.join("emptyfolder"), true));
+        assert!(rules.is_ignored(&root.join(".git"), true));
+        assert!(!rules.is_ignored(&root.join("noextension"), false));
+        assert!(!rules.is_ignored(&root.join("subfolder"), true));
+        // explicitly included paths take precedence
+        assert!(!rules.is_ignored(&root.join("subfolder/sub2.md"), false));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ignore_rules_ignore_files() -> Result<()> {
+        let root = std::env::temp_dir().join(random_nrs_name());
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap_or(&root))
+                .and_then(|_| fs::write(path, content))
+                .map_err(|err| Error::FileSystemError(err.to_string()))
+        };
+        write(".gitignore", "build/\n*.log\n")?;
+        write(".safeignore", "!important.log\n")?;
+        write("sub/.safeignore", "!debug.log\nsecret.txt\n")?;
+
+        let mut rules = IgnoreRules::new(&root, &[], &[])?;
+        assert!(rules.is_ignored(&root.join("build"), true));
+        assert!(rules.is_ignored(&root.join("output.log"), false));
+        assert!(!rules.is_ignored(&root.join("important.log"), false));
+        assert!(!rules.is_ignored(&root.join("readme.md"), false));
+        // patterns from subfolders take precedence
+        assert!(!rules.is_ignored(&root.join("sub/debug.log"), false));
+        assert!(rules.is_ignored(&root.join("sub/other.log"), false));
+        assert!(rules.is_ignored(&root.join("sub/secret.txt"), false));
+        assert!(!rules.is_ignored(&root.join("secret.txt"), false));
+        Ok(())
+    }
+
+    #[test]
+    fn test_ignore_rules_invalid_pattern() -> Result<()> {
+        match validate_patterns(&["valid/*".to_string(), "invalid/[".to_string()]) {
+            Err(Error::InvalidInput(msg)) if msg.contains("invalid/[") => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Unexpected result when validating an invalid pattern: {:?}",
+                other
+            ))),
+        }
+    }
+}
@@ -10,13 +10,13 @@
 use super::{
     common::ed_sk_from_hex,
     helpers::{parse_coins_amount, pk_to_hex},
+    wallet::{gen_transfer_history, TransferHistoryEntry, TransferReceipt},
     xorurl::XorUrlEncoder,
     xorurl::{SafeContentType, SafeDataType},
     Safe,
 };
 use crate::{Error, Result};
 use rand::rngs::OsRng;
-use sn_client::Client;
 use sn_data_types::{Keypair, SecretKey};
 use std::sync::Arc;
 use xor_name::XorName;
@@ -49,9 +49,9 @@ impl Safe {
         let (xorname, keypair) = {
             let our_new_keypair = Keypair::new_ed25519(&mut OsRng);
 
-            let mut paying_client = Client::new(Some(keypair)).await?;
-            paying_client
-                .send_money(our_new_keypair.public_key(), amount)
+            let _ = self
+                .safe_client
+                .safecoin_transfer_to_pk(Some(keypair), our_new_keypair.public_key(), amount)
                 .await?;
             (XorName::from(our_new_keypair.public_key()), our_new_keypair)
         };
@@ -70,7 +70,7 @@ impl Safe {
         let mut rng = OsRng;
         let keypair = Keypair::new_ed25519(&mut rng);
         self.safe_client
-            .trigger_simulated_farming_payout(amount)
+            .trigger_simulated_farming_payout(amount, Some(keypair.clone()))
             .await?;
         let xorname = XorName::from(keypair.public_key());
 
@@ -97,8 +97,7 @@ impl Safe {
             }
         };
 
-        let mut temp_client = Client::new(Some(keypair)).await?;
-        let balance = temp_client.get_balance().await?;
+        let balance = self.safe_client.read_balance_from_keypair(keypair).await?;
 
         Ok(balance.to_string())
     }
@@ -115,27 +114,57 @@ impl Safe {
         self.keys_balance_from_sk(secret_key).await
     }
 
+    /// # Get the history of transfers of a SafeKey
+    ///
+    /// Transfers made from and to the SafeKey at the given XOR/NRS-URL, oldest first, each of them
+    /// with the balance of the SafeKey right after it was made. The secret key provided has to
+    /// correspond to the URL. Only the local storage backends keep the history of transfers
+    /// for now, with the SAFE Network backend an error is returned.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::Safe;
+    /// # use std::sync::Arc;
+    /// let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let (key1_xorurl, keypair1) = safe.keys_create_preload_test_coins("14").await.unwrap();
+    ///     let (key2_xorurl, _) = safe.keys_create_preload_test_coins("1").await.unwrap();
+    ///     let sk1 = keypair1.secret_key().unwrap();
+    ///     safe.keys_transfer("10", Some(&sk1.to_string()), &key2_xorurl).await.unwrap();
+    ///
+    ///     let history = safe.keys_history(&key1_xorurl, Arc::new(sk1)).await.unwrap();
+    ///     assert_eq!(history.len(), 2);
+    ///     assert_eq!(history[0].balance, "14.000000000");
+    ///     assert_eq!(history[1].balance, "4.000000000");
+    /// # });
+    /// ```
+    pub async fn keys_history(
+        &mut self,
+        url: &str,
+        secret_key: Arc<SecretKey>,
+    ) -> Result<Vec<TransferHistoryEntry>> {
+        self.validate_sk_for_url(secret_key.clone(), url).await?;
+
+        let keypair = keypair_from_sk(&secret_key)?;
+        let xorname = XorName::from(keypair.public_key());
+        let records = self.safe_client.transfer_history(keypair).await?;
+
+        gen_transfer_history(
+            records
+                .into_iter()
+                .map(|record| (None, xorname, record))
+                .collect(),
+        )
+    }
+
     // Check that the XOR/NRS-URL corresponds to the public key derived from the provided client id
     pub async fn validate_sk_for_url(
         &mut self,
         secret_key: Arc<SecretKey>,
         url: &str,
     ) -> Result<String> {
-        let secret_key = Arc::as_ref(&secret_key);
-        let keypair = match secret_key {
-            SecretKey::Bls(sk) => Keypair::from(sk),
-            SecretKey::Ed25519(sk) => {
-                let bytes = sk.to_bytes();
-                let secret_key = ed25519_dalek::SecretKey::from_bytes(&bytes)
-                    .map_err(|_| Error::Unexpected("Error parsing SecretKey bytes".to_string()))?;
-                Keypair::from(secret_key)
-            }
-            _ => {
-                return Err(Error::InvalidInput(
-                    "Cannot form a keypair from a BlsKeyShare at this time.".to_string(),
-                ))
-            }
-        };
+        let keypair = keypair_from_sk(&secret_key)?;
 
         let (xorurl_encoder, _) = self.parse_and_resolve_url(url).await?;
         let public_key = keypair.public_key();
@@ -165,7 +194,8 @@ impl Safe {
     ///     let current_balance = safe.keys_balance_from_sk(keypair1.clone().unwrap().sk).await.unwrap();
     ///     assert_eq!("14.000000000", current_balance);
     ///
-    ///     safe.keys_transfer( "10", Some(&keypair1.clone().unwrap().sk), &key2_xorurl, None ).await.unwrap();
+    ///     let receipt = safe.keys_transfer( "10", Some(&keypair1.clone().unwrap().sk), &key2_xorurl ).await.unwrap();
+    ///     assert_eq!("10.000000000", receipt.amount);
     ///     let from_balance = safe.keys_balance_from_url( &key1_xorurl, &keypair1.unwrap().sk ).await.unwrap();
     ///     assert_eq!("4.000000000", from_balance);
     ///     let to_balance = safe.keys_balance_from_url( &key2_xorurl, &keypair2.unwrap().sk ).await.unwrap();
@@ -177,7 +207,7 @@ impl Safe {
         amount: &str,
         from_sk_str: Option<&str>,
         to_url: &str,
-    ) -> Result<()> {
+    ) -> Result<TransferReceipt> {
         // Parse and validate the amount is a valid
         let amount_coins = parse_coins_amount(amount)?;
 
@@ -206,6 +236,10 @@ impl Safe {
             Some(sk) => Some(Keypair::from(ed_sk_from_hex(sk)?)),
             None => None,
         };
+        let from_xorname = match &from {
+            Some(keypair) => XorName::from(keypair.public_key()),
+            None => XorName::from(self.safe_client.keypair().await?.public_key()),
+        };
 
         // Finally, let's make the transfer
         match self
@@ -230,17 +264,40 @@ impl Safe {
                 "Unexpected error when attempting to transfer: {}",
                 other_error
             ))),
-            // TODO: return transfer id...?
-            Ok(_tx) => Ok(()),
-            // Ok(tx) => Ok(tx.id),
+            Ok(id) => Ok(TransferReceipt::new(
+                id,
+                amount_coins,
+                from_xorname,
+                to_xorname,
+            )),
         }
     }
 }
 
+// Private helper to get the keypair of a secret key
+fn keypair_from_sk(secret_key: &SecretKey) -> Result<Keypair> {
+    match secret_key {
+        SecretKey::Bls(sk) => Ok(Keypair::from(sk)),
+        SecretKey::Ed25519(sk) => {
+            let bytes = sk.to_bytes();
+            let secret_key = ed25519_dalek::SecretKey::from_bytes(&bytes)
+                .map_err(|_| Error::Unexpected("Error parsing SecretKey bytes".to_string()))?;
+            Ok(Keypair::from(secret_key))
+        }
+        _ => Err(Error::InvalidInput(
+            "Cannot form a keypair from a BlsKeyShare at this time.".to_string(),
+        )),
+    }
+}
+
 #[cfg(all(test, feature = "simulated-payouts"))]
 mod tests {
     use super::*;
-    use crate::api::app::test_helpers::{new_safe_instance, random_nrs_name};
+    use crate::api::app::{
+        nrs_map::NrsLinkOptions,
+        test_helpers::{new_safe_instance, random_nrs_name},
+        wallet::TransferDirection,
+    };
 
     #[tokio::test]
     async fn test_keys_create_preload_test_coins() -> Result<()> {
@@ -646,7 +703,10 @@ mod tests {
                 "Transfer was expected to succeed: {}",
                 msg
             ))),
-            Ok(_) => {
+            Ok(receipt) => {
+                assert_eq!(receipt.amount, "100.400000000");
+                assert_eq!(receipt.from, XorName::from(keypair2.public_key()));
+                assert_eq!(receipt.to, XorName::from(keypair1.public_key()));
                 let from_current_balance = safe
                     .keys_balance_from_sk(Arc::new(keypair2.secret_key()?))
                     .await?;
@@ -711,7 +771,14 @@ mod tests {
 
         let to_nrsurl = random_nrs_name();
         let _ = safe
-            .nrs_map_container_create(&to_nrsurl, &to_safekey_xorurl, false, true, false)
+            .nrs_map_container_create(
+                &to_nrsurl,
+                &to_safekey_xorurl,
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
 
         // test successful transfer
@@ -736,4 +803,47 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_keys_history() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let (safekey1_xorurl, keypair1) = safe.keys_create_preload_test_coins("12.5").await?;
+        let (safekey2_xorurl, keypair2) = safe.keys_create_preload_test_coins("1").await?;
+        let sk1 = keypair1.secret_key()?;
+
+        let receipt = safe
+            .keys_transfer("2.5", Some(&sk1.to_string()), &safekey2_xorurl)
+            .await?;
+
+        let history = safe
+            .keys_history(&safekey1_xorurl, Arc::new(sk1.clone()))
+            .await?;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].direction, TransferDirection::Incoming);
+        assert_eq!(history[0].from, None);
+        assert_eq!(history[0].balance, "12.500000000");
+        assert_eq!(history[1].id, receipt.id);
+        assert_eq!(history[1].direction, TransferDirection::Outgoing);
+        assert_eq!(history[1].amount, "2.500000000");
+        assert_eq!(history[1].to, XorName::from(keypair2.public_key()));
+        assert_eq!(history[1].balance, "10.000000000");
+        assert_eq!(history[1].spendable_balance, None);
+
+        let history = safe
+            .keys_history(&safekey2_xorurl, Arc::new(keypair2.secret_key()?))
+            .await?;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].id, receipt.id);
+        assert_eq!(history[1].direction, TransferDirection::Incoming);
+        assert_eq!(history[1].balance, "3.500000000");
+
+        // the secret key has to correspond to the URL
+        match safe.keys_history(&safekey2_xorurl, Arc::new(sk1)).await {
+            Err(Error::InvalidInput(_)) => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "History was retrieved with the wrong secret key: {:?}",
+                other
+            ))),
+        }
+    }
 }
@@ -8,35 +8,51 @@
 // Software.
 
 mod auth;
+mod backend;
+mod blob_manifest;
+mod chunker;
 mod consts;
+mod encryption;
 mod helpers;
+mod idn;
+mod ignore_rules;
 mod keys;
 mod nrs;
 mod realpath;
+mod resolution_cache;
 mod safe_client;
 mod sequence;
 #[cfg(test)]
 mod test_helpers;
+mod upload_journal;
 mod xorurl_media_types;
 
 use super::common;
 use super::constants;
+use encryption::DataEncryptor;
+use resolution_cache::ResolutionCache;
 use safe_client::SafeAppClient;
+use upload_journal::UploadJournal;
 use xorurl::XorUrlBase;
 
 // The following is what's meant to be the public API
 
 pub mod fetch;
 pub mod files;
+pub mod files_view;
 pub mod nrs_map;
+pub mod rdf;
 pub mod wallet;
 pub mod xorurl;
-use super::Result;
-pub use consts::DEFAULT_XORURL_BASE;
+use super::{Error, Result};
+pub use backend::BackendConfig;
+pub use consts::{
+    CONTENT_ERROR_SIGN, CONTENT_SKIPPED_SIGN, DEFAULT_UPLOAD_CONCURRENCY, DEFAULT_XORURL_BASE,
+};
 pub use helpers::parse_coins_amount;
-pub use nrs::ProcessedEntries;
+pub use nrs::{AppliedChanges, NrsMapHistory, ProcessedEntries};
 use sn_data_types::Keypair;
-use std::sync::Arc;
+use std::{cmp::max, path::Path, sync::Arc, time::Duration};
 pub use xor_name::{XorName, XOR_NAME_LEN};
 
 // TODO: should we be cloning this?
@@ -44,6 +60,14 @@ pub use xor_name::{XorName, XOR_NAME_LEN};
 pub struct Safe {
     safe_client: SafeAppClient,
     pub xorurl_base: XorUrlBase,
+    upload_journal: Option<UploadJournal>,
+    resolution_cache: Option<ResolutionCache>,
+    upload_concurrency: usize,
+    legacy_maps_format: bool,
+    exclude_patterns: Vec<String>,
+    include_patterns: Vec<String>,
+    #[cfg(test)]
+    uploads_in_flight: files::UploadsInFlight,
 }
 
 impl Default for Safe {
@@ -54,13 +78,84 @@ impl Default for Safe {
 
 impl Safe {
     pub fn new(xorurl_base: Option<XorUrlBase>) -> Self {
+        Self::with_backend(xorurl_base, BackendConfig::Network)
+    }
+
+    // Create an instance which connects to the given storage backend, e.g. a local
+    // stand-in for the network, rather than to the SAFE Network
+    pub fn with_backend(xorurl_base: Option<XorUrlBase>, backend: BackendConfig) -> Self {
         Self {
-            safe_client: SafeAppClient::new(),
+            safe_client: SafeAppClient::new(backend),
             xorurl_base: xorurl_base.unwrap_or_else(|| DEFAULT_XORURL_BASE),
+            upload_journal: None,
+            resolution_cache: None,
+            upload_concurrency: DEFAULT_UPLOAD_CONCURRENCY,
+            legacy_maps_format: false,
+            exclude_patterns: vec![],
+            include_patterns: vec![],
+            #[cfg(test)]
+            uploads_in_flight: Default::default(),
         }
     }
 
+    // Maximum number of files to be uploaded concurrently by the FilesContainer operations
+    pub fn set_upload_concurrency(&mut self, concurrency: usize) {
+        self.upload_concurrency = max(concurrency, 1);
+    }
+
+    // Store the FilesMaps and NrsMaps as plain JSON, the format used before the RDF schema
+    // was introduced, rather than as JSON-LD. Versions prior to the RDF schema can only read
+    // maps stored in this format, while maps in either format can be read by this version.
+    pub fn set_legacy_maps_format(&mut self, legacy: bool) {
+        self.legacy_maps_format = legacy;
+    }
+
+    // Gitignore-syntax patterns of paths to be skipped, or uploaded even if they are
+    // ignored, when the FilesContainer operations upload the content of a local folder.
+    // Ignore files, i.e. '.gitignore' and '.safeignore', found in the folder are always honoured.
+    pub fn set_upload_patterns(&mut self, exclude: &[String], include: &[String]) -> Result<()> {
+        ignore_rules::validate_patterns(exclude)?;
+        ignore_rules::validate_patterns(include)?;
+        self.exclude_patterns = exclude.to_vec();
+        self.include_patterns = include.to_vec();
+        Ok(())
+    }
+
+    // Keep a journal, at the given local path, of the files stored by the FilesContainer
+    // operations. The files found in the journal with the same content, and stored with
+    // the same storage backend and keypair, are not uploaded again, e.g. to resume a
+    // previous operation which didn't complete.
+    pub fn set_upload_journal(&mut self, path: &Path) -> Result<()> {
+        self.upload_journal = Some(UploadJournal::load(path)?);
+        Ok(())
+    }
+
+    // Cache the NRS Map versions and the immutable content fetched, i.e. Blobs and specific
+    // versions of FilesContainers, for the given time and up to the given total size in bytes.
+    // Lookups of the latest version of a container are never served from the cache.
+    pub fn enable_resolution_cache(&mut self, ttl: Duration, max_size: usize) {
+        self.resolution_cache = Some(ResolutionCache::new(ttl, max_size));
+    }
+
+    pub fn disable_resolution_cache(&mut self) {
+        self.resolution_cache = None;
+    }
+
     pub async fn keypair(&self) -> Result<Arc<Keypair>> {
         self.safe_client.keypair().await
     }
+
+    // Encryptor for the content of private data, using a key derived from the keypair in use.
+    // The keypair needs to be the same in every session for the data to be decrypted again,
+    // so private data is refused if a new keypair is generated on each connection.
+    pub(crate) async fn data_encryptor(&self) -> Result<DataEncryptor> {
+        if !self.safe_client.has_persistent_keypair()? {
+            return Err(Error::AccessDenied(format!(
+                "Private data can only be stored and read with a keypair which persists across sessions, but the '{}' storage backend connects with a new keypair each time. Use a local disk storage backend instead",
+                self.safe_client.backend_config()
+            )));
+        }
+        let keypair = self.safe_client.keypair().await?;
+        DataEncryptor::from_keypair(&keypair)
+    }
 }
@@ -8,8 +8,13 @@
 // Software.
 
 use super::{
-    consts::{CONTENT_ADDED_SIGN, CONTENT_DELETED_SIGN},
-    nrs_map::NrsMap,
+    common::pk_bytes_to_hex,
+    consts::{
+        CONTENT_ADDED_SIGN, CONTENT_DELETED_SIGN, CONTENT_UPDATED_SIGN, FAKE_RDF_PREDICATE_LINK,
+    },
+    nrs_map::{NrsLinkOptions, NrsMap, NrsMapChange},
+    rdf::{decode_nrs_map, encode_nrs_map},
+    resolution_cache::{CacheKey, CachedContent},
     xorurl::SafeContentType,
     Safe,
 };
@@ -18,6 +23,7 @@ use crate::{
     Error, Result,
 };
 use log::{debug, info, warn};
+use sn_data_types::PublicKey;
 use std::collections::BTreeMap;
 
 // Type tag to use for the NrsMapContainer stored on Sequence
@@ -28,6 +34,12 @@ const ERROR_MSG_NO_NRS_MAP_FOUND: &str = "No NRS Map found at this address";
 // List of public names uploaded with details if they were added, updated or deleted from NrsMaps
 pub type ProcessedEntries = BTreeMap<String, (String, String)>;
 
+// Entries added, updated or deleted in each version of an NRS Map Container
+pub type NrsMapHistory = Vec<(u64, ProcessedEntries)>;
+
+// Names with details of each change applied to an NrsMap, in the order they were applied
+pub type AppliedChanges = Vec<(String, (String, String))>;
+
 impl Safe {
     pub fn parse_url(url: &str) -> Result<XorUrlEncoder> {
         XorUrlEncoder::from_url(&sanitised_url(url))
@@ -45,7 +57,7 @@ impl Safe {
         let orig_path = xorurl_encoder.path_decoded()?;
 
         // Obtain the resolution chain without resolving the URL's path
-        let mut resolution_chain = self
+        let (mut resolution_chain, _) = self
             .retrieve_from_url(
                 &xorurl_encoder.to_string(),
                 false,
@@ -84,34 +96,129 @@ impl Safe {
         link: &str,
         default: bool,
         hard_link: bool,
+        options: NrsLinkOptions,
         dry_run: bool,
     ) -> Result<(u64, XorUrl, ProcessedEntries, NrsMap)> {
         info!("Adding to NRS map...");
-        // GET current NRS map from name's TLD
-        let (xorurl_encoder, _) = validate_nrs_name(name)?;
-        let xorurl = xorurl_encoder.to_string();
-        let (version, mut nrs_map) = self.nrs_map_container_get(&xorurl).await?;
-        debug!("NRS, Existing data: {:?}", nrs_map);
+        self.nrs_map_container_update(name, dry_run, |nrs_map| {
+            let link =
+                nrs_map.nrs_update_map_or_create_data(name, link, default, hard_link, options)?;
+            let mut processed_entries = ProcessedEntries::new();
+            processed_entries.insert(name.to_string(), (CONTENT_ADDED_SIGN.to_string(), link));
+            Ok(processed_entries)
+        })
+        .await
+    }
 
-        let link = nrs_map.nrs_update_map_or_create_data(name, link, default, hard_link)?;
-        let mut processed_entries = ProcessedEntries::new();
-        processed_entries.insert(name.to_string(), (CONTENT_ADDED_SIGN.to_string(), link));
+    /// Add a name, or update it if it already exists, to be an alias of another NRS name.
+    /// The alias is resolved with the latest version of the target's NRS Map Container,
+    /// and a path in the target URL is prefixed to the path of the URL being resolved,
+    /// e.g. aliasing `blog.me` to `safe://me/blog` resolves `safe://blog.me/post.html`
+    /// as `safe://me/blog/post.html`.
+    pub async fn nrs_map_container_add_alias(
+        &mut self,
+        name: &str,
+        target: &str,
+        default: bool,
+        dry_run: bool,
+    ) -> Result<(u64, XorUrl, ProcessedEntries, NrsMap)> {
+        info!("Adding alias to NRS map...");
+        self.nrs_map_container_update(name, dry_run, |nrs_map| {
+            let link = nrs_map.nrs_update_map_or_create_alias(name, target, default)?;
+            let mut processed_entries = ProcessedEntries::new();
+            processed_entries.insert(name.to_string(), (CONTENT_ADDED_SIGN.to_string(), link));
+            Ok(processed_entries)
+        })
+        .await
+    }
 
-        debug!("The new NRS Map: {:?}", nrs_map);
-        if !dry_run {
-            // Append new version of the NrsMap in the Public Sequence (NRS Map Container)
-            let nrs_map_raw_data = gen_nrs_map_raw_data(&nrs_map)?;
-            self.safe_client
-                .append_to_sequence(
-                    &nrs_map_raw_data,
-                    xorurl_encoder.xorname(),
-                    xorurl_encoder.type_tag(),
-                    false,
-                )
-                .await?;
+    /// # Apply a list of changes to an NRS name as a single new version of its NRS Map Container.
+    /// The changes are applied in order, and if any of them fails none is applied.
+    /// The details of each change are returned in the same order as the changes.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use rand::distributions::Alphanumeric;
+    /// # use rand::{thread_rng, Rng};
+    /// # use sn_api::{Safe, nrs_map::{NrsLinkOptions, NrsMapChange}};
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let rand_string: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
+    ///     let file_xorurl = safe.files_store_public_blob(&vec![], None, false).await.unwrap();
+    ///     let _ = safe.nrs_map_container_create(&rand_string, &file_xorurl, true, false, NrsLinkOptions::default(), false).await.unwrap();
+    ///     let changes = vec![
+    ///         NrsMapChange::Add { name: format!("a.{}", rand_string), link: file_xorurl.clone(), default: false, hard_link: false, options: NrsLinkOptions::default() },
+    ///         NrsMapChange::Add { name: format!("b.{}", rand_string), link: file_xorurl, default: false, hard_link: false, options: NrsLinkOptions::default() },
+    ///         NrsMapChange::SetDefault { name: format!("b.{}", rand_string) },
+    ///     ];
+    ///     let (version, _, applied_changes, _) = safe.nrs_map_container_apply(&rand_string, &changes, false).await.unwrap();
+    ///     assert_eq!(version, 1);
+    ///     assert_eq!(applied_changes.len(), 3);
+    ///     assert_eq!(applied_changes[0].0, format!("a.{}", rand_string));
+    /// # });
+    /// ```
+    pub async fn nrs_map_container_apply(
+        &mut self,
+        name: &str,
+        changes: &[NrsMapChange],
+        dry_run: bool,
+    ) -> Result<(u64, XorUrl, AppliedChanges, NrsMap)> {
+        info!("Applying {} changes to NRS map...", changes.len());
+        let (xorurl_encoder, _) = validate_nrs_top_name(name)?;
+        let top_name = xorurl_encoder.top_name().to_string();
+        for change in changes.iter() {
+            let (change_encoder, _) = validate_nrs_name(change.name())?;
+            if change_encoder.top_name() != top_name {
+                return Err(Error::InvalidInput(format!(
+                    "The name \"{}\" is not a sub name of \"{}\"",
+                    change.name(),
+                    top_name
+                )));
+            }
         }
 
-        Ok((version + 1, xorurl, processed_entries, nrs_map))
+        self.nrs_map_container_update(name, dry_run, |nrs_map| {
+            let mut applied_changes = AppliedChanges::new();
+            for change in changes.iter() {
+                let (key, entry) = match change {
+                    NrsMapChange::Add {
+                        name,
+                        link,
+                        default,
+                        hard_link,
+                        options,
+                    } => {
+                        let link = nrs_map.nrs_update_map_or_create_data(
+                            name, link, *default, *hard_link, *options,
+                        )?;
+                        (name.to_string(), (CONTENT_ADDED_SIGN.to_string(), link))
+                    }
+                    NrsMapChange::Alias {
+                        name,
+                        target,
+                        default,
+                    } => {
+                        let link =
+                            nrs_map.nrs_update_map_or_create_alias(name, target, *default)?;
+                        (name.to_string(), (CONTENT_ADDED_SIGN.to_string(), link))
+                    }
+                    NrsMapChange::Remove { name } => {
+                        let link = nrs_map.nrs_map_remove_subname(name)?;
+                        (name.to_string(), (CONTENT_DELETED_SIGN.to_string(), link))
+                    }
+                    NrsMapChange::SetDefault { name } => {
+                        // it's the default of the top name which is updated
+                        let link = nrs_map.nrs_map_set_default(name)?;
+                        (top_name.clone(), (CONTENT_UPDATED_SIGN.to_string(), link))
+                    }
+                };
+                applied_changes.push((key, entry));
+            }
+            Ok(applied_changes)
+        })
+        .await
     }
 
     /// # Create a NrsMapContainer.
@@ -121,13 +228,13 @@ impl Safe {
     /// ```rust
     /// # use rand::distributions::Alphanumeric;
     /// # use rand::{thread_rng, Rng};
-    /// # use sn_api::Safe;
+    /// # use sn_api::{nrs_map::NrsLinkOptions, Safe};
     /// # let mut safe = Safe::default();
     /// # async_std::task::block_on(async {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
     ///     let rand_string: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
     ///     let file_xorurl = safe.files_store_public_blob(&vec![], None, false).await.unwrap();
-    ///     let (xorurl, _processed_entries, nrs_map_container) = safe.nrs_map_container_create(&rand_string, &file_xorurl, true, false, false).await.unwrap();
+    ///     let (xorurl, _processed_entries, nrs_map_container) = safe.nrs_map_container_create(&rand_string, &file_xorurl, true, false, NrsLinkOptions::default(), false).await.unwrap();
     ///     assert!(xorurl.contains("safe://"))
     /// # });
     /// ```
@@ -137,6 +244,7 @@ impl Safe {
         link: &str,
         default: bool,
         hard_link: bool,
+        options: NrsLinkOptions,
         dry_run: bool,
     ) -> Result<(XorUrl, ProcessedEntries, NrsMap)> {
         info!("Creating an NRS map");
@@ -148,7 +256,8 @@ impl Safe {
             ))
         } else {
             let mut nrs_map = NrsMap::default();
-            let link = nrs_map.nrs_update_map_or_create_data(&name, link, default, hard_link)?;
+            let link =
+                nrs_map.nrs_update_map_or_create_data(&name, link, default, hard_link, options)?;
             let mut processed_entries = ProcessedEntries::new();
             processed_entries.insert(name.to_string(), (CONTENT_ADDED_SIGN.to_string(), link));
 
@@ -160,7 +269,7 @@ impl Safe {
                 debug!("XorName for \"{:?}\" is \"{:?}\"", &nrs_url, &nrs_xorname);
 
                 // Store the NrsMapContainer in a Public Sequence
-                let nrs_map_raw_data = gen_nrs_map_raw_data(&nrs_map)?;
+                let nrs_map_raw_data = gen_nrs_map_raw_data(&nrs_map, self.legacy_maps_format)?;
                 let xorname = self
                     .safe_client
                     .store_sequence(
@@ -191,23 +300,164 @@ impl Safe {
         dry_run: bool,
     ) -> Result<(u64, XorUrl, ProcessedEntries, NrsMap)> {
         info!("Removing from NRS map...");
-        // GET current NRS map from &name TLD
+        self.nrs_map_container_update(name, dry_run, |nrs_map| {
+            let removed_link = nrs_map.nrs_map_remove_subname(name)?;
+            let mut processed_entries = ProcessedEntries::new();
+            processed_entries.insert(
+                name.to_string(),
+                (CONTENT_DELETED_SIGN.to_string(), removed_link),
+            );
+            Ok(processed_entries)
+        })
+        .await
+    }
+
+    /// # Get the owner of an NRS name.
+    /// Only the owner of an NRS name can add, update or remove its sub names, and transfer it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use sn_api::{nrs_map::NrsLinkOptions, Safe};
+    /// # use rand::distributions::Alphanumeric;
+    /// # use rand::{thread_rng, Rng};
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let rand_string: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
+    ///     let file_xorurl = safe.files_store_public_blob(&vec![], Some("text/plain"), false).await.unwrap();
+    ///     let _ = safe.nrs_map_container_create(&rand_string, &file_xorurl, true, false, NrsLinkOptions::default(), false).await.unwrap();
+    ///     let owner = safe.nrs_owner(&rand_string).await.unwrap();
+    ///     println!("NRS name '{}' is owned by: {}", rand_string, owner);
+    /// # });
+    /// ```
+    pub async fn nrs_owner(&mut self, name: &str) -> Result<PublicKey> {
+        let (xorurl_encoder, _) = validate_nrs_top_name(name)?;
+
+        self.safe_client
+            .sequence_get_owner(xorurl_encoder.xorname(), NRS_MAP_TYPE_TAG, false)
+            .await
+            .map_err(|err| match err {
+                Error::ContentNotFound(_) => {
+                    Error::ContentNotFound(ERROR_MSG_NO_NRS_MAP_FOUND.to_string())
+                }
+                other => other,
+            })
+    }
+
+    /// # Transfer an NRS name to a new owner.
+    /// The connected keypair needs to be the current owner of the NRS name, and it won't be
+    /// able to make any changes to it, or transfer it back, once transferred. On a dry run
+    /// only the ownership is checked, without transferring it.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use sn_api::{nrs_map::NrsLinkOptions, Safe};
+    /// # use rand::distributions::Alphanumeric;
+    /// # use rand::{thread_rng, Rng};
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let rand_string: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
+    ///     let file_xorurl = safe.files_store_public_blob(&vec![], Some("text/plain"), false).await.unwrap();
+    ///     let _ = safe.nrs_map_container_create(&rand_string, &file_xorurl, true, false, NrsLinkOptions::default(), false).await.unwrap();
+    ///     let new_owner = safe.generate_random_ed_keypair().unwrap().public_key();
+    ///     safe.nrs_transfer(&rand_string, new_owner, false).await.unwrap();
+    ///     assert_eq!(safe.nrs_owner(&rand_string).await.unwrap(), new_owner);
+    /// # });
+    /// ```
+    pub async fn nrs_transfer(
+        &mut self,
+        name: &str,
+        new_owner_pk: PublicKey,
+        dry_run: bool,
+    ) -> Result<()> {
+        info!("Transferring NRS name...");
+        let (xorurl_encoder, _) = validate_nrs_top_name(name)?;
+        self.check_nrs_map_container_owner(&xorurl_encoder).await?;
+        if dry_run {
+            return Ok(());
+        }
+
+        self.safe_client
+            .sequence_set_owner(
+                xorurl_encoder.xorname(),
+                NRS_MAP_TYPE_TAG,
+                new_owner_pk,
+                false,
+            )
+            .await
+    }
+
+    /// # Get the history of changes of an NRS name.
+    /// It returns the entries added (+), updated (*) and removed (-) in each version of the
+    /// NRS Map Container, the entries of the first version are all reported as added.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use sn_api::{nrs_map::NrsLinkOptions, Safe};
+    /// # use rand::distributions::Alphanumeric;
+    /// # use rand::{thread_rng, Rng};
+    /// # let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let rand_string: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
+    ///     let file_xorurl = safe.files_store_public_blob(&vec![], Some("text/plain"), false).await.unwrap();
+    ///     let _ = safe.nrs_map_container_create(&rand_string, &file_xorurl, true, false, NrsLinkOptions::default(), false).await.unwrap();
+    ///     let history = safe.nrs_map_container_history(&rand_string).await.unwrap();
+    ///     assert_eq!(history.len(), 1);
+    /// # });
+    /// ```
+    pub async fn nrs_map_container_history(&mut self, name: &str) -> Result<NrsMapHistory> {
+        let (xorurl_encoder, _) = validate_nrs_top_name(name)?;
+        let top_name = xorurl_encoder.top_name().to_string();
+        let (current_version, _) = self
+            .nrs_map_container_get(&xorurl_encoder.to_string())
+            .await?;
+
+        let mut history = NrsMapHistory::new();
+        let mut previous_summary = BTreeMap::new();
+        for version in 0..=current_version {
+            let mut versioned_encoder = xorurl_encoder.clone();
+            versioned_encoder.set_content_version(Some(version));
+            let (_, nrs_map) = self
+                .nrs_map_container_get(&versioned_encoder.to_string())
+                .await?;
+
+            let summary = nrs_map.get_map_summary();
+            let changes = nrs_map_summary_changes(&previous_summary, &summary, &top_name);
+            history.push((version, changes));
+            previous_summary = summary;
+        }
+
+        Ok(history)
+    }
+
+    // Apply an update to the NrsMap of a name's NRS Map Container, appending it as a new version
+    async fn nrs_map_container_update<T, F>(
+        &mut self,
+        name: &str,
+        dry_run: bool,
+        update: F,
+    ) -> Result<(u64, XorUrl, T, NrsMap)>
+    where
+        F: FnOnce(&mut NrsMap) -> Result<T>,
+    {
+        // GET current NRS map from name's TLD
         let (xorurl_encoder, _) = validate_nrs_name(name)?;
         let xorurl = xorurl_encoder.to_string();
         let (version, mut nrs_map) = self.nrs_map_container_get(&xorurl).await?;
+        self.check_nrs_map_container_owner(&xorurl_encoder).await?;
         debug!("NRS, Existing data: {:?}", nrs_map);
 
-        let removed_link = nrs_map.nrs_map_remove_subname(name)?;
-        let mut processed_entries = ProcessedEntries::new();
-        processed_entries.insert(
-            name.to_string(),
-            (CONTENT_DELETED_SIGN.to_string(), removed_link),
-        );
+        let processed_entries = update(&mut nrs_map)?;
 
         debug!("The new NRS Map: {:?}", nrs_map);
         if !dry_run {
             // Append new version of the NrsMap in the Public Sequence (NRS Map Container)
-            let nrs_map_raw_data = gen_nrs_map_raw_data(&nrs_map)?;
+            let nrs_map_raw_data = gen_nrs_map_raw_data(&nrs_map, self.legacy_maps_format)?;
             self.safe_client
                 .append_to_sequence(
                     &nrs_map_raw_data,
@@ -221,12 +471,37 @@ impl Safe {
         Ok((version + 1, xorurl, processed_entries, nrs_map))
     }
 
+    // Fail early if the connected keypair is not the owner of the NRS Map Container
+    async fn check_nrs_map_container_owner(&self, xorurl_encoder: &XorUrlEncoder) -> Result<()> {
+        let owner = self
+            .safe_client
+            .sequence_get_owner(xorurl_encoder.xorname(), NRS_MAP_TYPE_TAG, false)
+            .await
+            .map_err(|err| match err {
+                Error::ContentNotFound(_) => {
+                    Error::ContentNotFound(ERROR_MSG_NO_NRS_MAP_FOUND.to_string())
+                }
+                other => other,
+            })?;
+        let public_key = self.safe_client.keypair().await?.public_key();
+
+        if owner == public_key {
+            Ok(())
+        } else {
+            Err(Error::AccessDenied(format!(
+                "The NRS name '{}' is owned by '{}', it cannot be changed with the current keypair",
+                xorurl_encoder.top_name(),
+                pk_bytes_to_hex(&owner)
+            )))
+        }
+    }
+
     /// # Fetch an existing NrsMapContainer.
     ///
     /// ## Example
     ///
     /// ```rust
-    /// # use sn_api::Safe;
+    /// # use sn_api::{nrs_map::NrsLinkOptions, Safe};
     /// # use rand::distributions::Alphanumeric;
     /// # use rand::{thread_rng, Rng};
     /// # let mut safe = Safe::default();
@@ -234,7 +509,7 @@ impl Safe {
     /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
     ///     let rand_string: String = thread_rng().sample_iter(&Alphanumeric).take(15).collect();
     ///     let file_xorurl = safe.files_store_public_blob(&vec![], Some("text/plain"), false).await.unwrap();
-    ///     let (xorurl, _processed_entries, _nrs_map) = safe.nrs_map_container_create(&rand_string, &file_xorurl, true, false, false).await.unwrap();
+    ///     let (xorurl, _processed_entries, _nrs_map) = safe.nrs_map_container_create(&rand_string, &file_xorurl, true, false, NrsLinkOptions::default(), false).await.unwrap();
     ///     let (version, nrs_map_container) = safe.nrs_map_container_get(&xorurl).await.unwrap();
     ///     assert_eq!(version, 0);
     ///     assert_eq!(nrs_map_container.get_default_link().unwrap(), file_xorurl);
@@ -243,9 +518,34 @@ impl Safe {
     pub async fn nrs_map_container_get(&mut self, url: &str) -> Result<(u64, NrsMap)> {
         debug!("Getting latest resolvable map container from: {:?}", url);
         let xorurl_encoder = Safe::parse_url(url)?;
+        let mut content_version = xorurl_encoder.content_version();
+
+        // The latest version is served from the cache too if it's still the latest one,
+        // which is cheaper to check than fetching the map again
+        if content_version.is_none() && self.resolution_cache.is_some() {
+            match self
+                .safe_client
+                .sequence_get_last_index(xorurl_encoder.xorname(), NRS_MAP_TYPE_TAG, false)
+                .await
+            {
+                Ok(last_version) => content_version = Some(last_version),
+                Err(err) => debug!("Failed to get current version of NRS Map: {}", err),
+            }
+        }
+
+        // A specific version of an NRS Map never changes, thus it can be served from the cache
+        if let (Some(cache), Some(version)) = (&self.resolution_cache, content_version) {
+            let key = CacheKey::NrsMap {
+                xorname: xorurl_encoder.xorname(),
+                version,
+            };
+            if let Some(CachedContent::NrsMap(nrs_map)) = cache.get(&key) {
+                return Ok((version, nrs_map));
+            }
+        }
 
         // Check if the URL specified a specific version of the content or simply the latest available
-        let data = match xorurl_encoder.content_version() {
+        let data = match content_version {
             None => {
                 self.safe_client
                     .sequence_get_last_entry(xorurl_encoder.xorname(), NRS_MAP_TYPE_TAG, false)
@@ -275,14 +575,17 @@ impl Safe {
         match data {
             Ok((version, serialised_nrs_map)) => {
                 debug!("Nrs map v{} retrieved: {:?} ", version, &serialised_nrs_map);
-                let nrs_map =
-                    serde_json::from_str(&String::from_utf8_lossy(&serialised_nrs_map.as_slice()))
-                        .map_err(|err| {
-                            Error::ContentError(format!(
-                                "Couldn't deserialise the NrsMap stored in the NrsContainer: {:?}",
-                                err
-                            ))
-                        })?;
+                let nrs_map = decode_nrs_map(&serialised_nrs_map)?;
+
+                // Even when the latest version was looked up, the map is cached for that version
+                if let Some(cache) = &self.resolution_cache {
+                    let key = CacheKey::NrsMap {
+                        xorname: xorurl_encoder.xorname(),
+                        version,
+                    };
+                    let content = CachedContent::NrsMap(nrs_map.clone());
+                    cache.insert(key, content, serialised_nrs_map.len());
+                }
 
                 Ok((version, nrs_map))
             }
@@ -314,23 +617,65 @@ fn validate_nrs_name(name: &str) -> Result<(XorUrlEncoder, String)> {
     Ok((xorurl_encoder, sanitised_url))
 }
 
+// Entries added, updated and removed between two summaries of NrsMaps, by their full NRS name
+fn nrs_map_summary_changes(
+    previous: &BTreeMap<String, BTreeMap<String, String>>,
+    current: &BTreeMap<String, BTreeMap<String, String>>,
+    top_name: &str,
+) -> ProcessedEntries {
+    let link_of = |def_data: &BTreeMap<String, String>| {
+        def_data
+            .get(FAKE_RDF_PREDICATE_LINK)
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    let mut changes = ProcessedEntries::new();
+    for (sub_names, def_data) in current.iter() {
+        let change = match previous.get(sub_names) {
+            None => CONTENT_ADDED_SIGN,
+            Some(previous_def_data) if previous_def_data != def_data => CONTENT_UPDATED_SIGN,
+            Some(_) => continue,
+        };
+        let _ = changes.insert(
+            format!("{}{}", sub_names, top_name),
+            (change.to_string(), link_of(def_data)),
+        );
+    }
+    for (sub_names, def_data) in previous.iter() {
+        if !current.contains_key(sub_names) {
+            let _ = changes.insert(
+                format!("{}{}", sub_names, top_name),
+                (CONTENT_DELETED_SIGN.to_string(), link_of(def_data)),
+            );
+        }
+    }
+
+    changes
+}
+
+// Ownership is of the top name, thus sub names are not allowed
+fn validate_nrs_top_name(name: &str) -> Result<(XorUrlEncoder, String)> {
+    let (xorurl_encoder, sanitised_url) = validate_nrs_name(name)?;
+    if !xorurl_encoder.sub_names().is_empty() {
+        return Err(Error::InvalidInput(format!(
+            "The NRS name cannot contain sub names, only the owner of the top name '{}' can be changed: {}",
+            xorurl_encoder.top_name(),
+            sanitised_url
+        )));
+    }
+    Ok((xorurl_encoder, sanitised_url))
+}
+
 fn sanitised_url(name: &str) -> String {
     // FIXME: make sure we remove the starting 'safe://'
     format!("safe://{}", name.replace("safe://", ""))
 }
 
-fn gen_nrs_map_raw_data(nrs_map: &NrsMap) -> Result<Vec<u8>> {
+fn gen_nrs_map_raw_data(nrs_map: &NrsMap, legacy_format: bool) -> Result<Vec<u8>> {
     // The NrsMapContainer is a Sequence where each NRS Map version is
-    // an entry containing the serialised NrsMap
-    // TODO: use RDF format
-    let serialised_nrs_map = serde_json::to_string(nrs_map).map_err(|err| {
-        Error::Unexpected(format!(
-            "Couldn't serialise the NrsMap generated: {:?}",
-            err
-        ))
-    })?;
-
-    Ok(serialised_nrs_map.as_bytes().to_vec())
+    // an entry containing the NrsMap serialised as JSON-LD, or as plain JSON
+    encode_nrs_map(nrs_map, legacy_format)
 }
 
 #[cfg(test)]
@@ -353,6 +698,7 @@ mod tests {
                 "safe://linked-from-<site_name>?v=0",
                 true,
                 false,
+                NrsLinkOptions::default(),
                 false,
             )
             .await?;
@@ -396,6 +742,7 @@ mod tests {
                 "safe://linked-from-<b.site_name>?v=0",
                 true,
                 false,
+                NrsLinkOptions::default(),
                 false,
             )
             .await?;
@@ -412,6 +759,7 @@ mod tests {
                 "safe://linked-from-<a.b.site_name>?v=0",
                 true,
                 false,
+                NrsLinkOptions::default(),
                 false,
             )
             .await?;
@@ -424,6 +772,231 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_nrs_map_container_apply() -> Result<()> {
+        let site_name = random_nrs_name();
+        let mut safe = new_safe_instance().await?;
+        let (xorurl, _, _) = safe
+            .nrs_map_container_create(
+                &format!("b.{}", site_name),
+                "safe://linked-from-<b.site_name>?v=0",
+                true,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+
+        let add_change = |sub_name: &str| NrsMapChange::Add {
+            name: format!("{}.{}", sub_name, site_name),
+            link: format!("safe://linked-from-<{}.site_name>?v=0", sub_name),
+            default: false,
+            hard_link: false,
+            options: NrsLinkOptions::default(),
+        };
+        let changes = vec![
+            add_change("a"),
+            add_change("c"),
+            NrsMapChange::Remove {
+                name: format!("b.{}", site_name),
+            },
+            NrsMapChange::SetDefault {
+                name: format!("a.{}", site_name),
+            },
+        ];
+        let (version, nrs_xorurl, applied_changes, nrs_map) = safe
+            .nrs_map_container_apply(&site_name, &changes, false)
+            .await?;
+        assert_eq!(version, 1);
+        assert_eq!(nrs_xorurl, xorurl);
+        assert_eq!(applied_changes.len(), 4);
+        assert_eq!(applied_changes[0].0, format!("a.{}", site_name));
+        assert_eq!(applied_changes[1].0, format!("c.{}", site_name));
+        assert_eq!(
+            applied_changes[2],
+            (
+                format!("b.{}", site_name),
+                (
+                    CONTENT_DELETED_SIGN.to_string(),
+                    "safe://linked-from-<b.site_name>?v=0".to_string()
+                )
+            )
+        );
+        assert_eq!(
+            applied_changes[3].1,
+            (
+                CONTENT_UPDATED_SIGN.to_string(),
+                "safe://linked-from-<a.site_name>?v=0".to_string()
+            )
+        );
+        assert_eq!(nrs_map.sub_names_map.len(), 2);
+        assert_eq!(
+            nrs_map.get_default_link()?,
+            "safe://linked-from-<a.site_name>?v=0"
+        );
+
+        // nothing is applied if any of the changes fails
+        let changes = vec![
+            add_change("d"),
+            NrsMapChange::Remove {
+                name: format!("b.{}", site_name),
+            },
+        ];
+        if safe
+            .nrs_map_container_apply(&site_name, &changes, false)
+            .await
+            .is_ok()
+        {
+            return Err(Error::Unexpected(
+                "Removing a missing sub name was unexpectedly applied".to_string(),
+            ));
+        }
+        let (current_version, current_nrs_map) = safe.nrs_map_container_get(&xorurl).await?;
+        assert_eq!(current_version, 1);
+        assert_eq!(current_nrs_map, nrs_map);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_nrs_map_container_get_with_resolution_cache() -> Result<()> {
+        let site_name = random_nrs_name();
+        let mut safe = new_safe_instance().await?;
+        safe.enable_resolution_cache(std::time::Duration::from_secs(60), 1024 * 1024);
+        let (xorurl, _, original_nrs_map) = safe
+            .nrs_map_container_create(
+                &site_name,
+                "safe://linked-from-<site_name>?v=0",
+                true,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+        let (version, _) = safe.nrs_map_container_get(&xorurl).await?;
+        assert_eq!(version, 0);
+
+        // the latest version is always looked up again, even if a previous one was cached
+        let (_, _, _, updated_nrs_map) = safe
+            .nrs_map_container_add(
+                &format!("a.{}", site_name),
+                "safe://linked-from-<a.site_name>?v=0",
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+        let (version, nrs_map) = safe.nrs_map_container_get(&xorurl).await?;
+        assert_eq!(version, 1);
+        assert_eq!(nrs_map, updated_nrs_map);
+
+        // while it's still the latest version, the map cached for it is served
+        let xorname = XorUrlEncoder::from_url(&xorurl)?.xorname();
+        if let Some(cache) = &safe.resolution_cache {
+            let key = CacheKey::NrsMap {
+                xorname,
+                version: 1,
+            };
+            cache.insert(key, CachedContent::NrsMap(original_nrs_map.clone()), 1);
+        }
+        let (version, nrs_map) = safe.nrs_map_container_get(&xorurl).await?;
+        assert_eq!(version, 1);
+        assert_eq!(nrs_map, original_nrs_map);
+
+        let mut xorurl_encoder = XorUrlEncoder::from_url(&xorurl)?;
+        xorurl_encoder.set_content_version(Some(0));
+        let (version, nrs_map) = safe
+            .nrs_map_container_get(&xorurl_encoder.to_string())
+            .await?;
+        assert_eq!(version, 0);
+        assert_eq!(nrs_map, original_nrs_map);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_nrs_map_container_add_wildcard() -> Result<()> {
+        let site_name = random_nrs_name();
+        let mut safe = new_safe_instance().await?;
+        let _ = safe
+            .nrs_map_container_create(
+                &format!("docs.{}", site_name),
+                "safe://linked-from-<docs.site_name>?v=0",
+                true,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+
+        let _ = safe
+            .nrs_map_container_add(
+                &format!("*.docs.{}", site_name),
+                "safe://linked-from-wildcard?v=0",
+                false,
+                false,
+                NrsLinkOptions {
+                    capture_label: true,
+                    ..Default::default()
+                },
+                false,
+            )
+            .await?;
+        let (_, _, _, nrs_map) = safe
+            .nrs_map_container_add(
+                &format!("api.docs.{}", site_name),
+                "safe://linked-from-<api.docs.site_name>?v=0",
+                false,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+
+        // the label matched by the wildcard is captured as path of the link
+        let sub_names = vec!["pr-123".to_string(), "docs".to_string()];
+        assert_eq!(
+            nrs_map.resolve_for_subnames(&sub_names)?,
+            "safe://linked-from-wildcard/pr-123?v=0"
+        );
+
+        // exact sub names take precedence over the wildcard
+        let sub_names = vec!["api".to_string(), "docs".to_string()];
+        assert_eq!(
+            nrs_map.resolve_for_subnames(&sub_names)?,
+            "safe://linked-from-<api.docs.site_name>?v=0"
+        );
+        assert_eq!(
+            nrs_map.resolve_for_subnames(&["docs".to_string()])?,
+            "safe://linked-from-<docs.site_name>?v=0"
+        );
+
+        // the wildcard matches a single sub name only
+        let sub_names = vec!["a".to_string(), "pr-123".to_string(), "docs".to_string()];
+        if nrs_map.resolve_for_subnames(&sub_names).is_ok() {
+            return Err(Error::Unexpected(
+                "Wildcard unexpectedly matched more than one sub name".to_string(),
+            ));
+        }
+
+        match safe
+            .nrs_map_container_add(
+                &format!("pr-*.docs.{}", site_name),
+                "safe://linked-from-wildcard?v=0",
+                false,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await
+        {
+            Err(Error::InvalidInput(_)) => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Partial wildcard unexpectedly accepted: {:?}",
+                other
+            ))),
+        }
+    }
+
     #[tokio::test]
     async fn test_nrs_map_container_add_or_remove_with_versioned_target() -> Result<()> {
         let site_name = random_nrs_name();
@@ -434,6 +1007,7 @@ mod tests {
                 "safe://linked-from-<b.site_name>?v=0",
                 true,
                 false,
+                NrsLinkOptions::default(),
                 false,
             )
             .await?;
@@ -445,6 +1019,7 @@ mod tests {
                 "safe://linked-from-<a.b.site_name>?v=0",
                 true,
                 false,
+                NrsLinkOptions::default(),
                 false,
             )
             .await
@@ -493,6 +1068,7 @@ mod tests {
                 "safe://linked-from-<a.b.site_name>?v=0",
                 true,
                 false,
+                NrsLinkOptions::default(),
                 false,
             )
             .await?;
@@ -504,6 +1080,7 @@ mod tests {
                 "safe://linked-from-<a2.b.site_name>?v=0",
                 true,
                 false,
+                NrsLinkOptions::default(),
                 false,
             )
             .await?;
@@ -531,6 +1108,7 @@ mod tests {
                 "safe://linked-from-<a.b.site_name>?v=0",
                 true,
                 false,
+                NrsLinkOptions::default(),
                 false,
             )
             .await?;
@@ -572,6 +1150,7 @@ mod tests {
                 "safe://linked-from-<a.b.site_name>?v=0",
                 true,
                 true, // this sets the default to be a hard-link
+                NrsLinkOptions::default(),
                 false,
             )
             .await?;
@@ -590,6 +1169,180 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_nrs_transfer() -> Result<()> {
+        let site_name = random_nrs_name();
+        let mut safe = new_safe_instance().await?;
+        let _ = safe
+            .nrs_map_container_create(
+                &format!("a.{}", site_name),
+                "safe://linked-from-<a.site_name>?v=0",
+                true,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+        let owner = safe.safe_client.keypair().await?.public_key();
+        assert_eq!(safe.nrs_owner(&site_name).await?, owner);
+
+        // only the top name can be transferred
+        let new_owner = safe.generate_random_ed_keypair()?.public_key();
+        match safe
+            .nrs_transfer(&format!("a.{}", site_name), new_owner, false)
+            .await
+        {
+            Err(Error::InvalidInput(_)) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected result when transferring a sub name: {:?}",
+                    other
+                )))
+            }
+        }
+
+        // a dry run doesn't transfer it
+        safe.nrs_transfer(&site_name, new_owner, true).await?;
+        assert_eq!(safe.nrs_owner(&site_name).await?, owner);
+
+        safe.nrs_transfer(&site_name, new_owner, false).await?;
+        assert_eq!(safe.nrs_owner(&site_name).await?, new_owner);
+
+        // the previous owner cannot make any changes to it anymore
+        match safe
+            .nrs_map_container_add(
+                &format!("b.{}", site_name),
+                "safe://linked-from-<b.site_name>?v=0",
+                false,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await
+        {
+            Err(Error::AccessDenied(_)) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected result when adding to a transferred NRS name: {:?}",
+                    other
+                )))
+            }
+        }
+        match safe
+            .nrs_map_container_remove(&format!("a.{}", site_name), false)
+            .await
+        {
+            Err(Error::AccessDenied(_)) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected result when removing from a transferred NRS name: {:?}",
+                    other
+                )))
+            }
+        }
+        match safe.nrs_transfer(&site_name, owner, true).await {
+            Err(Error::AccessDenied(_)) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected result when dry-running a transfer of a transferred NRS name: {:?}",
+                    other
+                )))
+            }
+        }
+        match safe.nrs_transfer(&site_name, owner, false).await {
+            Err(Error::AccessDenied(_)) => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Unexpected result when transferring back a transferred NRS name: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_nrs_map_container_history() -> Result<()> {
+        let site_name = random_nrs_name();
+        let mut safe = new_safe_instance().await?;
+        let _ = safe
+            .nrs_map_container_create(
+                &format!("a.{}", site_name),
+                "safe://linked-from-<a.site_name>?v=0",
+                true,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+        let _ = safe
+            .nrs_map_container_add(
+                &format!("b.{}", site_name),
+                "safe://linked-from-<b.site_name>?v=0",
+                false,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+        let _ = safe
+            .nrs_map_container_add(
+                &format!("a.{}", site_name),
+                "safe://linked-from-<a.site_name>?v=1",
+                false,
+                false,
+                NrsLinkOptions::default(),
+                false,
+            )
+            .await?;
+        let _ = safe
+            .nrs_map_container_remove(&format!("b.{}", site_name), false)
+            .await?;
+
+        let history = safe.nrs_map_container_history(&site_name).await?;
+        let versions: Vec<u64> = history.iter().map(|(version, _)| *version).collect();
+        assert_eq!(versions, vec![0, 1, 2, 3]);
+
+        let a_name = format!("a.{}", site_name);
+        let b_name = format!("b.{}", site_name);
+        assert_eq!(
+            history[0].1.get(&a_name),
+            Some(&(
+                CONTENT_ADDED_SIGN.to_string(),
+                "safe://linked-from-<a.site_name>?v=0".to_string()
+            ))
+        );
+        // the top name is the default, set as an alias to the sub name
+        assert_eq!(
+            history[0]
+                .1
+                .get(&site_name)
+                .map(|(change, _)| change.as_str()),
+            Some(CONTENT_ADDED_SIGN)
+        );
+        assert_eq!(history[1].1.len(), 1);
+        assert_eq!(
+            history[1].1.get(&b_name),
+            Some(&(
+                CONTENT_ADDED_SIGN.to_string(),
+                "safe://linked-from-<b.site_name>?v=0".to_string()
+            ))
+        );
+        assert_eq!(
+            history[2].1.get(&a_name),
+            Some(&(
+                CONTENT_UPDATED_SIGN.to_string(),
+                "safe://linked-from-<a.site_name>?v=1".to_string()
+            ))
+        );
+        assert_eq!(history[3].1.len(), 1);
+        assert_eq!(
+            history[3].1.get(&b_name),
+            Some(&(
+                CONTENT_DELETED_SIGN.to_string(),
+                "safe://linked-from-<b.site_name>?v=0".to_string()
+            ))
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_nrs_no_scheme() -> Result<()> {
         let site_name = random_nrs_name();
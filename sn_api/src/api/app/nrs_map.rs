@@ -8,8 +8,12 @@
 // Software.
 
 use super::{
-    consts::{FAKE_RDF_PREDICATE_CREATED, FAKE_RDF_PREDICATE_LINK, FAKE_RDF_PREDICATE_MODIFIED},
+    consts::{
+        FAKE_RDF_PREDICATE_ALIAS, FAKE_RDF_PREDICATE_CAPTURE, FAKE_RDF_PREDICATE_CREATED,
+        FAKE_RDF_PREDICATE_LINK, FAKE_RDF_PREDICATE_LINK_POLICY, FAKE_RDF_PREDICATE_MODIFIED,
+    },
     helpers::gen_timestamp_secs,
+    idn::encode_public_name,
     Safe,
 };
 use crate::{
@@ -24,6 +28,82 @@ use std::{collections::BTreeMap, fmt, iter::FromIterator};
 type SubName = String;
 type DefinitionData = BTreeMap<String, String>;
 
+/// Sub name which matches any single label not mapped explicitly at the same level,
+/// e.g. `*.docs.mysite` resolves `pr-123.docs.mysite` unless `pr-123` is mapped itself
+pub const WILDCARD_SUB_NAME: &str = "*";
+
+// Value of the capture predicate of a wildcard's definition to pass the labels
+// matched by wildcards on as a path prefix of its link
+const CAPTURE_AS_PATH: &str = "path";
+
+// Value of the link policy predicate of a definition which follows the latest version
+const LINK_POLICY_FOLLOW_LATEST: &str = "follow_latest";
+
+/// How the version of the content linked from an NRS entry is selected when it's resolved
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NrsLinkPolicy {
+    /// The version set in the link when the entry was added
+    Pinned,
+    /// The latest version of the content at the time it's resolved
+    FollowLatest,
+}
+
+impl std::default::Default for NrsLinkPolicy {
+    fn default() -> Self {
+        NrsLinkPolicy::Pinned
+    }
+}
+
+impl fmt::Display for NrsLinkPolicy {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NrsLinkPolicy::Pinned => write!(fmt, "pinned"),
+            NrsLinkPolicy::FollowLatest => write!(fmt, "follow latest"),
+        }
+    }
+}
+
+/// Options of an NRS entry for how its link is resolved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NrsLinkOptions {
+    /// For a wildcard sub name, pass the labels it matched on as a path prefix of the link
+    pub capture_label: bool,
+    /// How the version of the content linked is selected when it's resolved
+    pub link_policy: NrsLinkPolicy,
+}
+
+/// Default of an NRS Map used to resolve a name without sub names
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NrsDefaultUsed {
+    /// The default is set to the existing entry of the sub name given
+    ExistingRdf(String),
+    /// The default has a definition of its own
+    OtherRdf,
+}
+
+impl fmt::Display for NrsDefaultUsed {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NrsDefaultUsed::ExistingRdf(sub_name) => {
+                write!(fmt, "default set to existing sub name \"{}\"", sub_name)
+            }
+            NrsDefaultUsed::OtherRdf => write!(fmt, "default with its own definition"),
+        }
+    }
+}
+
+/// Link resolved from an NRS Map, along with how the entry was found
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NrsResolution {
+    pub link: XorUrl,
+    pub link_policy: NrsLinkPolicy,
+    /// Sub name of the entry matched, e.g. `*.docs`, as opposed to the sub names resolved
+    pub matched_sub_name: Option<String>,
+    /// The default used, if the name was resolved without sub names
+    pub default: Option<NrsDefaultUsed>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub enum SubNameRDF {
     Definition(DefinitionData),
@@ -48,6 +128,60 @@ impl fmt::Display for SubNameRDF {
     }
 }
 
+/// A change to apply to an NrsMap, which can be applied along with others as a single
+/// new version of an NRS Map Container, e.g. the `changes` of a YAML file like:
+/// ```yaml
+/// name: mysite
+/// changes:
+///   - op: add
+///     name: docs.mysite
+///     link: "safe://hnyynyi...?v=0"
+///   - op: alias
+///     name: blog.mysite
+///     target: "safe://mysite/blog"
+///   - op: remove
+///     name: old.mysite
+///   - op: set_default
+///     name: docs.mysite
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum NrsMapChange {
+    /// Add a name, or update its link if it already exists
+    Add {
+        name: String,
+        link: String,
+        #[serde(default)]
+        default: bool,
+        #[serde(default)]
+        hard_link: bool,
+        #[serde(flatten)]
+        options: NrsLinkOptions,
+    },
+    /// Add a name, or update it if it already exists, as an alias of another NRS name
+    Alias {
+        name: String,
+        target: String,
+        #[serde(default)]
+        default: bool,
+    },
+    /// Remove a name
+    Remove { name: String },
+    /// Set an existing sub name as the default of the top name
+    SetDefault { name: String },
+}
+
+impl NrsMapChange {
+    pub fn name(&self) -> &str {
+        match self {
+            NrsMapChange::Add { name, .. }
+            | NrsMapChange::Alias { name, .. }
+            | NrsMapChange::Remove { name }
+            | NrsMapChange::SetDefault { name } => name,
+        }
+    }
+}
+
 // The default for a sub name can be unset (NotSet), reference to the same mapping as
 // another existing sub name (ExistingRdf), or just a different mapping (OtherRdf)
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
@@ -79,10 +213,28 @@ impl NrsMap {
     }
 
     pub fn resolve_for_subnames(&self, sub_names: &[SubName]) -> Result<XorUrl> {
+        let (link, _) = self.resolve_for_subnames_with_policy(sub_names)?;
+        Ok(link)
+    }
+
+    /// Resolve the link for the sub names along with the policy of the entry found
+    pub fn resolve_for_subnames_with_policy(
+        &self,
+        sub_names: &[SubName],
+    ) -> Result<(XorUrl, NrsLinkPolicy)> {
+        let resolution = self.resolve_entry(sub_names)?;
+        Ok((resolution.link, resolution.link_policy))
+    }
+
+    /// Resolve the link for the sub names, reporting which entry or default was used
+    pub fn resolve_entry(&self, sub_names: &[SubName]) -> Result<NrsResolution> {
         debug!("NRS: Attempting to resolve for subnames {:?}", sub_names);
         let mut nrs_map = self;
         let dereferenced_link: String;
         let sub_names_str = sub_names_vec_to_str(&sub_names);
+        let mut link_policy = NrsLinkPolicy::Pinned;
+        let mut default = None;
+        let mut matched_sub_name = None;
         let mut link = if sub_names.is_empty() {
             match &self.default {
                 DefaultRdf::OtherRdf(def_data) => {
@@ -90,11 +242,17 @@ impl NrsMap {
                         "NRS subname resolution done from default. Located: \"{:?}\"",
                         def_data
                     );
+                    link_policy = link_policy_of(def_data);
+                    default = Some(NrsDefaultUsed::OtherRdf);
                     def_data.get(FAKE_RDF_PREDICATE_LINK)
                 }
                 DefaultRdf::ExistingRdf(sub_name) => {
                     let sub_names = Vec::from_iter(sub_name.split('.').map(String::from));
-                    dereferenced_link = self.resolve_for_subnames(&sub_names)?;
+                    let existing = self.resolve_entry(&sub_names)?;
+                    dereferenced_link = existing.link;
+                    link_policy = existing.link_policy;
+                    matched_sub_name = existing.matched_sub_name;
+                    default = Some(NrsDefaultUsed::ExistingRdf(sub_name.clone()));
                     Some(&dereferenced_link)
                 }
                 DefaultRdf::NotSet => None,
@@ -103,15 +261,41 @@ impl NrsMap {
             None
         };
 
+        // Labels matched by wildcards, from the top name down, and
+        // whether the definition found asks for them to be captured
+        let mut captured_labels = Vec::new();
+        let mut capture_as_path = false;
+        // Labels of the entries matched, either the sub name itself or a wildcard
+        let mut matched_labels = Vec::new();
+
         let num_of_subnames = sub_names.len();
         for (i, curr_sub_name) in sub_names.iter().rev().enumerate() {
-            match nrs_map.sub_names_map.get(curr_sub_name) {
+            // exact sub names take precedence over a wildcard at the same level
+            let sub_name_rdf = match nrs_map.sub_names_map.get(curr_sub_name) {
+                Some(sub_name_rdf) => {
+                    matched_labels.push(curr_sub_name.as_str());
+                    Some(sub_name_rdf)
+                }
+                None => {
+                    let wildcard = nrs_map.sub_names_map.get(WILDCARD_SUB_NAME);
+                    if wildcard.is_some() {
+                        debug!("NRS sub name \"{}\" matched by wildcard", curr_sub_name);
+                        captured_labels.push(curr_sub_name.as_str());
+                        matched_labels.push(WILDCARD_SUB_NAME);
+                    }
+                    wildcard
+                }
+            };
+
+            match sub_name_rdf {
                 Some(SubNameRDF::SubName(nrs_sub_map)) => {
                     if nrs_sub_map.sub_names_map.is_empty() || i == num_of_subnames - 1 {
                         // we need default one then
                         if let DefaultRdf::OtherRdf(def_data) = &nrs_sub_map.default {
                             debug!("NRS subname resolution done. Located: \"{:?}\"", def_data);
                             link = def_data.get(FAKE_RDF_PREDICATE_LINK);
+                            capture_as_path = is_capture_as_path(def_data);
+                            link_policy = link_policy_of(def_data);
                         } else {
                             return Err(Error::ContentError(
                                 "Sub name not found in NRS Map Container".to_string(),
@@ -140,11 +324,37 @@ impl NrsMap {
             };
         }
 
+        if !matched_labels.is_empty() {
+            matched_labels.reverse();
+            matched_sub_name = Some(matched_labels.join("."));
+        }
+
         match link {
             Some(the_link) => {
-                // Let's make sure it's a versioned link
-                validate_nrs_link(the_link)?;
-                Ok(the_link.to_string())
+                // Let's make sure it's a versioned link, unless it follows the latest version
+                if link_policy == NrsLinkPolicy::Pinned {
+                    validate_nrs_link(the_link)?;
+                }
+                let link = if capture_as_path && !captured_labels.is_empty() {
+                    let mut link_encoder = Safe::parse_url(the_link)?;
+                    let path = format!(
+                        "{}/{}",
+                        link_encoder.path_decoded()?.trim_end_matches('/'),
+                        captured_labels.join("/")
+                    );
+                    link_encoder.set_path(&path);
+                    debug!("NRS captured labels {:?} as path", captured_labels);
+                    link_encoder.to_string()
+                } else {
+                    the_link.to_string()
+                };
+
+                Ok(NrsResolution {
+                    link,
+                    link_policy,
+                    matched_sub_name,
+                    default,
+                })
             }
             None => Err(Error::ContentError(format!(
                 "No link found for subname/s \"{}\"",
@@ -179,11 +389,34 @@ impl NrsMap {
         })?;
 
         debug!("Default link retrieved: \"{}\"", link);
-        // Let's make sure it's a versioned link
-        validate_nrs_link(link)?;
+        // Let's make sure it's a versioned link, unless it follows the latest version
+        match &self.default {
+            DefaultRdf::OtherRdf(def_data)
+                if link_policy_of(def_data) == NrsLinkPolicy::FollowLatest => {}
+            _ => validate_nrs_link(link)?,
+        }
         Ok(link.to_string())
     }
 
+    /// Set an existing sub name as the default of the top name, returning the link it resolves to
+    pub fn nrs_map_set_default(&mut self, name: &str) -> Result<String> {
+        info!("Setting sub name \"{}\" as default of NRS map", name);
+        let sub_names = parse_nrs_name(name)?;
+        if sub_names.is_empty() {
+            return Err(Error::InvalidInput(format!(
+                "Only a sub name can be set as default, \"{}\" is a top name",
+                name
+            )));
+        }
+        validate_wildcard_sub_names(&sub_names, true, false)?;
+
+        // the sub name has to be resolvable for the default to be
+        let link = self.resolve_for_subnames(&sub_names)?;
+        self.default = DefaultRdf::ExistingRdf(sub_names_vec_to_str(&sub_names));
+
+        Ok(link)
+    }
+
     pub fn nrs_map_remove_subname(&mut self, name: &str) -> Result<String> {
         info!("Removing sub name \"{}\" from NRS map", name);
         let sub_names = parse_nrs_name(name)?;
@@ -202,34 +435,60 @@ impl NrsMap {
         link: &str,
         default: bool,
         hard_link: bool,
+        options: NrsLinkOptions,
     ) -> Result<String> {
         info!("Updating NRS map for: {}", name);
 
-        // NRS resolver doesn't allow unversioned links
-        validate_nrs_link(link)?;
+        // NRS resolver doesn't allow unversioned links, unless they explicitly follow the latest
+        if options.link_policy == NrsLinkPolicy::FollowLatest {
+            validate_follow_latest_link(link)?;
+        } else {
+            validate_nrs_link(link)?;
+        }
 
         // Update NRS Map with new names
         let sub_names: Vec<String> = parse_nrs_name(name)?;
-        let updated_nrs_map = setup_nrs_tree(&self, sub_names.clone(), link)?;
-        self.sub_names_map = updated_nrs_map.sub_names_map;
-
-        // Set (top level) default if was requested
-        if default {
-            debug!("Setting {:?} as default for NrsMap", &name);
-            let definition_data = create_public_name_description(link)?;
-            if hard_link || sub_names.is_empty() {
-                self.default = DefaultRdf::OtherRdf(definition_data);
-            } else {
-                let sub_names_str = sub_names_vec_to_str(&sub_names);
-                self.default = DefaultRdf::ExistingRdf(sub_names_str);
-            }
-        } else {
-            self.default = updated_nrs_map.default;
+        validate_wildcard_sub_names(&sub_names, default, options.capture_label)?;
+        let mut definition_data = create_public_name_description(link)?;
+        if options.capture_label {
+            definition_data.insert(
+                FAKE_RDF_PREDICATE_CAPTURE.to_string(),
+                CAPTURE_AS_PATH.to_string(),
+            );
+        }
+        if options.link_policy == NrsLinkPolicy::FollowLatest {
+            definition_data.insert(
+                FAKE_RDF_PREDICATE_LINK_POLICY.to_string(),
+                LINK_POLICY_FOLLOW_LATEST.to_string(),
+            );
         }
+        self.update_definition(name, sub_names, definition_data, default, hard_link)?;
 
         Ok(link.to_string())
     }
 
+    /// Map a name to be an alias of another NRS name, i.e. resolved with the latest version
+    /// of the target's NRS Map Container. A path in the target URL is prefixed to the path
+    /// of the URLs being resolved, e.g. aliasing `blog.me` to `safe://me/blog`.
+    pub fn nrs_update_map_or_create_alias(
+        &mut self,
+        name: &str,
+        target: &str,
+        default: bool,
+    ) -> Result<String> {
+        info!("Updating NRS map for: {} as alias of {}", name, target);
+        let sanitised_name = str::replace(&name, "safe://", "");
+        validate_nrs_alias(&sanitised_name, target)?;
+
+        let sub_names: Vec<String> = parse_nrs_name(name)?;
+        validate_wildcard_sub_names(&sub_names, default, false)?;
+        let mut definition_data = create_public_name_description(target)?;
+        definition_data.insert(FAKE_RDF_PREDICATE_ALIAS.to_string(), "true".to_string());
+        self.update_definition(name, sub_names, definition_data, default, false)?;
+
+        Ok(target.to_string())
+    }
+
     pub fn get_link_for(&self, sub_name: &str) -> Result<XorUrl> {
         let the_entry = self.sub_names_map.get(sub_name);
 
@@ -256,6 +515,33 @@ impl NrsMap {
         gen_nrs_map_summary(&self, "", &mut nrs_map_summary);
         nrs_map_summary
     }
+
+    // Set the definition of a sub name, and the (top level) default if requested
+    fn update_definition(
+        &mut self,
+        name: &str,
+        sub_names: Vec<String>,
+        definition_data: DefinitionData,
+        default: bool,
+        hard_link: bool,
+    ) -> Result<()> {
+        let updated_nrs_map = setup_nrs_tree(&self, sub_names.clone(), &definition_data)?;
+        self.sub_names_map = updated_nrs_map.sub_names_map;
+
+        if default {
+            debug!("Setting {:?} as default for NrsMap", &name);
+            if hard_link || sub_names.is_empty() {
+                self.default = DefaultRdf::OtherRdf(definition_data);
+            } else {
+                let sub_names_str = sub_names_vec_to_str(&sub_names);
+                self.default = DefaultRdf::ExistingRdf(sub_names_str);
+            }
+        } else {
+            self.default = updated_nrs_map.default;
+        }
+
+        Ok(())
+    }
 }
 
 fn create_public_name_description(link: &str) -> Result<DefinitionData> {
@@ -287,8 +573,8 @@ fn sub_names_vec_to_str(sub_names: &[SubName]) -> String {
 }
 
 fn parse_nrs_name(name: &str) -> Result<Vec<String>> {
-    // santize to a simple string
-    let sanitized_name = str::replace(&name, "safe://", "");
+    // santize to a simple string, encoded the same way as in URLs if internationalised
+    let sanitized_name = encode_public_name(&str::replace(&name, "safe://", ""))?;
 
     let mut sub_names: Vec<String> = sanitized_name.split('.').map(String::from).collect();
     // get the TLD
@@ -299,6 +585,98 @@ fn parse_nrs_name(name: &str) -> Result<Vec<String>> {
     Ok(sub_names)
 }
 
+// A wildcard has to be a whole label, and only the labels it matches can be captured
+fn validate_wildcard_sub_names(
+    sub_names: &[String],
+    default: bool,
+    capture_label: bool,
+) -> Result<()> {
+    if sub_names
+        .iter()
+        .any(|sub_name| sub_name.contains(WILDCARD_SUB_NAME) && sub_name != WILDCARD_SUB_NAME)
+    {
+        return Err(Error::InvalidInput(format!(
+            "A wildcard '{}' can only be used as a whole sub name, e.g. '{}.mysite'",
+            WILDCARD_SUB_NAME, WILDCARD_SUB_NAME
+        )));
+    }
+
+    let has_wildcard = sub_names
+        .iter()
+        .any(|sub_name| sub_name == WILDCARD_SUB_NAME);
+    if has_wildcard && default {
+        return Err(Error::InvalidInput(
+            "A sub name with a wildcard cannot be set as the default".to_string(),
+        ));
+    }
+    if capture_label && !has_wildcard {
+        return Err(Error::InvalidInput(
+            "Only the labels matched by a wildcard sub name can be captured".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+// An alias has to target an NRS name other than itself, and without a version,
+// otherwise it's just a link to a version of the target's NRS Map Container
+fn validate_nrs_alias(name: &str, target: &str) -> Result<()> {
+    let target_encoder = Safe::parse_url(target)?;
+    if !target_encoder.is_nrsurl() {
+        return Err(Error::InvalidInput(format!(
+            "The target of an NRS alias must be an NRS-URL: \"{}\"",
+            target
+        )));
+    }
+    if target_encoder.content_version().is_some() {
+        return Err(Error::InvalidInput(format!(
+            "The target of an NRS alias cannot contain a version, it's always resolved with the latest version of its NRS Map Container: \"{}\"",
+            target
+        )));
+    }
+    if target_encoder.public_name() == name {
+        return Err(Error::InvalidInput(format!(
+            "The NRS name \"{}\" cannot be an alias of itself",
+            name
+        )));
+    }
+    Ok(())
+}
+
+// Aliases are always resolved with the latest version of the target's NRS Map Container
+fn link_policy_of(def_data: &DefinitionData) -> NrsLinkPolicy {
+    let is_alias = def_data
+        .get(FAKE_RDF_PREDICATE_ALIAS)
+        .map_or(false, |alias| alias == "true");
+    let follows_latest = def_data
+        .get(FAKE_RDF_PREDICATE_LINK_POLICY)
+        .map_or(false, |policy| policy == LINK_POLICY_FOLLOW_LATEST);
+
+    if is_alias || follows_latest {
+        NrsLinkPolicy::FollowLatest
+    } else {
+        NrsLinkPolicy::Pinned
+    }
+}
+
+// A link following the latest version cannot be pinned to a version at the same time
+fn validate_follow_latest_link(link: &str) -> Result<()> {
+    let link_encoder = Safe::parse_url(link)?;
+    if link_encoder.content_version().is_some() {
+        return Err(Error::InvalidInput(format!(
+            "The link cannot specify a version as it's set to follow the latest version of the content: \"{}\"",
+            link
+        )));
+    }
+    Ok(())
+}
+
+fn is_capture_as_path(def_data: &DefinitionData) -> bool {
+    def_data
+        .get(FAKE_RDF_PREDICATE_CAPTURE)
+        .map_or(false, |capture| capture == CAPTURE_AS_PATH)
+}
+
 fn validate_nrs_link(link: &str) -> Result<()> {
     let link_encoder = Safe::parse_url(link)?;
     if link_encoder.content_version().is_none() {
@@ -325,11 +703,14 @@ fn validate_nrs_link(link: &str) -> Result<()> {
     Ok(())
 }
 
-fn setup_nrs_tree(nrs_map: &NrsMap, mut sub_names: Vec<String>, link: &str) -> Result<NrsMap> {
+fn setup_nrs_tree(
+    nrs_map: &NrsMap,
+    mut sub_names: Vec<String>,
+    definition_data: &DefinitionData,
+) -> Result<NrsMap> {
     let mut updated_nrs_map = nrs_map.clone();
     let curr_sub_name = if sub_names.is_empty() {
-        let definition_data = create_public_name_description(link)?;
-        updated_nrs_map.default = DefaultRdf::OtherRdf(definition_data);
+        updated_nrs_map.default = DefaultRdf::OtherRdf(definition_data.clone());
         return Ok(updated_nrs_map);
     } else {
         sub_names
@@ -339,7 +720,7 @@ fn setup_nrs_tree(nrs_map: &NrsMap, mut sub_names: Vec<String>, link: &str) -> R
 
     match nrs_map.sub_names_map.get(&curr_sub_name) {
         Some(SubNameRDF::SubName(nrs_sub_map)) => {
-            let updated_sub_map = setup_nrs_tree(nrs_sub_map, sub_names, link)?;
+            let updated_sub_map = setup_nrs_tree(nrs_sub_map, sub_names, definition_data)?;
             updated_nrs_map
                 .sub_names_map
                 .insert(curr_sub_name, SubNameRDF::SubName(updated_sub_map));
@@ -349,7 +730,7 @@ fn setup_nrs_tree(nrs_map: &NrsMap, mut sub_names: Vec<String>, link: &str) -> R
             // we need to add the new sub nrs tree but as a sibling
             let mut new_nrs_map = NrsMap::default();
             new_nrs_map.default = DefaultRdf::OtherRdf(def_data.clone());
-            let updated_new_nrs_map = setup_nrs_tree(&new_nrs_map, sub_names, link)?;
+            let updated_new_nrs_map = setup_nrs_tree(&new_nrs_map, sub_names, definition_data)?;
             updated_nrs_map
                 .sub_names_map
                 .insert(curr_sub_name, SubNameRDF::SubName(updated_new_nrs_map));
@@ -359,7 +740,7 @@ fn setup_nrs_tree(nrs_map: &NrsMap, mut sub_names: Vec<String>, link: &str) -> R
             // Sub name not found in NRS Map Container
             // we need to add the new sub nrs tree
             let new_nrs_map = NrsMap::default();
-            let updated_new_nrs_map = setup_nrs_tree(&new_nrs_map, sub_names, link)?;
+            let updated_new_nrs_map = setup_nrs_tree(&new_nrs_map, sub_names, definition_data)?;
             updated_nrs_map
                 .sub_names_map
                 .insert(curr_sub_name, SubNameRDF::SubName(updated_new_nrs_map));
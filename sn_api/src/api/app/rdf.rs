@@ -0,0 +1,685 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! RDF representation of FilesMaps and NrsMaps.
+//!
+//! The maps are stored on the network as JSON-LD documents, in the compacted form generated by
+//! this module, and they can also be rendered as Turtle. Each document carries the version of
+//! the schema it was generated with, and readers reject documents with a newer version.
+//!
+//! Readers still accept the plain JSON maps stored before the RDF schema was introduced, but
+//! versions prior to it cannot read the JSON-LD documents. Maps can still be stored as plain
+//! JSON, see `Safe::set_legacy_maps_format`, while they need to be read by such versions.
+//!
+//! The terms are mapped to well-known predicates where there is one, i.e. from Dublin Core and
+//! schema.org, and to the SAFE namespace otherwise.
+
+use super::{
+    consts::{
+        FAKE_RDF_PREDICATE_ALIAS, FAKE_RDF_PREDICATE_CAPTURE, FAKE_RDF_PREDICATE_CREATED,
+        FAKE_RDF_PREDICATE_LINK, FAKE_RDF_PREDICATE_LINK_POLICY, FAKE_RDF_PREDICATE_MODE_BITS,
+        FAKE_RDF_PREDICATE_MODIFIED, FAKE_RDF_PREDICATE_ORIGINAL_CREATED,
+        FAKE_RDF_PREDICATE_ORIGINAL_MODIFIED, FAKE_RDF_PREDICATE_READONLY, FAKE_RDF_PREDICATE_SIZE,
+        FAKE_RDF_PREDICATE_TYPE,
+    },
+    files::{FileItem, FilesMap},
+    nrs_map::{DefaultRdf, NrsMap, SubNameRDF},
+};
+use crate::{Error, Result};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+/// Namespace of the terms of the RDF schema
+pub const SAFE_RDF_NAMESPACE: &str = "https://safenetwork.tech/ns#";
+
+/// Namespace of the Dublin Core terms used by the RDF schema
+pub const DCTERMS_NAMESPACE: &str = "http://purl.org/dc/terms/";
+
+/// Namespace of the schema.org terms used by the RDF schema
+pub const SCHEMA_ORG_NAMESPACE: &str = "http://schema.org/";
+
+/// Version of the RDF schema generated, readers reject maps with a newer version
+pub const RDF_SCHEMA_VERSION: u64 = 1;
+
+const FILES_MAP_TYPE: &str = "FilesMap";
+const FILE_ITEM_TYPE: &str = "FileItem";
+const NRS_MAP_TYPE: &str = "NrsMap";
+const SUB_NAME_TYPE: &str = "SubName";
+
+const TERM_SCHEMA_VERSION: &str = "schemaVersion";
+const TERM_FILES: &str = "files";
+const TERM_PATH: &str = "path";
+// The 'type' of a FileItem is its media type, not to be confused with its RDF type
+const TERM_MEDIA_TYPE: &str = "mediaType";
+const TERM_SUB_NAMES: &str = "subNames";
+const TERM_NAME: &str = "name";
+const TERM_DEFINITION: &str = "definition";
+const TERM_NRS_MAP: &str = "nrsMap";
+const TERM_DEFAULT: &str = "default";
+const TERM_EXISTING_SUB_NAME: &str = "existingSubName";
+
+// Prefixes of the namespaces, in the JSON-LD context and in Turtle
+const SAFE_PREFIX: &str = "safe";
+const DCTERMS_PREFIX: &str = "dcterms";
+const SCHEMA_ORG_PREFIX: &str = "schema";
+
+// Predicate of each term of FileItems and NRS definitions, as prefixed names. Terms not
+// listed here, e.g. the names of the sub names, are in the SAFE namespace with the same name.
+const TERM_PREDICATES: &[(&str, &str)] = &[
+    (FAKE_RDF_PREDICATE_LINK, "schema:url"),
+    (TERM_MEDIA_TYPE, "dcterms:format"),
+    (FAKE_RDF_PREDICATE_SIZE, "schema:contentSize"),
+    (FAKE_RDF_PREDICATE_CREATED, "dcterms:created"),
+    (FAKE_RDF_PREDICATE_MODIFIED, "dcterms:modified"),
+    (FAKE_RDF_PREDICATE_ORIGINAL_CREATED, "safe:originalCreated"),
+    (
+        FAKE_RDF_PREDICATE_ORIGINAL_MODIFIED,
+        "safe:originalModified",
+    ),
+    (FAKE_RDF_PREDICATE_READONLY, "safe:readonly"),
+    (FAKE_RDF_PREDICATE_MODE_BITS, "safe:modeBits"),
+    (FAKE_RDF_PREDICATE_CAPTURE, "safe:capture"),
+    (FAKE_RDF_PREDICATE_ALIAS, "safe:alias"),
+    (FAKE_RDF_PREDICATE_LINK_POLICY, "safe:linkPolicy"),
+    ("symlink_target", "safe:symlinkTarget"),
+    ("symlink_target_type", "safe:symlinkTargetType"),
+];
+
+/// Generate the JSON-LD document of a FilesMap, optionally with the URL
+/// of the FilesContainer it belongs to as the document's id
+pub fn files_map_to_jsonld(files_map: &FilesMap, id: Option<&str>) -> Value {
+    let files: Vec<Value> = files_map
+        .iter()
+        .map(|(path, file_item)| {
+            let mut node = Map::new();
+            let _ = node.insert("@type".to_string(), json!(FILE_ITEM_TYPE));
+            let _ = node.insert(TERM_PATH.to_string(), json!(path));
+            for (key, value) in file_item.iter() {
+                let term = if key == FAKE_RDF_PREDICATE_TYPE {
+                    TERM_MEDIA_TYPE
+                } else {
+                    key
+                };
+                let _ = node.insert(term.to_string(), json!(value));
+            }
+            Value::Object(node)
+        })
+        .collect();
+
+    let mut jsonld = jsonld_document(FILES_MAP_TYPE, id);
+    jsonld[TERM_FILES] = Value::Array(files);
+    jsonld
+}
+
+/// Read a FilesMap from its JSON-LD document, only the compacted form
+/// generated by `files_map_to_jsonld` is supported
+pub fn files_map_from_jsonld(jsonld: &Value) -> Result<FilesMap> {
+    check_document(jsonld, FILES_MAP_TYPE)?;
+
+    let mut files_map = FilesMap::new();
+    for node in values(jsonld.get(TERM_FILES)) {
+        let node = node
+            .as_object()
+            .ok_or_else(|| invalid_content(FILES_MAP_TYPE, "a file item is not an object"))?;
+        let path = node
+            .get(TERM_PATH)
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_content(FILES_MAP_TYPE, "a file item has no path"))?;
+
+        let mut file_item = FileItem::new();
+        for (term, value) in node.iter() {
+            if term.starts_with('@') || term == TERM_PATH {
+                continue;
+            }
+            let value = value.as_str().ok_or_else(|| {
+                invalid_content(
+                    FILES_MAP_TYPE,
+                    &format!("value of '{}' of file '{}' is not a string", term, path),
+                )
+            })?;
+            let key = if term == TERM_MEDIA_TYPE {
+                FAKE_RDF_PREDICATE_TYPE
+            } else {
+                term
+            };
+            let _ = file_item.insert(key.to_string(), value.to_string());
+        }
+        let _ = files_map.insert(path.to_string(), file_item);
+    }
+
+    Ok(files_map)
+}
+
+/// Generate the Turtle document of a FilesMap, optionally with the URL
+/// of the FilesContainer it belongs to as the subject
+pub fn files_map_to_turtle(files_map: &FilesMap, id: Option<&str>) -> String {
+    jsonld_to_turtle(&files_map_to_jsonld(files_map, id))
+}
+
+/// Generate the JSON-LD document of an NrsMap, optionally with the URL
+/// of the NRS Map Container it belongs to as the document's id
+pub fn nrs_map_to_jsonld(nrs_map: &NrsMap, id: Option<&str>) -> Value {
+    let mut jsonld = jsonld_document(NRS_MAP_TYPE, id);
+    if let (Value::Object(document), Value::Object(node)) = (&mut jsonld, nrs_map_node(nrs_map)) {
+        document.extend(node.into_iter().filter(|(term, _)| term != "@type"));
+    }
+    jsonld
+}
+
+/// Read an NrsMap from its JSON-LD document, only the compacted form
+/// generated by `nrs_map_to_jsonld` is supported
+pub fn nrs_map_from_jsonld(jsonld: &Value) -> Result<NrsMap> {
+    check_document(jsonld, NRS_MAP_TYPE)?;
+    nrs_map_from_node(jsonld)
+}
+
+/// Generate the Turtle document of an NrsMap, optionally with the URL
+/// of the NRS Map Container it belongs to as the subject
+pub fn nrs_map_to_turtle(nrs_map: &NrsMap, id: Option<&str>) -> String {
+    jsonld_to_turtle(&nrs_map_to_jsonld(nrs_map, id))
+}
+
+// Serialise a FilesMap as it's stored on the network, as JSON-LD unless the legacy
+// plain JSON format is requested
+pub(crate) fn encode_files_map(files_map: &FilesMap, legacy_format: bool) -> Result<Vec<u8>> {
+    let result = if legacy_format {
+        serde_json::to_vec(files_map)
+    } else {
+        serde_json::to_vec(&files_map_to_jsonld(files_map, None))
+    };
+    result.map_err(|err| {
+        Error::Unexpected(format!(
+            "Couldn't serialise the FilesMap generated: {:?}",
+            err
+        ))
+    })
+}
+
+// Deserialise a FilesMap stored on the network, either as JSON-LD or as plain JSON
+pub(crate) fn decode_files_map(serialised: &[u8]) -> Result<FilesMap> {
+    let value: Value = serde_json::from_slice(serialised).map_err(|err| {
+        Error::ContentError(format!(
+            "Couldn't deserialise the FilesMap stored in the FilesContainer: {:?}",
+            err
+        ))
+    })?;
+
+    if is_jsonld(&value) {
+        files_map_from_jsonld(&value)
+    } else {
+        serde_json::from_value(value).map_err(|err| {
+            Error::ContentError(format!(
+                "Couldn't deserialise the FilesMap stored in the FilesContainer: {:?}",
+                err
+            ))
+        })
+    }
+}
+
+// Serialise an NrsMap as it's stored on the network, as JSON-LD unless the legacy
+// plain JSON format is requested
+pub(crate) fn encode_nrs_map(nrs_map: &NrsMap, legacy_format: bool) -> Result<Vec<u8>> {
+    let result = if legacy_format {
+        serde_json::to_vec(nrs_map)
+    } else {
+        serde_json::to_vec(&nrs_map_to_jsonld(nrs_map, None))
+    };
+    result.map_err(|err| {
+        Error::Unexpected(format!(
+            "Couldn't serialise the NrsMap generated: {:?}",
+            err
+        ))
+    })
+}
+
+// Deserialise an NrsMap stored on the network, either as JSON-LD or as plain JSON
+pub(crate) fn decode_nrs_map(serialised: &[u8]) -> Result<NrsMap> {
+    let value: Value = serde_json::from_slice(serialised).map_err(|err| {
+        Error::ContentError(format!(
+            "Couldn't deserialise the NrsMap stored in the NrsContainer: {:?}",
+            err
+        ))
+    })?;
+
+    if is_jsonld(&value) {
+        nrs_map_from_jsonld(&value)
+    } else {
+        serde_json::from_value(value).map_err(|err| {
+            Error::ContentError(format!(
+                "Couldn't deserialise the NrsMap stored in the NrsContainer: {:?}",
+                err
+            ))
+        })
+    }
+}
+
+// Maps stored before the RDF schema was introduced have no context
+fn is_jsonld(value: &Value) -> bool {
+    value.get("@context").is_some()
+}
+
+// The context maps the terms to their predicates, or to the schema's namespace,
+// and the links to IRIs
+fn jsonld_document(rdf_type: &str, id: Option<&str>) -> Value {
+    let mut context = json!({
+        "@vocab": SAFE_RDF_NAMESPACE,
+        SAFE_PREFIX: SAFE_RDF_NAMESPACE,
+        DCTERMS_PREFIX: DCTERMS_NAMESPACE,
+        SCHEMA_ORG_PREFIX: SCHEMA_ORG_NAMESPACE,
+    });
+    for (term, predicate) in TERM_PREDICATES.iter() {
+        context[*term] = if *term == FAKE_RDF_PREDICATE_LINK {
+            json!({ "@id": predicate, "@type": "@id" })
+        } else {
+            json!(predicate)
+        };
+    }
+
+    let mut document = json!({
+        "@context": context,
+        "@type": rdf_type,
+        TERM_SCHEMA_VERSION: RDF_SCHEMA_VERSION,
+    });
+    if let Some(id) = id {
+        document["@id"] = json!(id);
+    }
+    document
+}
+
+fn check_document(jsonld: &Value, rdf_type: &str) -> Result<()> {
+    match jsonld.get("@type").and_then(Value::as_str) {
+        Some(found) if found == rdf_type => {}
+        other => {
+            return Err(invalid_content(
+                rdf_type,
+                &format!("unexpected type {:?}", other),
+            ))
+        }
+    }
+
+    match jsonld.get(TERM_SCHEMA_VERSION).and_then(Value::as_u64) {
+        Some(version) if version <= RDF_SCHEMA_VERSION => Ok(()),
+        Some(version) => Err(Error::ContentError(format!(
+            "The {} uses version {} of the RDF schema, but only up to version {} is supported, please upgrade to a newer version",
+            rdf_type, version, RDF_SCHEMA_VERSION
+        ))),
+        None => Err(invalid_content(rdf_type, "the schema version is missing")),
+    }
+}
+
+fn invalid_content(rdf_type: &str, reason: &str) -> Error {
+    Error::ContentError(format!("Invalid {} JSON-LD document: {}", rdf_type, reason))
+}
+
+// JSON-LD allows a single value in place of an array with a single element
+fn values(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        None => vec![],
+        Some(Value::Array(values)) => values.iter().collect(),
+        Some(value) => vec![value],
+    }
+}
+
+fn nrs_map_node(nrs_map: &NrsMap) -> Value {
+    let sub_names: Vec<Value> = nrs_map
+        .sub_names_map
+        .iter()
+        .map(|(name, sub_name_rdf)| {
+            let mut node = json!({ "@type": SUB_NAME_TYPE, TERM_NAME: name });
+            match sub_name_rdf {
+                SubNameRDF::Definition(def_data) => {
+                    node[TERM_DEFINITION] = definition_node(def_data)
+                }
+                SubNameRDF::SubName(sub_nrs_map) => node[TERM_NRS_MAP] = nrs_map_node(sub_nrs_map),
+            }
+            node
+        })
+        .collect();
+
+    let mut node = json!({ "@type": NRS_MAP_TYPE, TERM_SUB_NAMES: sub_names });
+    match &nrs_map.default {
+        DefaultRdf::NotSet => {}
+        DefaultRdf::ExistingRdf(sub_name) => {
+            node[TERM_DEFAULT] = json!({ TERM_EXISTING_SUB_NAME: sub_name })
+        }
+        DefaultRdf::OtherRdf(def_data) => {
+            node[TERM_DEFAULT] = json!({ TERM_DEFINITION: definition_node(def_data) })
+        }
+    }
+    node
+}
+
+fn definition_node(def_data: &BTreeMap<String, String>) -> Value {
+    Value::Object(
+        def_data
+            .iter()
+            .map(|(key, value)| (key.clone(), json!(value)))
+            .collect(),
+    )
+}
+
+fn nrs_map_from_node(node: &Value) -> Result<NrsMap> {
+    let mut nrs_map = NrsMap::default();
+    for sub_name_node in values(node.get(TERM_SUB_NAMES)) {
+        let name = sub_name_node
+            .get(TERM_NAME)
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_content(NRS_MAP_TYPE, "a sub name has no name"))?;
+        let sub_name_rdf = match (
+            sub_name_node.get(TERM_DEFINITION),
+            sub_name_node.get(TERM_NRS_MAP),
+        ) {
+            (Some(definition), None) => SubNameRDF::Definition(definition_from_node(definition)?),
+            (None, Some(sub_nrs_map)) => SubNameRDF::SubName(nrs_map_from_node(sub_nrs_map)?),
+            _ => {
+                return Err(invalid_content(
+                    NRS_MAP_TYPE,
+                    &format!(
+                        "sub name '{}' must have either a definition or an NRS map",
+                        name
+                    ),
+                ))
+            }
+        };
+        let _ = nrs_map.sub_names_map.insert(name.to_string(), sub_name_rdf);
+    }
+
+    nrs_map.default = match node.get(TERM_DEFAULT) {
+        None => DefaultRdf::NotSet,
+        Some(default) => match (
+            default.get(TERM_EXISTING_SUB_NAME).and_then(Value::as_str),
+            default.get(TERM_DEFINITION),
+        ) {
+            (Some(sub_name), None) => DefaultRdf::ExistingRdf(sub_name.to_string()),
+            (None, Some(definition)) => DefaultRdf::OtherRdf(definition_from_node(definition)?),
+            _ => {
+                return Err(invalid_content(
+                    NRS_MAP_TYPE,
+                    "the default must have either an existing sub name or a definition",
+                ))
+            }
+        },
+    };
+
+    Ok(nrs_map)
+}
+
+fn definition_from_node(node: &Value) -> Result<BTreeMap<String, String>> {
+    let node = node
+        .as_object()
+        .ok_or_else(|| invalid_content(NRS_MAP_TYPE, "a definition is not an object"))?;
+
+    node.iter()
+        .filter(|(term, _)| !term.starts_with('@'))
+        .map(|(term, value)| match value.as_str() {
+            Some(value) => Ok((term.clone(), value.to_string())),
+            None => Err(invalid_content(
+                NRS_MAP_TYPE,
+                &format!("value of '{}' of a definition is not a string", term),
+            )),
+        })
+        .collect()
+}
+
+// Render the JSON-LD documents generated by this module as Turtle,
+// nested objects are rendered as blank nodes
+fn jsonld_to_turtle(jsonld: &Value) -> String {
+    let subject = jsonld
+        .get("@id")
+        .and_then(Value::as_str)
+        .map_or_else(|| "[]".to_string(), turtle_iri);
+
+    format!(
+        "@prefix {}: <{}> .\n@prefix {}: <{}> .\n@prefix {}: <{}> .\n\n{}{} .\n",
+        SAFE_PREFIX,
+        SAFE_RDF_NAMESPACE,
+        DCTERMS_PREFIX,
+        DCTERMS_NAMESPACE,
+        SCHEMA_ORG_PREFIX,
+        SCHEMA_ORG_NAMESPACE,
+        subject,
+        turtle_predicates(jsonld, 1)
+    )
+}
+
+fn turtle_predicates(node: &Value, depth: usize) -> String {
+    let mut statements = vec![];
+    if let Some(rdf_type) = node.get("@type").and_then(Value::as_str) {
+        statements.push(format!("a {}", turtle_term(rdf_type)));
+    }
+    if let Some(node) = node.as_object() {
+        for (term, value) in node.iter().filter(|(term, _)| !term.starts_with('@')) {
+            let objects: Vec<String> = values(Some(value))
+                .into_iter()
+                .map(|value| turtle_object(term, value, depth))
+                .collect();
+            if !objects.is_empty() {
+                statements.push(format!("{} {}", turtle_term(term), objects.join(" , ")));
+            }
+        }
+    }
+
+    let indent = "    ".repeat(depth);
+    statements
+        .iter()
+        .map(|statement| format!("\n{}{}", indent, statement))
+        .collect::<Vec<String>>()
+        .join(" ;")
+}
+
+fn turtle_object(term: &str, value: &Value, depth: usize) -> String {
+    match value {
+        Value::String(string) if term == FAKE_RDF_PREDICATE_LINK => turtle_iri(string),
+        Value::String(string) => turtle_literal(string),
+        Value::Object(_) => format!(
+            "[{}\n{}]",
+            turtle_predicates(value, depth + 1),
+            "    ".repeat(depth)
+        ),
+        other => other.to_string(),
+    }
+}
+
+// Terms of the schema are prefixed names, unless they contain characters not allowed in them
+fn turtle_term(term: &str) -> String {
+    if let Some((_, predicate)) = TERM_PREDICATES.iter().find(|(known, _)| *known == term) {
+        return predicate.to_string();
+    }
+
+    let mut chars = term.chars();
+    let is_prefixable = chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if is_prefixable {
+        format!("safe:{}", term)
+    } else {
+        turtle_iri(&format!("{}{}", SAFE_RDF_NAMESPACE, term))
+    }
+}
+
+// Characters not allowed in IRIs are percent-encoded
+fn turtle_iri(iri: &str) -> String {
+    let mut turtle = String::with_capacity(iri.len() + 2);
+    turtle.push('<');
+    for c in iri.chars() {
+        if c <= ' ' || "<>\"{}|^`\\".contains(c) {
+            let mut buffer = [0; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                turtle.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            turtle.push(c);
+        }
+    }
+    turtle.push('>');
+    turtle
+}
+
+fn turtle_literal(literal: &str) -> String {
+    let mut turtle = String::with_capacity(literal.len() + 2);
+    turtle.push('"');
+    for c in literal.chars() {
+        match c {
+            '"' => turtle.push_str("\\\""),
+            '\\' => turtle.push_str("\\\\"),
+            '\n' => turtle.push_str("\\n"),
+            '\r' => turtle.push_str("\\r"),
+            '\t' => turtle.push_str("\\t"),
+            c => turtle.push(c),
+        }
+    }
+    turtle.push('"');
+    turtle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_files_map() -> FilesMap {
+        let mut file_item = FileItem::new();
+        let _ = file_item.insert(
+            FAKE_RDF_PREDICATE_LINK.to_string(),
+            "safe://hbyyyyd1k4s".to_string(),
+        );
+        let _ = file_item.insert(
+            FAKE_RDF_PREDICATE_TYPE.to_string(),
+            "text/markdown".to_string(),
+        );
+        let _ = file_item.insert(FAKE_RDF_PREDICATE_SIZE.to_string(), "12".to_string());
+        let _ = file_item.insert(
+            FAKE_RDF_PREDICATE_CREATED.to_string(),
+            "2020-10-16T10:20:30Z".to_string(),
+        );
+        let mut dir_item = FileItem::new();
+        let _ = dir_item.insert(
+            FAKE_RDF_PREDICATE_TYPE.to_string(),
+            "inode/directory".to_string(),
+        );
+
+        let mut files_map = FilesMap::new();
+        let _ = files_map.insert("/test \"quoted\".md".to_string(), file_item);
+        let _ = files_map.insert("/emptyfolder".to_string(), dir_item);
+        files_map
+    }
+
+    fn test_nrs_map() -> NrsMap {
+        let mut def_data = BTreeMap::new();
+        let _ = def_data.insert(
+            FAKE_RDF_PREDICATE_LINK.to_string(),
+            "safe://hnyynyw4gsy3i6hnbna?v=0".to_string(),
+        );
+        let _ = def_data.insert(
+            FAKE_RDF_PREDICATE_CREATED.to_string(),
+            "1602843630".to_string(),
+        );
+
+        let mut sub_nrs_map = NrsMap::default();
+        let _ = sub_nrs_map
+            .sub_names_map
+            .insert("b".to_string(), SubNameRDF::Definition(def_data.clone()));
+        sub_nrs_map.default = DefaultRdf::ExistingRdf("b".to_string());
+
+        let mut nrs_map = NrsMap::default();
+        let _ = nrs_map
+            .sub_names_map
+            .insert("a".to_string(), SubNameRDF::SubName(sub_nrs_map));
+        nrs_map.default = DefaultRdf::OtherRdf(def_data);
+        nrs_map
+    }
+
+    #[test]
+    fn test_rdf_files_map_jsonld() -> Result<()> {
+        let files_map = test_files_map();
+        let jsonld = files_map_to_jsonld(&files_map, Some("safe://hnyynyzonskbrgd57kt8c1?v=0"));
+        assert_eq!(jsonld["@type"], json!(FILES_MAP_TYPE));
+        assert_eq!(jsonld[TERM_SCHEMA_VERSION], json!(RDF_SCHEMA_VERSION));
+        assert_eq!(jsonld["@id"], json!("safe://hnyynyzonskbrgd57kt8c1?v=0"));
+        assert_eq!(
+            jsonld[TERM_FILES][1][TERM_MEDIA_TYPE],
+            json!("text/markdown")
+        );
+
+        assert_eq!(jsonld["@context"][TERM_MEDIA_TYPE], json!("dcterms:format"));
+        assert_eq!(
+            jsonld["@context"][FAKE_RDF_PREDICATE_LINK],
+            json!({ "@id": "schema:url", "@type": "@id" })
+        );
+
+        assert_eq!(files_map_from_jsonld(&jsonld)?, files_map);
+        assert_eq!(
+            decode_files_map(&encode_files_map(&files_map, false)?)?,
+            files_map
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rdf_nrs_map_jsonld() -> Result<()> {
+        let nrs_map = test_nrs_map();
+        let jsonld = nrs_map_to_jsonld(&nrs_map, None);
+        assert_eq!(jsonld["@type"], json!(NRS_MAP_TYPE));
+        assert_eq!(jsonld[TERM_SUB_NAMES][0][TERM_NAME], json!("a"));
+        assert_eq!(
+            jsonld[TERM_SUB_NAMES][0][TERM_NRS_MAP][TERM_DEFAULT][TERM_EXISTING_SUB_NAME],
+            json!("b")
+        );
+
+        assert_eq!(nrs_map_from_jsonld(&jsonld)?, nrs_map);
+        assert_eq!(decode_nrs_map(&encode_nrs_map(&nrs_map, false)?)?, nrs_map);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rdf_decode_legacy_json() -> Result<()> {
+        let files_map = test_files_map();
+        let legacy = serde_json::to_vec(&files_map)
+            .map_err(|err| Error::Unexpected(format!("Failed to serialise: {}", err)))?;
+        assert_eq!(decode_files_map(&legacy)?, files_map);
+        // the legacy format is still generated if requested
+        assert_eq!(encode_files_map(&files_map, true)?, legacy);
+
+        let nrs_map = test_nrs_map();
+        let legacy = serde_json::to_vec(&nrs_map)
+            .map_err(|err| Error::Unexpected(format!("Failed to serialise: {}", err)))?;
+        assert_eq!(decode_nrs_map(&legacy)?, nrs_map);
+        assert_eq!(encode_nrs_map(&nrs_map, true)?, legacy);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rdf_newer_schema_version() -> Result<()> {
+        let mut jsonld = files_map_to_jsonld(&test_files_map(), None);
+        jsonld[TERM_SCHEMA_VERSION] = json!(RDF_SCHEMA_VERSION + 1);
+        match files_map_from_jsonld(&jsonld) {
+            Err(Error::ContentError(msg)) if msg.contains("upgrade") => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Unexpected result when reading a newer schema version: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[test]
+    fn test_rdf_turtle() {
+        let turtle = files_map_to_turtle(&test_files_map(), Some("safe://hnyynyzonskbrgd57kt8c1"));
+        assert!(turtle.starts_with(&format!("@prefix safe: <{}> .", SAFE_RDF_NAMESPACE)));
+        assert!(turtle.contains(&format!("@prefix dcterms: <{}> .", DCTERMS_NAMESPACE)));
+        assert!(turtle.contains("<safe://hnyynyzonskbrgd57kt8c1>\n    a safe:FilesMap ;"));
+        assert!(turtle.contains("schema:url <safe://hbyyyyd1k4s> ;"));
+        assert!(turtle.contains("dcterms:format \"text/markdown\""));
+        assert!(turtle.contains("schema:contentSize \"12\""));
+        assert!(turtle.contains("safe:path \"/test \\\"quoted\\\".md\""));
+        assert!(turtle.ends_with("safe:schemaVersion 1 .\n"));
+
+        let turtle = nrs_map_to_turtle(&test_nrs_map(), None);
+        assert!(turtle.contains("[]\n    a safe:NrsMap ;"));
+        assert!(turtle.contains("safe:existingSubName \"b\""));
+        assert!(turtle.contains("schema:url <safe://hnyynyw4gsy3i6hnbna?v=0>"));
+    }
+}
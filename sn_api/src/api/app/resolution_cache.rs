@@ -0,0 +1,204 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use super::{fetch::Range, files::FilesMap, nrs_map::NrsMap};
+use log::debug;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+use xor_name::XorName;
+
+// Content which never changes once stored, thus it can be cached and served as is
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum CacheKey {
+    NrsMap {
+        xorname: XorName,
+        version: u64,
+    },
+    FilesMap {
+        xorname: XorName,
+        type_tag: u64,
+        private: bool,
+        version: u64,
+    },
+    Blob {
+        xorname: XorName,
+        private: bool,
+        range: Range,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum CachedContent {
+    NrsMap(NrsMap),
+    FilesMap(FilesMap),
+    Blob(Vec<u8>),
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    content: CachedContent,
+    size: usize,
+    inserted: Instant,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheEntries {
+    entries: HashMap<CacheKey, CacheEntry>,
+    size: usize,
+    // Counter to know which entry was used least recently when one has to be evicted
+    uses: u64,
+}
+
+// Cache of the NRS Map versions and immutable content fetched, i.e. Blobs and versions of
+// FilesContainers. Entries expire after the TTL, and the least recently used ones are
+// evicted to keep the total size of the content within the limit.
+// Clones share the same entries.
+#[derive(Debug, Clone)]
+pub(crate) struct ResolutionCache {
+    ttl: Duration,
+    max_size: usize,
+    entries: Arc<Mutex<CacheEntries>>,
+}
+
+impl ResolutionCache {
+    pub fn new(ttl: Duration, max_size: usize) -> Self {
+        Self {
+            ttl,
+            max_size,
+            entries: Arc::new(Mutex::new(CacheEntries::default())),
+        }
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<CachedContent> {
+        let mut cache = self.lock_entries();
+        cache.uses += 1;
+        let uses = cache.uses;
+
+        let expired = match cache.entries.get_mut(key) {
+            None => return None,
+            Some(entry) if entry.inserted.elapsed() < self.ttl => {
+                entry.last_used = uses;
+                debug!("Resolution cache hit for {:?}", key);
+                return Some(entry.content.clone());
+            }
+            Some(entry) => entry.size,
+        };
+
+        debug!("Resolution cache entry expired for {:?}", key);
+        let _ = cache.entries.remove(key);
+        cache.size -= expired;
+        None
+    }
+
+    // Content bigger than the size limit is not cached at all
+    pub fn insert(&self, key: CacheKey, content: CachedContent, size: usize) {
+        if size > self.max_size {
+            return;
+        }
+
+        let mut cache = self.lock_entries();
+        if let Some(previous) = cache.entries.remove(&key) {
+            cache.size -= previous.size;
+        }
+
+        while cache.size + size > self.max_size {
+            let least_recently_used = cache
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            match least_recently_used
+                .and_then(|lru_key| cache.entries.remove(&lru_key).map(|entry| entry.size))
+            {
+                Some(evicted) => cache.size -= evicted,
+                None => break,
+            }
+        }
+
+        cache.uses += 1;
+        let entry = CacheEntry {
+            content,
+            size,
+            inserted: Instant::now(),
+            last_used: cache.uses,
+        };
+        let _ = cache.entries.insert(key, entry);
+        cache.size += size;
+    }
+
+    pub fn clear(&self) {
+        let mut cache = self.lock_entries();
+        cache.entries.clear();
+        cache.size = 0;
+    }
+
+    // A poisoned lock only means another thread panicked while holding it,
+    // the entries are still consistent as they are updated in a single step
+    fn lock_entries(&self) -> MutexGuard<CacheEntries> {
+        match self.entries.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, Result};
+
+    fn blob_key(byte: u8) -> CacheKey {
+        CacheKey::Blob {
+            xorname: XorName([byte; 32]),
+            private: false,
+            range: None,
+        }
+    }
+
+    fn cached_blob(cache: &ResolutionCache, key: &CacheKey) -> Option<Vec<u8>> {
+        match cache.get(key) {
+            Some(CachedContent::Blob(data)) => Some(data),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_resolution_cache_evicts_least_recently_used() -> Result<()> {
+        let cache = ResolutionCache::new(Duration::from_secs(60), 10);
+        cache.insert(blob_key(1), CachedContent::Blob(vec![1; 4]), 4);
+        cache.insert(blob_key(2), CachedContent::Blob(vec![2; 4]), 4);
+
+        // using the first one makes the second the least recently used
+        assert_eq!(cached_blob(&cache, &blob_key(1)), Some(vec![1; 4]));
+        cache.insert(blob_key(3), CachedContent::Blob(vec![3; 4]), 4);
+        assert_eq!(cached_blob(&cache, &blob_key(1)), Some(vec![1; 4]));
+        assert_eq!(cached_blob(&cache, &blob_key(2)), None);
+        assert_eq!(cached_blob(&cache, &blob_key(3)), Some(vec![3; 4]));
+
+        // content bigger than the limit is not cached
+        cache.insert(blob_key(4), CachedContent::Blob(vec![4; 11]), 11);
+        if cache.get(&blob_key(4)).is_some() {
+            return Err(Error::Unexpected(
+                "Content bigger than the cache was cached".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolution_cache_ttl() {
+        let cache = ResolutionCache::new(Duration::from_millis(0), 10);
+        cache.insert(blob_key(1), CachedContent::Blob(vec![1; 4]), 4);
+        assert_eq!(cached_blob(&cache, &blob_key(1)), None);
+    }
+}
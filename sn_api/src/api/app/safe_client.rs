@@ -7,18 +7,15 @@
 // specific language governing permissions and limitations relating to use of the SAFE Network
 // Software.
 
-use super::{fetch::Range, helpers::xorname_to_hex};
+use super::{
+    backend::{BackendConfig, LocalBackend, NetworkBackend, StorageBackend, TransferRecord},
+    fetch::Range,
+};
 use crate::{Error, Result};
 
-use log::{debug, info, warn};
+use log::debug;
 
-use sn_client::{Client, ClientError as SafeClientError};
-use sn_data_types::{
-    Blob, BlobAddress, Error as SafeNdError, Keypair, Map, MapAction, MapAddress, MapEntryActions,
-    MapPermissionSet, MapSeqEntryActions, MapSeqValue, MapValue, Money, PublicBlob,
-    PublicKey as SafeNdPublicKey, SeqMap, SequenceAddress, SequenceIndex,
-    SequencePrivatePermissions, SequencePublicPermissions, SequenceUser,
-};
+use sn_data_types::{Keypair, Map, MapSeqValue, MapValue, Money, PublicKey as SafeNdPublicKey};
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use xor_name::XorName;
@@ -29,377 +26,209 @@ const APP_NOT_CONNECTED: &str = "Application is not connected to the network";
 
 #[derive(Default, Clone)]
 pub struct SafeAppClient {
-    safe_client: Option<Client>,
+    backend_config: BackendConfig,
+    backend: Option<Arc<dyn StorageBackend>>,
 }
 
 impl SafeAppClient {
-    // Private helper to obtain the App instance
-    fn get_safe_client(&mut self) -> Result<Client> {
-        match &self.safe_client {
-            Some(app) => Ok(app.clone()),
+    // Private helper to obtain the storage backend instance
+    fn get_backend(&self) -> Result<Arc<dyn StorageBackend>> {
+        match &self.backend {
+            Some(backend) => Ok(Arc::clone(backend)),
             None => Err(Error::ConnectionError(APP_NOT_CONNECTED.to_string())),
         }
     }
 
-    pub fn new() -> Self {
-        Self { safe_client: None }
+    pub fn new(backend_config: BackendConfig) -> Self {
+        Self {
+            backend_config,
+            backend: None,
+        }
     }
 
-    pub async fn keypair(&self) -> Result<Arc<Keypair>> {
-        if let Some(client) = &self.safe_client {
-            let kp = client.keypair().await;
+    pub fn backend_config(&self) -> &BackendConfig {
+        &self.backend_config
+    }
 
-            Ok(kp)
+    pub async fn keypair(&self) -> Result<Arc<Keypair>> {
+        if let Some(backend) = &self.backend {
+            backend.keypair().await
         } else {
             Err(Error::ConnectionError("Not yet connected".to_string()))
         }
     }
 
-    // Connect to the SAFE Network using the provided app id and auth credentials
-    pub async fn connect(&mut self, _app_id: &str, _auth_credentials: Option<&str>) -> Result<()> {
-        debug!("Connecting to SAFE Network...");
+    pub fn has_persistent_keypair(&self) -> Result<bool> {
+        Ok(self.get_backend()?.has_persistent_keypair())
+    }
 
-        let _disconnect_cb = || {
-            warn!("Connection with the SAFE Network was lost");
+    // Connect to the SAFE Network, or to the local stand-in for it,
+    // using the provided app id and auth credentials
+    pub async fn connect(&mut self, _app_id: &str, _auth_credentials: Option<&str>) -> Result<()> {
+        let backend: Arc<dyn StorageBackend> = match &self.backend_config {
+            BackendConfig::Network => Arc::new(NetworkBackend::connect().await?),
+            BackendConfig::InMemory => {
+                debug!("Connecting to in-memory storage...");
+                Arc::new(LocalBackend::in_memory())
+            }
+            BackendConfig::LocalDisk(path) => {
+                debug!("Connecting to local storage...");
+                Arc::new(LocalBackend::on_disk(path)?)
+            }
         };
 
-        let app = Client::new(None).await?;
-
-        // let app = match auth_credentials {
-        //     Some(auth_credentials) => {
-        //         let auth_granted = decode_ipc_msg(auth_credentials)?;
-        //         match auth_granted {
-        //             AuthResponseType::Registered(authgranted) => {
-        //                 // TODO: This needs an existing SK now.
-        //                 Client::new(None).await
-        //                 // Client::new(app_id.to_string(), authgranted, disconnect_cb).await
-        //             }
-        //             // unregistered type used for returning bootstrap config for client
-        //             // TODO: rename?
-        //             AuthResponseType::Unregistered(config) => {
-        //                 // TODO: what to do with config...
-        //                 Client::new(None).await
-        //             }
-        //         }
-        //     }
-        //     None => Client::new(None).await,
-        // }
-        // .map_err(|err| {
-        //     Error::ConnectionError(format!("Failed to connect to the SAFE Network: {:?}", err))
-        // })?;
-
-        self.safe_client = Some(app);
-        debug!("Successfully connected to the Network!!!");
+        self.backend = Some(backend);
         Ok(())
     }
 
     // === Money operations ===
-    pub async fn read_balance_from_keypair(&mut self, id: Keypair) -> Result<Money> {
-        let mut temp_client = Client::new(Some(id)).await?;
-        let coins = temp_client
-            .get_balance()
-            .await
-            .map_err(|e| Error::NetDataError(format!("Failed to retrieve balance: {:?}", e)))?;
-
-        Ok(coins)
+    pub async fn read_balance_from_keypair(&self, id: Keypair) -> Result<Money> {
+        self.get_backend()?.read_balance_from_keypair(id).await
     }
 
     #[cfg(feature = "simulated-payouts")]
-    pub async fn trigger_simulated_farming_payout(&mut self, amount: Money) -> Result<()> {
-        let mut client = self.get_safe_client()?;
-
-        client.trigger_simulated_farming_payout(amount).await?;
-
-        Ok(())
+    pub async fn trigger_simulated_farming_payout(
+        &self,
+        amount: Money,
+        id: Option<Keypair>,
+    ) -> Result<()> {
+        self.get_backend()?
+            .trigger_simulated_farming_payout(amount, id)
+            .await
     }
 
     pub async fn safecoin_transfer_to_xorname(
-        &mut self,
+        &self,
         from_id: Option<Keypair>,
-        _to_xorname: XorName,
-        _amount: Money,
-    ) -> Result<()> {
-        let _client = match from_id {
-            Some(id) => Client::new(Some(id)).await?,
-            None => self.get_safe_client()?,
-        };
-
-        unimplemented!();
-
-        // TODO: attempt to get wallet pk from xorname
-
-        // let to_pk = self.fetch(format!("safe://{:?}", to_xorname));
-        // let to_url  = xorurl::SafeUrl::from::<XorName>(to_xorname);
-
-        // let from_fullid = from_sk.map(Keypair::from);
-        // let transfer_id = client
-        //     .send_money( to_xorname, amount)
-        //     .await
-        //     .map_err(|err| match err {
-        //         SafeClientError::DataError(SafeNdError::ExcessiveValue)
-        //         | SafeClientError::DataError(SafeNdError::InsufficientBalance) => {
-        //             Error::NotEnoughBalance(amount.to_string())
-        //         }
-        //         SafeClientError::DataError(SafeNdError::InvalidOperation) => {
-        //             Error::InvalidAmount(amount.to_string())
-        //         }
-        //         other => Error::NetDataError(format!("Failed to transfer coins: {:?}", other)),
-        //     })?;
-
-        // Ok(transfer_id)
+        to_xorname: XorName,
+        amount: Money,
+    ) -> Result<u64> {
+        self.get_backend()?
+            .safecoin_transfer_to_xorname(from_id, to_xorname, amount)
+            .await
     }
 
-    #[allow(dead_code)]
     pub async fn safecoin_transfer_to_pk(
-        &mut self,
+        &self,
         from_id: Option<Keypair>,
-        to_pk: PublicKey,
+        to_pk: SafeNdPublicKey,
         amount: Money,
     ) -> Result<(u64, SafeNdPublicKey)> {
-        let mut client = match from_id {
-            Some(id) => Client::new(Some(id)).await?,
-            None => self.get_safe_client()?,
-        };
-
-        let transfer_id = client
-            .send_money(SafeNdPublicKey::Bls(to_pk), amount)
-            .await?;
+        self.get_backend()?
+            .safecoin_transfer_to_pk(from_id, to_pk, amount)
+            .await
+    }
 
-        Ok(transfer_id)
+    pub async fn transfer_history(&self, id: Keypair) -> Result<Vec<TransferRecord>> {
+        self.get_backend()?.transfer_history(id).await
     }
 
-    // // === Blob operations ===
-    pub async fn store_public_blob(&mut self, data: &[u8], dry_run: bool) -> Result<XorName> {
+    // === Blob operations ===
+    pub async fn store_public_blob(&self, data: &[u8], dry_run: bool) -> Result<XorName> {
         // TODO: allow this operation to work without a connection when it's a dry run
-        let mut client = self.get_safe_client()?;
+        self.get_backend()?.store_public_blob(data, dry_run).await
+    }
 
-        let data_vec = data.to_vec();
-        let blob_for_storage = Blob::Public(PublicBlob::new(data_vec));
-        let xorname = *blob_for_storage.address().name();
+    pub async fn get_public_blob(&self, xorname: XorName, range: Range) -> Result<Vec<u8>> {
+        debug!("Fetching immutable data: {:?}", &xorname);
 
-        let _data_map = client
-            .generate_data_map(&blob_for_storage)
-            .await
-            .map_err(|e| {
-                Error::NetDataError(format!(
-                    "Failed to create data map for Public Blob: {:?}",
-                    e
-                ))
-            })?;
-
-        if !dry_run {
-            client
-                .store_blob(blob_for_storage)
-                .await
-                .map_err(|e| Error::NetDataError(format!("Failed to PUT Public Blob: {:?}", e)))?;
-        }
+        let data = self.get_backend()?.get_public_blob(xorname, range).await?;
+
+        debug!(
+            "Public Blob data successfully retrieved from: {:?}",
+            &xorname
+        );
 
-        Ok(xorname)
+        Ok(data)
     }
 
-    pub async fn get_public_blob(&mut self, xorname: XorName, range: Range) -> Result<Vec<u8>> {
-        debug!("Fetching immutable data: {:?}", &xorname);
+    pub async fn store_private_blob(&self, data: &[u8], dry_run: bool) -> Result<XorName> {
+        self.get_backend()?.store_private_blob(data, dry_run).await
+    }
 
-        let mut client = self.get_safe_client()?;
-        let blob_address = BlobAddress::Public(xorname);
-        let data = if let Some((start, end)) = range {
-            let len = if let Some(end_index) = end {
-                Some(end_index - start.unwrap_or_else(|| 0))
-            } else {
-                None
-            };
-            client.get_blob(blob_address, start, len).await
-        } else {
-            client.get_blob(blob_address, None, None).await
-        }
-        .map_err(|e| Error::NetDataError(format!("Failed to GET Public Blob: {:?}", e)))?;
+    pub async fn get_private_blob(&self, xorname: XorName) -> Result<Vec<u8>> {
+        debug!("Fetching private immutable data: {:?}", &xorname);
+
+        let data = self.get_backend()?.get_private_blob(xorname).await?;
 
         debug!(
-            "Public Blob data successfully retrieved from: {:?}",
+            "Private Blob data successfully retrieved from: {:?}",
             &xorname
         );
 
-        Ok(data.value().clone())
+        Ok(data)
     }
 
     // === Map operations ===
     pub async fn store_map(
-        &mut self,
+        &self,
         name: Option<XorName>,
         tag: u64,
         // _data: Option<String>,
-        _permissions: Option<String>,
+        permissions: Option<String>,
     ) -> Result<XorName> {
-        let mut safe_client = self.get_safe_client()?;
-        let client = &safe_client;
-        let owner_key_option = client.public_key().await;
-        let owners = if let SafeNdPublicKey::Bls(owners) = owner_key_option {
-            owners
-        } else {
-            return Err(Error::Unexpected(
-                "Failed to retrieve public key.".to_string(),
-            ));
-        };
-
-        let xorname = name.unwrap_or_else(rand::random);
-
-        let permission_set = MapPermissionSet::new()
-            .allow(MapAction::Read)
-            .allow(MapAction::Insert)
-            .allow(MapAction::Update)
-            .allow(MapAction::Delete)
-            .allow(MapAction::ManagePermissions);
-
-        let mut permission_map = BTreeMap::new();
-        let sign_pk = get_public_bls_key(&safe_client).await?;
-        let app_pk = SafeNdPublicKey::Bls(sign_pk);
-        permission_map.insert(app_pk, permission_set);
-
-        let map = Map::Seq(SeqMap::new_with_data(
-            xorname,
-            tag,
-            BTreeMap::new(),
-            permission_map,
-            SafeNdPublicKey::Bls(owners),
-        ));
-
-        safe_client
-            .store_map(map)
-            .await
-            .map_err(|err| Error::NetDataError(format!("Failed to put mutable data: {}", err)))?;
-
-        Ok(xorname)
+        self.get_backend()?.store_map(name, tag, permissions).await
     }
 
     #[allow(dead_code)]
-    pub async fn get_map(&mut self, name: XorName, tag: u64) -> Result<Map> {
-        let mut client = self.get_safe_client()?;
-        let address = MapAddress::Seq { name, tag };
-
-        client
-            .get_map(address)
-            .await
-            .map_err(|e| Error::NetDataError(format!("Failed to get MD: {:?}", e)))
+    pub async fn get_map(&self, name: XorName, tag: u64) -> Result<Map> {
+        self.get_backend()?.get_map(name, tag).await
     }
 
     pub async fn map_insert(
-        &mut self,
+        &self,
         name: XorName,
         tag: u64,
         key: &[u8],
         value: &[u8],
     ) -> Result<()> {
-        let entry_actions = MapSeqEntryActions::new();
-        let entry_actions = entry_actions.ins(key.to_vec(), value.to_vec(), 0);
-        self.edit_map_entries(name, tag, entry_actions, "Failed to insert to SeqMD")
-            .await
+        self.get_backend()?.map_insert(name, tag, key, value).await
     }
 
-    pub async fn map_get_value(&mut self, name: XorName, tag: u64, key: &[u8]) -> Result<MapValue> {
-        let mut client = self.get_safe_client()?;
-        let key_vec = key.to_vec();
-        let address = MapAddress::Seq { name, tag };
-
-        client
-            .get_map_value(address, key_vec)
-            .await
-            .map_err(|err| match err {
-                SafeClientError::DataError(SafeNdError::AccessDenied) => {
-                    Error::AccessDenied(format!("Failed to retrieve a key: {:?}", key))
-                }
-                SafeClientError::DataError(SafeNdError::NoSuchData) => {
-                    Error::ContentNotFound(format!(
-                        "Sequenced Map not found at Xor name: {}",
-                        xorname_to_hex(&name)
-                    ))
-                }
-                SafeClientError::DataError(SafeNdError::NoSuchEntry) => {
-                    Error::EntryNotFound(format!(
-                        "Entry not found in Sequenced Map found at Xor name: {}",
-                        xorname_to_hex(&name)
-                    ))
-                }
-                err => Error::NetDataError(format!("Failed to retrieve a key. {:?}", err)),
-            })
+    pub async fn map_get_value(&self, name: XorName, tag: u64, key: &[u8]) -> Result<MapValue> {
+        self.get_backend()?.map_get_value(name, tag, key).await
     }
 
     pub async fn list_map_entries(
-        &mut self,
+        &self,
         name: XorName,
         tag: u64,
     ) -> Result<BTreeMap<Vec<u8>, MapSeqValue>> {
-        let mut client = self.get_safe_client()?;
-        client
-            .list_seq_map_entries(name, tag)
-            .await
-            .map_err(|err| match err {
-                SafeClientError::DataError(SafeNdError::AccessDenied) => {
-                    Error::AccessDenied(format!(
-                        "Failed to get Sequenced Map at: {:?} (type tag: {})",
-                        name, tag
-                    ))
-                }
-                SafeClientError::DataError(SafeNdError::NoSuchData) => {
-                    Error::ContentNotFound(format!(
-                        "Sequenced Map not found at Xor name: {} (type tag: {})",
-                        xorname_to_hex(&name),
-                        tag
-                    ))
-                }
-                SafeClientError::DataError(SafeNdError::NoSuchEntry) => {
-                    Error::EntryNotFound(format!(
-                        "Entry not found in Sequenced Map found at Xor name: {} (type tag: {})",
-                        xorname_to_hex(&name),
-                        tag
-                    ))
-                }
-                err => Error::NetDataError(format!("Failed to get Sequenced Map. {:?}", err)),
-            })
+        self.get_backend()?.list_map_entries(name, tag).await
     }
 
-    async fn edit_map_entries(
-        &mut self,
+    pub async fn update_map(
+        &self,
         name: XorName,
         tag: u64,
-        entry_actions: MapSeqEntryActions,
-        error_msg: &str,
+        key: &[u8],
+        value: &[u8],
+        version: u64,
     ) -> Result<()> {
-        let mut client = self.get_safe_client()?;
-        let message = error_msg.to_string();
-        let address = MapAddress::Seq { name, tag };
-        client
-            .edit_map_entries(address, MapEntryActions::Seq(entry_actions))
+        self.get_backend()?
+            .update_map(name, tag, key, value, version)
             .await
-            .map_err(|err| {
-                if let SafeClientError::DataError(SafeNdError::InvalidEntryActions(_)) = err {
-                    Error::EntryExists(format!("{}: {}", message, err))
-                } else {
-                    Error::NetDataError(format!("{}: {}", message, err))
-                }
-            })
     }
 
-    pub async fn update_map(
-        &mut self,
+    pub async fn map_delete(
+        &self,
         name: XorName,
         tag: u64,
         key: &[u8],
-        value: &[u8],
         version: u64,
     ) -> Result<()> {
-        let entry_actions = MapSeqEntryActions::new();
-        let entry_actions = entry_actions.update(key.to_vec(), value.to_vec(), version);
-        self.edit_map_entries(name, tag, entry_actions, "Failed to update SeqMD")
+        self.get_backend()?
+            .map_delete(name, tag, key, version)
             .await
     }
 
     // === Sequence data operations ===
     pub async fn store_sequence(
-        &mut self,
+        &self,
         data: &[u8],
         name: Option<XorName>,
         tag: u64,
-        _permissions: Option<String>,
+        permissions: Option<String>,
         private: bool,
     ) -> Result<XorName> {
         debug!(
@@ -409,61 +238,13 @@ impl SafeAppClient {
             name
         );
 
-        let mut safe_client = self.get_safe_client()?;
-        let xorname = name.unwrap_or_else(rand::random);
-        info!("Xorname for storage: {:?}", &xorname);
-
-        let app_public_key = get_public_bls_key(&safe_client).await?;
-
-        // The Sequence's owner will be the user
-        let user_acc_owner = safe_client.public_key().await;
-
-        // Store the Sequence on the network
-        let _address = if private {
-            // Set permissions for append, delete, and manage perms to this application
-            let mut perms = BTreeMap::default();
-            let _ = perms.insert(
-                SafeNdPublicKey::Bls(app_public_key),
-                SequencePrivatePermissions::new(true, true, true),
-            );
-
-            safe_client
-                .store_private_sequence(
-                    Some(vec![data.to_vec()]),
-                    xorname,
-                    tag,
-                    user_acc_owner,
-                    perms,
-                )
-                .await
-                .map_err(|e| {
-                    Error::NetDataError(format!("Failed to store Private Sequence data: {:?}", e))
-                })?
-        } else {
-            // Set permissions for append and manage perms to this application
-            let user_app = SequenceUser::Key(SafeNdPublicKey::Bls(app_public_key));
-            let mut perms = BTreeMap::default();
-            let _ = perms.insert(user_app, SequencePublicPermissions::new(true, true));
-
-            safe_client
-                .store_public_sequence(
-                    Some(vec![data.to_vec()]),
-                    xorname,
-                    tag,
-                    user_acc_owner,
-                    perms,
-                )
-                .await
-                .map_err(|e| {
-                    Error::NetDataError(format!("Failed to store Public Sequence data: {:?}", e))
-                })?
-        };
-
-        Ok(xorname)
+        self.get_backend()?
+            .store_sequence(data, name, tag, permissions, private)
+            .await
     }
 
     pub async fn sequence_get_last_entry(
-        &mut self,
+        &self,
         name: XorName,
         tag: u64,
         private: bool,
@@ -475,30 +256,31 @@ impl SafeAppClient {
             name
         );
 
-        let mut safe_client = self.get_safe_client()?;
+        self.get_backend()?
+            .sequence_get_last_entry(name, tag, private)
+            .await
+    }
+
+    pub async fn sequence_get_last_index(
+        &self,
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<u64> {
+        debug!(
+            "Fetching last index of {} Sequence data w/ type: {:?}, xorname: {:?}",
+            if private { "Private" } else { "Public" },
+            tag,
+            name
+        );
 
-        let sequence_address = if private {
-            SequenceAddress::Private { name, tag }
-        } else {
-            SequenceAddress::Public { name, tag }
-        };
-        safe_client
-            .get_sequence_last_entry(sequence_address)
+        self.get_backend()?
+            .sequence_get_last_index(name, tag, private)
             .await
-            .map_err(|err| {
-                if let SafeClientError::DataError(SafeNdError::NoSuchEntry) = err {
-                    Error::EmptyContent(format!("Empty Sequence found at XoR name {}", name))
-                } else {
-                    Error::NetDataError(format!(
-                        "Failed to retrieve last entry from Sequence data: {:?}",
-                        err
-                    ))
-                }
-            })
     }
 
     pub async fn sequence_get_entry(
-        &mut self,
+        &self,
         name: XorName,
         tag: u64,
         index: u64,
@@ -511,44 +293,13 @@ impl SafeAppClient {
             name
         );
 
-        let mut safe_client = self.get_safe_client()?;
-
-        let sequence_address = if private {
-            SequenceAddress::Private { name, tag }
-        } else {
-            SequenceAddress::Public { name, tag }
-        };
-        let start = SequenceIndex::FromStart(index);
-        let end = SequenceIndex::FromStart(index + 1);
-        let res = safe_client
-            .get_sequence_range(sequence_address, (start, end))
+        self.get_backend()?
+            .sequence_get_entry(name, tag, index, private)
             .await
-            .map_err(|err| {
-                if let SafeClientError::DataError(SafeNdError::NoSuchEntry) = err {
-                    Error::VersionNotFound(format!(
-                        "Invalid version ({}) for Sequence found at XoR name {}",
-                        index, name
-                    ))
-                } else {
-                    Error::NetDataError(format!(
-                        "Failed to retrieve entry at index {} from Sequence data: {:?}",
-                        index, err
-                    ))
-                }
-            })?;
-
-        let entry = res.get(0).ok_or_else(|| {
-            Error::EmptyContent(format!(
-                "Empty Sequence found at Xor name {}",
-                xorname_to_hex(&name)
-            ))
-        })?;
-
-        Ok(entry.to_vec())
     }
 
     pub async fn append_to_sequence(
-        &mut self,
+        &self,
         data: &[u8],
         name: XorName,
         tag: u64,
@@ -561,28 +312,45 @@ impl SafeAppClient {
             name
         );
 
-        let mut safe_client = self.get_safe_client()?;
-
-        let sequence_address = if private {
-            SequenceAddress::Private { name, tag }
-        } else {
-            SequenceAddress::Public { name, tag }
-        };
-        safe_client
-            .append_to_sequence(sequence_address, data.to_vec())
+        self.get_backend()?
+            .append_to_sequence(data, name, tag, private)
             .await
-            .map_err(|e| Error::NetDataError(format!("Failed to append to Sequence: {:?}", e)))
     }
-}
 
-// Helpers
+    pub async fn sequence_get_owner(
+        &self,
+        name: XorName,
+        tag: u64,
+        private: bool,
+    ) -> Result<SafeNdPublicKey> {
+        debug!(
+            "Fetching owner of {} Sequence data w/ type: {:?}, xorname: {:?}",
+            if private { "Private" } else { "Public" },
+            tag,
+            name
+        );
 
-async fn get_public_bls_key(safe_client: &Client) -> Result<PublicKey> {
-    let pk = safe_client
-        .public_key()
-        .await
-        .bls()
-        .ok_or_else(|| Error::Unexpected("Client's key is not a BLS Public Key".to_string()))?;
+        self.get_backend()?
+            .sequence_get_owner(name, tag, private)
+            .await
+    }
 
-    Ok(pk)
+    pub async fn sequence_set_owner(
+        &self,
+        name: XorName,
+        tag: u64,
+        owner: SafeNdPublicKey,
+        private: bool,
+    ) -> Result<()> {
+        debug!(
+            "Setting owner of {} Sequence data w/ type: {:?}, xorname: {:?}",
+            if private { "Private" } else { "Public" },
+            tag,
+            name
+        );
+
+        self.get_backend()?
+            .sequence_set_owner(name, tag, owner, private)
+            .await
+    }
 }
@@ -7,17 +7,27 @@
 // specific language governing permissions and limitations relating to use of the SAFE Network
 // Software.
 
-use crate::{Result, Safe};
+use crate::{BackendConfig, Result, Safe};
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
-use std::env::var;
+use std::{
+    env::{temp_dir, var},
+    path::{Path, PathBuf},
+};
 
 // Environment variable where to read auth credentials to be used for all sn_api tests
 const TEST_AUTH_CREDENTIALS: &str = "TEST_AUTH_CREDENTIALS";
 
+// Environment variable where to read the storage backend to be used for all sn_api tests,
+// e.g. 'memory' to run them without a network
+const TEST_STORAGE_BACKEND: &str = "TEST_STORAGE_BACKEND";
+
 // Instantiate a Safe instance
 pub async fn new_safe_instance() -> Result<Safe> {
-    let mut safe = Safe::default();
+    let mut safe = match var(TEST_STORAGE_BACKEND) {
+        Ok(val) => Safe::with_backend(None, val.parse::<BackendConfig>()?),
+        Err(_) => Safe::default(),
+    };
     let credentials = match var(TEST_AUTH_CREDENTIALS) {
         Ok(val) => val,
         Err(_) => "fake-credentials".to_string(),
@@ -26,6 +36,19 @@ pub async fn new_safe_instance() -> Result<Safe> {
     Ok(safe)
 }
 
+// Instantiate a Safe instance connected to the local storage at the given path, whose keypair
+// persists across sessions as needed to read private data back
+pub async fn new_safe_instance_on_disk(path: &Path) -> Result<Safe> {
+    let mut safe = Safe::with_backend(None, BackendConfig::LocalDisk(path.to_path_buf()));
+    safe.connect("", Some("fake-credentials")).await?;
+    Ok(safe)
+}
+
+// Path for a new local storage file in the temp directory
+pub fn random_local_storage_path() -> PathBuf {
+    temp_dir().join(format!("sn_api_local_storage_{}.db", random_nrs_name()))
+}
+
 // Create a random NRS name
 pub fn random_nrs_name() -> String {
     thread_rng().sample_iter(&Alphanumeric).take(15).collect()
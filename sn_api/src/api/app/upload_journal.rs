@@ -0,0 +1,409 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use crate::{xorurl::XorUrl, Error, Result};
+use data_encoding::HEXLOWER;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tiny_keccak::Keccak;
+
+// Size of the buffer used to read the content of a file to hash it
+const HASH_READ_BUFFER_SIZE: usize = 64 * 1024;
+
+// Entries older than this, in seconds, are dropped when the journal is loaded
+const MAX_ENTRY_AGE: u64 = 30 * 24 * 60 * 60;
+
+// Maximum number of entries kept when the journal is loaded, the oldest ones are dropped first
+const MAX_ENTRIES: usize = 10_000;
+
+// A file which content was stored, one per line in the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct JournalEntry {
+    // Storage backend and keypair the content was stored with, entries recorded
+    // before they were journaled have none and are never used
+    #[serde(default)]
+    scope: String,
+    path: String,
+    hash: String,
+    private: bool,
+    xorurl: XorUrl,
+    // Seconds since the Unix epoch when the entry was recorded
+    #[serde(default)]
+    timestamp: u64,
+}
+
+// Scope, source path, hash of the content, and whether it was stored as private data
+type EntryKey = (String, String, String, bool);
+
+// Local journal of the files whose content was already stored, by storage backend and keypair,
+// source path and hash of their content. Entries are appended as soon as each file is stored,
+// so if an upload is interrupted it can be resumed later on without uploading again the files
+// it already stored. Clones share the same entries, so files can be uploaded concurrently with
+// the same journal.
+#[derive(Debug, Clone)]
+pub(crate) struct UploadJournal {
+    path: PathBuf,
+    entries: Arc<Mutex<BTreeMap<EntryKey, (XorUrl, u64)>>>,
+}
+
+impl UploadJournal {
+    // Load the journal from the given location, it's created if it doesn't exist yet.
+    // Entries which are too old, or the oldest ones above the maximum number of entries,
+    // are dropped and the journal is rewritten without them.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut entries = BTreeMap::new();
+        let mut lines_count = 0;
+        if path.exists() {
+            let file = File::open(path).map_err(|err| {
+                Error::FileSystemError(format!(
+                    "Failed to open upload journal at '{}': {}",
+                    path.display(),
+                    err
+                ))
+            })?;
+
+            for line in BufReader::new(file).lines() {
+                let line = line.map_err(|err| {
+                    Error::FileSystemError(format!(
+                        "Failed to read upload journal at '{}': {}",
+                        path.display(),
+                        err
+                    ))
+                })?;
+                lines_count += 1;
+
+                // An entry can be incomplete if the process was killed while writing it
+                match serde_json::from_str::<JournalEntry>(&line) {
+                    Ok(entry) => {
+                        let _ = entries.insert(
+                            (entry.scope, entry.path, entry.hash, entry.private),
+                            (entry.xorurl, entry.timestamp),
+                        );
+                    }
+                    Err(err) => warn!("Ignoring invalid upload journal entry: {}", err),
+                }
+            }
+        } else if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| {
+                Error::FileSystemError(format!(
+                    "Failed to create folder for the upload journal at '{}': {}",
+                    parent.display(),
+                    err
+                ))
+            })?;
+        }
+
+        prune_entries(&mut entries, now_secs());
+        let journal = Self {
+            path: path.to_path_buf(),
+            entries: Arc::new(Mutex::new(entries)),
+        };
+
+        // Rewrite the journal if entries were dropped, or lines were invalid or duplicated
+        if lines_count > journal.lock_entries().len() {
+            journal.rewrite()?;
+        }
+
+        Ok(journal)
+    }
+
+    // XOR-URL of the content of the file if it was already stored with the same scope
+    pub fn get(&self, scope: &str, path: &str, hash: &str, private: bool) -> Option<XorUrl> {
+        self.lock_entries()
+            .get(&(
+                scope.to_string(),
+                path.to_string(),
+                hash.to_string(),
+                private,
+            ))
+            .map(|(xorurl, _)| xorurl.clone())
+    }
+
+    pub fn record(
+        &self,
+        scope: &str,
+        path: &str,
+        hash: &str,
+        private: bool,
+        xorurl: &str,
+    ) -> Result<()> {
+        let entry = JournalEntry {
+            scope: scope.to_string(),
+            path: path.to_string(),
+            hash: hash.to_string(),
+            private,
+            xorurl: xorurl.to_string(),
+            timestamp: now_secs(),
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|err| {
+                Error::FileSystemError(format!(
+                    "Failed to open upload journal at '{}': {}",
+                    self.path.display(),
+                    err
+                ))
+            })?;
+        self.write_entry(&mut file, &entry)?;
+
+        debug!("File '{}' recorded in upload journal", path);
+        let _ = self.lock_entries().insert(
+            (entry.scope, entry.path, entry.hash, entry.private),
+            (entry.xorurl, entry.timestamp),
+        );
+        Ok(())
+    }
+
+    // Replace the content of the journal with the entries currently kept. The new content is
+    // written onto a temporary file first so the journal is never left half written.
+    fn rewrite(&self) -> Result<()> {
+        let tmp_path = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp_path).map_err(|err| {
+            Error::FileSystemError(format!(
+                "Failed to create upload journal at '{}': {}",
+                tmp_path.display(),
+                err
+            ))
+        })?;
+        for ((scope, path, hash, private), (xorurl, timestamp)) in self.lock_entries().iter() {
+            let entry = JournalEntry {
+                scope: scope.clone(),
+                path: path.clone(),
+                hash: hash.clone(),
+                private: *private,
+                xorurl: xorurl.clone(),
+                timestamp: *timestamp,
+            };
+            self.write_entry(&mut file, &entry)?;
+        }
+
+        fs::rename(&tmp_path, &self.path).map_err(|err| {
+            Error::FileSystemError(format!(
+                "Failed to replace upload journal at '{}': {}",
+                self.path.display(),
+                err
+            ))
+        })
+    }
+
+    fn write_entry(&self, file: &mut File, entry: &JournalEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry).map_err(|err| {
+            Error::Unexpected(format!("Failed to serialise upload journal entry: {}", err))
+        })?;
+        line.push('\n');
+
+        file.write_all(line.as_bytes()).map_err(|err| {
+            Error::FileSystemError(format!(
+                "Failed to write to upload journal at '{}': {}",
+                self.path.display(),
+                err
+            ))
+        })
+    }
+
+    // A panic while holding the lock cannot leave the entries inconsistent,
+    // thus they can still be used if the lock was poisoned
+    fn lock_entries(&self) -> MutexGuard<BTreeMap<EntryKey, (XorUrl, u64)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// Drop the entries which are too old, or without a scope, and then the oldest ones
+// until there are no more than the maximum number of entries
+fn prune_entries(entries: &mut BTreeMap<EntryKey, (XorUrl, u64)>, now: u64) {
+    entries.retain(|(scope, _, _, _), (_, timestamp)| {
+        !scope.is_empty() && now.saturating_sub(*timestamp) <= MAX_ENTRY_AGE
+    });
+
+    if entries.len() > MAX_ENTRIES {
+        let mut timestamps: Vec<u64> = entries.values().map(|(_, timestamp)| *timestamp).collect();
+        timestamps.sort_unstable();
+        let min_timestamp = timestamps[timestamps.len() - MAX_ENTRIES];
+        entries.retain(|_, (_, timestamp)| *timestamp >= min_timestamp);
+
+        // Entries recorded at the same second as the oldest one kept can still exceed the limit
+        let mut excess = entries.len().saturating_sub(MAX_ENTRIES);
+        entries.retain(|_, (_, timestamp)| {
+            if excess > 0 && *timestamp == min_timestamp {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+// Path of a file, as it's recorded in the upload journal, along with the hash of its content
+pub(crate) fn journal_file_key(path: &Path) -> Result<(String, String)> {
+    let canonical_path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut file = File::open(path).map_err(|err| {
+        Error::FileSystemError(format!("Failed to read file from local location: {}", err))
+    })?;
+
+    let mut hasher = Keccak::new_sha3_256();
+    let mut buffer = vec![0; HASH_READ_BUFFER_SIZE];
+    loop {
+        let bytes_read = file.read(&mut buffer).map_err(|err| {
+            Error::FileSystemError(format!("Failed to read file from local location: {}", err))
+        })?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+    let mut hash = [0; 32];
+    hasher.finalize(&mut hash);
+
+    Ok((canonical_path.display().to_string(), HEXLOWER.encode(&hash)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::app::test_helpers::random_nrs_name;
+
+    const SCOPE: &str = "memory|testpublickey";
+
+    #[test]
+    fn test_upload_journal_resume() -> Result<()> {
+        let journal_path = std::env::temp_dir()
+            .join(random_nrs_name())
+            .join("upload_journal");
+        let (path, hash) = journal_file_key(Path::new("../testdata/test.md"))?;
+
+        let journal = UploadJournal::load(&journal_path)?;
+        assert_eq!(journal.get(SCOPE, &path, &hash, false), None);
+        journal.record(SCOPE, &path, &hash, false, "safe://testxorurl")?;
+        assert_eq!(
+            journal.get(SCOPE, &path, &hash, false),
+            Some("safe://testxorurl".to_string())
+        );
+        assert_eq!(journal.get(SCOPE, &path, &hash, true), None);
+
+        // entries are kept when loading the journal again
+        let journal = UploadJournal::load(&journal_path)?;
+        assert_eq!(
+            journal.get(SCOPE, &path, &hash, false),
+            Some("safe://testxorurl".to_string())
+        );
+
+        // the content is not found with another backend or keypair
+        assert_eq!(
+            journal.get("network|testpublickey", &path, &hash, false),
+            None
+        );
+        assert_eq!(
+            journal.get("memory|otherpublickey", &path, &hash, false),
+            None
+        );
+
+        // the content of another file is not found in the journal
+        let (other_path, other_hash) = journal_file_key(Path::new("../testdata/another.md"))?;
+        assert_ne!(other_hash, hash);
+        assert_eq!(journal.get(SCOPE, &other_path, &other_hash, false), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_upload_journal_prune() -> Result<()> {
+        let journal_path = std::env::temp_dir()
+            .join(random_nrs_name())
+            .join("upload_journal");
+        let now = now_secs();
+
+        // entries which are too old, or recorded without a scope, are dropped
+        let old_entry = JournalEntry {
+            scope: SCOPE.to_string(),
+            path: "/old".to_string(),
+            hash: "oldhash".to_string(),
+            private: false,
+            xorurl: "safe://oldxorurl".to_string(),
+            timestamp: now - MAX_ENTRY_AGE - 1,
+        };
+        let unscoped_entry = JournalEntry {
+            scope: "".to_string(),
+            path: "/unscoped".to_string(),
+            timestamp: now,
+            ..old_entry.clone()
+        };
+        let recent_entry = JournalEntry {
+            path: "/recent".to_string(),
+            timestamp: now,
+            ..old_entry.clone()
+        };
+        let journal = UploadJournal::load(&journal_path)?;
+        let mut file = File::create(&journal_path)
+            .map_err(|err| Error::FileSystemError(format!("Failed to create journal: {}", err)))?;
+        for entry in &[&old_entry, &unscoped_entry, &recent_entry] {
+            journal.write_entry(&mut file, entry)?;
+        }
+
+        let journal = UploadJournal::load(&journal_path)?;
+        assert_eq!(journal.get(SCOPE, "/old", "oldhash", false), None);
+        assert_eq!(journal.get("", "/unscoped", "oldhash", false), None);
+        assert_eq!(
+            journal.get(SCOPE, "/recent", "oldhash", false),
+            Some("safe://oldxorurl".to_string())
+        );
+
+        // the journal was rewritten with only the entries kept
+        let content = fs::read_to_string(&journal_path)
+            .map_err(|err| Error::FileSystemError(format!("Failed to read journal: {}", err)))?;
+        assert_eq!(content.lines().count(), 1);
+
+        // only the most recent entries are kept above the maximum number of entries
+        let mut entries = BTreeMap::new();
+        for i in 0..MAX_ENTRIES + 10 {
+            let _ = entries.insert(
+                (
+                    SCOPE.to_string(),
+                    format!("/file{}", i),
+                    "hash".to_string(),
+                    false,
+                ),
+                ("safe://xorurl".to_string(), now - 100 + (i / 100) as u64),
+            );
+        }
+        prune_entries(&mut entries, now);
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert!(!entries.contains_key(&(
+            SCOPE.to_string(),
+            "/file0".to_string(),
+            "hash".to_string(),
+            false
+        )));
+        assert!(entries.contains_key(&(
+            SCOPE.to_string(),
+            format!("/file{}", MAX_ENTRIES + 9),
+            "hash".to_string(),
+            false
+        )));
+        Ok(())
+    }
+}
@@ -7,8 +7,9 @@
 // specific language governing permissions and limitations relating to use of the SAFE Network
 // Software.
 
+use super::backend::TransferRecord;
 use super::common::ed_sk_from_hex;
-use super::helpers::{parse_coins_amount, xorname_to_hex};
+use super::helpers::{gen_timestamp_secs, parse_coins_amount, xorname_to_hex};
 use crate::{
     xorurl::{SafeContentType, SafeDataType, XorUrl, XorUrlEncoder},
     Error, Result, Safe,
@@ -17,7 +18,7 @@ use crate::{
 use log::debug;
 use serde::{Deserialize, Serialize};
 use sn_data_types::{Keypair, MapValue, Money};
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, str::FromStr};
 use xor_name::XorName;
 
 // pub use threshold_crypto::{PublicKey, SecretKey};
@@ -36,6 +37,133 @@ pub struct WalletSpendableBalance {
 
 pub type WalletSpendableBalances = BTreeMap<String, (bool, WalletSpendableBalance)>;
 
+/// Receipt of a transfer of safecoins between SafeKeys
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct TransferReceipt {
+    /// Id of the transfer, as set by the network when it was made
+    pub id: u64,
+    /// Amount of safecoins transferred, e.g. "1.500000000"
+    pub amount: String,
+    /// XorName of the SafeKey the safecoins were transferred from
+    pub from: XorName,
+    /// XorName of the SafeKey the safecoins were transferred to
+    pub to: XorName,
+    /// Time the transfer was made at, in RFC 3339 format
+    pub timestamp: String,
+}
+
+impl TransferReceipt {
+    pub(crate) fn new(id: u64, amount: Money, from: XorName, to: XorName) -> Self {
+        Self {
+            id,
+            amount: amount.to_string(),
+            from,
+            to,
+            timestamp: gen_timestamp_secs(),
+        }
+    }
+}
+
+/// How the spendable balances of a Wallet are combined to cover the amount of a transfer
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoinSelection {
+    /// Spend from the balances with the most safecoins first
+    LargestFirst,
+    /// Spend from as few balances as possible, and from the smallest one able to cover
+    /// what's left of the amount, to keep the rest of the large balances untouched
+    FewestKeys,
+    /// Spend only from the balances with the given names, in the given order
+    Named(Vec<String>),
+}
+
+impl FromStr for CoinSelection {
+    type Err = Error;
+
+    // Supported values are "largest-first", "fewest-keys" and "named:<name>,<name>,..."
+    fn from_str(str: &str) -> Result<Self> {
+        match str {
+            "largest-first" => Ok(Self::LargestFirst),
+            "fewest-keys" => Ok(Self::FewestKeys),
+            other if other.starts_with("named:") && other.len() > "named:".len() => {
+                let mut names = Vec::<String>::new();
+                for name in other["named:".len()..].split(',') {
+                    if name.is_empty() {
+                        return Err(Error::InvalidInput(format!(
+                            "Invalid coin selection '{}'. The names of the spendable balances cannot be empty",
+                            other
+                        )));
+                    }
+                    if names.iter().any(|n| n == name) {
+                        return Err(Error::InvalidInput(format!(
+                            "Invalid coin selection '{}'. The spendable balance '{}' is named more than once",
+                            other, name
+                        )));
+                    }
+                    names.push(name.to_string());
+                }
+                Ok(Self::Named(names))
+            }
+            other => Err(Error::InvalidInput(format!(
+                "Invalid coin selection '{}'. Supported values are 'largest-first', 'fewest-keys' and 'named:<name>,<name>,...'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Outcome of sweeping one of the spendable balances of a Wallet
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum SweepOutcome {
+    /// The whole balance was transferred to the destination
+    Transferred(TransferReceipt),
+    /// Nothing was transferred as there was nothing to sweep, with the reason
+    Skipped(String),
+    /// The transfer failed and the balance was left untouched, with the error
+    Failed(String),
+}
+
+/// Outcome of sweeping each of the spendable balances of a Wallet, by name
+pub type WalletSweepOutcomes = BTreeMap<String, SweepOutcome>;
+
+/// Outcome of a transfer made from several spendable balances of a Wallet
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct SelectionTransferOutcome {
+    /// The transfers made, along with the name of the spendable balance each was made from
+    pub receipts: Vec<(String, TransferReceipt)>,
+    /// The spendable balance which transfer failed, if any, with the error. Once a transfer
+    /// fails no more are made from the rest of the selected spendable balances
+    pub failed: Option<(String, String)>,
+}
+
+/// Whether safecoins were received or sent in a transfer of a history
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum TransferDirection {
+    Incoming,
+    Outgoing,
+}
+
+/// A transfer in the history of a SafeKey or Wallet
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct TransferHistoryEntry {
+    /// Id of the transfer, as set by the network when it was made
+    pub id: u64,
+    pub direction: TransferDirection,
+    /// Amount of safecoins transferred, e.g. "1.500000000"
+    pub amount: String,
+    /// XorName of the SafeKey the safecoins were transferred from,
+    /// none if they were credited by the network, e.g. a farming payout
+    pub from: Option<XorName>,
+    /// XorName of the SafeKey the safecoins were transferred to
+    pub to: XorName,
+    /// Balance of the SafeKey, or total balance of the Wallet, after the transfer
+    pub balance: String,
+    /// Name of the Wallet's spendable balance the transfer was made from/to,
+    /// none in the history of a SafeKey
+    pub spendable_balance: Option<String>,
+    /// Time the transfer was made at, in RFC 3339 format
+    pub timestamp: String,
+}
+
 impl Safe {
     // Create an empty Wallet and return its XOR-URL
     pub async fn wallet_create(&mut self) -> Result<XorUrl> {
@@ -113,88 +241,119 @@ impl Safe {
         );
 
         if default {
-            match self
-                .safe_client
-                .map_insert(
-                    xorurl_encoder.xorname(),
-                    WALLET_TYPE_TAG,
-                    WALLET_DEFAULT_BYTES,
-                    &md_key.to_string().into_bytes(),
-                )
-                .await
-            {
-                Err(Error::EntryExists(_)) => {
-                    let (_, version) = self.wallet_get_default_balance(url).await?;
-                    self.safe_client
-                        .update_map(
-                            xorurl_encoder.xorname(),
-                            WALLET_TYPE_TAG,
-                            WALLET_DEFAULT_BYTES,
-                            &md_key.to_string().into_bytes(),
-                            version + 1,
-                        )
-                        .await
-                }
-                other => other,
-            }?;
-
+            wallet_set_default_name(self, xorurl_encoder.xorname(), WALLET_TYPE_TAG, md_key)
+                .await?;
             debug!("Default wallet set.");
         }
 
         Ok(md_key.to_string())
     }
 
-    // Check the total balance of a Wallet found at a given XOR-URL
-    pub async fn wallet_balance(&mut self, url: &str) -> Result<String> {
-        debug!("Finding total wallet balance for: {:?}", url);
-        let mut total_balance = Money::from_nano(0);
+    // Set the spendable balance with the given name as the default one of a Wallet
+    pub async fn wallet_set_default(&mut self, url: &str, name: &str) -> Result<()> {
+        let xorurl_encoder = resolve_wallet(self, url).await?;
+        let _ = wallet_get_named_balance(self, &xorurl_encoder, url, name).await?;
 
-        // Let's get the list of balances from the Wallet
-        let (xorurl_encoder, nrs_xorurl_encoder) = self.parse_and_resolve_url(url).await?;
+        wallet_set_default_name(
+            self,
+            xorurl_encoder.xorname(),
+            xorurl_encoder.type_tag(),
+            name,
+        )
+        .await?;
         debug!(
-            "Wallet URL was parsed and resolved to: {:?}",
-            xorurl_encoder
+            "Default spendable balance of Wallet at {} set to: {}",
+            url, name
         );
-        let url_path = if let Some(nrs_url) = nrs_xorurl_encoder {
-            nrs_url.path().to_string()
-        } else {
-            xorurl_encoder.path().to_string()
-        };
+        Ok(())
+    }
 
-        let balances = if url_path.is_empty() {
-            debug!("We'll check the total balance of the Wallet");
-            gen_wallet_spendable_balances_list(
-                self,
-                xorurl_encoder.xorname(),
-                xorurl_encoder.type_tag(),
-                url,
-            )
-            .await?
-        } else {
-            let balance_name = &url_path[1..]; // we get rid of starting '/'
-            debug!(
-                "We'll check only the balance for spendable balance named: '{}'",
-                balance_name
-            );
-            let (spendable_balance, _) = wallet_get_spendable_balance(
-                self,
-                xorurl_encoder.xorname(),
-                xorurl_encoder.type_tag(),
-                balance_name.as_bytes(),
-            )
-            .await
-            .map_err(|_| {
-                Error::InvalidInput(format!(
-                    "No spendable balance named '{}' found in Wallet: '{}'",
-                    balance_name, url
-                ))
-            })?;
+    // Give a new friendly name to a spendable balance of a Wallet,
+    // it remains the default one if it was
+    pub async fn wallet_rename(&mut self, url: &str, name: &str, new_name: &str) -> Result<()> {
+        if new_name.is_empty() || new_name.as_bytes() == WALLET_DEFAULT_BYTES {
+            return Err(Error::InvalidInput(format!(
+                "'{}' is not a valid name for a spendable balance",
+                new_name
+            )));
+        }
 
-            let mut balances = WalletSpendableBalances::default();
-            balances.insert(balance_name.to_string(), (false, spendable_balance));
-            balances
-        };
+        let xorurl_encoder = resolve_wallet(self, url).await?;
+        let (xorname, type_tag) = (xorurl_encoder.xorname(), xorurl_encoder.type_tag());
+        let (spendable_balance, version) =
+            wallet_get_named_balance(self, &xorurl_encoder, url, name).await?;
+        if name == new_name {
+            return Ok(());
+        }
+
+        wallet_move_balance(
+            self,
+            xorname,
+            type_tag,
+            name,
+            new_name,
+            &spendable_balance,
+            version,
+        )
+        .await?;
+        debug!(
+            "Spendable balance '{}' of Wallet at {} renamed to: {}",
+            name, url, new_name
+        );
+        Ok(())
+    }
+
+    // Remove a spendable balance from a Wallet. The default spendable balance can only be
+    // removed if another one is set as the new default.
+    pub async fn wallet_remove(
+        &mut self,
+        url: &str,
+        name: &str,
+        new_default: Option<&str>,
+    ) -> Result<()> {
+        let xorurl_encoder = resolve_wallet(self, url).await?;
+        let (xorname, type_tag) = (xorurl_encoder.xorname(), xorurl_encoder.type_tag());
+        let (_, version) = wallet_get_named_balance(self, &xorurl_encoder, url, name).await?;
+
+        match new_default {
+            Some(new_default) if new_default == name => {
+                return Err(Error::InvalidInput(format!(
+                    "The spendable balance '{}' being removed cannot be set as the new default",
+                    name
+                )))
+            }
+            Some(new_default) => {
+                let _ = wallet_get_named_balance(self, &xorurl_encoder, url, new_default).await?;
+            }
+            None => {
+                if let Some((default_name, _)) =
+                    wallet_get_default_name(self, xorname, type_tag).await?
+                {
+                    if default_name == name {
+                        return Err(Error::InvalidInput(format!(
+                            "The spendable balance '{}' is the default of the Wallet, a new default needs to be set in order to remove it",
+                            name
+                        )));
+                    }
+                }
+            }
+        }
+
+        wallet_remove_balance(self, xorname, type_tag, name, new_default, version).await?;
+        debug!(
+            "Spendable balance '{}' removed from Wallet at {}",
+            name, url
+        );
+        Ok(())
+    }
+
+    // Check the total balance of a Wallet found at a given XOR-URL
+    pub async fn wallet_balance(&mut self, url: &str) -> Result<String> {
+        debug!("Finding total wallet balance for: {:?}", url);
+        let mut total_balance = Money::from_nano(0);
 
+        // Let's get the list of balances from the Wallet
+        let balances = wallet_spendable_balances_for_url(self, url).await?;
         debug!("Spendable balances to check: {:?}", balances);
         // Iterate through the Keys and query the balance for each
         for (name, (_, balance)) in balances.iter() {
@@ -225,6 +384,64 @@ impl Safe {
         Ok(total_balance.to_string())
     }
 
+    /// # Get the history of transfers of a Wallet
+    ///
+    /// Transfers made from and to all the spendable balances of the Wallet, oldest first,
+    /// or only the ones of a spendable balance if its name is set as the path of the URL.
+    /// Each of them comes with the total balance of the Wallet right after it was made.
+    /// Only the local storage backends keep the history of transfers for now, with the
+    /// SAFE Network backend an error is returned.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::Safe;
+    /// let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let wallet_xorurl = safe.wallet_create().await.unwrap();
+    ///     let (_key_xorurl, keypair) = safe.keys_create_preload_test_coins("14").await.unwrap();
+    ///     safe.wallet_insert(
+    ///         &wallet_xorurl,
+    ///         Some("mybalance"),
+    ///         true,
+    ///         &keypair.secret_key().unwrap().to_string(),
+    ///     ).await.unwrap();
+    ///     let (key2_xorurl, _) = safe.keys_create_preload_test_coins("1").await.unwrap();
+    ///     let receipt = safe.wallet_transfer("10", Some(&wallet_xorurl), &key2_xorurl).await.unwrap();
+    ///
+    ///     let history = safe.wallet_history(&wallet_xorurl).await.unwrap();
+    ///     assert_eq!(history.len(), 2);
+    ///     assert_eq!(history[1].id, receipt.id);
+    ///     assert_eq!(history[1].balance, "4.000000000");
+    /// # });
+    /// ```
+    pub async fn wallet_history(&mut self, url: &str) -> Result<Vec<TransferHistoryEntry>> {
+        let balances = wallet_spendable_balances_for_url(self, url).await?;
+
+        let mut transfers = vec![];
+        for (name, (_, balance)) in balances.into_iter() {
+            let keypair = Keypair::from(ed_sk_from_hex(&balance.sk)?);
+            let xorname = XorName::from(keypair.public_key());
+            let records = self
+                .safe_client
+                .transfer_history(keypair)
+                .await
+                .map_err(|err| {
+                    Error::ContentError(format!(
+                        "Failed to retrieve transfers of spendable balance '{}': {}",
+                        name, err
+                    ))
+                })?;
+            transfers.extend(
+                records
+                    .into_iter()
+                    .map(|record| (Some(name.clone()), xorname, record)),
+            );
+        }
+
+        gen_transfer_history(transfers)
+    }
+
     pub async fn wallet_get_default_balance(
         &mut self,
         url: &str,
@@ -300,7 +517,8 @@ impl Safe {
     ///     ).await.unwrap();
     ///
     ///
-    ///     safe.wallet_transfer( "10", Some(&wallet_xorurl), &wallet_xorurl2, None ).await.unwrap();
+    ///     let receipt = safe.wallet_transfer( "10", Some(&wallet_xorurl), &wallet_xorurl2 ).await.unwrap();
+    ///     assert_eq!("10.000000000", receipt.amount);
     ///     let from_balance = safe.keys_balance_from_url( &key1_xorurl, &key_pair1.unwrap().sk ).await.unwrap();
     ///     assert_eq!("4.000000000", from_balance);
     ///     let to_balance = safe.keys_balance_from_url( &key2_xorurl, &key_pair2.unwrap().sk ).await.unwrap();
@@ -312,9 +530,7 @@ impl Safe {
         amount: &str,
         from_url: Option<&str>,
         to_url: &str,
-        // tx_id: Option<u64>,
-        // TODO: return tx id if that stillmakes sense
-    ) -> Result<()> {
+    ) -> Result<TransferReceipt> {
         // Parse and validate the amount is valid
         let amount_coins = parse_coins_amount(amount)?;
 
@@ -343,29 +559,7 @@ impl Safe {
         }?;
 
         // Now check if the 'to_url' is a valid Wallet or a SafeKey URL
-        let (to_xorurl_encoder, to_nrs_xorurl_encoder) =
-            self.parse_and_resolve_url(to_url).await.map_err(|_| {
-                Error::InvalidInput(format!("Failed to parse the 'to_url' URL: {}", to_url))
-            })?;
-
-        let to_xorname = if to_xorurl_encoder.content_type() == SafeContentType::Wallet {
-            let to_wallet_balance =
-                resolve_wallet_url(self, to_url, to_xorurl_encoder, to_nrs_xorurl_encoder).await?;
-            XorUrlEncoder::from_url(&to_wallet_balance.xorurl)?.xorname()
-        } else if to_xorurl_encoder.content_type() == SafeContentType::Raw
-            && to_xorurl_encoder.data_type() == SafeDataType::SafeKey
-        {
-            to_xorurl_encoder.xorname()
-        } else {
-            return Err(Error::InvalidInput(format!(
-                "The destination URL doesn't target a SafeKey or Wallet, target is: {:?} ({})",
-                to_xorurl_encoder.content_type(),
-                to_xorurl_encoder.data_type()
-            )));
-        };
-
-        // Generate a random transfer TX ID
-        // let tx_id = tx_id.unwrap_or_else(|| rand::thread_rng().next_u64());
+        let to_xorname = resolve_transfer_destination(self, to_url).await?;
 
         let from_wallet_balance = resolve_wallet_url(
             self,
@@ -376,6 +570,7 @@ impl Safe {
         .await?;
         let from_sk = ed_sk_from_hex(&from_wallet_balance.sk)?;
         let keypair = Keypair::from(from_sk);
+        let from_xorname = XorName::from(keypair.public_key());
         // Finally, let's make the transfer
         match self
             .safe_client
@@ -394,10 +589,214 @@ impl Safe {
                 "Unexpected error when attempting to transfer: {}",
                 other_error
             ))),
-            // TODO: return tx id...
-            Ok(_tx) => Ok(()),
-            // Ok(tx) => Ok(tx.id),
+            Ok(id) => Ok(TransferReceipt::new(
+                id,
+                amount_coins,
+                from_xorname,
+                to_xorname,
+            )),
+        }
+    }
+
+    /// # Transfer safecoins from several spendable balances of a Wallet
+    ///
+    /// Unlike `wallet_transfer`, which spends only from a single spendable balance, the
+    /// spendable balances of the source Wallet are combined as per the coin selection strategy
+    /// to cover the amount, making a transfer from each of them. The receipts of the transfers
+    /// are returned along with the name of the spendable balance each was made from.
+    /// If a transfer fails after others were already made, the receipts of those are returned
+    /// along with the spendable balance which failed, as they cannot be undone. An error is
+    /// returned only if no transfer was made at all.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::{wallet::CoinSelection, Safe};
+    /// let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let wallet_xorurl = safe.wallet_create().await.unwrap();
+    ///     let (_key1_xorurl, keypair1) = safe.keys_create_preload_test_coins("14").await.unwrap();
+    ///     let (_key2_xorurl, keypair2) = safe.keys_create_preload_test_coins("5").await.unwrap();
+    ///     safe.wallet_insert(
+    ///         &wallet_xorurl,
+    ///         Some("balance1"),
+    ///         true,
+    ///         &keypair1.secret_key().unwrap().to_string(),
+    ///     ).await.unwrap();
+    ///     safe.wallet_insert(
+    ///         &wallet_xorurl,
+    ///         Some("balance2"),
+    ///         false,
+    ///         &keypair2.secret_key().unwrap().to_string(),
+    ///     ).await.unwrap();
+    ///
+    ///     let (key_xorurl, _) = safe.keys_create_preload_test_coins("0.5").await.unwrap();
+    ///     let outcome = safe.wallet_transfer_with_selection(
+    ///         "16",
+    ///         &wallet_xorurl,
+    ///         &key_xorurl,
+    ///         &CoinSelection::LargestFirst,
+    ///     ).await.unwrap();
+    ///     assert_eq!(outcome.failed, None);
+    ///     let receipts = outcome.receipts;
+    ///     assert_eq!(receipts.len(), 2);
+    ///     assert_eq!(receipts[0].0, "balance1");
+    ///     assert_eq!(receipts[1].1.amount, "2.000000000");
+    ///     let current_balance = safe.wallet_balance(&wallet_xorurl).await.unwrap();
+    ///     assert_eq!("3.000000000", current_balance);
+    /// # });
+    /// ```
+    pub async fn wallet_transfer_with_selection(
+        &mut self,
+        amount: &str,
+        from_url: &str,
+        to_url: &str,
+        selection: &CoinSelection,
+    ) -> Result<SelectionTransferOutcome> {
+        // Parse and validate the amount is valid
+        let amount_coins = parse_coins_amount(amount)?;
+        if amount_coins == Money::from_nano(0) {
+            return Err(Error::InvalidAmount(format!(
+                "The amount '{}' specified for the transfer is invalid",
+                amount
+            )));
+        }
+
+        check_wallet_url(self, from_url).await?;
+        let to_xorname = resolve_transfer_destination(self, to_url).await?;
+        let balances = wallet_spendable_balances_for_url(self, from_url).await?;
+
+        if let CoinSelection::Named(names) = selection {
+            if let Some(name) = names.iter().find(|name| !balances.contains_key(*name)) {
+                return Err(Error::InvalidInput(format!(
+                    "No spendable balance named '{}' found in Wallet: '{}'",
+                    name, from_url
+                )));
+            }
+        }
+
+        // The destination is never spent from, even if it's one of the Wallet's balances
+        let mut keypairs = BTreeMap::new();
+        let mut available = vec![];
+        for (name, (_, balance)) in balances.into_iter() {
+            let keypair = Keypair::from(ed_sk_from_hex(&balance.sk)?);
+            if XorName::from(keypair.public_key()) == to_xorname {
+                continue;
+            }
+            let current_balance = self
+                .safe_client
+                .read_balance_from_keypair(keypair.clone())
+                .await?;
+            available.push((name.clone(), current_balance));
+            let _ = keypairs.insert(name, keypair);
+        }
+
+        let selected = select_spendable_balances(available, amount_coins, selection)
+            .ok_or_else(|| {
+                Error::NotEnoughBalance(format!(
+                    "Not enough balance for the transfer at the selected spendable balances of Wallet \"{}\"",
+                    from_url
+                ))
+            })?;
+        debug!(
+            "Spendable balances selected for the transfer: {:?}",
+            selected
+        );
+
+        let mut receipts: Vec<(String, TransferReceipt)> = vec![];
+        for (name, amount_from_balance) in selected.into_iter() {
+            let keypair = keypairs.remove(&name).ok_or_else(|| {
+                Error::Unexpected(format!("Spendable balance '{}' was not read", name))
+            })?;
+            let from_xorname = XorName::from(keypair.public_key());
+            match self
+                .safe_client
+                .safecoin_transfer_to_xorname(Some(keypair), to_xorname, amount_from_balance)
+                .await
+            {
+                Ok(id) => receipts.push((
+                    name,
+                    TransferReceipt::new(id, amount_from_balance, from_xorname, to_xorname),
+                )),
+                Err(err) if receipts.is_empty() => return Err(err),
+                Err(err) => {
+                    debug!(
+                        "Transfer from spendable balance '{}' failed after {} were made: {}",
+                        name,
+                        receipts.len(),
+                        err
+                    );
+                    return Ok(SelectionTransferOutcome {
+                        receipts,
+                        failed: Some((name, err.to_string())),
+                    });
+                }
+            }
+        }
+
+        Ok(SelectionTransferOutcome {
+            receipts,
+            failed: None,
+        })
+    }
+
+    /// # Move all safecoins of a Wallet to another Wallet or SafeKey
+    ///
+    /// The whole balance of each of the spendable balances of the source Wallet, or only of the
+    /// one which name is set as the path of the URL, is transferred to the destination.
+    /// Transfers currently have no fees, thus no amount is kept back for them.
+    /// Each spendable balance is swept with a single transfer, so if it fails the balance is left
+    /// untouched and the rest of them are still swept. The outcome of each is returned by name.
+    ///
+    /// ## Example
+    /// ```
+    /// # use sn_api::{wallet::SweepOutcome, Safe};
+    /// let mut safe = Safe::default();
+    /// # async_std::task::block_on(async {
+    /// #   safe.connect("", Some("fake-credentials")).await.unwrap();
+    ///     let wallet_xorurl = safe.wallet_create().await.unwrap();
+    ///     let (_key1_xorurl, keypair1) = safe.keys_create_preload_test_coins("14").await.unwrap();
+    ///     let (_key2_xorurl, keypair2) = safe.keys_create_preload_test_coins("1").await.unwrap();
+    ///     safe.wallet_insert(
+    ///         &wallet_xorurl,
+    ///         Some("balance1"),
+    ///         true,
+    ///         &keypair1.secret_key().unwrap().to_string(),
+    ///     ).await.unwrap();
+    ///     safe.wallet_insert(
+    ///         &wallet_xorurl,
+    ///         Some("balance2"),
+    ///         false,
+    ///         &keypair2.secret_key().unwrap().to_string(),
+    ///     ).await.unwrap();
+    ///
+    ///     let (key_xorurl, _) = safe.keys_create_preload_test_coins("0.5").await.unwrap();
+    ///     let outcomes = safe.wallet_sweep(&wallet_xorurl, &key_xorurl).await.unwrap();
+    ///     match &outcomes["balance1"] {
+    ///         SweepOutcome::Transferred(receipt) => assert_eq!(receipt.amount, "14.000000000"),
+    ///         other => panic!("Unexpected outcome: {:?}", other),
+    ///     }
+    ///     let current_balance = safe.wallet_balance(&wallet_xorurl).await.unwrap();
+    ///     assert_eq!("0.000000000", current_balance);
+    /// # });
+    /// ```
+    pub async fn wallet_sweep(
+        &mut self,
+        from_url: &str,
+        to_url: &str,
+    ) -> Result<WalletSweepOutcomes> {
+        check_wallet_url(self, from_url).await?;
+        let to_xorname = resolve_transfer_destination(self, to_url).await?;
+        let balances = wallet_spendable_balances_for_url(self, from_url).await?;
+
+        let mut outcomes = WalletSweepOutcomes::default();
+        for (name, (_, balance)) in balances.into_iter() {
+            let outcome = sweep_spendable_balance(self, &balance, to_xorname).await;
+            debug!("Spendable balance '{}' swept: {:?}", name, outcome);
+            let _ = outcomes.insert(name, outcome);
         }
+
+        Ok(outcomes)
     }
 
     pub async fn wallet_get(&mut self, url: &str) -> Result<WalletSpendableBalances> {
@@ -420,46 +819,147 @@ impl Safe {
     }
 }
 
-// Private helper to generate the list of SpendableBalances which is used for different purposes
-async fn gen_wallet_spendable_balances_list(
+// Private helper to get the list of SpendableBalances of a Wallet, or only the one which name is
+// set as the path of the URL
+async fn wallet_spendable_balances_for_url(
     safe: &mut Safe,
-    xorname: XorName,
-    type_tag: u64,
     url: &str,
 ) -> Result<WalletSpendableBalances> {
-    let entries = match safe.safe_client.list_map_entries(xorname, type_tag).await {
-        Ok(entries) => entries,
-        Err(Error::AccessDenied(_)) => {
-            return Err(Error::AccessDenied(format!(
-                "Couldn't read Wallet at \"{}\"",
-                url
-            )))
-        }
-        Err(Error::ContentNotFound(_)) => {
-            return Err(Error::ContentNotFound(format!(
-                "No Wallet found at {}",
-                url
-            )))
-        }
-        Err(err) => {
-            return Err(Error::ContentError(format!(
-                "Failed to read balances from Wallet: {}",
-                err
-            )))
-        }
+    let (xorurl_encoder, nrs_xorurl_encoder) = safe.parse_and_resolve_url(url).await?;
+    debug!(
+        "Wallet URL was parsed and resolved to: {:?}",
+        xorurl_encoder
+    );
+    let url_path = if let Some(nrs_url) = nrs_xorurl_encoder {
+        nrs_url.path().to_string()
+    } else {
+        xorurl_encoder.path().to_string()
     };
 
-    let mut balances = WalletSpendableBalances::default();
-    let mut default_balance = "".to_string();
-    for (key, value) in entries.iter() {
-        let value_str = String::from_utf8_lossy(&value.data).to_string();
-        if key.as_slice() == WALLET_DEFAULT_BYTES {
-            default_balance = value_str;
-        } else {
-            let spendable_balance: WalletSpendableBalance = serde_json::from_str(&value_str)
-                .map_err(|_| {
-                    Error::ContentError(
-                        "Couldn't deserialise data stored in the Wallet".to_string(),
+    if url_path.is_empty() {
+        debug!("We'll use all the spendable balances of the Wallet");
+        gen_wallet_spendable_balances_list(
+            safe,
+            xorurl_encoder.xorname(),
+            xorurl_encoder.type_tag(),
+            url,
+        )
+        .await
+    } else {
+        let balance_name = &url_path[1..]; // we get rid of starting '/'
+        debug!(
+            "We'll use only the spendable balance named: '{}'",
+            balance_name
+        );
+        let (spendable_balance, _) = wallet_get_spendable_balance(
+            safe,
+            xorurl_encoder.xorname(),
+            xorurl_encoder.type_tag(),
+            balance_name.as_bytes(),
+        )
+        .await
+        .map_err(|_| {
+            Error::InvalidInput(format!(
+                "No spendable balance named '{}' found in Wallet: '{}'",
+                balance_name, url
+            ))
+        })?;
+
+        let mut balances = WalletSpendableBalances::default();
+        balances.insert(balance_name.to_string(), (false, spendable_balance));
+        Ok(balances)
+    }
+}
+
+// Helper to generate a history out of the transfers of one or more SafeKeys, each of them
+// with the name of the spendable balance and the XorName of the SafeKey it was retrieved for.
+// The running balance is the sum of the balances of all the SafeKeys.
+pub(crate) fn gen_transfer_history(
+    mut transfers: Vec<(Option<String>, XorName, TransferRecord)>,
+) -> Result<Vec<TransferHistoryEntry>> {
+    transfers.sort_by(|(_, _, a), (_, _, b)| (&a.timestamp, a.id).cmp(&(&b.timestamp, b.id)));
+
+    let mut balance = Money::from_nano(0);
+    let mut history = vec![];
+    for (spendable_balance, xorname, record) in transfers.into_iter() {
+        // a transfer between SafeKeys of the same Wallet is retrieved once for each of them,
+        // and a transfer from a SafeKey to itself is both outgoing and incoming
+        let mut directions = vec![];
+        if record.from == Some(xorname) {
+            directions.push(TransferDirection::Outgoing);
+        }
+        if record.to == xorname {
+            directions.push(TransferDirection::Incoming);
+        }
+
+        for direction in directions {
+            let new_balance = match direction {
+                TransferDirection::Incoming => balance.checked_add(record.amount),
+                TransferDirection::Outgoing => balance.checked_sub(record.amount),
+            };
+            balance = new_balance.ok_or_else(|| {
+                Error::Unexpected(format!(
+                    "Failed to calculate balance after transfer with id {}",
+                    record.id
+                ))
+            })?;
+
+            history.push(TransferHistoryEntry {
+                id: record.id,
+                direction,
+                amount: record.amount.to_string(),
+                from: record.from,
+                to: record.to,
+                balance: balance.to_string(),
+                spendable_balance: spendable_balance.clone(),
+                timestamp: record.timestamp.clone(),
+            });
+        }
+    }
+
+    Ok(history)
+}
+
+// Private helper to generate the list of SpendableBalances which is used for different purposes
+async fn gen_wallet_spendable_balances_list(
+    safe: &mut Safe,
+    xorname: XorName,
+    type_tag: u64,
+    url: &str,
+) -> Result<WalletSpendableBalances> {
+    let entries = match safe.safe_client.list_map_entries(xorname, type_tag).await {
+        Ok(entries) => entries,
+        Err(Error::AccessDenied(_)) => {
+            return Err(Error::AccessDenied(format!(
+                "Couldn't read Wallet at \"{}\"",
+                url
+            )))
+        }
+        Err(Error::ContentNotFound(_)) => {
+            return Err(Error::ContentNotFound(format!(
+                "No Wallet found at {}",
+                url
+            )))
+        }
+        Err(err) => {
+            return Err(Error::ContentError(format!(
+                "Failed to read balances from Wallet: {}",
+                err
+            )))
+        }
+    };
+
+    let mut balances = WalletSpendableBalances::default();
+    let mut default_balance = "".to_string();
+    for (key, value) in entries.iter() {
+        let value_str = String::from_utf8_lossy(&value.data).to_string();
+        if key.as_slice() == WALLET_DEFAULT_BYTES {
+            default_balance = value_str;
+        } else {
+            let spendable_balance: WalletSpendableBalance = serde_json::from_str(&value_str)
+                .map_err(|_| {
+                    Error::ContentError(
+                        "Couldn't deserialise data stored in the Wallet".to_string(),
                     )
                 })?;
             let thename = String::from_utf8_lossy(key).to_string();
@@ -517,6 +1017,360 @@ async fn wallet_get_spendable_balance(
     Ok(the_balance)
 }
 
+// Private helper to resolve a URL which is expected to target a Wallet
+async fn resolve_wallet(safe: &mut Safe, url: &str) -> Result<XorUrlEncoder> {
+    let (xorurl_encoder, _) = safe.parse_and_resolve_url(url).await?;
+    if xorurl_encoder.content_type() == SafeContentType::Wallet {
+        Ok(xorurl_encoder)
+    } else {
+        Err(Error::InvalidInput(format!(
+            "The URL doesn't target a Wallet, it is: {:?} ({})",
+            xorurl_encoder.content_type(),
+            xorurl_encoder.data_type()
+        )))
+    }
+}
+
+// Private helper to fetch a spendable balance from a Wallet, failing if there is none with such name
+async fn wallet_get_named_balance(
+    safe: &mut Safe,
+    xorurl_encoder: &XorUrlEncoder,
+    url: &str,
+    name: &str,
+) -> Result<(WalletSpendableBalance, u64)> {
+    if name.as_bytes() == WALLET_DEFAULT_BYTES {
+        return Err(Error::InvalidInput(format!(
+            "No spendable balance named '{}' found in Wallet: '{}'",
+            name, url
+        )));
+    }
+
+    wallet_get_spendable_balance(
+        safe,
+        xorurl_encoder.xorname(),
+        xorurl_encoder.type_tag(),
+        name.as_bytes(),
+    )
+    .await
+    .map_err(|_| {
+        Error::InvalidInput(format!(
+            "No spendable balance named '{}' found in Wallet: '{}'",
+            name, url
+        ))
+    })
+}
+
+// Private helper to get the name of the default spendable balance of a Wallet, if it has one,
+// along with the version of its entry in the Wallet
+async fn wallet_get_default_name(
+    safe: &mut Safe,
+    xorname: XorName,
+    type_tag: u64,
+) -> Result<Option<(String, u64)>> {
+    match safe
+        .safe_client
+        .map_get_value(xorname, type_tag, WALLET_DEFAULT_BYTES)
+        .await
+    {
+        Ok(MapValue::Seq(value)) => Ok(Some((
+            String::from_utf8_lossy(&value.data).to_string(),
+            value.version,
+        ))),
+        Ok(MapValue::Unseq(_)) => Err(Error::ClientError(
+            "Wallet could not be parsed as wallet map is unsequenced.".to_string(),
+        )),
+        Err(Error::EntryNotFound(_)) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+// Private helper to set the name of the default spendable balance of a Wallet
+async fn wallet_set_default_name(
+    safe: &mut Safe,
+    xorname: XorName,
+    type_tag: u64,
+    name: &str,
+) -> Result<()> {
+    match wallet_get_default_name(safe, xorname, type_tag).await? {
+        Some((_, version)) => {
+            safe.safe_client
+                .update_map(
+                    xorname,
+                    type_tag,
+                    WALLET_DEFAULT_BYTES,
+                    name.as_bytes(),
+                    version + 1,
+                )
+                .await
+        }
+        None => {
+            safe.safe_client
+                .map_insert(xorname, type_tag, WALLET_DEFAULT_BYTES, name.as_bytes())
+                .await
+        }
+    }
+}
+
+// Private helper to move a spendable balance of a Wallet to a new name. It's inserted with the
+// new name before the default is updated and the old name removed, so the default always
+// points to an existing one. If any of the writes after the insert fails, the ones made are
+// undone so the Wallet is left as it was.
+async fn wallet_move_balance(
+    safe: &mut Safe,
+    xorname: XorName,
+    type_tag: u64,
+    name: &str,
+    new_name: &str,
+    spendable_balance: &WalletSpendableBalance,
+    version: u64,
+) -> Result<()> {
+    let serialised_value = serde_json::to_string(spendable_balance).map_err(|err| {
+        Error::Unexpected(format!(
+            "Failed to serialise data to insert in Wallet container: {:?}",
+            err
+        ))
+    })?;
+
+    safe.safe_client
+        .map_insert(
+            xorname,
+            type_tag,
+            new_name.as_bytes(),
+            serialised_value.as_bytes(),
+        )
+        .await
+        .map_err(|err| match err {
+            Error::EntryExists(_) => Error::EntryExists(format!(
+                "A spendable balance already exists in the Wallet with the same name: '{}'",
+                new_name
+            )),
+            other => other,
+        })?;
+
+    let mut default_moved = false;
+    let mut result = match wallet_get_default_name(safe, xorname, type_tag).await {
+        Ok(Some((default_name, _))) if default_name == name => {
+            let result = wallet_set_default_name(safe, xorname, type_tag, new_name).await;
+            default_moved = result.is_ok();
+            result
+        }
+        Ok(_) => Ok(()),
+        Err(err) => Err(err),
+    };
+    if result.is_ok() {
+        result = safe
+            .safe_client
+            .map_delete(xorname, type_tag, name.as_bytes(), version + 1)
+            .await;
+    }
+
+    let err = match result {
+        Ok(()) => return Ok(()),
+        Err(err) => err,
+    };
+    debug!(
+        "Failed to move spendable balance '{}' to '{}', undoing the changes made: {}",
+        name, new_name, err
+    );
+
+    // The entry with the new name is only removed once the default no longer points to it,
+    // it was inserted at version 0 so it's deleted with version 1
+    let mut undo = Ok(());
+    if default_moved {
+        undo = wallet_set_default_name(safe, xorname, type_tag, name).await;
+    }
+    if undo.is_ok() {
+        undo = safe
+            .safe_client
+            .map_delete(xorname, type_tag, new_name.as_bytes(), 1)
+            .await;
+    }
+
+    match undo {
+        Ok(()) => Err(err),
+        Err(undo_err) => Err(Error::Unexpected(format!(
+            "Failed to rename spendable balance '{}' to '{}': {}. The changes made could not be undone, the spendable balance may remain with both names: {}",
+            name, new_name, err, undo_err
+        ))),
+    }
+}
+
+// Private helper to remove a spendable balance from a Wallet, setting the new default first
+// if one is provided. If the removal fails the previous default is set back, so the Wallet is
+// left as it was. A default set on a Wallet which had none is kept, as the spendable balance
+// it points to still exists.
+async fn wallet_remove_balance(
+    safe: &mut Safe,
+    xorname: XorName,
+    type_tag: u64,
+    name: &str,
+    new_default: Option<&str>,
+    version: u64,
+) -> Result<()> {
+    let prev_default = match new_default {
+        Some(new_default) => {
+            let prev_default = wallet_get_default_name(safe, xorname, type_tag).await?;
+            wallet_set_default_name(safe, xorname, type_tag, new_default).await?;
+            prev_default
+        }
+        None => None,
+    };
+
+    let err = match safe
+        .safe_client
+        .map_delete(xorname, type_tag, name.as_bytes(), version + 1)
+        .await
+    {
+        Ok(()) => return Ok(()),
+        Err(err) => err,
+    };
+    let prev_default_name = match prev_default {
+        Some((prev_default_name, _)) => prev_default_name,
+        None => return Err(err),
+    };
+    debug!(
+        "Failed to remove spendable balance '{}', setting '{}' back as the default: {}",
+        name, prev_default_name, err
+    );
+
+    match wallet_set_default_name(safe, xorname, type_tag, &prev_default_name).await {
+        Ok(()) => Err(err),
+        Err(undo_err) => Err(Error::Unexpected(format!(
+            "Failed to remove spendable balance '{}': {}. The previous default '{}' could not be set back: {}",
+            name, err, prev_default_name, undo_err
+        ))),
+    }
+}
+
+// Private helper to check the source URL of a transfer targets a Wallet
+async fn check_wallet_url(safe: &mut Safe, from_url: &str) -> Result<()> {
+    let (from_xorurl_encoder, _) = safe.parse_and_resolve_url(from_url).await.map_err(|_| {
+        Error::InvalidInput(format!("Failed to parse the 'from_url' URL: {}", from_url))
+    })?;
+
+    if from_xorurl_encoder.content_type() == SafeContentType::Wallet {
+        Ok(())
+    } else {
+        Err(Error::InvalidInput(format!(
+            "The 'from_url' URL doesn't target a Wallet, it is: {:?} ({})",
+            from_xorurl_encoder.content_type(),
+            from_xorurl_encoder.data_type()
+        )))
+    }
+}
+
+// Private helper to choose the spendable balances, and the amount to spend from each of them,
+// to cover the amount of a transfer. None is returned if they are not enough to cover it.
+fn select_spendable_balances(
+    mut available: Vec<(String, Money)>,
+    amount: Money,
+    selection: &CoinSelection,
+) -> Option<Vec<(String, Money)>> {
+    let ordered = match selection {
+        CoinSelection::LargestFirst | CoinSelection::FewestKeys => {
+            available.sort_by(|(name_a, a), (name_b, b)| {
+                b.as_nano().cmp(&a.as_nano()).then(name_a.cmp(name_b))
+            });
+            available
+        }
+        CoinSelection::Named(names) => names
+            .iter()
+            .filter_map(|name| available.iter().find(|(n, _)| n == name).cloned())
+            .collect(),
+    };
+
+    let mut selected = vec![];
+    let mut remaining = amount.as_nano();
+    for (index, (name, balance)) in ordered.iter().enumerate() {
+        if balance.as_nano() == 0 {
+            continue;
+        }
+
+        if balance.as_nano() >= remaining {
+            // the smallest of the balances left which covers the rest of the amount,
+            // as they are ordered by balance it's the last one which does
+            let name = match selection {
+                CoinSelection::FewestKeys => ordered[index..]
+                    .iter()
+                    .filter(|(_, balance)| balance.as_nano() >= remaining)
+                    .last()
+                    .map_or(name, |(name, _)| name),
+                _ => name,
+            };
+            selected.push((name.clone(), Money::from_nano(remaining)));
+            return Some(selected);
+        }
+
+        selected.push((name.clone(), *balance));
+        remaining -= balance.as_nano();
+    }
+
+    None
+}
+
+// Private helper to transfer the whole balance of a spendable balance with a single transfer
+async fn sweep_spendable_balance(
+    safe: &mut Safe,
+    balance: &WalletSpendableBalance,
+    to_xorname: XorName,
+) -> SweepOutcome {
+    let keypair = match ed_sk_from_hex(&balance.sk) {
+        Ok(sk) => Keypair::from(sk),
+        Err(err) => return SweepOutcome::Failed(err.to_string()),
+    };
+    let from_xorname = XorName::from(keypair.public_key());
+    if from_xorname == to_xorname {
+        return SweepOutcome::Skipped("it's the destination of the sweep".to_string());
+    }
+
+    let amount = match safe
+        .safe_client
+        .read_balance_from_keypair(keypair.clone())
+        .await
+    {
+        Ok(amount) => amount,
+        Err(err) => return SweepOutcome::Failed(err.to_string()),
+    };
+    if amount == Money::from_nano(0) {
+        return SweepOutcome::Skipped("its balance is zero".to_string());
+    }
+
+    match safe
+        .safe_client
+        .safecoin_transfer_to_xorname(Some(keypair), to_xorname, amount)
+        .await
+    {
+        Ok(id) => {
+            SweepOutcome::Transferred(TransferReceipt::new(id, amount, from_xorname, to_xorname))
+        }
+        Err(err) => SweepOutcome::Failed(err.to_string()),
+    }
+}
+
+// Private helper to get the SafeKey a transfer to a Wallet or SafeKey URL is made to
+async fn resolve_transfer_destination(safe: &mut Safe, to_url: &str) -> Result<XorName> {
+    let (to_xorurl_encoder, to_nrs_xorurl_encoder) =
+        safe.parse_and_resolve_url(to_url).await.map_err(|_| {
+            Error::InvalidInput(format!("Failed to parse the 'to_url' URL: {}", to_url))
+        })?;
+
+    if to_xorurl_encoder.content_type() == SafeContentType::Wallet {
+        let to_wallet_balance =
+            resolve_wallet_url(safe, to_url, to_xorurl_encoder, to_nrs_xorurl_encoder).await?;
+        Ok(XorUrlEncoder::from_url(&to_wallet_balance.xorurl)?.xorname())
+    } else if to_xorurl_encoder.content_type() == SafeContentType::Raw
+        && to_xorurl_encoder.data_type() == SafeDataType::SafeKey
+    {
+        Ok(to_xorurl_encoder.xorname())
+    } else {
+        Err(Error::InvalidInput(format!(
+            "The destination URL doesn't target a SafeKey or Wallet, target is: {:?} ({})",
+            to_xorurl_encoder.content_type(),
+            to_xorurl_encoder.data_type()
+        )))
+    }
+}
+
 // Get a specific spendable balance if the URL has a path, or the default
 async fn resolve_wallet_url(
     safe: &mut Safe,
@@ -557,9 +1411,48 @@ async fn resolve_wallet_url(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::api::app::test_helpers::{new_safe_instance, random_nrs_name};
+    use crate::api::app::{
+        nrs_map::NrsLinkOptions,
+        test_helpers::{new_safe_instance, random_nrs_name},
+    };
     use std::sync::Arc;
 
+    #[test]
+    fn test_coin_selection_from_str() -> Result<()> {
+        assert_eq!(
+            CoinSelection::from_str("largest-first")?,
+            CoinSelection::LargestFirst
+        );
+        assert_eq!(
+            CoinSelection::from_str("fewest-keys")?,
+            CoinSelection::FewestKeys
+        );
+        assert_eq!(
+            CoinSelection::from_str("named:a,b")?,
+            CoinSelection::Named(vec!["a".to_string(), "b".to_string()])
+        );
+
+        for invalid in &[
+            "named:",
+            "named:a,",
+            "named:,a",
+            "named:a,,b",
+            "named:a,a",
+            "other",
+        ] {
+            match CoinSelection::from_str(invalid) {
+                Err(Error::InvalidInput(_)) => {}
+                other => {
+                    return Err(Error::Unexpected(format!(
+                        "Coin selection '{}' wasn't rejected: {:?}",
+                        invalid, other
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_wallet_create() -> Result<()> {
         let mut safe = new_safe_instance().await?;
@@ -628,57 +1521,281 @@ mod tests {
         let wallet_balances = safe.wallet_get(&wallet_xorurl).await?;
         assert_eq!(wallet_balances["my-first-balance"].0, true);
         assert_eq!(wallet_balances["my-first-balance"].1.xorurl, key1_xorurl);
-        assert_eq!(
-            wallet_balances["my-first-balance"].1.sk,
-            key_pair1.secret_key()?.to_string()
-        );
+        assert_eq!(
+            wallet_balances["my-first-balance"].1.sk,
+            key_pair1.secret_key()?.to_string()
+        );
+
+        assert_eq!(wallet_balances["my-second-balance"].0, false);
+        assert_eq!(wallet_balances["my-second-balance"].1.xorurl, key2_xorurl);
+        assert_eq!(
+            wallet_balances["my-second-balance"].1.sk,
+            key_pair2.secret_key()?.to_string()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wallet_insert_and_set_default() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let wallet_xorurl = safe.wallet_create().await?;
+        let (key1_xorurl, key_pair1) = safe.keys_create_preload_test_coins("65.82").await?;
+        let (key2_xorurl, key_pair2) = safe.keys_create_preload_test_coins("11.44").await?;
+
+        safe.wallet_insert(
+            &wallet_xorurl,
+            Some("my-first-balance"),
+            true,
+            &key_pair1.secret_key()?.to_string(),
+        )
+        .await?;
+
+        safe.wallet_insert(
+            &wallet_xorurl,
+            Some("my-second-balance"),
+            true,
+            &key_pair2.secret_key()?.to_string(),
+        )
+        .await?;
+
+        let wallet_balances = safe.wallet_get(&wallet_xorurl).await?;
+        assert_eq!(wallet_balances["my-first-balance"].0, false);
+        assert_eq!(wallet_balances["my-first-balance"].1.xorurl, key1_xorurl);
+        assert_eq!(
+            wallet_balances["my-first-balance"].1.sk,
+            key_pair1.secret_key()?.to_string()
+        );
+
+        assert_eq!(wallet_balances["my-second-balance"].0, true);
+        assert_eq!(wallet_balances["my-second-balance"].1.xorurl, key2_xorurl);
+        assert_eq!(
+            wallet_balances["my-second-balance"].1.sk,
+            key_pair2.secret_key()?.to_string()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wallet_remove_rename_and_set_default() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let wallet_xorurl = safe.wallet_create().await?;
+        let (key1_xorurl, key_pair1) = safe.keys_create_preload_test_coins("65.82").await?;
+        let (key2_xorurl, key_pair2) = safe.keys_create_preload_test_coins("11.44").await?;
+        safe.wallet_insert(
+            &wallet_xorurl,
+            Some("my-first-balance"),
+            true,
+            &key_pair1.secret_key()?.to_string(),
+        )
+        .await?;
+        safe.wallet_insert(
+            &wallet_xorurl,
+            Some("my-second-balance"),
+            false,
+            &key_pair2.secret_key()?.to_string(),
+        )
+        .await?;
+
+        // the default remains the same balance after it's renamed
+        safe.wallet_rename(&wallet_xorurl, "my-first-balance", "my-renamed-balance")
+            .await?;
+        let wallet_balances = safe.wallet_get(&wallet_xorurl).await?;
+        assert_eq!(wallet_balances.len(), 2);
+        assert!(!wallet_balances.contains_key("my-first-balance"));
+        assert_eq!(wallet_balances["my-renamed-balance"].0, true);
+        assert_eq!(wallet_balances["my-renamed-balance"].1.xorurl, key1_xorurl);
+
+        match safe
+            .wallet_rename(&wallet_xorurl, "my-second-balance", "my-renamed-balance")
+            .await
+        {
+            Err(Error::EntryExists(_)) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Rename to an existing name didn't fail: {:?}",
+                    other
+                )))
+            }
+        }
+
+        // the default can be set more than once
+        safe.wallet_set_default(&wallet_xorurl, "my-second-balance")
+            .await?;
+        safe.wallet_set_default(&wallet_xorurl, "my-renamed-balance")
+            .await?;
+        safe.wallet_set_default(&wallet_xorurl, "my-second-balance")
+            .await?;
+        let (default_balance, _) = safe.wallet_get_default_balance(&wallet_xorurl).await?;
+        assert_eq!(default_balance.xorurl, key2_xorurl);
+
+        match safe
+            .wallet_remove(&wallet_xorurl, "my-second-balance", None)
+            .await
+        {
+            Err(Error::InvalidInput(msg)) => assert!(msg.contains("new default")),
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Removal of the default balance didn't fail: {:?}",
+                    other
+                )))
+            }
+        }
+
+        safe.wallet_remove(
+            &wallet_xorurl,
+            "my-second-balance",
+            Some("my-renamed-balance"),
+        )
+        .await?;
+        let wallet_balances = safe.wallet_get(&wallet_xorurl).await?;
+        assert_eq!(wallet_balances.len(), 1);
+        assert_eq!(wallet_balances["my-renamed-balance"].0, true);
+        assert_eq!(safe.wallet_balance(&wallet_xorurl).await?, "65.820000000");
+
+        match safe
+            .wallet_remove(&wallet_xorurl, "my-second-balance", None)
+            .await
+        {
+            Err(Error::InvalidInput(_)) => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Removal of a missing balance didn't fail: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wallet_rename_failure_undone() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let wallet_xorurl = safe.wallet_create().await?;
+        let (key1_xorurl, key_pair1) = safe.keys_create_preload_test_coins("65.82").await?;
+        let (_, key_pair2) = safe.keys_create_preload_test_coins("11.44").await?;
+        safe.wallet_insert(
+            &wallet_xorurl,
+            Some("my-first-balance"),
+            true,
+            &key_pair1.secret_key()?.to_string(),
+        )
+        .await?;
+        safe.wallet_insert(
+            &wallet_xorurl,
+            Some("my-second-balance"),
+            false,
+            &key_pair2.secret_key()?.to_string(),
+        )
+        .await?;
+
+        // a stale version of the entry makes its removal fail, as if it was
+        // updated concurrently, after the default was already moved to the new name
+        let xorurl_encoder = resolve_wallet(&mut safe, &wallet_xorurl).await?;
+        let (xorname, type_tag) = (xorurl_encoder.xorname(), xorurl_encoder.type_tag());
+        let (spendable_balance, version) = wallet_get_named_balance(
+            &mut safe,
+            &xorurl_encoder,
+            &wallet_xorurl,
+            "my-first-balance",
+        )
+        .await?;
+        match wallet_move_balance(
+            &mut safe,
+            xorname,
+            type_tag,
+            "my-first-balance",
+            "my-renamed-balance",
+            &spendable_balance,
+            version + 1,
+        )
+        .await
+        {
+            Err(_) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Rename with a stale version didn't fail: {:?}",
+                    other
+                )))
+            }
+        }
+
+        let wallet_balances = safe.wallet_get(&wallet_xorurl).await?;
+        assert_eq!(wallet_balances.len(), 2);
+        assert!(!wallet_balances.contains_key("my-renamed-balance"));
+        assert_eq!(wallet_balances["my-first-balance"].0, true);
+        assert_eq!(wallet_balances["my-first-balance"].1.xorurl, key1_xorurl);
 
-        assert_eq!(wallet_balances["my-second-balance"].0, false);
-        assert_eq!(wallet_balances["my-second-balance"].1.xorurl, key2_xorurl);
-        assert_eq!(
-            wallet_balances["my-second-balance"].1.sk,
-            key_pair2.secret_key()?.to_string()
-        );
+        // the rename succeeds once retried
+        safe.wallet_rename(&wallet_xorurl, "my-first-balance", "my-renamed-balance")
+            .await?;
+        let (default_balance, _) = safe.wallet_get_default_balance(&wallet_xorurl).await?;
+        assert_eq!(default_balance.xorurl, key1_xorurl);
         Ok(())
     }
 
     #[tokio::test]
-    async fn test_wallet_insert_and_set_default() -> Result<()> {
+    async fn test_wallet_remove_failure_undone() -> Result<()> {
         let mut safe = new_safe_instance().await?;
         let wallet_xorurl = safe.wallet_create().await?;
         let (key1_xorurl, key_pair1) = safe.keys_create_preload_test_coins("65.82").await?;
         let (key2_xorurl, key_pair2) = safe.keys_create_preload_test_coins("11.44").await?;
-
         safe.wallet_insert(
             &wallet_xorurl,
             Some("my-first-balance"),
-            true,
+            true, // set --default
             &key_pair1.secret_key()?.to_string(),
         )
         .await?;
-
         safe.wallet_insert(
             &wallet_xorurl,
             Some("my-second-balance"),
-            true,
+            false,
             &key_pair2.secret_key()?.to_string(),
         )
         .await?;
 
+        // a stale version of the entry makes its removal fail, as if it was
+        // updated concurrently, after the default was already set to the new one
+        let xorurl_encoder = resolve_wallet(&mut safe, &wallet_xorurl).await?;
+        let (xorname, type_tag) = (xorurl_encoder.xorname(), xorurl_encoder.type_tag());
+        let (_, version) = wallet_get_named_balance(
+            &mut safe,
+            &xorurl_encoder,
+            &wallet_xorurl,
+            "my-first-balance",
+        )
+        .await?;
+        match wallet_remove_balance(
+            &mut safe,
+            xorname,
+            type_tag,
+            "my-first-balance",
+            Some("my-second-balance"),
+            version + 1,
+        )
+        .await
+        {
+            Err(_) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Removal with a stale version didn't fail: {:?}",
+                    other
+                )))
+            }
+        }
+
         let wallet_balances = safe.wallet_get(&wallet_xorurl).await?;
-        assert_eq!(wallet_balances["my-first-balance"].0, false);
+        assert_eq!(wallet_balances.len(), 2);
+        assert_eq!(wallet_balances["my-first-balance"].0, true);
         assert_eq!(wallet_balances["my-first-balance"].1.xorurl, key1_xorurl);
-        assert_eq!(
-            wallet_balances["my-first-balance"].1.sk,
-            key_pair1.secret_key()?.to_string()
-        );
+        assert_eq!(wallet_balances["my-second-balance"].0, false);
 
-        assert_eq!(wallet_balances["my-second-balance"].0, true);
-        assert_eq!(wallet_balances["my-second-balance"].1.xorurl, key2_xorurl);
-        assert_eq!(
-            wallet_balances["my-second-balance"].1.sk,
-            key_pair2.secret_key()?.to_string()
-        );
+        // the removal succeeds once retried
+        safe.wallet_remove(
+            &wallet_xorurl,
+            "my-first-balance",
+            Some("my-second-balance"),
+        )
+        .await?;
+        let (default_balance, _) = safe.wallet_get_default_balance(&wallet_xorurl).await?;
+        assert_eq!(default_balance.xorurl, key2_xorurl);
         Ok(())
     }
 
@@ -939,7 +2056,10 @@ mod tests {
                 "Transfer was expected to succeed: {}",
                 msg
             ))),
-            Ok(_) => {
+            Ok(receipt) => {
+                assert_eq!(receipt.amount, "523.870000000");
+                assert_eq!(receipt.from, XorName::from(key_pair1.public_key()));
+                assert_eq!(receipt.to, XorName::from(key_pair2.public_key()));
                 let from_current_balance = safe.wallet_balance(&from_wallet_xorurl).await?;
                 assert_eq!(
                     "4097.580000000", /* 4621.45 - 523.87 */
@@ -954,6 +2074,426 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_wallet_history() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let wallet_xorurl = safe.wallet_create().await?;
+        let (_, key_pair1) = safe.keys_create_preload_test_coins("20").await?;
+        let (_, key_pair2) = safe.keys_create_preload_test_coins("5").await?;
+        safe.wallet_insert(
+            &wallet_xorurl,
+            Some("my-first-balance"),
+            true,
+            &key_pair1.secret_key()?.to_string(),
+        )
+        .await?;
+        safe.wallet_insert(
+            &wallet_xorurl,
+            Some("my-second-balance"),
+            false,
+            &key_pair2.secret_key()?.to_string(),
+        )
+        .await?;
+
+        let (key_xorurl, _) = safe.keys_create_preload_test_coins("1").await?;
+        let outgoing = safe
+            .wallet_transfer("7", Some(&wallet_xorurl), &key_xorurl)
+            .await?;
+        let internal = safe
+            .wallet_transfer(
+                "3",
+                Some(&format!("{}/my-second-balance", wallet_xorurl)),
+                &format!("{}/my-first-balance", wallet_xorurl),
+            )
+            .await?;
+
+        let history = safe.wallet_history(&wallet_xorurl).await?;
+        assert_eq!(history.len(), 5);
+        assert_eq!(history[0].balance, "20.000000000");
+        assert_eq!(history[1].balance, "25.000000000");
+
+        assert_eq!(history[2].id, outgoing.id);
+        assert_eq!(history[2].direction, TransferDirection::Outgoing);
+        assert_eq!(
+            history[2].spendable_balance,
+            Some("my-first-balance".to_string())
+        );
+        assert_eq!(history[2].balance, "18.000000000");
+
+        // a transfer between spendable balances of the Wallet doesn't change its total balance
+        assert_eq!(history[3].id, internal.id);
+        assert_eq!(history[4].id, internal.id);
+        assert_ne!(history[3].direction, history[4].direction);
+        assert_eq!(history[4].balance, "18.000000000");
+        assert_eq!(
+            safe.wallet_balance(&wallet_xorurl).await?,
+            history[4].balance
+        );
+
+        // history of a single spendable balance
+        let history = safe
+            .wallet_history(&format!("{}/my-second-balance", wallet_xorurl))
+            .await?;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].id, internal.id);
+        assert_eq!(history[1].direction, TransferDirection::Outgoing);
+        assert_eq!(history[1].balance, "2.000000000");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wallet_sweep() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let from_wallet_xorurl = safe.wallet_create().await?;
+        let (_, key_pair1) = safe.keys_create_preload_test_coins("120.3").await?;
+        let (_, key_pair2) = safe.keys_create_preload_test_coins("0").await?;
+        let (_, key_pair3) = safe.keys_create_preload_test_coins("4.7").await?;
+        for (name, key_pair) in [
+            ("my-first-balance", &key_pair1),
+            ("my-empty-balance", &key_pair2),
+            ("my-third-balance", &key_pair3),
+        ]
+        .iter()
+        {
+            safe.wallet_insert(
+                &from_wallet_xorurl,
+                Some(*name),
+                true,
+                &key_pair.secret_key()?.to_string(),
+            )
+            .await?;
+        }
+
+        let to_wallet_xorurl = safe.wallet_create().await?;
+        let (_, key_pair4) = safe.keys_create_preload_test_coins("1").await?;
+        safe.wallet_insert(
+            &to_wallet_xorurl,
+            Some("my-balance"),
+            true,
+            &key_pair4.secret_key()?.to_string(),
+        )
+        .await?;
+
+        let outcomes = safe
+            .wallet_sweep(&from_wallet_xorurl, &to_wallet_xorurl)
+            .await?;
+        assert_eq!(outcomes.len(), 3);
+        match &outcomes["my-first-balance"] {
+            SweepOutcome::Transferred(receipt) => {
+                assert_eq!(receipt.amount, "120.300000000");
+                assert_eq!(receipt.from, XorName::from(key_pair1.public_key()));
+                assert_eq!(receipt.to, XorName::from(key_pair4.public_key()));
+            }
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected sweep outcome: {:?}",
+                    other
+                )))
+            }
+        }
+        match &outcomes["my-empty-balance"] {
+            SweepOutcome::Skipped(_) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected sweep outcome: {:?}",
+                    other
+                )))
+            }
+        }
+        match &outcomes["my-third-balance"] {
+            SweepOutcome::Transferred(receipt) => assert_eq!(receipt.amount, "4.700000000"),
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected sweep outcome: {:?}",
+                    other
+                )))
+            }
+        }
+
+        assert_eq!(
+            safe.wallet_balance(&from_wallet_xorurl).await?,
+            "0.000000000"
+        );
+        assert_eq!(
+            safe.wallet_balance(&to_wallet_xorurl).await?,
+            "126.000000000" /* 1 + 120.3 + 4.7 */
+        );
+
+        // the destination itself is not swept when it's part of the source Wallet
+        let (_, key_pair5) = safe.keys_create_preload_test_coins("2").await?;
+        safe.wallet_insert(
+            &to_wallet_xorurl,
+            Some("my-other-balance"),
+            false,
+            &key_pair5.secret_key()?.to_string(),
+        )
+        .await?;
+        let outcomes = safe
+            .wallet_sweep(&to_wallet_xorurl, &to_wallet_xorurl)
+            .await?;
+        match (&outcomes["my-balance"], &outcomes["my-other-balance"]) {
+            (SweepOutcome::Skipped(_), SweepOutcome::Transferred(receipt)) => {
+                assert_eq!(receipt.amount, "2.000000000")
+            }
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Unexpected sweep outcomes: {:?}",
+                    other
+                )))
+            }
+        }
+        assert_eq!(
+            safe.wallet_balance(&format!("{}/my-balance", to_wallet_xorurl))
+                .await?,
+            "128.000000000"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_select_spendable_balances() {
+        let available = vec![
+            ("a".to_string(), Money::from_nano(5)),
+            ("b".to_string(), Money::from_nano(20)),
+            ("c".to_string(), Money::from_nano(0)),
+            ("d".to_string(), Money::from_nano(8)),
+        ];
+        let select = |amount, selection| {
+            select_spendable_balances(available.clone(), Money::from_nano(amount), &selection)
+        };
+        let selected = |balances: &[(&str, u64)]| {
+            Some(
+                balances
+                    .iter()
+                    .map(|(name, nanos)| (name.to_string(), Money::from_nano(*nanos)))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(
+            select(6, CoinSelection::LargestFirst),
+            selected(&[("b", 6)])
+        );
+        assert_eq!(
+            select(26, CoinSelection::LargestFirst),
+            selected(&[("b", 20), ("d", 6)])
+        );
+        assert_eq!(select(6, CoinSelection::FewestKeys), selected(&[("d", 6)]));
+        assert_eq!(
+            select(24, CoinSelection::FewestKeys),
+            selected(&[("b", 20), ("a", 4)])
+        );
+        assert_eq!(
+            select(
+                10,
+                CoinSelection::Named(vec!["c".to_string(), "a".to_string(), "d".to_string()])
+            ),
+            selected(&[("a", 5), ("d", 5)])
+        );
+        assert_eq!(
+            select(10, CoinSelection::Named(vec!["a".to_string()])),
+            None
+        );
+        assert_eq!(select(34, CoinSelection::LargestFirst), None);
+    }
+
+    #[tokio::test]
+    async fn test_wallet_transfer_with_selection() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let from_wallet_xorurl = safe.wallet_create().await?;
+        let (_, key_pair1) = safe.keys_create_preload_test_coins("10").await?;
+        let (_, key_pair2) = safe.keys_create_preload_test_coins("30").await?;
+        let (_, key_pair3) = safe.keys_create_preload_test_coins("12").await?;
+        for (name, key_pair) in [
+            ("my-first-balance", &key_pair1),
+            ("my-second-balance", &key_pair2),
+            ("my-third-balance", &key_pair3),
+        ]
+        .iter()
+        {
+            safe.wallet_insert(
+                &from_wallet_xorurl,
+                Some(*name),
+                *name == "my-first-balance",
+                &key_pair.secret_key()?.to_string(),
+            )
+            .await?;
+        }
+        let (key_xorurl, key_pair4) = safe.keys_create_preload_test_coins("0.5").await?;
+
+        // the default spendable balance alone is not enough
+        match safe
+            .wallet_transfer("35", Some(&from_wallet_xorurl), &key_xorurl)
+            .await
+        {
+            Err(Error::NotEnoughBalance(_)) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Transfer from the default balance didn't fail: {:?}",
+                    other
+                )))
+            }
+        }
+
+        let outcome = safe
+            .wallet_transfer_with_selection(
+                "35",
+                &from_wallet_xorurl,
+                &key_xorurl,
+                &CoinSelection::FewestKeys,
+            )
+            .await?;
+        assert_eq!(outcome.failed, None);
+        let receipts = outcome.receipts;
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].0, "my-second-balance");
+        assert_eq!(receipts[0].1.amount, "30.000000000");
+        assert_eq!(receipts[1].0, "my-first-balance");
+        assert_eq!(receipts[1].1.amount, "5.000000000");
+        assert_eq!(receipts[1].1.to, XorName::from(key_pair4.public_key()));
+        assert_eq!(
+            safe.wallet_balance(&from_wallet_xorurl).await?,
+            "17.000000000" /* 10 + 30 + 12 - 35 */
+        );
+
+        let names = vec![
+            "my-first-balance".to_string(),
+            "my-third-balance".to_string(),
+        ];
+        match safe
+            .wallet_transfer_with_selection(
+                "18",
+                &from_wallet_xorurl,
+                &key_xorurl,
+                &CoinSelection::Named(names.clone()),
+            )
+            .await
+        {
+            Err(Error::NotEnoughBalance(_)) => {}
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Transfer from named balances didn't fail: {:?}",
+                    other
+                )))
+            }
+        }
+
+        let outcome = safe
+            .wallet_transfer_with_selection(
+                "17",
+                &from_wallet_xorurl,
+                &key_xorurl,
+                &CoinSelection::Named(names),
+            )
+            .await?;
+        assert_eq!(outcome.failed, None);
+        let receipts = outcome.receipts;
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].1.amount, "5.000000000");
+        assert_eq!(receipts[1].1.amount, "12.000000000");
+        assert_eq!(
+            safe.keys_balance_from_sk(Arc::new(key_pair4.secret_key()?))
+                .await?,
+            "52.500000000"
+        );
+
+        match safe
+            .wallet_transfer_with_selection(
+                "1",
+                &from_wallet_xorurl,
+                &key_xorurl,
+                &CoinSelection::Named(vec!["missing-balance".to_string()]),
+            )
+            .await
+        {
+            Err(Error::InvalidInput(msg)) => {
+                assert!(msg.contains("missing-balance"));
+                Ok(())
+            }
+            other => Err(Error::Unexpected(format!(
+                "Transfer from a missing balance didn't fail: {:?}",
+                other
+            ))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wallet_transfer_with_selection_partial_failure() -> Result<()> {
+        let mut safe = new_safe_instance().await?;
+        let from_wallet_xorurl = safe.wallet_create().await?;
+        let (_, key_pair1) = safe.keys_create_preload_test_coins("30").await?;
+        let (_, key_pair2) = safe.keys_create_preload_test_coins("10").await?;
+        for (name, key_pair) in [
+            ("my-first-balance", &key_pair1),
+            ("my-second-balance", &key_pair2),
+        ]
+        .iter()
+        {
+            safe.wallet_insert(
+                &from_wallet_xorurl,
+                Some(*name),
+                *name == "my-first-balance",
+                &key_pair.secret_key()?.to_string(),
+            )
+            .await?;
+        }
+
+        // the destination can only take 33.709551615 more safecoins before its balance
+        // overflows, so the second of the transfers fails
+        let (key_xorurl, key_pair3) = safe.keys_create_preload_test_coins("1").await?;
+        safe.safe_client
+            .trigger_simulated_farming_payout(
+                Money::from_nano(u64::MAX - 34_709_551_615),
+                Some(key_pair3.clone()),
+            )
+            .await?;
+        let outcome = safe
+            .wallet_transfer_with_selection(
+                "35",
+                &from_wallet_xorurl,
+                &key_xorurl,
+                &CoinSelection::LargestFirst,
+            )
+            .await?;
+        assert_eq!(outcome.receipts.len(), 1);
+        assert_eq!(outcome.receipts[0].0, "my-first-balance");
+        assert_eq!(outcome.receipts[0].1.amount, "30.000000000");
+        match outcome.failed {
+            Some((name, _)) => assert_eq!(name, "my-second-balance"),
+            None => {
+                return Err(Error::Unexpected(
+                    "Transfer from the second balance didn't fail".to_string(),
+                ))
+            }
+        }
+        assert_eq!(
+            safe.wallet_balance(&from_wallet_xorurl).await?,
+            "10.000000000"
+        );
+        assert_eq!(
+            safe.keys_balance_from_sk(Arc::new(key_pair3.secret_key()?))
+                .await?,
+            "18446744070.000000000"
+        );
+
+        // nothing is returned but the error if not even the first transfer was made
+        match safe
+            .wallet_transfer_with_selection(
+                "5",
+                &from_wallet_xorurl,
+                &key_xorurl,
+                &CoinSelection::LargestFirst,
+            )
+            .await
+        {
+            Err(Error::InvalidAmount(_)) => Ok(()),
+            other => Err(Error::Unexpected(format!(
+                "Transfer to the full destination didn't fail: {:?}",
+                other
+            ))),
+        }
+    }
+
     #[tokio::test]
     async fn test_wallet_transfer_from_safekey() -> Result<()> {
         let mut safe = new_safe_instance().await?;
@@ -1006,13 +2546,27 @@ mod tests {
 
         let from_nrsurl = random_nrs_name();
         let _ = safe
-            .nrs_map_container_create(&from_nrsurl, &from_wallet_xorurl, false, true, false)
+            .nrs_map_container_create(
+                &from_nrsurl,
+                &from_wallet_xorurl,
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
 
         let (key_xorurl, key_pair2) = safe.keys_create_preload_test_coins("0.1").await?;
         let to_nrsurl = random_nrs_name();
         let _ = safe
-            .nrs_map_container_create(&to_nrsurl, &key_xorurl, false, true, false)
+            .nrs_map_container_create(
+                &to_nrsurl,
+                &key_xorurl,
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
 
         // test successful transfer
@@ -1191,7 +2745,14 @@ mod tests {
         // let's also test checking the balance with NRS URL of the destination spendable balances
         let to_wallet_nrsurl = random_nrs_name();
         let _ = safe
-            .nrs_map_container_create(&to_wallet_nrsurl, &to_wallet_xorurl, false, true, false)
+            .nrs_map_container_create(
+                &to_wallet_nrsurl,
+                &to_wallet_xorurl,
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
 
         let to_first_current_balance = safe
@@ -1255,11 +2816,25 @@ mod tests {
         // create NRS URLs for both wallets
         let from_nrsurl = random_nrs_name();
         let _ = safe
-            .nrs_map_container_create(&from_nrsurl, &from_wallet_xorurl, false, true, false)
+            .nrs_map_container_create(
+                &from_nrsurl,
+                &from_wallet_xorurl,
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
         let to_nrsurl = random_nrs_name();
         let _ = safe
-            .nrs_map_container_create(&to_nrsurl, &to_wallet_xorurl, false, true, false)
+            .nrs_map_container_create(
+                &to_nrsurl,
+                &to_wallet_xorurl,
+                false,
+                true,
+                NrsLinkOptions::default(),
+                false,
+            )
             .await?;
 
         // test successful transfer from 'from-second-balance' to 'to-second-balance'
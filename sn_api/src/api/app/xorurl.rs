@@ -8,6 +8,7 @@
 // Software.
 
 use super::{
+    idn::{decode_public_name, encode_public_name},
     nrs::NRS_MAP_TYPE_TAG,
     xorurl_media_types::{MEDIA_TYPE_CODES, MEDIA_TYPE_STR},
     DEFAULT_XORURL_BASE,
@@ -96,6 +97,8 @@ pub enum SafeContentType {
     Wallet,
     FilesContainer,
     NrsMapContainer,
+    // A Blob with the list of chunks which make up some content, rather than the content itself
+    BlobManifest,
     MediaType(String),
 }
 
@@ -112,6 +115,7 @@ impl SafeContentType {
             1 => Ok(Self::Wallet),
             2 => Ok(Self::FilesContainer),
             3 => Ok(Self::NrsMapContainer),
+            4 => Ok(Self::BlobManifest),
             _other => Err(Error::InvalidInput("Invalid Media-type code".to_string())),
         }
     }
@@ -122,6 +126,7 @@ impl SafeContentType {
             Self::Wallet => Ok(1),
             Self::FilesContainer => Ok(2),
             Self::NrsMapContainer => Ok(3),
+            Self::BlobManifest => Ok(4),
             Self::MediaType(media_type) => match MEDIA_TYPE_CODES.get(media_type) {
                 Some(media_type_code) => Ok(*media_type_code),
                 None => Err(Error::InvalidMediaType(format!("Media-type '{}' not supported. You can use 'SafeContentType::Raw' as the 'content_type' for this type of content", media_type))),
@@ -172,9 +177,10 @@ impl SafeDataType {
 #[derive(Debug, Clone)]
 pub(crate) struct SafeUrlParts {
     pub scheme: String,
-    pub public_name: String, // "a.b.name" in "a.b.name"
-    pub top_name: String,    // "name"     in "a.b.name"
-    pub sub_names: String,   // "a.b"      in "a.b.name"
+    pub public_name: String,         // "a.b.name" in "a.b.name"
+    pub public_name_display: String, // "a.b.café" in "a.b.xn--caf-dma"
+    pub top_name: String,            // "name"     in "a.b.name"
+    pub sub_names: String,           // "a.b"      in "a.b.name"
     pub sub_names_vec: Vec<String>,
     pub path: String,
     pub query_string: String,
@@ -189,6 +195,11 @@ impl SafeUrlParts {
         // (see below) we use a different parser to avoid normalization.
         // Parsing twice is inefficient, so there is room for improvement
         // later to standardize on a single parser.
+        //
+        // Internationalised names are punycode encoded beforehand, since rust-url
+        // would only percent-encode them as the 'safe' scheme is not a special one.
+        let encoded_url = encode_url_public_name(url)?;
+        let url = encoded_url.as_str();
         let parsing_url = Url::parse(&url).map_err(|parse_err| {
             let msg = format!("Problem parsing the URL \"{}\": {}", url, parse_err);
             Error::InvalidXorUrl(msg)
@@ -273,6 +284,7 @@ impl SafeUrlParts {
 
         let s = Self {
             scheme,
+            public_name_display: decode_public_name(&public_name),
             public_name,
             sub_names,
             sub_names_vec,
@@ -286,6 +298,26 @@ impl SafeUrlParts {
     }
 }
 
+// Replaces the public name of a URL with its encoded form, which is the
+// same as the original unless it's an internationalised name
+fn encode_url_public_name(url: &str) -> Result<String> {
+    let name_start = match url.find("://") {
+        Some(index) => index + "://".len(),
+        None => return Ok(url.to_string()),
+    };
+    let name_end = url[name_start..]
+        .find(|c| c == '/' || c == '?' || c == '#')
+        .map_or(url.len(), |index| name_start + index);
+
+    let encoded_name = encode_public_name(&url[name_start..name_end])?;
+    Ok(format!(
+        "{}{}{}",
+        &url[..name_start],
+        encoded_name,
+        &url[name_end..]
+    ))
+}
+
 /// An enumeration of possible SafeUrl types.
 ///
 /// This is the type of safe url itself,
@@ -336,11 +368,13 @@ impl SafeUrlType {
 ///   sub_names()   --> None
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SafeUrl {
-    encoding_version: u64,      // currently only v1 supported
-    xor_name: XorName,          // applies to nrsurl and xorurl
-    public_name: String,        // "a.b.name" in "a.b.name"
-    top_name: String,           // "name" in "a.b.name"
-    sub_names: String,          // "a.b" in "a.b.name"
+    encoding_version: u64, // currently only v1 supported
+    xor_name: XorName,     // applies to nrsurl and xorurl
+    public_name: String,   // "a.b.name" in "a.b.name"
+    #[serde(default)]
+    public_name_display: String, // "a.b.café" in "a.b.xn--caf-dma"
+    top_name: String,      // "name" in "a.b.name"
+    sub_names: String,     // "a.b" in "a.b.name"
     sub_names_vec: Vec<String>, // vec!["a", "b"] in "a.b.name"
     type_tag: u64,
     data_type: SafeDataType,       // See SafeDataType
@@ -402,6 +436,7 @@ impl SafeUrl {
         let content_type_u16 = content_type.value()?;
 
         let public_name: String;
+        let public_name_display: String;
         let top_name: String;
         let sub_names_str: String;
         let sub_names_vec: Vec<String>;
@@ -425,6 +460,7 @@ impl SafeUrl {
                     return Err(Error::InvalidInput(msg));
                 }
                 public_name = parts.public_name;
+                public_name_display = parts.public_name_display;
                 top_name = parts.top_name;
                 sub_names_str = parts.sub_names;
                 sub_names_vec = parts.sub_names_vec; // use sub_names from nrs_name, ignoring sub_names arg, in case they do not match.
@@ -433,6 +469,7 @@ impl SafeUrl {
             None => {
                 // we have an xorurl
                 public_name = String::default(); // set later
+                public_name_display = String::default(); // set later
                 top_name = String::default(); // set later
                 sub_names_vec = sub_names.unwrap_or_else(Vec::new);
                 sub_names_str = sub_names_vec.join(".");
@@ -452,6 +489,7 @@ impl SafeUrl {
             encoding_version: XOR_URL_VERSION_1,
             xor_name,
             public_name,
+            public_name_display,
             top_name,
             sub_names: sub_names_str,
             sub_names_vec,
@@ -471,6 +509,7 @@ impl SafeUrl {
             x.top_name = x.name_to_base(DEFAULT_XORURL_BASE, false);
             let sep = if x.sub_names.is_empty() { "" } else { "." };
             x.public_name = format!("{}{}{}", x.sub_names(), sep, x.top_name);
+            x.public_name_display = x.public_name.clone();
         }
 
         // we call this to add leading slash if needed
@@ -584,6 +623,7 @@ impl SafeUrl {
             1 => SafeContentType::Wallet,
             2 => SafeContentType::FilesContainer,
             3 => SafeContentType::NrsMapContainer,
+            4 => SafeContentType::BlobManifest,
             other => match MEDIA_TYPE_STR.get(&other) {
                 Some(media_type_str) => SafeContentType::MediaType((*media_type_str).to_string()),
                 None => {
@@ -696,6 +736,20 @@ impl SafeUrl {
         &self.public_name
     }
 
+    /// The public_name in url, in the form it's meant to be displayed.
+    ///
+    /// It differs from public_name() only for internationalised names,
+    /// which are punycode encoded in the url.
+    ///
+    /// eg a.b.xn--caf-dma --> a.b.café
+    pub fn public_name_display(&self) -> &str {
+        if self.public_name_display.is_empty() {
+            &self.public_name
+        } else {
+            &self.public_name_display
+        }
+    }
+
     /// returns top name of name field.
     ///
     /// eg: a.b.name --> name
@@ -724,6 +778,7 @@ impl SafeUrl {
         self.sub_names = parts.sub_names;
         self.sub_names_vec = parts.sub_names_vec;
         self.public_name = parts.public_name;
+        self.public_name_display = parts.public_name_display;
         Ok(())
     }
 
@@ -977,6 +1032,25 @@ impl SafeUrl {
         Some(url)
     }
 
+    /// serializes the URL to a string in the form it's meant to be displayed.
+    ///
+    /// It differs from to_string() only for an NrsUrl with an
+    /// internationalised name, which is displayed in Unicode form.
+    pub fn to_display_string(&self) -> String {
+        if !self.is_nrsurl() {
+            return self.to_string();
+        }
+
+        format!(
+            "{}{}{}{}{}",
+            SAFE_URL_PROTOCOL,
+            self.public_name_display(),
+            self.path,
+            self.query_string_with_separator(),
+            self.fragment_with_separator()
+        )
+    }
+
     /// serializes entire xorurl using a particular base encoding.
     pub fn to_base(&self, base: XorUrlBase) -> String {
         let name = self.name_to_base(base, true);
@@ -1105,12 +1179,17 @@ impl SafeUrl {
         xor_name: XorName,
         content_type: SafeContentType,
         base: XorUrlBase,
+        is_private: bool,
     ) -> Result<String> {
         SafeUrl::encode(
             xor_name,
             None,
             0,
-            SafeDataType::PublicBlob,
+            if is_private {
+                SafeDataType::PrivateBlob
+            } else {
+                SafeDataType::PublicBlob
+            },
             content_type,
             None,
             None,
@@ -1475,7 +1554,8 @@ mod tests {
     #[test]
     fn test_safeurl_base32z_encoding() -> Result<()> {
         let xor_name = XorName(*b"12345678901234567890123456789012");
-        let xorurl = SafeUrl::encode_blob(xor_name, SafeContentType::Raw, XorUrlBase::Base32z)?;
+        let xorurl =
+            SafeUrl::encode_blob(xor_name, SafeContentType::Raw, XorUrlBase::Base32z, false)?;
         let base32z_xorurl = "safe://hbyyyyncj1gc4dkptz8yhuycj1gc4dkptz8yhuycj1gc4dkptz8yhuycj1";
         assert_eq!(xorurl, base32z_xorurl);
         Ok(())
@@ -1511,7 +1591,8 @@ mod tests {
     fn test_safeurl_default_base_encoding() -> Result<()> {
         let xor_name = XorName(*b"12345678901234567890123456789012");
         let base32z_xorurl = "safe://hbyyyyncj1gc4dkptz8yhuycj1gc4dkptz8yhuycj1gc4dkptz8yhuycj1";
-        let xorurl = SafeUrl::encode_blob(xor_name, SafeContentType::Raw, DEFAULT_XORURL_BASE)?;
+        let xorurl =
+            SafeUrl::encode_blob(xor_name, SafeContentType::Raw, DEFAULT_XORURL_BASE, false)?;
         assert_eq!(xorurl, base32z_xorurl);
         Ok(())
     }
@@ -1631,6 +1712,7 @@ mod tests {
             xor_name,
             SafeContentType::MediaType("text/html".to_string()),
             XorUrlBase::Base32z,
+            false,
         )?;
 
         let xorurl_encoder = SafeUrl::from_url(&xorurl)?;
@@ -1668,6 +1750,7 @@ mod tests {
             xor_name,
             SafeContentType::MediaType("text/html".to_string()),
             XorUrlBase::Base32z,
+            false,
         )?;
 
         let len = xorurl.len() - 1;
@@ -1913,6 +1996,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_safeurl_internationalised_name() -> Result<()> {
+        let nrs = SafeUrl::from_url("safe://blog.café/my%20file.txt?v=1")?;
+        assert_eq!(nrs.public_name(), "blog.xn--caf-dma");
+        assert_eq!(nrs.public_name_display(), "blog.café");
+        assert_eq!(nrs.top_name(), "xn--caf-dma");
+        assert_eq!(nrs.sub_names_vec(), ["blog"]);
+        assert_eq!(nrs.path_decoded()?, "/my file.txt");
+        assert_eq!(nrs.to_string(), "safe://blog.xn--caf-dma/my%20file.txt?v=1");
+        assert_eq!(
+            nrs.to_display_string(),
+            "safe://blog.café/my%20file.txt?v=1"
+        );
+
+        // the encoded and the normalised forms refer to the same name
+        let encoded = SafeUrl::from_url("safe://blog.xn--caf-dma")?;
+        let denormalised = SafeUrl::from_url("safe://blog.cafe\u{301}")?;
+        assert_eq!(encoded.xorname(), nrs.xorname());
+        assert_eq!(denormalised.xorname(), nrs.xorname());
+        assert_eq!(encoded.public_name_display(), "blog.café");
+
+        // ASCII names are displayed as they are
+        let ascii = SafeUrl::from_url("safe://blog.mysite")?;
+        assert_eq!(ascii.public_name_display(), "blog.mysite");
+        assert_eq!(ascii.to_display_string(), "safe://blog.mysite");
+
+        match SafeUrl::from_url("safe://p\u{430}ypal") {
+            Err(Error::InvalidXorUrl(msg)) => assert!(msg.contains("Latin and Cyrillic")),
+            other => {
+                return Err(Error::Unexpected(format!(
+                    "Confusable name was not rejected: {:?}",
+                    other
+                )))
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_safeurl_from_url_validation() -> Result<()> {
         // Tests basic URL syntax errors that are common to
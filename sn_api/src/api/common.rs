@@ -11,6 +11,7 @@ use super::{constants::SN_AUTHD_CONNECTION_IDLE_TIMEOUT, Error, Result};
 use log::info;
 use qjsonrpc::ClientEndpoint;
 use serde::de::DeserializeOwned;
+use sn_data_types::PublicKey;
 use tokio::runtime;
 
 pub mod auth_types {
@@ -66,6 +67,36 @@ pub fn ed_sk_from_hex(hex_str: &str) -> Result<ed25519_dalek::SecretKey> {
         .map_err(|_| Error::InvalidInput("Failed to deserialize provided secret key".to_string()))
 }
 
+// Public keys are encoded as the hex string of their bytes, which length tells their type
+pub fn pk_from_hex(hex_str: &str) -> Result<PublicKey> {
+    let pk_bytes = parse_hex(&hex_str);
+    match pk_bytes.len() {
+        ed25519_dalek::PUBLIC_KEY_LENGTH => ed25519_dalek::PublicKey::from_bytes(&pk_bytes)
+            .map(PublicKey::Ed25519)
+            .map_err(|_| Error::InvalidInput("Invalid Ed25519 public key bytes".to_string())),
+        threshold_crypto::PK_SIZE => {
+            let mut pk_bytes_array = [0; threshold_crypto::PK_SIZE];
+            pk_bytes_array.copy_from_slice(&pk_bytes);
+            threshold_crypto::PublicKey::from_bytes(pk_bytes_array)
+                .map(PublicKey::Bls)
+                .map_err(|_| Error::InvalidInput("Invalid BLS public key bytes".to_string()))
+        }
+        _ => Err(Error::InvalidInput(format!(
+            "Invalid public key '{}', it must be the hex encoding of an Ed25519 or a BLS public key",
+            hex_str
+        ))),
+    }
+}
+
+pub fn pk_bytes_to_hex(pk: &PublicKey) -> String {
+    let pk_bytes = match pk {
+        PublicKey::Ed25519(pk) => pk.to_bytes().to_vec(),
+        PublicKey::Bls(pk) => pk.to_bytes().to_vec(),
+        PublicKey::BlsShare(pk) => pk.to_bytes().to_vec(),
+    };
+    pk_bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 // Send a request to authd using JSON-RPC over QUIC
 pub async fn send_authd_request<T>(
     dest_endpoint: &str,
@@ -36,6 +36,7 @@ pub enum Error {
     InvalidMediaType(String),
     NotEnoughBalance(String),
     FileSystemError(String),
+    NotImplementedError(String),
     Unexpected(String),
     Unknown(String),
 }
@@ -84,6 +85,7 @@ impl fmt::Display for Error {
             InvalidMediaType(info) => ("InvalidMediaType", info),
             NotEnoughBalance(info) => ("NotEnoughBalance", info),
             FileSystemError(info) => ("FileSystemError", info),
+            NotImplementedError(info) => ("NotImplementedError", info),
             Unexpected(info) => ("Unexpected", info),
             Unknown(info) => ("Unknown", info),
         };
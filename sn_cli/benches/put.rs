@@ -15,6 +15,7 @@ use std::time::Duration;
 use criterion::{BatchSize, Criterion};
 
 const TEST_FILE_RANDOM_CONTENT: &str = "test_file_random_content.txt";
+const TEST_FOLDER_RANDOM_CONTENT: &str = "test_folder_random_content";
 
 // sample size is _NOT_ the number of times the command is run...
 // https://bheisler.github.io/criterion.rs/book/analysis.html#measurement
@@ -25,6 +26,8 @@ const SIZE_500KB: usize = 500_000;
 const SIZE_250KB: usize = 250_000;
 const SIZE_100KB: usize = 100_000;
 const TINY_FILE: usize = 10;
+// number of files in the folder generated with random data
+const FOLDER_FILES_COUNT: usize = 20;
 
 use sn_cmd_test_utilities::get_bin_location;
 
@@ -36,6 +39,7 @@ fn main() {
     criterion = criterion.measurement_time(Duration::from_millis(10000));
 
     bench_cli_put(&mut criterion);
+    bench_cli_put_folder(&mut criterion);
 }
 
 fn put_random_content(size: usize) -> Result<(), String> {
@@ -46,6 +50,22 @@ fn put_random_content(size: usize) -> Result<(), String> {
     Ok(())
 }
 
+fn put_random_folder(files_count: usize, size: usize) -> Result<(), String> {
+    let _ = fs::remove_dir_all(TEST_FOLDER_RANDOM_CONTENT);
+    fs::create_dir_all(TEST_FOLDER_RANDOM_CONTENT)
+        .map_err(|_| "Error creating folder for random content".to_string())?;
+    for i in 0..files_count {
+        let random_content: String = (0..size).map(|_| rand::random::<char>()).collect();
+        fs::write(
+            format!("{}/file_{}.txt", TEST_FOLDER_RANDOM_CONTENT, i),
+            random_content,
+        )
+        .map_err(|_| "Error writing random content".to_string())?;
+    }
+
+    Ok(())
+}
+
 fn bench_cli_put(c: &mut Criterion) {
     c.bench_function("cli put random tiny file", |b| {
         b.iter_batched(
@@ -109,3 +129,35 @@ fn bench_cli_put(c: &mut Criterion) {
         )
     });
 }
+
+// Compare uploading a folder one file at a time with uploading several files concurrently
+fn bench_cli_put_folder(c: &mut Criterion) {
+    for concurrency in &["1", "4", "8"] {
+        c.bench_function(
+            &format!(
+                "cli put folder of {} random 100 KB files with concurrency {}",
+                FOLDER_FILES_COUNT, concurrency
+            ),
+            |b| {
+                b.iter_batched(
+                    || put_random_folder(FOLDER_FILES_COUNT, SIZE_100KB),
+                    |_| {
+                        //  use the safe command, so for bench it has to be installed
+                        cmd!(
+                            get_bin_location(),
+                            "files",
+                            "put",
+                            TEST_FOLDER_RANDOM_CONTENT,
+                            "--recursive",
+                            "--concurrency",
+                            *concurrency
+                        )
+                        .read()
+                        .unwrap()
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+}
@@ -16,12 +16,16 @@ use crate::{
     subcommands::{
         auth::auth_commander, cat::cat_commander, config::config_commander, dog::dog_commander,
         files::files_commander, keys::key_commander, networks::networks_commander,
-        node::node_commander, nrs::nrs_commander, seq::seq_commander, setup::setup_commander,
-        update::update_commander, wallet::wallet_commander, xorurl::xorurl_commander, OutputFmt,
-        SubCommands,
+        node::node_commander, nrs::nrs_commander, reject_rdf_output_fmt, seq::seq_commander,
+        setup::setup_commander, update::update_commander, wallet::wallet_commander,
+        xorurl::xorurl_commander, OutputFmt, SubCommands,
     },
 };
-use sn_api::{xorurl::XorUrlBase, Safe};
+use sn_api::{xorurl::XorUrlBase, BackendConfig, Safe};
+
+// Environment variable to use a storage backend other than the SAFE Network,
+// e.g. 'local' to keep all data in a local file instead
+const ENV_VAR_SN_CLI_STORAGE_BACKEND: &str = "SN_CLI_STORAGE_BACKEND";
 
 #[derive(StructOpt, Debug)]
 /// Interact with the Safe Network
@@ -30,7 +34,7 @@ pub struct CmdArgs {
     /// subcommands
     #[structopt(subcommand)]
     pub cmd: Option<SubCommands>,
-    /// Output data serialisation: [json, jsoncompact, yaml]
+    /// Output data serialisation: [json, jsoncompact, yaml, turtle, jsonld]. Turtle and JSON-LD are only supported when fetching FilesContainers and NRS Map Containers with 'cat'
     #[structopt(short = "o", long = "output", global(true))]
     output_fmt: Option<OutputFmt>,
     /// Sets JSON as output serialisation format (alias of '--output json')
@@ -45,16 +49,32 @@ pub struct CmdArgs {
     /// Base encoding to be used for XOR-URLs generated. Currently supported: base32z (default), base32 and base64
     #[structopt(long = "xorurl", global(true))]
     xorurl_base: Option<XorUrlBase>,
+    /// Store FilesMaps and NRS Maps as plain JSON rather than JSON-LD, so versions of the CLI released before the RDF schema was introduced can still read them
+    #[structopt(long = "legacy-maps", global(true))]
+    legacy_maps: bool,
     /// Endpoint of the Authenticator daemon where to send requests to. If not provided, https://localhost:33000 is assumed.
     #[structopt(long = "endpoint", global(true))]
     pub endpoint: Option<String>,
 }
 
 pub async fn run() -> Result<(), String> {
-    let mut safe = Safe::default();
+    let mut safe = new_safe_instance()?;
     run_with(None, &mut safe).await
 }
 
+// Instantiate a Safe with the storage backend set in the environment, if any
+pub fn new_safe_instance() -> Result<Safe, String> {
+    match std::env::var(ENV_VAR_SN_CLI_STORAGE_BACKEND) {
+        Ok(backend) => {
+            let backend = backend
+                .parse::<BackendConfig>()
+                .map_err(|err| format!("Invalid storage backend: {}", err))?;
+            Ok(Safe::with_backend(None, backend))
+        }
+        Err(_) => Ok(Safe::default()),
+    }
+}
+
 pub async fn run_with(cmd_args: Option<&[&str]>, safe: &mut Safe) -> Result<(), String> {
     // Let's first get all the arguments passed in, either as function's args, or CLI args
     let args = match cmd_args {
@@ -62,11 +82,6 @@ pub async fn run_with(cmd_args: Option<&[&str]>, safe: &mut Safe) -> Result<(),
         Some(cmd_args) => CmdArgs::from_iter_safe(cmd_args).map_err(|err| err.to_string())?,
     };
 
-    let prev_base = safe.xorurl_base;
-    if let Some(base) = args.xorurl_base {
-        safe.xorurl_base = base;
-    }
-
     let output_fmt = if args.output_json {
         OutputFmt::Json
     } else {
@@ -75,6 +90,15 @@ pub async fn run_with(cmd_args: Option<&[&str]>, safe: &mut Safe) -> Result<(),
             None => OutputFmt::Pretty,
         }
     };
+    if !matches!(args.cmd, Some(SubCommands::Cat(_))) {
+        reject_rdf_output_fmt(output_fmt)?;
+    }
+
+    let prev_base = safe.xorurl_base;
+    if let Some(base) = args.xorurl_base {
+        safe.xorurl_base = base;
+    }
+    safe.set_legacy_maps_format(args.legacy_maps);
 
     debug!("Processing command: {:?}", args);
 
@@ -11,6 +11,8 @@ mod auth_and_connect;
 pub mod auth_daemon;
 pub mod config;
 mod helpers;
+#[cfg(all(unix, feature = "mount"))]
+pub mod mount;
 pub mod node;
 
 pub mod safe_net {
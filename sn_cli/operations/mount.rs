@@ -0,0 +1,301 @@
+// Copyright 2020 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// http://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use fuse::{
+    FileAttr, FileType, Filesystem, ReplyAttr, ReplyData, ReplyDirectory, ReplyEntry, Request,
+};
+use futures::{channel::mpsc, StreamExt};
+use libc::{EINVAL, EIO, ENOENT, ENOTDIR};
+use log::{debug, warn};
+use sn_api::{
+    files::FilesMap,
+    files_view::{FilesContainerView, FsNode, FsNodeKind},
+    Safe,
+};
+use std::{
+    ffi::OsStr,
+    sync::Arc,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use time::Timespec;
+
+// How long the kernel can cache the attributes of the files, a version of
+// a FilesContainer never changes thus it can be as long as it's mounted
+const ATTR_TTL: Timespec = Timespec { sec: 3600, nsec: 0 };
+
+// Options the filesystem is mounted with
+const MOUNT_OPTIONS: &[&str] = &["-o", "ro", "-o", "fsname=safe", "-o", "subtype=safe"];
+
+// A request which needs content to be fetched, thus it's served by the task fetching it
+enum FetchRequest {
+    // Read the content of a file
+    Read {
+        ino: u64,
+        offset: u64,
+        size: u32,
+        reply: ReplyData,
+    },
+    // Get the attributes of a file whose size is only known once its content is fetched
+    Attr {
+        ino: u64,
+        reply: AttrReply,
+    },
+}
+
+// The reply to send the attributes of a file with, depending on the operation requesting them
+enum AttrReply {
+    Lookup(ReplyEntry),
+    Getattr(ReplyAttr),
+}
+
+impl AttrReply {
+    fn attr(self, attr: &FileAttr) {
+        match self {
+            Self::Lookup(reply) => reply.entry(&ATTR_TTL, attr, 0),
+            Self::Getattr(reply) => reply.attr(&ATTR_TTL, attr),
+        }
+    }
+
+    fn error(self, err: i32) {
+        match self {
+            Self::Lookup(reply) => reply.error(err),
+            Self::Getattr(reply) => reply.error(err),
+        }
+    }
+}
+
+// Owner the files are reported with, i.e. the user mounting the filesystem
+#[derive(Clone, Copy)]
+struct Owner {
+    uid: u32,
+    gid: u32,
+}
+
+// Read-only FUSE filesystem exposing the files of a FilesContainer
+struct FilesContainerFs {
+    view: Arc<FilesContainerView>,
+    requests: mpsc::UnboundedSender<FetchRequest>,
+    owner: Owner,
+}
+
+impl FilesContainerFs {
+    // Reply with the attributes of a node, unless its size needs to be fetched first,
+    // in which case it's the task fetching it which replies
+    fn reply_attr(&self, node: &FsNode, reply: AttrReply) {
+        match self.view.known_size(node.ino) {
+            Some(size) => reply.attr(&file_attr(node, size, self.owner)),
+            None => self.send(FetchRequest::Attr {
+                ino: node.ino,
+                reply,
+            }),
+        }
+    }
+
+    fn send(&self, request: FetchRequest) {
+        if let Err(err) = self.requests.unbounded_send(request) {
+            match err.into_inner() {
+                FetchRequest::Read { reply, .. } => reply.error(EIO),
+                FetchRequest::Attr { reply, .. } => reply.error(EIO),
+            }
+        }
+    }
+}
+
+fn file_attr(node: &FsNode, size: u64, owner: Owner) -> FileAttr {
+    let (kind, nlink, size) = match node.kind {
+        FsNodeKind::Directory => (FileType::Directory, 2, 0),
+        FsNodeKind::File => (FileType::RegularFile, 1, size),
+        FsNodeKind::Symlink => (
+            FileType::Symlink,
+            1,
+            node.symlink_target
+                .as_ref()
+                .map_or(0, |target| target.len() as u64),
+        ),
+    };
+    let modified = to_timespec(node.modified);
+
+    FileAttr {
+        ino: node.ino,
+        size,
+        blocks: (size + 511) / 512,
+        atime: modified,
+        mtime: modified,
+        ctime: modified,
+        crtime: to_timespec(node.created),
+        kind,
+        perm: node.perm,
+        nlink,
+        uid: owner.uid,
+        gid: owner.gid,
+        rdev: 0,
+        flags: 0,
+    }
+}
+
+impl Filesystem for FilesContainerFs {
+    fn lookup(&mut self, _req: &Request, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        match name
+            .to_str()
+            .and_then(|name| self.view.lookup(parent, name))
+        {
+            Some(node) => self.reply_attr(node, AttrReply::Lookup(reply)),
+            None => reply.error(ENOENT),
+        }
+    }
+
+    fn getattr(&mut self, _req: &Request, ino: u64, reply: ReplyAttr) {
+        match self.view.getattr(ino) {
+            Some(node) => self.reply_attr(node, AttrReply::Getattr(reply)),
+            None => reply.error(ENOENT),
+        }
+    }
+
+    fn readlink(&mut self, _req: &Request, ino: u64, reply: ReplyData) {
+        match self.view.readlink(ino) {
+            Some(target) => reply.data(target.as_bytes()),
+            None => reply.error(EINVAL),
+        }
+    }
+
+    fn readdir(
+        &mut self,
+        _req: &Request,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        mut reply: ReplyDirectory,
+    ) {
+        let parent = match self.view.getattr(ino) {
+            Some(node) => node.parent,
+            None => return reply.error(ENOENT),
+        };
+        let children = match self.view.readdir(ino) {
+            Some(children) => children,
+            None => return reply.error(ENOTDIR),
+        };
+
+        let entries = vec![
+            (ino, FileType::Directory, "."),
+            (parent, FileType::Directory, ".."),
+        ]
+        .into_iter()
+        .chain(children.iter().map(|child| {
+            let kind = match child.kind {
+                FsNodeKind::Directory => FileType::Directory,
+                FsNodeKind::File => FileType::RegularFile,
+                FsNodeKind::Symlink => FileType::Symlink,
+            };
+            (child.ino, kind, child.name.as_str())
+        }));
+
+        // Each entry is added along with the offset to continue listing from after it
+        for (index, (ino, kind, name)) in entries.enumerate().skip(offset as usize) {
+            if reply.add(ino, (index + 1) as i64, kind, name) {
+                break;
+            }
+        }
+        reply.ok();
+    }
+
+    fn read(
+        &mut self,
+        _req: &Request,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        size: u32,
+        reply: ReplyData,
+    ) {
+        self.send(FetchRequest::Read {
+            ino,
+            offset: offset as u64,
+            size,
+            reply,
+        });
+    }
+}
+
+// Mount the files of a FilesContainer as a read-only filesystem at the given location,
+// it returns only once the filesystem is unmounted
+pub async fn mount_files_map(
+    safe: &mut Safe,
+    files_map: &FilesMap,
+    mountpoint: &str,
+) -> Result<(), String> {
+    let view = Arc::new(FilesContainerView::from_files_map(files_map));
+    let (requests_sender, mut requests) = mpsc::unbounded();
+    let owner = Owner {
+        uid: unsafe { libc::getuid() },
+        gid: unsafe { libc::getgid() },
+    };
+    let fs = FilesContainerFs {
+        view: Arc::clone(&view),
+        requests: requests_sender,
+        owner,
+    };
+
+    // The filesystem is served from its own thread since its callbacks are blocking,
+    // while the content of the files, and the size of those which don't have it in
+    // their FileItem, are fetched from here, as they're requested
+    let mountpoint = mountpoint.to_string();
+    let session = thread::spawn(move || {
+        let options: Vec<&OsStr> = MOUNT_OPTIONS.iter().map(OsStr::new).collect();
+        fuse::mount(fs, &mountpoint, &options)
+            .map_err(|err| format!("Failed to mount at '{}': {}", mountpoint, err))
+    });
+
+    // Requests stop coming once the filesystem is unmounted
+    while let Some(request) = requests.next().await {
+        match request {
+            FetchRequest::Read {
+                ino,
+                offset,
+                size,
+                reply,
+            } => {
+                debug!(
+                    "Reading {} bytes from offset {} of inode {}",
+                    size, offset, ino
+                );
+                match view.read(safe, ino, offset, u64::from(size)).await {
+                    Ok(data) => reply.data(&data),
+                    Err(err) => {
+                        warn!("Failed to read content of inode {}: {}", ino, err);
+                        reply.error(EIO);
+                    }
+                }
+            }
+            FetchRequest::Attr { ino, reply } => {
+                debug!("Fetching size of inode {}", ino);
+                match (view.getattr(ino), view.file_size(safe, ino).await) {
+                    (Some(node), Ok(size)) => reply.attr(&file_attr(node, size, owner)),
+                    (None, _) => reply.error(ENOENT),
+                    (Some(_), Err(err)) => {
+                        warn!("Failed to fetch size of inode {}: {}", ino, err);
+                        reply.error(EIO);
+                    }
+                }
+            }
+        }
+    }
+
+    session
+        .join()
+        .map_err(|_| "Unexpectedly failed to serve the filesystem".to_string())?
+}
+
+fn to_timespec(time: Option<SystemTime>) -> Timespec {
+    time.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or_else(
+            || Timespec::new(0, 0),
+            |duration| Timespec::new(duration.as_secs() as i64, duration.subsec_nanos() as i32),
+        )
+}
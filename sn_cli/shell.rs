@@ -22,7 +22,7 @@ use std::io::{stdout, Write};
 use structopt::StructOpt;
 
 pub fn shell_run() -> Result<(), String> {
-    let safe = Safe::default();
+    let safe = cli::new_safe_instance()?;
     let sn_authd_client = SafeAuthdClient::new(None);
     let mut shell = Shell::new((safe, sn_authd_client));
     shell.set_default(|io, _, cmd| {
@@ -9,11 +9,15 @@
 
 use super::{
     helpers::{get_from_arg_or_stdin, print_nrs_map, serialise_output},
-    OutputFmt,
+    reject_rdf_output_fmt, OutputFmt,
 };
 use log::debug;
 use prettytable::Table;
-use sn_api::{fetch::SafeData, Safe};
+use sn_api::{
+    fetch::SafeData,
+    rdf::{files_map_to_jsonld, files_map_to_turtle, nrs_map_to_jsonld, nrs_map_to_turtle},
+    Safe,
+};
 use std::io::{self, Write};
 use structopt::StructOpt;
 
@@ -35,6 +39,11 @@ pub async fn cat_commander(
     debug!("Running cat for: {:?}", &url);
 
     let content = safe.fetch(&url, None).await?;
+    match &content {
+        SafeData::FilesContainer { .. } | SafeData::NrsMapContainer { .. } => {}
+        _ => reject_rdf_output_fmt(output_fmt)?,
+    }
+
     match &content {
         SafeData::FilesContainer {
             version, files_map, ..
@@ -60,11 +69,15 @@ pub async fn cat_commander(
                     ]);
                 });
                 table.printstd();
+            } else if OutputFmt::Turtle == output_fmt {
+                print!("{}", files_map_to_turtle(files_map, Some(&url)));
+            } else if OutputFmt::JsonLd == output_fmt {
+                print_jsonld(&files_map_to_jsonld(files_map, Some(&url)));
             } else {
                 println!("{}", serialise_output(&(url, files_map), output_fmt));
             }
         }
-        SafeData::PublicBlob { data, .. } => {
+        SafeData::PublicBlob { data, .. } | SafeData::PrivateBlob { data, .. } => {
             if cmd.hexdump {
                 // Render hex representation of Blob file
                 println!("{}", pretty_hex::pretty_hex(data));
@@ -100,6 +113,10 @@ pub async fn cat_commander(
             if OutputFmt::Pretty == output_fmt {
                 println!("NRS Map Container (version {}) at \"{}\":", version, url);
                 print_nrs_map(&nrs_map, public_name);
+            } else if OutputFmt::Turtle == output_fmt {
+                print!("{}", nrs_map_to_turtle(nrs_map, Some(&url)));
+            } else if OutputFmt::JsonLd == output_fmt {
+                print_jsonld(&nrs_map_to_jsonld(nrs_map, Some(&url)));
             } else {
                 println!("{}", serialise_output(&(url, nrs_map), output_fmt));
             }
@@ -143,3 +160,11 @@ pub async fn cat_commander(
 
     Ok(())
 }
+
+fn print_jsonld(jsonld: &serde_json::Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(jsonld)
+            .unwrap_or_else(|_| "Failed to serialise output to json-ld".to_string())
+    );
+}
@@ -13,7 +13,7 @@ use super::{
 };
 use log::debug;
 use sn_api::{
-    fetch::{SafeContentType, SafeData},
+    fetch::{ResolutionHop, SafeContentType, SafeData},
     xorurl::XorUrlEncoder,
     Safe,
 };
@@ -23,6 +23,9 @@ use structopt::StructOpt;
 pub struct DogCommands {
     /// The safe:// location to inspect
     location: Option<String>,
+    /// Show how each of the URL resolution steps was taken, and the time it took
+    #[structopt(long = "trace")]
+    trace: bool,
 }
 
 pub async fn dog_commander(
@@ -33,9 +36,21 @@ pub async fn dog_commander(
     let url = get_from_arg_or_stdin(cmd.location, None)?;
     debug!("Running dog for: {:?}", &url);
 
-    let resolved_content = safe.inspect(&url).await?;
+    let (resolved_content, trace) = if cmd.trace {
+        safe.inspect_with_trace(&url).await?
+    } else {
+        (safe.inspect(&url).await?, vec![])
+    };
+
     if OutputFmt::Pretty != output_fmt {
-        println!("{}", serialise_output(&(url, resolved_content), output_fmt));
+        if cmd.trace {
+            println!(
+                "{}",
+                serialise_output(&(url, resolved_content, trace), output_fmt)
+            );
+        } else {
+            println!("{}", serialise_output(&(url, resolved_content), output_fmt));
+        }
     } else {
         for (i, ref content) in resolved_content.iter().enumerate() {
             println!();
@@ -49,6 +64,7 @@ pub async fn dog_commander(
                     version,
                     nrs_map,
                     data_type,
+                    link_policy,
                     resolved_from,
                 } => {
                     println!("Resolved from: {}", resolved_from);
@@ -65,6 +81,13 @@ pub async fn dog_commander(
                     let mut xorurl_encoder = XorUrlEncoder::from_url(xorurl)?;
                     xorurl_encoder.set_content_type(SafeContentType::Raw)?;
                     println!("Native data XOR-URL: {}", xorurl_encoder.to_string());
+                    match resolved_content.get(i + 1).and_then(SafeData::version) {
+                        Some(target_version) => println!(
+                            "Link policy: {} (resolved version {})",
+                            link_policy, target_version
+                        ),
+                        None => println!("Link policy: {}", link_policy),
+                    }
                     print_nrs_map(&nrs_map, &public_name);
                 }
                 SafeData::FilesContainer {
@@ -93,12 +116,24 @@ pub async fn dog_commander(
                     media_type,
                     resolved_from,
                     ..
+                }
+                | SafeData::PrivateBlob {
+                    xorurl,
+                    xorname,
+                    media_type,
+                    resolved_from,
+                    ..
                 } => {
+                    let data_type = if let SafeData::PrivateBlob { .. } = content {
+                        "PrivateBlob"
+                    } else {
+                        "PublicBlob"
+                    };
                     println!("Resolved from: {}", resolved_from);
                     println!("= File =");
                     println!("XOR-URL: {}", xorurl);
                     println!("XOR name: 0x{}", xorname_to_hex(xorname));
-                    println!("Native data type: PublicBlob");
+                    println!("Native data type: {}", data_type);
                     println!(
                         "Media type: {}",
                         media_type.clone().unwrap_or_else(|| "Unknown".to_string())
@@ -172,7 +207,38 @@ pub async fn dog_commander(
             }
         }
         println!();
+        if cmd.trace {
+            print_resolution_trace(&trace);
+        }
     }
 
     Ok(())
 }
+
+fn print_resolution_trace(trace: &[ResolutionHop]) {
+    println!("== URL resolution trace ==");
+    for (i, hop) in trace.iter().enumerate() {
+        println!("{}. {} ({:?})", i + 1, hop.url, hop.elapsed);
+        match (&hop.matched_sub_name, &hop.default) {
+            (Some(sub_name), Some(default)) => {
+                println!(
+                    "   Matched sub name: \"{}\", through the {}",
+                    sub_name, default
+                )
+            }
+            (Some(sub_name), None) => println!("   Matched sub name: \"{}\"", sub_name),
+            (None, Some(default)) => println!("   Matched the {}", default),
+            (None, None) => {}
+        }
+        if let Some(version) = hop.version {
+            println!("   Version: {}", version);
+        }
+        if let Some(link_policy) = hop.link_policy {
+            println!("   Link policy: {}", link_policy);
+        }
+        if let Some(realpath) = &hop.realpath {
+            println!("   Path resolved through realpath: {}", realpath);
+        }
+    }
+    println!();
+}
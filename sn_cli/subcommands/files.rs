@@ -15,13 +15,15 @@ use super::{
     },
     OutputFmt,
 };
+#[cfg(all(unix, feature = "mount"))]
+use crate::operations::mount::mount_files_map;
 use ansi_term::Colour;
 use log::debug;
 use prettytable::{format::FormatBuilder, Table};
 use serde::Serialize;
 use sn_api::{
     fetch::SafeData,
-    files::{FilesMap, ProcessedFiles},
+    files::{FilesContainerCreateOptions, FilesMap, FilesMapDiff, ProcessedFiles},
     xorurl::{XorUrl, XorUrlEncoder},
     Safe,
 };
@@ -33,6 +35,8 @@ type FileDetails = BTreeMap<String, String>;
 
 const UNKNOWN_FILE_NAME: &str = "<unknown>";
 
+const UPLOAD_JOURNAL_FILENAME: &str = "upload_journal";
+
 // Differentiates between nodes in a file system.
 #[derive(Debug, Serialize, PartialEq)]
 enum FileTreeNodeType {
@@ -108,6 +112,21 @@ pub enum FilesSubCommands {
         /// Follow symlinks
         #[structopt(short = "l", long = "follow-links")]
         follow_links: bool,
+        /// Encrypt the files and the FilesContainer so only the same keypair can access them. It requires a storage backend which keeps the keypair across sessions, e.g. SN_CLI_STORAGE_BACKEND=local
+        #[structopt(long = "private")]
+        private: bool,
+        /// Keep track of the files uploaded in a local journal, and skip the files a previous run with this flag already uploaded, e.g. to resume an upload which didn't complete
+        #[structopt(long = "resume")]
+        resume: bool,
+        /// Maximum number of files to upload concurrently (default is 4)
+        #[structopt(long = "concurrency")]
+        concurrency: Option<usize>,
+        /// Skip the paths matching this gitignore-syntax pattern, in addition to those listed in '.gitignore' and '.safeignore' files
+        #[structopt(long = "exclude", number_of_values = 1)]
+        exclude: Vec<String>,
+        /// Upload the paths matching this gitignore-syntax pattern even if they are excluded or ignored
+        #[structopt(long = "include", number_of_values = 1)]
+        include: Vec<String>,
     },
     /// Get a file or folder from the SAFE Network
     Get {
@@ -141,9 +160,21 @@ pub enum FilesSubCommands {
         /// Delete files found at the target FilesContainer that are not in the source location. This is only allowed when --recursive is passed as well
         #[structopt(short = "d", long = "delete")]
         delete: bool,
-        /// Automatically update the NRS name to link to the new version of the FilesContainer. This is only allowed if an NRS URL was provided, and if the NRS name is currently linked to a specific version of the FilesContainer
+        /// Automatically update the NRS name to link to the new version of the FilesContainer. This is only allowed if an NRS URL was provided. An NRS name which follows the latest version of the FilesContainer is kept as is
         #[structopt(short = "u", long = "update-nrs")]
         update_nrs: bool,
+        /// Keep track of the files uploaded in a local journal, and skip the files a previous run with this flag already uploaded, e.g. to resume an upload which didn't complete
+        #[structopt(long = "resume")]
+        resume: bool,
+        /// Maximum number of files to upload concurrently (default is 4)
+        #[structopt(long = "concurrency")]
+        concurrency: Option<usize>,
+        /// Skip the paths matching this gitignore-syntax pattern, in addition to those listed in '.gitignore' and '.safeignore' files
+        #[structopt(long = "exclude", number_of_values = 1)]
+        exclude: Vec<String>,
+        /// Upload the paths matching this gitignore-syntax pattern even if they are excluded or ignored
+        #[structopt(long = "include", number_of_values = 1)]
+        include: Vec<String>,
     },
     #[structopt(name = "add")]
     /// Add a file to an existing FilesContainer on the network
@@ -158,7 +189,7 @@ pub enum FilesSubCommands {
         /// The target FilesContainer to add the source file to, optionally including the destination path (default is '/') and new file name
         #[structopt(parse(from_str = parse_stdin_arg))]
         target: Option<String>,
-        /// Automatically update the NRS name to link to the new version of the FilesContainer. This is only allowed if an NRS URL was provided, and if the NRS name is currently linked to a specific version of the FilesContainer
+        /// Automatically update the NRS name to link to the new version of the FilesContainer. This is only allowed if an NRS URL was provided. An NRS name which follows the latest version of the FilesContainer is kept as is
         #[structopt(short = "u", long = "update-nrs")]
         update_nrs: bool,
         /// Overwrite the file on the FilesContainer if there already exists a file with the same name
@@ -173,13 +204,25 @@ pub enum FilesSubCommands {
     Rm {
         /// The full URL of the file to remove from its FilesContainer
         target: String,
-        /// Automatically update the NRS name to link to the new version of the FilesContainer. This is only allowed if an NRS URL was provided, and if the NRS name is currently linked to a specific version of the FilesContainer
+        /// Automatically update the NRS name to link to the new version of the FilesContainer. This is only allowed if an NRS URL was provided. An NRS name which follows the latest version of the FilesContainer is kept as is
         #[structopt(short = "u", long = "update-nrs")]
         update_nrs: bool,
         /// Recursively remove files found in the target path
         #[structopt(short = "r", long = "recursive")]
         recursive: bool,
     },
+    #[structopt(name = "restore")]
+    /// Restore an existing FilesContainer on the network to a previous version
+    Restore {
+        /// The target FilesContainer to restore
+        target: String,
+        /// The version of the FilesContainer to restore its files from
+        #[structopt(short = "v", long = "version")]
+        version: u64,
+        /// Automatically update the NRS name to link to the new version of the FilesContainer. This is only allowed if an NRS URL was provided. An NRS name which follows the latest version of the FilesContainer is kept as is
+        #[structopt(short = "u", long = "update-nrs")]
+        update_nrs: bool,
+    },
     #[structopt(name = "ls")]
     /// List files found in an existing FilesContainer on the network
     Ls {
@@ -195,6 +238,22 @@ pub enum FilesSubCommands {
         #[structopt(short = "d", long = "details")]
         details: bool,
     },
+    #[structopt(name = "diff")]
+    /// Show the files changed between two versions of a FilesContainer, e.g. 'safe://<xorurl>?v=1' and 'safe://<xorurl>?v=3'
+    Diff {
+        /// The FilesContainer, at the version to compare from
+        url_a: String,
+        /// The FilesContainer, at the version to compare to
+        url_b: String,
+    },
+    #[structopt(name = "mount")]
+    /// Mount a FilesContainer as a read-only filesystem, until it's unmounted, e.g. with 'fusermount -u <mountpoint>'
+    Mount {
+        /// The target FilesContainer to mount, optionally including the version (default is the latest)
+        target: String,
+        /// The local folder where to mount it, it must exist and be empty
+        mountpoint: String,
+    },
 }
 
 pub async fn files_commander(
@@ -209,18 +268,30 @@ pub async fn files_commander(
             dest,
             recursive,
             follow_links,
+            private,
+            resume,
+            concurrency,
+            exclude,
+            include,
         } => {
             // create FilesContainer from a given path to local files/folders
             if dry_run && OutputFmt::Pretty == output_fmt {
                 notice_dry_run();
             }
+            if resume {
+                set_upload_journal(safe)?;
+            }
+            if let Some(concurrency) = concurrency {
+                safe.set_upload_concurrency(concurrency);
+            }
+            safe.set_upload_patterns(&exclude, &include)?;
             let (files_container_xorurl, processed_files, _files_map) = safe
                 .files_container_create(
                     Some(&location),
                     dest.as_deref(),
                     recursive,
                     follow_links,
-                    dry_run,
+                    FilesContainerCreateOptions { private, dry_run },
                 )
                 .await?;
 
@@ -237,8 +308,8 @@ pub async fn files_commander(
                     .padding(0, 1)
                     .build();
                 table.set_format(format);
-                for (file_name, (change, link)) in processed_files.iter() {
-                    table.add_row(row![change, file_name, link]);
+                for (file_name, processed_file) in processed_files.iter() {
+                    table.add_row(row![processed_file.change, file_name, processed_file.link]);
                 }
                 table.printstd();
             } else {
@@ -254,11 +325,22 @@ pub async fn files_commander(
             follow_links,
             delete,
             update_nrs,
+            resume,
+            concurrency,
+            exclude,
+            include,
         } => {
             let target = get_from_arg_or_stdin(target, None)?;
             if dry_run && OutputFmt::Pretty == output_fmt {
                 notice_dry_run();
             }
+            if resume {
+                set_upload_journal(safe)?;
+            }
+            if let Some(concurrency) = concurrency {
+                safe.set_upload_concurrency(concurrency);
+            }
+            safe.set_upload_patterns(&exclude, &include)?;
             // Update the FilesContainer on the Network
             let (version, processed_files, _files_map) = safe
                 .files_container_sync(
@@ -360,6 +442,24 @@ pub async fn files_commander(
             output_processed_files_list(output_fmt, processed_files, version, target_url)?;
             Ok(())
         }
+        FilesSubCommands::Restore {
+            target,
+            version,
+            update_nrs,
+        } => {
+            if dry_run && OutputFmt::Pretty == output_fmt {
+                notice_dry_run();
+            }
+
+            // Append a new version of the FilesContainer with the files of the version to restore
+            let (new_version, processed_files, _files_map) = safe
+                .files_container_restore(&target, version, update_nrs, dry_run)
+                .await?;
+
+            // Now let's just print out a list of the files restored
+            output_processed_files_list(output_fmt, processed_files, new_version, target)?;
+            Ok(())
+        }
         FilesSubCommands::Ls { target } => {
             let target_url =
                 get_from_arg_or_stdin(target, Some("...awaiting target URl from STDIN"))?;
@@ -377,7 +477,7 @@ pub async fn files_commander(
                     let (total, filtered_filesmap) = filter_files_map(&files_map, &target_url)?;
                     (version, filtered_filesmap, total)
                 }
-                SafeData::PublicBlob { metadata, .. } => {
+                SafeData::PublicBlob { metadata, .. } | SafeData::PrivateBlob { metadata, .. } => {
                     if let Some(file_item) = metadata {
                         let mut files_map = FilesMap::new();
                         let name = match file_item.get("name") {
@@ -418,6 +518,20 @@ pub async fn files_commander(
         FilesSubCommands::Tree { target, details } => {
             process_tree_command(safe, target, details, output_fmt).await
         }
+        FilesSubCommands::Diff { url_a, url_b } => {
+            let (version_a, version_b, diff) = safe.files_container_diff(&url_a, &url_b).await?;
+
+            if OutputFmt::Pretty == output_fmt {
+                print_files_map_diff(&diff, version_a, version_b);
+            } else {
+                println!("{}", serialise_output(&diff, output_fmt));
+            }
+
+            Ok(())
+        }
+        FilesSubCommands::Mount { target, mountpoint } => {
+            process_mount_command(safe, &target, &mountpoint, output_fmt).await
+        }
         FilesSubCommands::Get {
             source,
             dest,
@@ -428,6 +542,55 @@ pub async fn files_commander(
     }
 }
 
+// Keep track of the files uploaded in the local upload journal, and skip those it already
+// has, so an upload which didn't complete can be resumed
+fn set_upload_journal(safe: &mut Safe) -> Result<(), String> {
+    let mut journal_path =
+        dirs_next::home_dir().ok_or_else(|| "Failed to obtain user's home path".to_string())?;
+    journal_path.push(".safe");
+    journal_path.push("cli");
+    journal_path.push(UPLOAD_JOURNAL_FILENAME);
+
+    safe.set_upload_journal(&journal_path)
+        .map_err(|err| format!("Failed to load the upload journal: {}", err))
+}
+
+// processes the `safe files mount` command.
+#[cfg(all(unix, feature = "mount"))]
+async fn process_mount_command(
+    safe: &mut Safe,
+    target: &str,
+    mountpoint: &str,
+    output_fmt: OutputFmt,
+) -> Result<(), String> {
+    debug!("Mounting container {:?} at {:?}", target, mountpoint);
+    let (version, files_map) = match safe.fetch(target, None).await? {
+        SafeData::FilesContainer {
+            version, files_map, ..
+        } => (version, files_map),
+        _other_type => return Err("Make sure the URL targets a FilesContainer.".to_string()),
+    };
+
+    if OutputFmt::Pretty == output_fmt {
+        println!(
+            "Mounting version {} of FilesContainer at \"{}\", unmount it to exit...",
+            version, mountpoint
+        );
+    }
+
+    mount_files_map(safe, &files_map, mountpoint).await
+}
+
+#[cfg(not(all(unix, feature = "mount")))]
+async fn process_mount_command(
+    _safe: &mut Safe,
+    _target: &str,
+    _mountpoint: &str,
+    _output_fmt: OutputFmt,
+) -> Result<(), String> {
+    Err("Mounting FilesContainers is only supported on Unix platforms when the CLI is built with the 'mount' feature, e.g. with 'cargo build --features mount'".to_string())
+}
+
 // processes the `safe files tree` command.
 async fn process_tree_command(
     safe: &mut Safe,
@@ -480,7 +643,7 @@ async fn process_tree_command(
 fn print_serialized_output(
     xorurl: XorUrl,
     version: u64,
-    processed_files: BTreeMap<String, (String, String)>,
+    processed_files: ProcessedFiles,
     output_fmt: OutputFmt,
 ) -> Result<(), String> {
     let url = match XorUrlEncoder::from_url(&xorurl) {
@@ -516,6 +679,17 @@ fn output_processed_files_list(
 
             println!("FilesContainer updated (version {}): \"{}\"", version, url);
             table.printstd();
+
+            let deduplicated: u64 = processed_files
+                .values()
+                .filter_map(|processed_file| processed_file.deduplicated_size)
+                .sum();
+            if deduplicated > 0 {
+                println!(
+                    "{} bytes were not uploaded since they were already stored",
+                    deduplicated
+                );
+            }
         } else if !processed_files.is_empty() {
             println!(
                 "No changes were made to FilesContainer (version {}) at \"{}\"",
@@ -535,6 +709,57 @@ fn output_processed_files_list(
     Ok(())
 }
 
+// Prints the changes made to the files between two versions of a FilesContainer
+fn print_files_map_diff(diff: &FilesMapDiff, version_a: u64, version_b: u64) {
+    if diff.is_empty() {
+        println!(
+            "No changes were made to files from version {} to version {}",
+            version_a, version_b
+        );
+        return;
+    }
+
+    println!(
+        "Files changed from version {} to version {}: {}",
+        version_a,
+        version_b,
+        diff.len()
+    );
+    let mut table = Table::new();
+    table.set_format(
+        FormatBuilder::new()
+            .column_separator(' ')
+            .padding(0, 1)
+            .build(),
+    );
+    for (path, file_diff) in diff.iter() {
+        let name = match &file_diff.renamed_from {
+            Some(renamed_from) => format!("{} -> {}", renamed_from, path),
+            None => path.to_string(),
+        };
+        let metadata_changes = file_diff
+            .metadata_changes
+            .iter()
+            .map(|(key, (value_a, value_b))| {
+                format!(
+                    "{}: {} -> {}",
+                    key,
+                    value_a.as_deref().unwrap_or(""),
+                    value_b.as_deref().unwrap_or("")
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+        table.add_row(row![
+            file_diff.change,
+            name,
+            file_diff.link,
+            metadata_changes
+        ]);
+    }
+    table.printstd();
+}
+
 // Builds a file-system tree (hierarchy) from a single file path, split into its parts.
 // May be called multiple times to expand the tree.
 fn build_tree(
@@ -11,20 +11,22 @@ use super::{
     helpers::{div_or, pluralize, prompt_user},
     OutputFmt,
 };
+use async_std::io::{prelude::WriteExt, Write as AsyncWrite};
 use console::Term;
 use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle, TickTimeLimit};
 use log::{debug, info, trace, warn};
 use sn_api::{
     fetch::Range,
     fetch::SafeData,
-    files::{FilesMap, GetAttr, ProcessedFiles},
+    files::{FilesMap, GetAttr, ProcessedFile, ProcessedFiles},
     xorurl::{SafeDataType, XorUrl, XorUrlEncoder},
     Error, Result as ApiResult, Safe,
 };
 use std::fs;
-use std::io::BufWriter;
-use std::io::Write;
+use std::io::{self, BufWriter, Write};
 use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
 /// # Retrieval/write status for current file and overall transfer.
@@ -429,7 +431,7 @@ async fn files_container_get_files(
         SafeData::FilesContainer {
             version, files_map, ..
         } => (version, files_map),
-        SafeData::PublicBlob { metadata, .. } => {
+        SafeData::PublicBlob { metadata, .. } | SafeData::PrivateBlob { metadata, .. } => {
             if let Some(file_item) = metadata {
                 let mut files_map = FilesMap::new();
                 files_map.insert("".to_string(), file_item);
@@ -632,7 +634,10 @@ async fn files_map_get_files(
             Some(p) => p,
             None => {
                 let msg = "Could not get parent directory";
-                processed_files.insert(path.to_string(), ("E".to_string(), format!("<{}>", msg)));
+                processed_files.insert(
+                    path.to_string(),
+                    ProcessedFile::new("E", format!("<{}>", msg), None),
+                );
                 warn!("Skipping file \"{}\". {}", path, msg);
                 continue;
             }
@@ -672,10 +677,16 @@ async fn files_map_get_files(
         .await
         {
             Ok(_bytes) => {
-                processed_files.insert(path.to_string(), ("+".to_string(), (*xorurl).to_string()));
+                processed_files.insert(
+                    path.to_string(),
+                    ProcessedFile::new("+", (*xorurl).to_string(), None),
+                );
             }
             Err(err) => {
-                processed_files.insert(path.to_string(), ("E".to_string(), format!("<{}>", err)));
+                processed_files.insert(
+                    path.to_string(),
+                    ProcessedFile::new("E", format!("<{}>", err), None),
+                );
                 info!("Skipping file \"{}\". {}", path, err);
             }
         };
@@ -747,68 +758,125 @@ fn denormalize_slashes(p: &str) -> String {
 }
 
 // Downloads a file from the network to a given file path
-// Data is downloaded and written to filesystem in 64k chunks.
+// Data is downloaded and written to filesystem one Blob at a time.
 // xorurl must point to immutable data
 // size (in bytes) must be provided
-// A callback/closure is called after each chunk is downloaded.
+// A callback/closure is called after each chunk is written.
 async fn download_file_from_net(
-    safe: Safe,
+    mut safe: Safe,
     xorurl: &str,
     path: &Path,
     size: u64,
     //Path, file_size, file_bytes_written, bytes_written.  return false to cancel download.
-    mut callback: impl FnMut(&Path, u64, u64, u64) -> bool,
+    callback: impl FnMut(&Path, u64, u64, u64) -> bool + Unpin,
 ) -> ApiResult<u64> {
     debug!("downloading file {} to {}", xorurl, path.display());
 
-    // chunk_size based on https://stackoverflow.com/questions/8803515/optimal-buffer-size-for-write2
-    // originally it was 4096 to match common disk block size, but that seems a bit small for the
-    // network, so I multiplied by 16.  Perhaps should make it a param so caller can decide.
-    let chunk_size: u64 = 65536;
-    let mut rcvd: u64 = 0;
-    let mut bytes_written: u64 = 0;
-
     let fh = file_create(path)?;
-    let mut stream = BufWriter::new(fh);
-
-    // stream and write the file in chunk_size pieces
-    while rcvd < size {
-        let start = rcvd;
-        let end = if rcvd + chunk_size < size {
-            rcvd + chunk_size
-        } else {
-            size
-        };
-        let range = Some((Some(start), Some(end)));
-        // gets public or private, based on xorurl type
-        let filedata = files_get_blob(safe.clone(), &xorurl, range).await?;
-        bytes_written += stream_write(&mut stream, &filedata, &path)? as u64;
-        rcvd += filedata.len() as u64;
-        trace!(
-            "received {} bytes of {}.  chunk start: {}, end: {}",
-            rcvd,
-            size,
-            start,
-            end
-        );
+    let mut writer = ProgressWriter {
+        inner: BufWriter::new(fh),
+        path,
+        size,
+        bytes_written: 0,
+        callback,
+        cancelled: false,
+    };
 
-        // invoke callback if present, with status info.
-        //        if let Some(cb) = callback {
-        let b_continue = callback(path, size, bytes_written, filedata.len() as u64);
-        if !b_continue {
-            trace!("download cancelled by callback");
-            break;
+    // the data is streamed and written as each Blob it's made of is fetched, so
+    // we don't need to hold the whole file in memory
+    let result = match XorUrlEncoder::from_url(&xorurl)?.data_type() {
+        SafeDataType::PublicBlob => safe
+            .files_get_public_blob_to_writer(&xorurl, None, &mut writer)
+            .await
+            .map(|_| ()),
+        _ => {
+            // gets private blob, or fails, based on xorurl type
+            let filedata = files_get_blob(safe.clone(), &xorurl, None).await?;
+            writer.write_all(&filedata).await.map_err(|err| {
+                Error::FileSystemError(format!(
+                    "Error writing to file: \"{}\" {:?}",
+                    path.display(),
+                    err
+                ))
+            })
         }
+    };
+
+    // a download cancelled by the callback is not an error, we just stop writing
+    match result {
+        Err(_) if writer.cancelled => trace!("download cancelled by callback"),
+        result => result?,
     }
+    trace!("received {} bytes of {}", writer.bytes_written, size);
 
     // Close may generate an error, so we do a flush/sync first to detect such.
     // see https://github.com/rust-lang/rust/pull/63410#issuecomment-519965351
-    let fh = bufwriter_into_inner(stream, &path)?;
+    let bytes_written = writer.bytes_written;
+    let fh = bufwriter_into_inner(writer.inner, &path)?;
     file_sync_all(&fh, &path)?;
 
     Ok(bytes_written as u64)
 }
 
+// Error message of the write error used to stop a download cancelled by the callback
+const DOWNLOAD_CANCELLED: &str = "download cancelled";
+
+// Writer which writes onto a file, reporting the progress to a callback after each write.
+// If the callback cancels the download, writes fail and `cancelled` is set.
+struct ProgressWriter<'a, W: Write, F: FnMut(&Path, u64, u64, u64) -> bool> {
+    inner: W,
+    path: &'a Path,
+    size: u64,
+    bytes_written: u64,
+    callback: F,
+    cancelled: bool,
+}
+
+impl<W, F> AsyncWrite for ProgressWriter<'_, W, F>
+where
+    W: Write + Unpin,
+    F: FnMut(&Path, u64, u64, u64) -> bool + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.cancelled {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::Other,
+                DOWNLOAD_CANCELLED,
+            )));
+        }
+
+        let written = match this.inner.write(buf) {
+            Ok(written) => written,
+            Err(err) => return Poll::Ready(Err(err)),
+        };
+        this.bytes_written += written as u64;
+
+        // invoke callback with status info.
+        if !(this.callback)(this.path, this.size, this.bytes_written, written as u64) {
+            this.cancelled = true;
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::Other,
+                DOWNLOAD_CANCELLED,
+            )));
+        }
+
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().inner.flush())
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().inner.flush())
+    }
+}
+
 // syncs file to filesystem.
 fn file_sync_all(f: &fs::File, path: &Path) -> ApiResult<()> {
     f.sync_all().map_err(|err| {
@@ -831,17 +899,6 @@ fn bufwriter_into_inner<W: Write>(w: BufWriter<W>, path: &Path) -> ApiResult<W>
     })
 }
 
-// Writes data to a file/stream.
-fn stream_write(writer: &mut dyn Write, data: &[u8], path: &Path) -> ApiResult<usize> {
-    writer.write(&data).map_err(|err| {
-        Error::FileSystemError(format!(
-            "Error writing to file: \"{}\" {:?}",
-            path.display(),
-            err
-        ))
-    })
-}
-
 // Creates a file, ready for writing.
 fn file_create(path: &Path) -> ApiResult<fs::File> {
     fs::File::create(path).map_err(|err| {
@@ -870,22 +927,9 @@ fn create_dir_all(dir_path: &Path) -> ApiResult<()> {
     })
 }
 
-/// # Get Private Blob
-/// Get private immutable data blobs from the network.
-///
-async fn files_get_private_blob(_safe: &Safe, _url: &str, _range: Range) -> ApiResult<Vec<u8>> {
-    unimplemented!();
-}
-
 /// # Get Public or Private Blob
 /// Get immutable data blobs from the network.
 ///
 pub async fn files_get_blob(mut safe: Safe, url: &str, range: Range) -> ApiResult<Vec<u8>> {
-    match XorUrlEncoder::from_url(&url)?.data_type() {
-        SafeDataType::PublicBlob => safe.files_get_public_blob(&url, range).await,
-        SafeDataType::PrivateBlob => files_get_private_blob(&safe, &url, range).await,
-        _ => Err(Error::InvalidInput(
-            "URL target is not immutable data.".to_string(),
-        )),
-    }
+    safe.files_get_blob(&url, range).await
 }
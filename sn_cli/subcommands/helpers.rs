@@ -13,11 +13,13 @@ use log::debug;
 use num_traits::Float;
 use prettytable::{format::FormatBuilder, Table};
 use serde::ser::Serialize;
-use sn_api::nrs_map::NrsMap;
-use std::{
-    collections::BTreeMap,
-    io::{stdin, stdout, Read, Write},
+use sn_api::{
+    files::{ProcessedFile, ProcessedFiles},
+    nrs_map::NrsMap,
+    wallet::{TransferDirection, TransferHistoryEntry, TransferReceipt},
+    CONTENT_ERROR_SIGN, CONTENT_SKIPPED_SIGN,
 };
+use std::io::{stdin, stdout, Read, Write};
 use xor_name::{XorName, XOR_NAME_LEN};
 
 const UNKNOWN_PUBLIC_NAME: &str = "<unknown>";
@@ -130,7 +132,7 @@ pub fn get_secret_key(key_xorurl: &str, sk: Option<String>, msg: &str) -> Result
 }
 
 pub fn gen_processed_files_table(
-    processed_files: &BTreeMap<String, (String, String)>,
+    processed_files: &ProcessedFiles,
     show_change_sign: bool,
 ) -> (Table, u64) {
     let mut table = Table::new();
@@ -140,8 +142,8 @@ pub fn gen_processed_files_table(
         .build();
     table.set_format(format);
     let mut success_count = 0;
-    for (file_name, (change, link)) in processed_files.iter() {
-        if change != "E" {
+    for (file_name, ProcessedFile { change, link, .. }) in processed_files.iter() {
+        if change != CONTENT_ERROR_SIGN && change != CONTENT_SKIPPED_SIGN {
             success_count += 1;
         }
         if show_change_sign {
@@ -163,7 +165,7 @@ pub fn parse_stdin_arg(src: &str) -> String {
 }
 
 // serialize structured value using any format from OutputFmt
-// except OutputFmt::Pretty, which must be handled by caller.
+// except OutputFmt::Pretty and the RDF formats, which must be handled by caller.
 pub fn serialise_output<T: ?Sized>(value: &T, fmt: OutputFmt) -> String
 where
     T: Serialize,
@@ -178,6 +180,9 @@ where
         OutputFmt::Pretty => {
             "OutputFmt::Pretty' not handled by caller, in serialise_output()".to_string()
         }
+        OutputFmt::Turtle | OutputFmt::JsonLd => {
+            "RDF output formats not handled by caller, in serialise_output()".to_string()
+        }
     }
 }
 
@@ -202,6 +207,70 @@ pub fn print_nrs_map(nrs_map: &NrsMap, public_name: &Option<String>) {
     table.printstd();
 }
 
+// Print the receipt of a transfer of safecoins
+pub fn print_transfer_receipt(receipt: &TransferReceipt, output_fmt: OutputFmt) {
+    if OutputFmt::Pretty == output_fmt {
+        println!("Success. TX_ID: {}", receipt.id);
+        println!("Amount: {}", receipt.amount);
+        println!("From SafeKey: 0x{}", xorname_to_hex(&receipt.from));
+        println!("To SafeKey: 0x{}", xorname_to_hex(&receipt.to));
+        println!("Timestamp: {}", receipt.timestamp);
+    } else {
+        println!("{}", serialise_output(receipt, output_fmt));
+    }
+}
+
+// Pretty print a list of transfers of a SafeKey or Wallet
+pub fn print_transfer_history(history: &[TransferHistoryEntry]) {
+    let with_spendable_balances = history
+        .iter()
+        .any(|entry| entry.spendable_balance.is_some());
+
+    let mut table = Table::new();
+    if with_spendable_balances {
+        table.add_row(row![bFg->"TX_ID", bFg->"Timestamp", bFg->"Spendable Balance", bFg->"Amount", bFg->"From/To SafeKey", bFg->"Balance"]);
+    } else {
+        table.add_row(row![bFg->"TX_ID", bFg->"Timestamp", bFg->"Amount", bFg->"From/To SafeKey", bFg->"Balance"]);
+    }
+
+    for entry in history.iter() {
+        let (amount, other_key) = match entry.direction {
+            TransferDirection::Incoming => (
+                format!("+{}", entry.amount),
+                entry.from.map_or_else(
+                    || "(payout)".to_string(),
+                    |from| format!("from 0x{}", xorname_to_hex(&from)),
+                ),
+            ),
+            TransferDirection::Outgoing => (
+                format!("-{}", entry.amount),
+                format!("to 0x{}", xorname_to_hex(&entry.to)),
+            ),
+        };
+
+        if with_spendable_balances {
+            let name = entry.spendable_balance.as_deref().unwrap_or("");
+            table.add_row(row![
+                entry.id,
+                entry.timestamp,
+                name,
+                amount,
+                other_key,
+                entry.balance
+            ]);
+        } else {
+            table.add_row(row![
+                entry.id,
+                entry.timestamp,
+                amount,
+                other_key,
+                entry.balance
+            ]);
+        }
+    }
+    table.printstd();
+}
+
 // returns singular or plural version of string, based on count.
 pub fn pluralize<'a>(singular: &'a str, plural: &'a str, count: u64) -> &'a str {
     if count == 1 {
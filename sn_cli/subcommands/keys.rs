@@ -8,7 +8,7 @@
 // Software.
 
 use super::{
-    helpers::{get_from_arg_or_stdin, get_secret_key, serialise_output},
+    helpers::{get_from_arg_or_stdin, get_secret_key, print_transfer_receipt, serialise_output},
     OutputFmt,
 };
 use crate::operations::safe_net::connect;
@@ -140,16 +140,11 @@ pub async fn key_commander(
                 Some("...awaiting destination Wallet/SafeKey URL from STDIN stream..."),
             )?;
 
-            let tx_id = safe
+            let receipt = safe
                 .keys_transfer(&amount, from.as_deref(), &destination)
                 .await?;
 
-            if OutputFmt::Pretty == output_fmt {
-                println!("Successful. TX_ID: {:?}", &tx_id);
-            } else {
-                println!("{:?}", &tx_id)
-            }
-
+            print_transfer_receipt(&receipt, output_fmt);
             Ok(())
         }
     }
@@ -33,6 +33,8 @@ pub enum OutputFmt {
     Json,
     JsonCompact,
     Yaml,
+    Turtle,
+    JsonLd,
 }
 
 impl std::str::FromStr for OutputFmt {
@@ -42,9 +44,11 @@ impl std::str::FromStr for OutputFmt {
             "json" => Ok(Self::Json),
             "jsoncompact" => Ok(Self::JsonCompact),
             "yaml" => Ok(Self::Yaml),
+            "turtle" => Ok(Self::Turtle),
+            "jsonld" => Ok(Self::JsonLd),
             other => {
                 Err(format!(
-                    "Output serialisation format '{}' not supported. Supported values are json, jsoncompact, and yaml",
+                    "Output serialisation format '{}' not supported. Supported values are json, jsoncompact, yaml, turtle, and jsonld",
                     other
                 ))
             }
@@ -52,6 +56,15 @@ impl std::str::FromStr for OutputFmt {
     }
 }
 
+// RDF output formats are only supported when fetching FilesContainers and NRS Map Containers
+// with 'cat', any other command or content rejects them so nothing is output as if it was data
+pub fn reject_rdf_output_fmt(output_fmt: OutputFmt) -> Result<(), String> {
+    match output_fmt {
+        OutputFmt::Turtle | OutputFmt::JsonLd => Err("RDF output formats are only supported when fetching FilesContainers and NRS Map Containers with 'cat'".to_string()),
+        _ => Ok(()),
+    }
+}
+
 #[derive(StructOpt, Debug)]
 pub enum SubCommands {
     #[structopt(name = "config")]
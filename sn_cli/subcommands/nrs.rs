@@ -12,8 +12,15 @@ use super::{
     OutputFmt,
 };
 use prettytable::{format::FormatBuilder, Table};
-use sn_api::{xorurl::XorUrl, Safe};
-use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use sn_api::{
+    fetch::SafeContentType,
+    nrs_map::{DefaultRdf, NrsLinkOptions, NrsLinkPolicy, NrsMap, NrsMapChange, SubNameRDF},
+    pk_bytes_to_hex, pk_from_hex,
+    xorurl::{XorUrl, XorUrlEncoder},
+    Safe,
+};
+use std::fs;
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -21,7 +28,7 @@ pub enum NrsSubCommands {
     #[structopt(name = "add")]
     /// Add a subname to an existing NRS name, or updates its link if it already exists
     Add {
-        /// The name to add (or update if it already exists)
+        /// The name to add (or update if it already exists). A '*' sub name matches any sub name not added explicitly, e.g. '*.docs.mysite', which should be wrapped in quotes on bash based systems
         name: String,
         /// The safe:// URL to map this to. Usually a FilesContainer for a website. This should be wrapped in double quotes on bash based systems.
         #[structopt(short = "l", long = "link")]
@@ -32,11 +39,28 @@ pub enum NrsSubCommands {
         /// If --default is set, the default name is set using a direct link to the final destination that was provided with `--link`, rather than a link to the sub name being added (which is the default behaviour if this flag is not passed)
         #[structopt(long = "direct")]
         direct_link: bool,
+        /// For a '*' sub name, pass the sub name it matched on as a path prefix of the link, e.g. resolving 'pr-123.docs.mysite' to '<link>/pr-123'
+        #[structopt(long = "capture-path")]
+        capture_path: bool,
+        /// Resolve the link with the latest version of the content at the time it's fetched, rather than a version set in the link, e.g. to follow a FilesContainer as it's synced. The link must not specify a version
+        #[structopt(long = "follow-latest")]
+        follow_latest: bool,
+    },
+    #[structopt(name = "alias")]
+    /// Add a subname to an existing NRS name as an alias of another NRS name, or updates it if it already exists
+    Alias {
+        /// The name to add (or update if it already exists)
+        name: String,
+        /// The NRS-URL of the NRS name to alias, without a version as it's always resolved with its latest version. A path in it is prefixed to the path of the URLs resolved with the alias, e.g. 'safe://mysite/blog'
+        target: String,
+        /// Set the sub name as default for this public name
+        #[structopt(long = "default")]
+        default: bool,
     },
     #[structopt(name = "create")]
     /// Create a new public name
     Create {
-        /// The name to give site, eg 'safenetwork'
+        /// The name to give site, eg 'safenetwork'. Names in any language are supported, e.g. 'café', they are normalised and punycode encoded, e.g. as 'xn--caf-dma', and have to be in lowercase if they are not ASCII
         name: String,
         /// The safe:// URL to map this to. Usually a FilesContainer for a website. This should be wrapped in double quotes on bash based systems.
         #[structopt(short = "l", long = "link")]
@@ -44,6 +68,15 @@ pub enum NrsSubCommands {
         /// The default name is set using a direct link to the final destination that was provided with `--link`, rather than a link to the sub name being created (which is the default behaviour if this flag is not passed)
         #[structopt(long = "direct")]
         direct_link: bool,
+        /// Resolve the link with the latest version of the content at the time it's fetched, rather than a version set in the link, e.g. to follow a FilesContainer as it's synced. The link must not specify a version
+        #[structopt(long = "follow-latest")]
+        follow_latest: bool,
+    },
+    #[structopt(name = "apply")]
+    /// Apply the changes listed in a YAML file to an NRS name, all in a single new version of its NRS Map Container
+    Apply {
+        /// The YAML file with the NRS name and the list of changes to apply, each of them with an 'op' of 'add', 'alias', 'remove' or 'set_default', and the arguments of the corresponding command, e.g. 'name: mysite' and 'changes: [{op: add, name: docs.mysite, link: "safe://..."}]'
+        file: String,
     },
     #[structopt(name = "remove")]
     /// Remove a subname from an NRS name
@@ -51,6 +84,66 @@ pub enum NrsSubCommands {
         /// The name to remove
         name: String,
     },
+    #[structopt(name = "owner")]
+    /// Show the public key of the owner of an NRS name
+    Owner {
+        /// The NRS name, without any sub names
+        name: String,
+    },
+    #[structopt(name = "transfer")]
+    /// Transfer an NRS name to a new owner. Once transferred, it can only be changed by the new owner
+    Transfer {
+        /// The NRS name to transfer, without any sub names
+        name: String,
+        /// The public key of the new owner, as a hex-encoded Ed25519 or BLS public key
+        new_owner: String,
+    },
+    #[structopt(name = "ls")]
+    /// List the sub names of an NRS name as a tree, with their links and defaults
+    Ls {
+        /// The NRS name to list, without any sub names
+        name: String,
+    },
+    #[structopt(name = "history")]
+    /// Show the changes made in each version of the NRS Map Container of an NRS name
+    History {
+        /// The NRS name, without any sub names
+        name: String,
+    },
+    #[structopt(name = "whois")]
+    /// Show the NRS Map Container, owner and current version of an NRS name
+    Whois {
+        /// The NRS name, without any sub names
+        name: String,
+    },
+}
+
+// A sub name of an NRS name, with the link it resolves to, and the
+// default for its own sub names if it has any
+#[derive(Debug, Serialize)]
+struct NrsTreeNode {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    sub_names: Vec<NrsTreeNode>,
+}
+
+// The content of a file with changes to apply to an NRS name
+#[derive(Debug, Deserialize)]
+struct NrsChangesFile {
+    name: String,
+    changes: Vec<NrsMapChange>,
+}
+
+#[derive(Debug, Serialize)]
+struct NrsWhois {
+    name: String,
+    nrs_map_container: XorUrl,
+    owner: String,
+    version: u64,
 }
 
 pub async fn nrs_commander(
@@ -64,6 +157,7 @@ pub async fn nrs_commander(
             name,
             link,
             direct_link,
+            follow_latest,
         } => {
             // TODO: Where do we store/reference these? add it to the Root container,
             // sanitize name / spacing etc., validate destination?
@@ -77,16 +171,35 @@ pub async fn nrs_commander(
             let default = true;
 
             let (nrs_map_container_xorurl, processed_entries, _nrs_map) = safe
-                .nrs_map_container_create(&name, &link, default, direct_link, dry_run)
+                .nrs_map_container_create(
+                    &name,
+                    &link,
+                    default,
+                    direct_link,
+                    NrsLinkOptions {
+                        link_policy: link_policy(follow_latest),
+                        ..Default::default()
+                    },
+                    dry_run,
+                )
                 .await?;
 
+            // Internationalised names are shown along with their encoded form
+            let nrs_url = Safe::parse_url(&name)?;
+            let header_msg = if nrs_url.public_name() == nrs_url.public_name_display() {
+                format!("New NRS Map for \"{}\" created at", nrs_url)
+            } else {
+                format!(
+                    "New NRS Map for \"{}\" (encoded as \"{}\") created at",
+                    nrs_url.to_display_string(),
+                    nrs_url
+                )
+            };
+
             // Now let's just print out a summary
             print_summary(
                 output_fmt,
-                &format!(
-                    "New NRS Map for \"safe://{}\" created at",
-                    name.replace("safe://", "")
-                ),
+                &header_msg,
                 nrs_map_container_xorurl,
                 processed_entries,
             );
@@ -98,6 +211,8 @@ pub async fn nrs_commander(
             link,
             default,
             direct_link,
+            capture_path,
+            follow_latest,
         } => {
             let link = get_from_arg_or_stdin(link, Some("...awaiting link URL from stdin"))?;
 
@@ -106,7 +221,64 @@ pub async fn nrs_commander(
             }
 
             let (version, xorurl, processed_entries, _nrs_map) = safe
-                .nrs_map_container_add(&name, &link, default, direct_link, dry_run)
+                .nrs_map_container_add(
+                    &name,
+                    &link,
+                    default,
+                    direct_link,
+                    NrsLinkOptions {
+                        capture_label: capture_path,
+                        link_policy: link_policy(follow_latest),
+                    },
+                    dry_run,
+                )
+                .await?;
+
+            // Now let's just print out the summary
+            print_summary(
+                output_fmt,
+                &format!("NRS Map updated (version {})", version),
+                xorurl,
+                processed_entries,
+            );
+
+            Ok(())
+        }
+        NrsSubCommands::Alias {
+            name,
+            target,
+            default,
+        } => {
+            if dry_run && OutputFmt::Pretty == output_fmt {
+                notice_dry_run();
+            }
+
+            let (version, xorurl, processed_entries, _nrs_map) = safe
+                .nrs_map_container_add_alias(&name, &target, default, dry_run)
+                .await?;
+
+            // Now let's just print out the summary
+            print_summary(
+                output_fmt,
+                &format!("NRS Map updated (version {})", version),
+                xorurl,
+                processed_entries,
+            );
+
+            Ok(())
+        }
+        NrsSubCommands::Apply { file } => {
+            let content = fs::read_to_string(&file)
+                .map_err(|err| format!("Failed to read file '{}': {}", file, err))?;
+            let changes_file: NrsChangesFile = serde_yaml::from_str(&content)
+                .map_err(|err| format!("Failed to parse changes from '{}': {}", file, err))?;
+
+            if dry_run && OutputFmt::Pretty == output_fmt {
+                notice_dry_run();
+            }
+
+            let (version, xorurl, processed_entries, _nrs_map) = safe
+                .nrs_map_container_apply(&changes_file.name, &changes_file.changes, dry_run)
                 .await?;
 
             // Now let's just print out the summary
@@ -135,17 +307,142 @@ pub async fn nrs_commander(
                 processed_entries,
             );
 
+            Ok(())
+        }
+        NrsSubCommands::Owner { name } => {
+            let owner = pk_bytes_to_hex(&safe.nrs_owner(&name).await?);
+
+            if OutputFmt::Pretty == output_fmt {
+                println!(
+                    "Owner of NRS name \"safe://{}\": {}",
+                    name.replace("safe://", ""),
+                    owner
+                );
+            } else {
+                println!("{}", serialise_output(&(name, owner), output_fmt));
+            }
+
+            Ok(())
+        }
+        NrsSubCommands::Transfer { name, new_owner } => {
+            let new_owner_pk = pk_from_hex(&new_owner)?;
+
+            if dry_run && OutputFmt::Pretty == output_fmt {
+                notice_dry_run();
+            }
+
+            // The ownership is checked even on a dry run
+            safe.nrs_transfer(&name, new_owner_pk, dry_run).await?;
+
+            let new_owner = pk_bytes_to_hex(&new_owner_pk);
+            if OutputFmt::Pretty == output_fmt {
+                println!(
+                    "NRS name \"safe://{}\" transferred to new owner: {}",
+                    name.replace("safe://", ""),
+                    new_owner
+                );
+            } else {
+                println!("{}", serialise_output(&(name, new_owner), output_fmt));
+            }
+
+            Ok(())
+        }
+        NrsSubCommands::Ls { name } => {
+            let top_name = name.replace("safe://", "");
+            let (version, nrs_map) = safe
+                .nrs_map_container_get(&format!("safe://{}", top_name))
+                .await?;
+            let tree = gen_nrs_tree(&nrs_map, &top_name);
+
+            if OutputFmt::Pretty == output_fmt {
+                println!(
+                    "NRS Map Container (version {}) of \"safe://{}\":",
+                    version, top_name
+                );
+                println!("{}", format_nrs_tree_node(&tree));
+                print_nrs_tree_children(&tree, "");
+            } else {
+                println!("{}", serialise_output(&(version, tree), output_fmt));
+            }
+
+            Ok(())
+        }
+        NrsSubCommands::History { name } => {
+            let history = safe.nrs_map_container_history(&name).await?;
+
+            if OutputFmt::Pretty == output_fmt {
+                println!(
+                    "History of NRS name \"safe://{}\":",
+                    name.replace("safe://", "")
+                );
+                for (version, changes) in history.iter() {
+                    println!("Version {}:", version);
+                    let mut table = Table::new();
+                    let format = FormatBuilder::new()
+                        .column_separator(' ')
+                        .padding(0, 1)
+                        .build();
+                    table.set_format(format);
+                    for (public_name, (change, link)) in changes.iter() {
+                        table.add_row(row![change, public_name, link]);
+                    }
+                    table.printstd();
+                }
+            } else {
+                println!("{}", serialise_output(&(name, history), output_fmt));
+            }
+
+            Ok(())
+        }
+        NrsSubCommands::Whois { name } => {
+            let top_name = name.replace("safe://", "");
+            let nrs_url = format!("safe://{}", top_name);
+            let owner = pk_bytes_to_hex(&safe.nrs_owner(&nrs_url).await?);
+            let (version, _) = safe.nrs_map_container_get(&nrs_url).await?;
+            let xorurl_encoder = XorUrlEncoder::from_nrsurl(&nrs_url)?;
+            let nrs_map_container = XorUrlEncoder::encode_sequence_data(
+                xorurl_encoder.xorname(),
+                xorurl_encoder.type_tag(),
+                SafeContentType::NrsMapContainer,
+                safe.xorurl_base,
+                false,
+            )?;
+            let whois = NrsWhois {
+                name: nrs_url,
+                nrs_map_container,
+                owner,
+                version,
+            };
+
+            if OutputFmt::Pretty == output_fmt {
+                let mut table = Table::new();
+                table.add_row(row!["NRS name", whois.name]);
+                table.add_row(row!["NRS Map Container", whois.nrs_map_container]);
+                table.add_row(row!["Owner", whois.owner]);
+                table.add_row(row!["Version", whois.version]);
+                table.printstd();
+            } else {
+                println!("{}", serialise_output(&whois, output_fmt));
+            }
+
             Ok(())
         }
     }
 }
 
-fn print_summary(
-    output_fmt: OutputFmt,
-    header_msg: &str,
-    xorurl: XorUrl,
-    processed_entries: BTreeMap<String, (String, String)>,
-) {
+// Link policy of an entry added with or without the --follow-latest flag
+fn link_policy(follow_latest: bool) -> NrsLinkPolicy {
+    if follow_latest {
+        NrsLinkPolicy::FollowLatest
+    } else {
+        NrsLinkPolicy::Pinned
+    }
+}
+
+fn print_summary<E>(output_fmt: OutputFmt, header_msg: &str, xorurl: XorUrl, processed_entries: E)
+where
+    E: IntoIterator<Item = (String, (String, String))> + Serialize,
+{
     if OutputFmt::Pretty == output_fmt {
         let mut table = Table::new();
         let format = FormatBuilder::new()
@@ -154,7 +451,7 @@ fn print_summary(
             .build();
         table.set_format(format);
 
-        for (public_name, (change, name_link)) in processed_entries.iter() {
+        for (public_name, (change, name_link)) in processed_entries {
             table.add_row(row![change, public_name, name_link]);
         }
         println!("{}: \"{}\"", header_msg, xorurl);
@@ -166,3 +463,60 @@ fn print_summary(
         );
     }
 }
+
+fn gen_nrs_tree(nrs_map: &NrsMap, name: &str) -> NrsTreeNode {
+    let default = match &nrs_map.default {
+        DefaultRdf::NotSet => None,
+        DefaultRdf::ExistingRdf(sub_name) => Some(format!("{}.{}", sub_name, name)),
+        DefaultRdf::OtherRdf(def_data) => def_data.get("link").cloned(),
+    };
+
+    let sub_names = nrs_map
+        .sub_names_map
+        .iter()
+        .map(|(sub_name, sub_name_rdf)| {
+            let sub_name = format!("{}.{}", sub_name, name);
+            match sub_name_rdf {
+                SubNameRDF::Definition(def_data) => NrsTreeNode {
+                    name: sub_name,
+                    link: def_data.get("link").cloned(),
+                    default: None,
+                    sub_names: vec![],
+                },
+                SubNameRDF::SubName(sub_nrs_map) => gen_nrs_tree(sub_nrs_map, &sub_name),
+            }
+        })
+        .collect();
+
+    NrsTreeNode {
+        name: name.to_string(),
+        link: None,
+        default,
+        sub_names,
+    }
+}
+
+fn format_nrs_tree_node(node: &NrsTreeNode) -> String {
+    let mut line = node.name.clone();
+    if let Some(link) = &node.link {
+        line.push_str(&format!(" -> {}", link));
+    }
+    if let Some(default) = &node.default {
+        line.push_str(&format!(" (default: {})", default));
+    }
+    line
+}
+
+// Print the sub names of a node in a format similar to unix `tree` command
+fn print_nrs_tree_children(node: &NrsTreeNode, prefix: &str) {
+    let last_index = node.sub_names.len().saturating_sub(1);
+    for (index, child) in node.sub_names.iter().enumerate() {
+        let (branch, indent) = if index == last_index {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        println!("{}{}{}", prefix, branch, format_nrs_tree_node(child));
+        print_nrs_tree_children(child, &format!("{}{}", prefix, indent));
+    }
+}
@@ -10,12 +10,20 @@
 use structopt::StructOpt;
 
 use super::{
-    helpers::{get_from_arg_or_stdin, get_secret_key, serialise_output},
+    helpers::{
+        get_from_arg_or_stdin, get_secret_key, pluralize, print_transfer_history,
+        print_transfer_receipt, serialise_output, xorname_to_hex,
+    },
     keys::{create_new_key, print_new_key_output},
     OutputFmt,
 };
 use log::debug;
-use sn_api::{bls_sk_from_hex, ed_sk_from_hex, Keypair, Safe, SecretKey};
+use prettytable::Table;
+use sn_api::{
+    bls_sk_from_hex, ed_sk_from_hex,
+    wallet::{CoinSelection, SweepOutcome},
+    Keypair, Safe, SecretKey,
+};
 use std::sync::Arc;
 
 #[derive(StructOpt, Debug)]
@@ -50,9 +58,15 @@ pub enum WalletSubCommands {
         /// The target Wallet to check the total balance
         target: Option<String>,
     },
-    /*#[structopt(name = "check-tx")]
-    /// Check the status of a given transfer
-    CheckTx {},*/
+    #[structopt(name = "check-tx")]
+    /// Check the status of a given transfer made from or to a Wallet. This is only supported by the local storage backends, set with the SN_CLI_STORAGE_BACKEND env var, as the SAFE Network doesn't serve the transfer history yet
+    CheckTx {
+        /// The id of the transfer, as reported when it was made
+        tx_id: u64,
+        /// The Wallet the transfer was made from or to, or pulled from stdin if not provided
+        #[structopt(long = "wallet")]
+        wallet: Option<String>,
+    },
     #[structopt(name = "create")]
     /// Create a new Wallet
     Create {
@@ -81,6 +95,18 @@ pub enum WalletSubCommands {
         #[structopt(long = "bls")]
         is_bls: bool,
     },
+    #[structopt(name = "history")]
+    /// List the transfers made from and to a Wallet, oldest first. This is only supported by the local storage backends, set with the SN_CLI_STORAGE_BACKEND env var, as the SAFE Network doesn't serve the transfer history yet
+    History {
+        /// The target Wallet, or one of its spendable balances with its name as the URL path
+        target: Option<String>,
+        /// The page of transfers to list, starting from 1
+        #[structopt(long = "page", default_value = "1")]
+        page: usize,
+        /// The maximum number of transfers to list in each page
+        #[structopt(long = "page-size", default_value = "20")]
+        page_size: usize,
+    },
     #[structopt(name = "transfer")]
     /// Transfer safecoins from one Wallet to another, or to a SafeKey
     Transfer {
@@ -98,18 +124,50 @@ pub enum WalletSubCommands {
         /// The target secret key is a BLS secret key. (Defaults to an ED25519 Secret Key)
         #[structopt(long = "to-is-bls")]
         to_is_bls: bool,
+        /// Combine several spendable balances of the source Wallet to cover the amount, selecting them with one of the strategies: 'largest-first', 'fewest-keys', or 'named:<name>,<name>,...'
+        #[structopt(long = "coin-selection")]
+        coin_selection: Option<CoinSelection>,
         // TODO: BlsShare when we have multisig
     },
-    /*#[structopt(name = "sweep")]
+    #[structopt(name = "rm")]
+    /// Remove a spendable balance from a Wallet
+    Remove {
+        /// The target Wallet to remove the spendable balance from
+        target: String,
+        /// The name of the spendable balance to remove
+        name: String,
+        /// The name of the spendable balance to set as the new default one, required to remove the current default
+        #[structopt(long = "new-default")]
+        new_default: Option<String>,
+    },
+    #[structopt(name = "rename")]
+    /// Rename a spendable balance of a Wallet
+    Rename {
+        /// The target Wallet the spendable balance is in
+        target: String,
+        /// The current name of the spendable balance
+        name: String,
+        /// The new name to give the spendable balance
+        new_name: String,
+    },
+    #[structopt(name = "set-default")]
+    /// Set the default spendable balance of a Wallet
+    SetDefault {
+        /// The target Wallet the spendable balance is in
+        target: String,
+        /// The name of the spendable balance to set as the default one
+        name: String,
+    },
+    #[structopt(name = "sweep")]
     /// Move all coins within a Wallet to a second given Wallet or Key
     Sweep {
-        /// The source Wallet for funds
+        /// The source Wallet for funds, or one of its spendable balances with its name as the URL path
         #[structopt(long = "from")]
         from: String,
         /// The receiving Wallet/Key
         #[structopt(long = "to")]
         to: String,
-    },*/
+    },
 }
 
 pub async fn wallet_commander(
@@ -227,6 +285,77 @@ pub async fn wallet_commander(
 
             Ok(())
         }
+        WalletSubCommands::CheckTx { tx_id, wallet } => {
+            let wallet = get_from_arg_or_stdin(
+                wallet,
+                Some("...awaiting Wallet address/location from STDIN stream..."),
+            )?;
+
+            // a transfer between spendable balances of the Wallet is listed twice
+            let transfers: Vec<_> = safe
+                .wallet_history(&wallet)
+                .await?
+                .into_iter()
+                .filter(|entry| entry.id == tx_id)
+                .collect();
+            if transfers.is_empty() {
+                return Err(format!(
+                    "No transfer with TX_ID {} was found in Wallet at \"{}\"",
+                    tx_id, wallet
+                ));
+            }
+
+            if OutputFmt::Pretty == output_fmt {
+                println!(
+                    "Transfer with TX_ID {} of Wallet at \"{}\" is complete",
+                    tx_id, wallet
+                );
+                print_transfer_history(&transfers);
+            } else {
+                println!("{}", serialise_output(&transfers, output_fmt));
+            }
+            Ok(())
+        }
+        WalletSubCommands::History {
+            target,
+            page,
+            page_size,
+        } => {
+            if page == 0 || page_size == 0 {
+                return Err("Both the page and the page size need to be at least 1".to_string());
+            }
+
+            let target = get_from_arg_or_stdin(
+                target,
+                Some("...awaiting Wallet address/location from STDIN stream..."),
+            )?;
+
+            let history = safe.wallet_history(&target).await?;
+            let total_pages = (history.len() + page_size - 1) / page_size;
+            let transfers: Vec<_> = history
+                .into_iter()
+                .skip((page - 1) * page_size)
+                .take(page_size)
+                .collect();
+
+            if OutputFmt::Pretty == output_fmt {
+                if transfers.is_empty() {
+                    println!(
+                        "No transfers found in page {} of Wallet's history at \"{}\"",
+                        page, target
+                    );
+                } else {
+                    println!(
+                        "Transfers of Wallet at \"{}\" (page {} of {}):",
+                        target, page, total_pages
+                    );
+                    print_transfer_history(&transfers);
+                }
+            } else {
+                println!("{}", serialise_output(&transfers, output_fmt));
+            }
+            Ok(())
+        }
         WalletSubCommands::Insert {
             target,
             keyurl,
@@ -278,12 +407,61 @@ pub async fn wallet_commander(
             }
             Ok(())
         }
+        WalletSubCommands::Remove {
+            target,
+            name,
+            new_default,
+        } => {
+            safe.wallet_remove(&target, &name, new_default.as_deref())
+                .await?;
+            if OutputFmt::Pretty == output_fmt {
+                println!(
+                    "Spendable balance '{}' removed from Wallet located at \"{}\"",
+                    name, target
+                );
+                if let Some(new_default) = new_default {
+                    println!("Default spendable balance set to '{}'", new_default);
+                }
+            } else {
+                println!("{}", target);
+            }
+            Ok(())
+        }
+        WalletSubCommands::Rename {
+            target,
+            name,
+            new_name,
+        } => {
+            safe.wallet_rename(&target, &name, &new_name).await?;
+            if OutputFmt::Pretty == output_fmt {
+                println!(
+                    "Spendable balance '{}' renamed to '{}' in Wallet located at \"{}\"",
+                    name, new_name, target
+                );
+            } else {
+                println!("{}", target);
+            }
+            Ok(())
+        }
+        WalletSubCommands::SetDefault { target, name } => {
+            safe.wallet_set_default(&target, &name).await?;
+            if OutputFmt::Pretty == output_fmt {
+                println!(
+                    "Default spendable balance set to '{}' in Wallet located at \"{}\"",
+                    name, target
+                );
+            } else {
+                println!("{}", target);
+            }
+            Ok(())
+        }
         WalletSubCommands::Transfer {
             amount,
             from,
             to,
             from_is_bls: _,
             to_is_bls: _,
+            coin_selection,
         } => {
             //TODO: if to starts without safe://, i.e. if it's a PK hex string.
             let destination = get_from_arg_or_stdin(
@@ -291,19 +469,102 @@ pub async fn wallet_commander(
                 Some("...awaiting destination Wallet/SafeKey URL from STDIN stream..."),
             )?;
 
-            safe.wallet_transfer(&amount, from.as_deref(), &destination)
+            let selection = match coin_selection {
+                Some(selection) => selection,
+                None => {
+                    let receipt = safe
+                        .wallet_transfer(&amount, from.as_deref(), &destination)
+                        .await?;
+
+                    print_transfer_receipt(&receipt, output_fmt);
+                    return Ok(());
+                }
+            };
+
+            let from = from.ok_or("A source Wallet is required to combine its spendable balances, please provide it with '--from'")?;
+            let outcome = safe
+                .wallet_transfer_with_selection(&amount, &from, &destination, &selection)
                 .await?;
+            let receipts = &outcome.receipts;
+
+            if OutputFmt::Pretty == output_fmt {
+                if outcome.failed.is_none() {
+                    println!(
+                        "Success. Transferred {} safecoins from {} spendable {} of Wallet at \"{}\":",
+                        amount,
+                        receipts.len(),
+                        pluralize("balance", "balances", receipts.len() as u64),
+                        from
+                    );
+                } else {
+                    println!(
+                        "Only part of the {} safecoins were transferred, from {} spendable {} of Wallet at \"{}\":",
+                        amount,
+                        receipts.len(),
+                        pluralize("balance", "balances", receipts.len() as u64),
+                        from
+                    );
+                }
+                let mut table = Table::new();
+                table.add_row(row![bFg->"Friendly Name", bFg->"TX_ID", bFg->"Amount", bFg->"From SafeKey", bFg->"To SafeKey"]);
+                for (name, receipt) in receipts.iter() {
+                    table.add_row(row![
+                        name,
+                        receipt.id,
+                        receipt.amount,
+                        format!("0x{}", xorname_to_hex(&receipt.from)),
+                        format!("0x{}", xorname_to_hex(&receipt.to))
+                    ]);
+                }
+                table.printstd();
+            } else {
+                println!("{}", serialise_output(&outcome, output_fmt));
+            }
 
-            // if OutputFmt::Pretty == output_fmt {
-            //     println!("Success. TX_ID: {}", &tx_id);
-            // } else {
-            //     println!("{}", &tx_id)
-            // }
-            // if OutputFmt::Pretty == output_fmt {
-            println!("Transfer Success.");
-            // } else {
-            //     println!("{}", &tx_id)
-            // }
+            if let Some((name, error)) = outcome.failed {
+                return Err(format!(
+                    "Failed to transfer from spendable balance '{}' of Wallet at \"{}\": {}",
+                    name, from, error
+                ));
+            }
+            Ok(())
+        }
+        WalletSubCommands::Sweep { from, to } => {
+            let outcomes = safe.wallet_sweep(&from, &to).await?;
+
+            if OutputFmt::Pretty == output_fmt {
+                println!(
+                    "Spendable balances of Wallet at \"{}\" swept to \"{}\":",
+                    from, to
+                );
+                let mut table = Table::new();
+                table.add_row(row![bFg->"Friendly Name", bFg->"Outcome", bFg->"Details"]);
+                for (name, outcome) in outcomes.iter() {
+                    let (status, details) = match outcome {
+                        SweepOutcome::Transferred(receipt) => (
+                            "Transferred",
+                            format!("{} safecoins, TX_ID: {}", receipt.amount, receipt.id),
+                        ),
+                        SweepOutcome::Skipped(reason) => ("Skipped", reason.to_string()),
+                        SweepOutcome::Failed(error) => ("Failed", error.to_string()),
+                    };
+                    table.add_row(row![name, status, details]);
+                }
+                table.printstd();
+            } else {
+                println!("{}", serialise_output(&outcomes, output_fmt));
+            }
+
+            let failed = outcomes
+                .values()
+                .filter(|outcome| matches!(outcome, SweepOutcome::Failed(_)))
+                .count();
+            if failed > 0 {
+                return Err(format!(
+                    "Failed to sweep {} of the spendable balances of Wallet at \"{}\"",
+                    failed, from
+                ));
+            }
             Ok(())
         }
     }
@@ -12,7 +12,11 @@ use super::{
     OutputFmt,
 };
 use crate::operations::safe_net::connect;
-use sn_api::{xorurl::SafeUrl, Safe};
+use sn_api::{
+    files::{FilesContainerCreateOptions, ProcessedFile},
+    xorurl::SafeUrl,
+    Safe,
+};
 use structopt::StructOpt;
 
 // Defines subcommands of 'xorurl'
@@ -76,7 +80,16 @@ pub async fn xorurl_commander(
 
             // Do a dry-run on the location
             let (_version, processed_files, _files_map) = safe
-                .files_container_create(Some(&location), None, recursive, follow_symlinks, true)
+                .files_container_create(
+                    Some(&location),
+                    None,
+                    recursive,
+                    follow_symlinks,
+                    FilesContainerCreateOptions {
+                        dry_run: true,
+                        ..Default::default()
+                    },
+                )
                 .await?;
 
             // Now let's just print out a list of the xorurls
@@ -90,7 +103,7 @@ pub async fn xorurl_commander(
                 }
             } else {
                 let mut list = Vec::<(String, String)>::new();
-                for (file_name, (_change, link)) in processed_files {
+                for (file_name, ProcessedFile { link, .. }) in processed_files {
                     list.push((file_name, link));
                 }
                 println!("{}", serialise_output(&list, output_fmt));
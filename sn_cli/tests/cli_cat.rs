@@ -48,12 +48,12 @@ fn calling_safe_cat() {
 
     let (_container_xorurl, map) = parse_files_put_or_sync_output(&content);
     let mut cmd = Command::cargo_bin(CLI).unwrap();
-    cmd.args(&vec!["cat", &map[TEST_FILE].1])
+    cmd.args(&vec!["cat", &map[TEST_FILE].link])
         .assert()
         .stdout(predicate::str::contains(TEST_FILE_CONTENT))
         .success();
 
-    let xorurl_encoder = unwrap!(XorUrlEncoder::from_url(&map[TEST_FILE].1));
+    let xorurl_encoder = unwrap!(XorUrlEncoder::from_url(&map[TEST_FILE].link));
     assert_eq!(
         xorurl_encoder.content_type(),
         SafeContentType::MediaType("text/markdown".to_string())
@@ -108,7 +108,7 @@ fn calling_safe_cat_on_relative_file_from_id_fails() {
     let (_container_xorurl, map) = parse_files_put_or_sync_output(&content);
     let mut cmd = Command::cargo_bin(CLI).unwrap();
 
-    let relative_url = format!("{}/something_relative.wasm", &map[TEST_FILE].1);
+    let relative_url = format!("{}/something_relative.wasm", &map[TEST_FILE].link);
     cmd.args(&vec!["cat", &relative_url])
         .assert()
         .stderr(predicate::str::contains(ID_RELATIVE_FILE_ERROR))
@@ -129,12 +129,12 @@ fn calling_safe_cat_hexdump() {
 
     let (_container_xorurl, map) = parse_files_put_or_sync_output(&content);
     let mut cmd = Command::cargo_bin(CLI).unwrap();
-    cmd.args(&vec!["cat", "--hexdump", &map[TEST_FILE].1])
+    cmd.args(&vec!["cat", "--hexdump", &map[TEST_FILE].link])
         .assert()
         .stdout(predicate::str::contains(TEST_FILE_HEXDUMP_CONTENT))
         .success();
 
-    let xorurl_encoder = unwrap!(XorUrlEncoder::from_url(&map[TEST_FILE].1));
+    let xorurl_encoder = unwrap!(XorUrlEncoder::from_url(&map[TEST_FILE].link));
     assert_eq!(
         xorurl_encoder.content_type(),
         SafeContentType::MediaType("text/markdown".to_string())
@@ -197,6 +197,49 @@ fn calling_safe_cat_xorurl_url_with_version() {
         .failure();
 }
 
+#[test]
+fn calling_safe_cat_files_container_rdf() {
+    let content = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "put",
+        TEST_FILE,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (container_xorurl, map) = parse_files_put_or_sync_output(&content);
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["cat", &container_xorurl, "--output=turtle"])
+        .assert()
+        .stdout(predicate::str::contains(format!(
+            "<{}>\n    a safe:FilesMap ;",
+            container_xorurl
+        )))
+        .stdout(predicate::str::contains(format!(
+            "schema:url <{}>",
+            map[TEST_FILE].link
+        )))
+        .stdout(predicate::str::contains("safe:path \"/test.md\""))
+        .success();
+
+    let jsonld = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "cat",
+        &container_xorurl,
+        "--output=jsonld"
+    )
+    .read()
+    .unwrap();
+    let jsonld: serde_json::Value = unwrap!(serde_json::from_str(&jsonld));
+    assert_eq!(jsonld["@id"], container_xorurl);
+    assert_eq!(jsonld["@type"], "FilesMap");
+    assert_eq!(jsonld["files"][0]["path"], "/test.md");
+    assert_eq!(jsonld["files"][0]["link"], map[TEST_FILE].link);
+    assert_eq!(jsonld["files"][0]["mediaType"], "text/markdown");
+}
+
 #[test]
 fn calling_safe_cat_nrsurl_with_version() {
     let content = cmd!(
@@ -267,6 +310,84 @@ fn calling_safe_cat_nrsurl_with_version() {
         .failure();
 }
 
+#[test]
+fn calling_safe_cat_nrs_wildcard() {
+    let content = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "put",
+        TEST_DATA,
+        "--json",
+        "--recursive",
+    )
+    .read()
+    .unwrap();
+    let (container_xorurl, _files_map) = parse_files_put_or_sync_output(&content);
+
+    let site_name = get_random_nrs_string();
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "create",
+        format!("safe://docs.{}", site_name),
+        "-l",
+        &container_xorurl,
+    )
+    .read()
+    .unwrap();
+
+    // any sub name of 'docs' not added explicitly is resolved with the wildcard
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "add",
+        format!("safe://*.docs.{}", site_name),
+        "-l",
+        &container_xorurl,
+    )
+    .read()
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "cat",
+        &format!("safe://pr-1.docs.{}/test.md", site_name),
+    ])
+    .assert()
+    .stdout(predicate::str::contains(TEST_FILE_CONTENT))
+    .success();
+
+    // the label matched by the wildcard is now passed on as a path prefix of the link
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "add",
+        format!("safe://*.docs.{}", site_name),
+        "-l",
+        &container_xorurl,
+        "--capture-path",
+    )
+    .read()
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "cat",
+        &format!("safe://subfolder.docs.{}/sub2.md", site_name),
+    ])
+    .assert()
+    .stdout(predicate::str::contains("sub2"))
+    .success();
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "cat",
+        &format!("safe://pr-1.docs.{}/test.md", site_name),
+    ])
+    .assert()
+    .failure();
+}
+
 #[test]
 fn calling_safe_cat_wallet_xorurl() {
     let wallet_create = cmd!(
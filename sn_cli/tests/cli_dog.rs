@@ -12,7 +12,10 @@ extern crate sn_cmd_test_utilities;
 #[macro_use]
 extern crate duct;
 
-use sn_api::{fetch::SafeData, Safe};
+use sn_api::{
+    fetch::{ResolutionHop, SafeData},
+    Safe,
+};
 use sn_cmd_test_utilities::{
     create_preload_and_get_keys, get_random_nrs_string, parse_dog_output,
     parse_files_put_or_sync_output,
@@ -240,3 +243,149 @@ fn calling_safe_dog_nrs_url_with_subnames() {
         panic!("Content retrieved was unexpected: {:?}", safe_data_vec);
     }
 }
+
+#[test]
+fn calling_safe_dog_nrs_follow_latest() {
+    let content = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "put",
+        TEST_FILE,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (container_xorurl, _files_map) = parse_files_put_or_sync_output(&content);
+    let mut xorurl_encoder = Safe::parse_url(&container_xorurl).unwrap();
+    xorurl_encoder.set_content_version(None);
+    let unversioned_link = xorurl_encoder.to_string();
+
+    let nrsurl = format!("safe://{}", get_random_nrs_string());
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "create",
+        &nrsurl,
+        "-l",
+        &unversioned_link,
+        "--follow-latest",
+    )
+    .read()
+    .unwrap();
+
+    let dog_output = cmd!(env!("CARGO_BIN_EXE_safe"), "dog", &nrsurl)
+        .read()
+        .unwrap();
+    assert!(dog_output.contains("Link policy: follow latest (resolved version 0)"));
+}
+
+#[test]
+fn calling_safe_dog_nrs_wildcard() {
+    let content = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "put",
+        TEST_FILE,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (container_xorurl, _files_map) = parse_files_put_or_sync_output(&content);
+
+    let site_name = get_random_nrs_string();
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "create",
+        format!("safe://docs.{}", site_name),
+        "-l",
+        &container_xorurl,
+    )
+    .read()
+    .unwrap();
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "add",
+        format!("safe://*.docs.{}", site_name),
+        "-l",
+        &container_xorurl,
+    )
+    .read()
+    .unwrap();
+
+    let nrsurl = format!("safe://pr-1.docs.{}", site_name);
+    let dog_output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "dog",
+        &nrsurl,
+        "--trace",
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (url, content, trace): (String, Vec<SafeData>, Vec<ResolutionHop>) =
+        serde_json::from_str(&dog_output).expect("Failed to parse output of `safe dog --trace`");
+    assert_eq!(url, nrsurl);
+    assert_eq!(content.len(), 2);
+    assert_eq!(trace[0].matched_sub_name, Some("*.docs".to_string()));
+    assert_eq!(trace[1].version, Some(0));
+
+    let dog_output = cmd!(env!("CARGO_BIN_EXE_safe"), "dog", &nrsurl, "--trace")
+        .read()
+        .unwrap();
+    assert!(dog_output.contains("Matched sub name: \"*.docs\""));
+}
+
+#[test]
+fn calling_safe_dog_trace() {
+    let content = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "put",
+        TEST_FILE,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (container_xorurl, _files_map) = parse_files_put_or_sync_output(&content);
+
+    let site_name = get_random_nrs_string();
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "create",
+        format!("safe://docs.{}", site_name),
+        "-l",
+        &container_xorurl,
+    )
+    .read()
+    .unwrap();
+
+    let nrsurl = format!("safe://{}", site_name);
+    let dog_output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "dog",
+        &nrsurl,
+        "--trace",
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (url, content, trace): (String, Vec<SafeData>, Vec<ResolutionHop>) =
+        serde_json::from_str(&dog_output).expect("Failed to parse output of `safe dog --trace`");
+    assert_eq!(url, nrsurl);
+    assert_eq!(content.len(), 2);
+    assert_eq!(trace.len(), 2);
+    assert_eq!(trace[0].url, nrsurl);
+    assert_eq!(trace[0].matched_sub_name, Some("docs".to_string()));
+    assert_eq!(trace[1].version, Some(0));
+
+    let dog_output = cmd!(env!("CARGO_BIN_EXE_safe"), "dog", &nrsurl, "--trace")
+        .read()
+        .unwrap();
+    assert!(dog_output.contains("== URL resolution trace =="));
+    assert!(dog_output.contains(
+        "Matched sub name: \"docs\", through the default set to existing sub name \"docs\""
+    ));
+}
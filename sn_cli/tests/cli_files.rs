@@ -78,7 +78,7 @@ fn calling_safe_files_put_dry_run() {
 
     let (_container_xorurl, map) = parse_files_put_or_sync_output(&content);
     let mut cmd = Command::cargo_bin(CLI).unwrap();
-    cmd.args(&vec!["cat", &map[TEST_FILE_RANDOM_CONTENT].1])
+    cmd.args(&vec!["cat", &map[TEST_FILE_RANDOM_CONTENT].link])
         .assert()
         .failure();
 }
@@ -95,6 +95,41 @@ fn calling_safe_files_put_recursive() {
         .success();
 }
 
+#[test]
+fn calling_safe_files_put_resume() -> Result<(), String> {
+    // the files are only recorded in the upload journal when resuming
+    let files_container_output = read_cmd(cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "put",
+        TEST_FOLDER,
+        "--recursive",
+        "--resume",
+        "--json"
+    ))?;
+    let (_, processed_files) = parse_files_put_or_sync_output(&files_container_output);
+
+    let files_container_output = read_cmd(cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "put",
+        TEST_FOLDER,
+        "--recursive",
+        "--resume",
+        "--json"
+    ))?;
+
+    let (_, resumed_files) = parse_files_put_or_sync_output(&files_container_output);
+    assert_eq!(resumed_files.len(), EXPECT_TESTDATA_PUT_CNT);
+    let test_file = format!("{}test.md", TEST_FOLDER);
+    assert_eq!(resumed_files[&test_file].change, "R");
+    assert_eq!(
+        resumed_files[&test_file].link,
+        processed_files[&test_file].link
+    );
+    Ok(())
+}
+
 #[test]
 fn calling_safe_files_put_recursive_and_set_dest_path() {
     let files_container = cmd!(
@@ -327,7 +362,7 @@ fn calling_safe_files_sync_dry_run() {
 
     let (_, map) = parse_files_put_or_sync_output(&sync_content);
     let mut cmd = Command::cargo_bin(CLI).unwrap();
-    cmd.args(&vec!["cat", &map[TEST_FILE_RANDOM_CONTENT].1])
+    cmd.args(&vec!["cat", &map[TEST_FILE_RANDOM_CONTENT].link])
         .assert()
         .failure();
 }
@@ -779,6 +814,88 @@ fn calling_safe_files_add() {
     assert_eq!(synced_file_cat, "hello tests!");
 }
 
+#[test]
+fn calling_safe_files_diff() -> Result<(), String> {
+    let (files_container_xor, _processed_files) = upload_testfolder_trailing_slash()?;
+
+    let mut xorurl_encoder = unwrap!(XorUrlEncoder::from_url(&files_container_xor));
+    xorurl_encoder.set_content_version(None);
+    let _ = read_cmd(cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "add",
+        TEST_FILE,
+        &format!("{}/new_test.md", xorurl_encoder),
+    ))?;
+
+    xorurl_encoder.set_content_version(Some(0));
+    let url_a = xorurl_encoder.to_string();
+    xorurl_encoder.set_content_version(Some(1));
+    let url_b = xorurl_encoder.to_string();
+    let diff_output = read_cmd(cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "diff",
+        &url_a,
+        &url_b,
+        "--json"
+    ))?;
+
+    let diff: serde_json::Value = serde_json::from_str(&diff_output)
+        .map_err(|err| format!("Failed to parse output of `safe files diff`: {}", err))?;
+    let diff = diff
+        .as_object()
+        .ok_or_else(|| "Unexpected output of `safe files diff`".to_string())?;
+    assert_eq!(diff.len(), 1);
+    assert_eq!(diff["/new_test.md"]["change"], "+");
+    Ok(())
+}
+
+#[test]
+fn calling_safe_files_restore() -> Result<(), String> {
+    let (files_container_xor, processed_files) = upload_testfolder_trailing_slash()?;
+
+    let mut xorurl_encoder = unwrap!(XorUrlEncoder::from_url(&files_container_xor));
+    xorurl_encoder.set_content_version(None);
+    let container_xorurl_no_version = xorurl_encoder.to_string();
+    let _ = read_cmd(cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "rm",
+        &format!("{}/test.md", container_xorurl_no_version),
+    ))?;
+
+    let restore_output = read_cmd(cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "files",
+        "restore",
+        &container_xorurl_no_version,
+        "--version",
+        "0",
+        "--json"
+    ))?;
+
+    let (target, restored_files) = parse_files_put_or_sync_output(&restore_output);
+    xorurl_encoder.set_content_version(Some(2));
+    assert_eq!(target, xorurl_encoder.to_string());
+    assert_eq!(restored_files.len(), 1);
+    assert_eq!(restored_files["/test.md"].change, "+");
+    assert_eq!(
+        restored_files["/test.md"].link,
+        processed_files[&format!("{}test.md", TEST_FOLDER)].link
+    );
+
+    xorurl_encoder.set_content_version(None);
+    xorurl_encoder.set_path("/test.md");
+    let restored_file_cat = read_cmd(cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "cat",
+        xorurl_encoder.to_string()
+    ))?;
+    assert_eq!(restored_file_cat, "hello tests!");
+    Ok(())
+}
+
 #[test]
 fn calling_safe_files_add_dry_run() {
     let files_container_output = cmd!(
@@ -837,7 +954,7 @@ fn calling_safe_files_add_a_url() {
         env!("CARGO_BIN_EXE_safe"),
         "files",
         "add",
-        &processed_files[TEST_FILE].1,
+        &processed_files[TEST_FILE].link,
         &xorurl_encoder.to_string(),
         "--json"
     )
@@ -902,19 +1019,19 @@ fn calling_files_ls() {
     assert_eq!(files_map.len(), 7);
     assert_eq!(
         files_map[".hidden.txt"]["link"],
-        processed_files[&format!("{}.hidden.txt", TEST_FOLDER)].1
+        processed_files[&format!("{}.hidden.txt", TEST_FOLDER)].link
     );
     assert_eq!(
         files_map["another.md"]["link"],
-        processed_files[&format!("{}another.md", TEST_FOLDER)].1
+        processed_files[&format!("{}another.md", TEST_FOLDER)].link
     );
     assert_eq!(
         files_map["noextension"]["link"],
-        processed_files[&format!("{}noextension", TEST_FOLDER)].1
+        processed_files[&format!("{}noextension", TEST_FOLDER)].link
     );
     assert_eq!(
         files_map["test.md"]["link"],
-        processed_files[&format!("{}test.md", TEST_FOLDER)].1
+        processed_files[&format!("{}test.md", TEST_FOLDER)].link
     );
 
     assert_eq!(files_map["subfolder/"]["size"], "27");
@@ -938,12 +1055,12 @@ fn calling_files_ls() {
     assert_eq!(files_map.len(), 2);
     assert_eq!(
         files_map["sub2.md"]["link"],
-        processed_files[&format!("{}sub2.md", TEST_FOLDER_SUBFOLDER)].1
+        processed_files[&format!("{}sub2.md", TEST_FOLDER_SUBFOLDER)].link
     );
     assert_eq!(files_map["sub2.md"]["size"], "4");
     assert_eq!(
         files_map["subexists.md"]["link"],
-        processed_files[&format!("{}subexists.md", TEST_FOLDER_SUBFOLDER)].1
+        processed_files[&format!("{}subexists.md", TEST_FOLDER_SUBFOLDER)].link
     );
     assert_eq!(files_map["subexists.md"]["size"], "23");
 }
@@ -14,6 +14,7 @@ extern crate duct;
 
 use assert_cmd::prelude::*;
 use predicates::prelude::*;
+use sn_api::{wallet::TransferReceipt, xorurl::XorUrlEncoder};
 use sn_cmd_test_utilities::{
     create_preload_and_get_keys, create_wallet_with_balance, get_random_nrs_string, CLI,
     SAFE_PROTOCOL,
@@ -49,6 +50,24 @@ fn calling_safe_keys_create() {
     .success();
 }
 
+#[test]
+fn calling_safe_keys_create_rdf_output() {
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "keys",
+        "create",
+        "--test-coins",
+        "--preload",
+        "123",
+        "--output",
+        "turtle",
+    ])
+    .assert()
+    .stdout(predicate::str::is_empty())
+    .stderr(predicate::str::contains("RDF output formats"))
+    .failure();
+}
+
 #[test]
 fn calling_safe_keypair() {
     let mut cmd = Command::cargo_bin(CLI).unwrap();
@@ -262,3 +281,33 @@ fn calling_safe_keys_transfer_to_key_nrsurl() {
 
     assert_eq!(from_has, "1417.420000000" /* 1535.65 - 118.23 */)
 }
+
+#[test]
+fn calling_safe_keys_transfer_json() {
+    let (_safekey1_xorurl, sk1) = create_preload_and_get_keys("160.0");
+    let (safekey2_xorurl, _sk2) = create_preload_and_get_keys("5.0");
+
+    let transfer_output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "keys",
+        "transfer",
+        "100.5",
+        "--from",
+        &sk1,
+        "--to",
+        &safekey2_xorurl,
+        "--json"
+    )
+    .read()
+    .unwrap();
+
+    let receipt: TransferReceipt = serde_json::from_str(&transfer_output)
+        .expect("Failed to parse output of `safe keys transfer`");
+    assert_eq!(receipt.amount, "100.500000000");
+    assert_eq!(
+        receipt.to,
+        XorUrlEncoder::from_url(&safekey2_xorurl).unwrap().xorname()
+    );
+    assert_ne!(receipt.from, receipt.to);
+    assert!(!receipt.timestamp.is_empty());
+}
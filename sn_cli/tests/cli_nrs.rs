@@ -14,14 +14,18 @@ extern crate duct;
 
 use assert_cmd::prelude::*;
 use predicates::prelude::*;
+use rand::rngs::OsRng;
 use sn_api::{
     fetch::{SafeContentType, SafeDataType},
+    pk_bytes_to_hex,
     xorurl::{XorUrlBase, XorUrlEncoder},
+    Keypair,
 };
 use sn_cmd_test_utilities::{
-    get_random_nrs_string, parse_nrs_create_output, upload_test_folder, CLI, SAFE_PROTOCOL,
+    get_random_nrs_string, mk_emptyfolder, parse_nrs_create_output, upload_test_folder, CLI,
+    SAFE_PROTOCOL,
 };
-use std::process::Command;
+use std::{fs, path::Path, process::Command};
 use unwrap::unwrap;
 use xor_name::XorName;
 
@@ -520,3 +524,303 @@ fn calling_safe_nrs_put_and_add_new_subnames_remove_one_and_so_fail_to_retrieve(
         ))
         .failure();
 }
+
+#[test]
+fn calling_safe_nrs_transfer() {
+    let test_name = get_random_nrs_string();
+    let fake_target = gen_fake_target();
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "create",
+        &test_name,
+        "-l",
+        &fake_target,
+        "--json"
+    )
+    .read()
+    .unwrap();
+
+    let owner = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "owner",
+        &test_name,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (_, owner): (String, String) = unwrap!(serde_json::from_str(&owner));
+
+    let new_owner = pk_bytes_to_hex(&Keypair::new_ed25519(&mut OsRng).public_key());
+    assert_ne!(owner, new_owner);
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["nrs", "transfer", &test_name, &new_owner])
+        .assert()
+        .stdout(predicate::str::contains(&new_owner))
+        .success();
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["nrs", "owner", &test_name])
+        .assert()
+        .stdout(predicate::str::contains(&new_owner))
+        .success();
+
+    // the previous owner cannot change it anymore
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "nrs",
+        "add",
+        &format!("sub.{}", test_name),
+        "-l",
+        &fake_target,
+    ])
+    .assert()
+    .stderr(predicate::str::contains("AccessDenied"))
+    .failure();
+
+    // nor transfer it back, which is checked even on a dry run
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["nrs", "transfer", &test_name, &owner, "--dry-run"])
+        .assert()
+        .stderr(predicate::str::contains("AccessDenied"))
+        .failure();
+}
+
+#[test]
+fn calling_safe_nrs_ls_history_and_whois() {
+    let test_name = get_random_nrs_string();
+    let fake_target = gen_fake_target();
+    let nrs_creation = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "create",
+        &test_name,
+        "-l",
+        &fake_target,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (nrs_map_xorurl, _) = parse_nrs_create_output(&nrs_creation);
+
+    let sub_name = format!("a.b.{}", test_name);
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "add",
+        &sub_name,
+        "-l",
+        &fake_target,
+        "--default",
+        "--json"
+    )
+    .read()
+    .unwrap();
+
+    let ls_output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "ls",
+        &test_name,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (version, tree): (u64, serde_json::Value) = unwrap!(serde_json::from_str(&ls_output));
+    assert_eq!(version, 1);
+    assert_eq!(tree["name"], test_name.as_str());
+    assert_eq!(tree["default"], sub_name.as_str());
+    assert_eq!(tree["sub_names"][0]["name"], format!("b.{}", test_name));
+    assert_eq!(tree["sub_names"][0]["sub_names"][0]["name"], sub_name);
+    assert_eq!(
+        tree["sub_names"][0]["sub_names"][0]["link"],
+        fake_target.as_str()
+    );
+
+    let history_output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "history",
+        &test_name,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (_, history): (String, Vec<(u64, serde_json::Value)>) =
+        unwrap!(serde_json::from_str(&history_output));
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].0, 1);
+    assert_eq!(history[1].1[&sub_name][0], "+");
+
+    let whois_output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "whois",
+        &test_name,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let whois: serde_json::Value = unwrap!(serde_json::from_str(&whois_output));
+    assert_eq!(whois["nrs_map_container"], nrs_map_xorurl.as_str());
+    assert_eq!(whois["version"], 1);
+    assert!(whois["owner"].is_string());
+}
+
+#[test]
+fn calling_safe_nrs_alias() {
+    let (container_xorurl, _) = upload_test_folder();
+    let site_name = get_random_nrs_string();
+    let other_name = get_random_nrs_string();
+    for name in &[&site_name, &other_name] {
+        let _ = cmd!(
+            env!("CARGO_BIN_EXE_safe"),
+            "nrs",
+            "create",
+            name,
+            "-l",
+            &container_xorurl,
+            "--json"
+        )
+        .read()
+        .unwrap();
+    }
+
+    let alias_name = format!("blog.{}", other_name);
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "nrs",
+        "alias",
+        &alias_name,
+        &format!("safe://{}/subfolder", site_name),
+    ])
+    .assert()
+    .stdout(predicate::str::contains("NRS Map updated"))
+    .success();
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["cat", &format!("safe://{}/subexists.md", alias_name)])
+        .assert()
+        .stdout(predicate::str::contains("hello from a subfolder!"))
+        .success();
+
+    // an alias with a version is rejected
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "nrs",
+        "alias",
+        &alias_name,
+        &format!("safe://{}?v=0", site_name),
+    ])
+    .assert()
+    .failure();
+}
+
+#[test]
+fn calling_safe_nrs_apply() {
+    let test_name = get_random_nrs_string();
+    let fake_target = gen_fake_target();
+    let _ = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "create",
+        &format!("b.{}", test_name),
+        "-l",
+        &fake_target,
+        "--json"
+    )
+    .read()
+    .unwrap();
+
+    let changes = format!(
+        "name: {name}
+changes:
+  - op: add
+    name: a.{name}
+    link: \"{link}\"
+  - op: add
+    name: c.{name}
+    link: \"{link}\"
+  - op: remove
+    name: b.{name}
+  - op: set_default
+    name: a.{name}
+",
+        name = test_name,
+        link = fake_target
+    );
+    let folder = mk_emptyfolder("nrs_apply").unwrap();
+    let file = Path::new(&folder.1).join("changes.yaml");
+    unwrap!(fs::write(&file, changes));
+
+    let output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "apply",
+        file.display().to_string(),
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (_, applied_changes): (String, Vec<(String, (String, String))>) =
+        unwrap!(serde_json::from_str(&output));
+    assert_eq!(applied_changes.len(), 4);
+    assert_eq!(applied_changes[0].0, format!("a.{}", test_name));
+    assert_eq!(applied_changes[1].0, format!("c.{}", test_name));
+    assert_eq!(applied_changes[2].0, format!("b.{}", test_name));
+    assert_eq!((applied_changes[2].1).0, "-");
+    assert_eq!(applied_changes[3].0, test_name);
+    assert_eq!((applied_changes[3].1).0, "*");
+
+    // all the changes were applied in a single version
+    let history_output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "nrs",
+        "history",
+        &test_name,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (_, history): (String, Vec<(u64, serde_json::Value)>) =
+        unwrap!(serde_json::from_str(&history_output));
+    assert_eq!(history.len(), 2);
+}
+
+#[test]
+fn calling_safe_nrs_create_internationalised_name() {
+    let nrs_name = format!("{}-café", get_random_nrs_string().to_lowercase());
+    let test_name = format!("safe://blog.{}", nrs_name);
+    let encoded_name = unwrap!(XorUrlEncoder::from_url(&test_name)).to_string();
+    assert!(encoded_name.contains("xn--"));
+
+    let (container_xorurl, _map) = upload_test_folder();
+    let mut xorurl_encoder = unwrap!(XorUrlEncoder::from_url(&container_xorurl));
+    xorurl_encoder.set_path("/test.md");
+    let link = xorurl_encoder.to_string();
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["nrs", "create", &test_name, "-l", &link])
+        .assert()
+        .stdout(predicate::str::contains(format!(
+            "New NRS Map for \"{}\" (encoded as \"{}\")",
+            test_name, encoded_name
+        )))
+        .success();
+
+    // both the Unicode and the encoded forms of the name can be fetched
+    let cat_of_new_url = cmd!(env!("CARGO_BIN_EXE_safe"), "cat", &test_name)
+        .read()
+        .unwrap();
+    assert_eq!(cat_of_new_url, "hello tests!");
+    let cat_of_new_url = cmd!(env!("CARGO_BIN_EXE_safe"), "cat", &encoded_name)
+        .read()
+        .unwrap();
+    assert_eq!(cat_of_new_url, "hello tests!");
+
+    // names with characters which can be confused with others are rejected
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["nrs", "create", "safe://p\u{430}ypal", "-l", &link])
+        .assert()
+        .failure();
+}
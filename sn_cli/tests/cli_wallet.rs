@@ -14,11 +14,18 @@ extern crate duct;
 
 use assert_cmd::prelude::*;
 use predicates::prelude::*;
+use sn_api::{
+    wallet::{
+        SelectionTransferOutcome, SweepOutcome, TransferDirection, TransferHistoryEntry,
+        TransferReceipt, WalletSweepOutcomes,
+    },
+    Keypair,
+};
 use sn_cmd_test_utilities::{
-    create_preload_and_get_keys, create_wallet_with_balance, get_random_nrs_string, CLI,
-    SAFE_PROTOCOL,
+    create_preload_and_get_keys, create_wallet_with_balance, get_random_nrs_string,
+    parse_wallet_create_output, CLI, SAFE_PROTOCOL,
 };
-use std::process::Command;
+use std::{env, process::Command};
 
 const PRETTY_WALLET_CREATION_RESPONSE: &str = "Wallet created at";
 const UNMATCHED_SK_XORURL: &str =
@@ -509,6 +516,300 @@ fn calling_safe_wallet_transfer_to_key_xorurl() {
     assert_eq!(from_has, "17.420000000" /* 35.65 - 18.23 */)
 }
 
+// The transfer history is only kept by the local storage backends, thus
+// all the commands of this test are run against a local storage of its own
+#[test]
+fn calling_safe_wallet_history_and_check_tx() {
+    let storage = env::temp_dir().join(format!("{}.db", get_random_nrs_string()));
+    let backend = format!("local:{}", storage.display());
+    let safe_local = |args: &[&str]| {
+        duct::cmd(env!("CARGO_BIN_EXE_safe"), args)
+            .env("SN_CLI_STORAGE_BACKEND", &backend)
+            .read()
+            .unwrap()
+    };
+
+    let pay_with_output = safe_local(&[
+        "keys",
+        "create",
+        "--test-coins",
+        "--preload",
+        "35.650000001",
+        "--json",
+    ]);
+    let (_pay_with_xorurl, pay_with_pair): (String, Keypair) =
+        serde_json::from_str(&pay_with_output)
+            .expect("Failed to parse output of `safe keys create`");
+    let pay_with_sk = pay_with_pair
+        .secret_key()
+        .expect("Error extracting SecretKey from keypair")
+        .to_string();
+    // we spent 1 nano for creating the Wallet
+    let wallet_output = safe_local(&[
+        "wallet",
+        "create",
+        "--pay-with",
+        &pay_with_sk,
+        "--preload",
+        "35.650000000",
+        "--name",
+        "default-balance",
+        "--json",
+    ]);
+    let (wallet_xorurl, _key_xorurl, _key_pair) = parse_wallet_create_output(&wallet_output);
+    let key_output = safe_local(&[
+        "keys",
+        "create",
+        "--test-coins",
+        "--preload",
+        "0.0",
+        "--json",
+    ]);
+    let (key_xorurl, _key_pair): (String, Keypair) =
+        serde_json::from_str(&key_output).expect("Failed to parse output of `safe keys create`");
+
+    let transfer_output = safe_local(&[
+        "wallet",
+        "transfer",
+        "18.23",
+        "--from",
+        &wallet_xorurl,
+        "--to",
+        &key_xorurl,
+        "--json",
+    ]);
+    let receipt: TransferReceipt = serde_json::from_str(&transfer_output)
+        .expect("Failed to parse output of `safe wallet transfer`");
+
+    // the preload of the Wallet's spendable balance is in the first page
+    let history_output = safe_local(&[
+        "wallet",
+        "history",
+        &wallet_xorurl,
+        "--page",
+        "2",
+        "--page-size",
+        "1",
+        "--json",
+    ]);
+    let history: Vec<TransferHistoryEntry> = serde_json::from_str(&history_output)
+        .expect("Failed to parse output of `safe wallet history`");
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].id, receipt.id);
+    assert_eq!(history[0].direction, TransferDirection::Outgoing);
+    assert_eq!(history[0].balance, "17.420000000" /* 35.65 - 18.23 */);
+
+    let check_tx_output = safe_local(&[
+        "wallet",
+        "check-tx",
+        &receipt.id.to_string(),
+        "--wallet",
+        &wallet_xorurl,
+        "--json",
+    ]);
+    let transfers: Vec<TransferHistoryEntry> = serde_json::from_str(&check_tx_output)
+        .expect("Failed to parse output of `safe wallet check-tx`");
+    assert_eq!(transfers, history);
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["wallet", "history", &wallet_xorurl, "--page", "0"])
+        .env("SN_CLI_STORAGE_BACKEND", &backend)
+        .assert()
+        .stderr(predicate::str::contains("at least 1"))
+        .failure();
+
+    let _ = std::fs::remove_file(storage);
+}
+
+#[test]
+fn calling_safe_wallet_transfer_with_coin_selection() {
+    let (wallet_from, _pk, _sk) = create_wallet_with_balance("35.650000001", None); // we need 1 nano to pay for the costs of creation
+    let (second_key_xorurl, second_key_sk) = create_preload_and_get_keys("10.0");
+    let _wallet_insert_result = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "wallet",
+        "insert",
+        &wallet_from,
+        "--keyurl",
+        &second_key_xorurl,
+        "--sk",
+        &second_key_sk,
+        "--name",
+        "second-balance",
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let (key_xorurl, _key_sk) = create_preload_and_get_keys("0.0");
+
+    // none of the spendable balances is enough on its own
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "wallet",
+        "transfer",
+        "40",
+        "--from",
+        &wallet_from,
+        "--to",
+        &key_xorurl,
+    ])
+    .assert()
+    .failure();
+
+    let transfer_output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "wallet",
+        "transfer",
+        "40",
+        "--from",
+        &wallet_from,
+        "--to",
+        &key_xorurl,
+        "--coin-selection",
+        "largest-first",
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let outcome: SelectionTransferOutcome = serde_json::from_str(&transfer_output)
+        .expect("Failed to parse output of `safe wallet transfer`");
+    assert_eq!(outcome.failed, None);
+    let receipts = outcome.receipts;
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(receipts[0].0, "default-balance");
+    assert_eq!(receipts[0].1.amount, "35.650000000");
+    assert_eq!(receipts[1].0, "second-balance");
+    assert_eq!(receipts[1].1.amount, "4.350000000");
+
+    let from_has = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "wallet",
+        "balance",
+        &wallet_from,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    assert_eq!(from_has, "5.650000000" /* 35.65 + 10 - 40 */);
+}
+
+#[test]
+fn calling_safe_wallet_rm_rename_and_set_default() {
+    let (wallet_xorurl, _pk, _sk) = create_wallet_with_balance("35.650000001", None); // we need 1 nano to pay for the costs of creation
+    let (key_xorurl, key_sk) = create_preload_and_get_keys("10.0");
+    let _wallet_insert_result = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "wallet",
+        "insert",
+        &wallet_xorurl,
+        "--keyurl",
+        &key_xorurl,
+        "--sk",
+        &key_sk,
+        "--name",
+        "second-balance",
+        "--json"
+    )
+    .read()
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "wallet",
+        "rename",
+        &wallet_xorurl,
+        "second-balance",
+        "savings",
+    ])
+    .assert()
+    .stdout(predicate::str::contains("renamed to 'savings'"))
+    .success();
+
+    // the default balance cannot be removed without setting a new one
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["wallet", "rm", &wallet_xorurl, "default-balance"])
+        .assert()
+        .stderr(predicate::str::contains("new default"))
+        .failure();
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec![
+        "wallet",
+        "rm",
+        &wallet_xorurl,
+        "default-balance",
+        "--new-default",
+        "savings",
+    ])
+    .assert()
+    .success();
+
+    let mut cmd = Command::cargo_bin(CLI).unwrap();
+    cmd.args(&vec!["wallet", "set-default", &wallet_xorurl, "savings"])
+        .assert()
+        .stdout(predicate::str::contains("set to 'savings'"))
+        .success();
+
+    let balance = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "wallet",
+        "balance",
+        &wallet_xorurl,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    assert_eq!(balance, "10.000000000");
+}
+
+#[test]
+fn calling_safe_wallet_sweep() {
+    let (wallet_from, _pk, _sk) = create_wallet_with_balance("35.650000001", None); // we need 1 nano to pay for the costs of creation
+    let (key_xorurl, key_sk) = create_preload_and_get_keys("0.0");
+
+    let sweep_output = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "wallet",
+        "sweep",
+        "--from",
+        &wallet_from,
+        "--to",
+        &key_xorurl,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    let outcomes: WalletSweepOutcomes =
+        serde_json::from_str(&sweep_output).expect("Failed to parse output of `safe wallet sweep`");
+    match &outcomes["default-balance"] {
+        SweepOutcome::Transferred(receipt) => assert_eq!(receipt.amount, "35.650000000"),
+        other => panic!("Unexpected sweep outcome: {:?}", other),
+    }
+
+    let from_has = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "wallet",
+        "balance",
+        &wallet_from,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    assert_eq!(from_has, "0.000000000");
+
+    let key_has = cmd!(
+        env!("CARGO_BIN_EXE_safe"),
+        "keys",
+        "balance",
+        "--sk",
+        &key_sk,
+        "--json"
+    )
+    .read()
+    .unwrap();
+    assert_eq!(key_has, "35.650000000");
+}
+
 #[test]
 fn calling_safe_wallet_transfer_to_key_nrsurl() {
     let mut cmd = Command::cargo_bin(CLI).unwrap();
@@ -10,7 +10,10 @@
 use multibase::{encode, Base};
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
-use sn_api::{fetch::SafeData, files::ProcessedFiles, wallet::WalletSpendableBalances, Keypair};
+use sn_api::{
+    fetch::SafeData, files::ProcessedFiles, wallet::WalletSpendableBalances, Keypair,
+    ProcessedEntries,
+};
 use sn_data_types::Money;
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -316,7 +319,7 @@ pub fn parse_files_put_or_sync_output(output: &str) -> (String, ProcessedFiles)
 }
 
 #[allow(dead_code)]
-pub fn parse_nrs_create_output(output: &str) -> (String, ProcessedFiles) {
+pub fn parse_nrs_create_output(output: &str) -> (String, ProcessedEntries) {
     serde_json::from_str(output).expect("Failed to parse output of `safe nrs create`")
 }
 